@@ -17,6 +17,19 @@ mod m20260212_000002_add_snapshot_deleted_at;
 mod m20260215_000001_seed_default_user;
 mod m20260216_000001_comparison_sets;
 mod m20260314_000001_add_dividend_fields_to_historicals;
+mod m20261017_000001_add_source_to_historicals;
+mod m20261017_000002_corporate_actions;
+mod m20261017_000003_harvest_jobs;
+mod m20261017_000004_historicals_quarterly;
+mod m20261017_000005_add_fiscal_year_end_to_tickers;
+mod m20261017_000006_quotes;
+mod m20261017_000007_daily_prices;
+mod m20261017_000008_add_identifiers_to_tickers;
+mod m20261017_000009_provider_health_checks;
+mod m20261017_000010_add_token_buckets_to_provider_rate_limits;
+mod m20261017_000011_add_year_end_rate_to_exchange_rates;
+mod m20261017_000012_add_display_currency_to_harvest_jobs;
+mod m20261017_000013_month_end_exchange_rates;
 pub struct Migrator;
 
 #[async_trait::async_trait]
@@ -38,6 +51,19 @@ impl MigratorTrait for Migrator {
             Box::new(m20260215_000001_seed_default_user::Migration),
             Box::new(m20260216_000001_comparison_sets::Migration),
             Box::new(m20260314_000001_add_dividend_fields_to_historicals::Migration),
+            Box::new(m20261017_000001_add_source_to_historicals::Migration),
+            Box::new(m20261017_000002_corporate_actions::Migration),
+            Box::new(m20261017_000003_harvest_jobs::Migration),
+            Box::new(m20261017_000004_historicals_quarterly::Migration),
+            Box::new(m20261017_000005_add_fiscal_year_end_to_tickers::Migration),
+            Box::new(m20261017_000006_quotes::Migration),
+            Box::new(m20261017_000007_daily_prices::Migration),
+            Box::new(m20261017_000008_add_identifiers_to_tickers::Migration),
+            Box::new(m20261017_000009_provider_health_checks::Migration),
+            Box::new(m20261017_000010_add_token_buckets_to_provider_rate_limits::Migration),
+            Box::new(m20261017_000011_add_year_end_rate_to_exchange_rates::Migration),
+            Box::new(m20261017_000012_add_display_currency_to_harvest_jobs::Migration),
+            Box::new(m20261017_000013_month_end_exchange_rates::Migration),
             // inject-above (do not remove this comment)
         ]
     }
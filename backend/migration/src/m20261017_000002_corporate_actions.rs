@@ -0,0 +1,100 @@
+//! Creates the `corporate_actions` ledger and widens `historicals.adjustment_factor`.
+//!
+//! The ledger holds splits, reverse splits, stock dividends and spin-offs per
+//! ticker. Harvest computes each fiscal year's cumulative `adjustment_factor`
+//! from it, so the factor column must hold fractional ratios (reverse splits,
+//! stock dividends) instead of whole numbers only.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(DeriveIden)]
+enum CorporateActions {
+    Table,
+    Id,
+    Ticker,
+    Kind,
+    Ratio,
+    EffectiveDate,
+    Source,
+    Note,
+    AppliedAt,
+    CreatedAt,
+    UpdatedAt,
+    DeletedAt,
+}
+
+#[derive(DeriveIden)]
+enum Historicals {
+    Table,
+    AdjustmentFactor,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.create_table(
+            Table::create()
+                .table(CorporateActions::Table)
+                .if_not_exists()
+                .col(pk_auto(CorporateActions::Id))
+                .col(string(CorporateActions::Ticker))
+                .col(string_len(CorporateActions::Kind, 20))
+                .col(decimal_len(CorporateActions::Ratio, 19, 8))
+                .col(date(CorporateActions::EffectiveDate))
+                .col(string(CorporateActions::Source))
+                .col(string_null(CorporateActions::Note))
+                .col(timestamp_with_time_zone_null(CorporateActions::AppliedAt))
+                .col(timestamp_with_time_zone(CorporateActions::CreatedAt))
+                .col(timestamp_with_time_zone(CorporateActions::UpdatedAt))
+                .col(timestamp_with_time_zone_null(CorporateActions::DeletedAt))
+                .to_owned(),
+        )
+        .await?;
+
+        m.create_index(
+            Index::create()
+                .name("idx-corporate_actions-lookup")
+                .table(CorporateActions::Table)
+                .col(CorporateActions::Ticker)
+                .col(CorporateActions::EffectiveDate)
+                .col(CorporateActions::Kind)
+                .unique()
+                .to_owned(),
+        )
+        .await?;
+
+        m.alter_table(
+            Table::alter()
+                .table(Historicals::Table)
+                .modify_column(
+                    ColumnDef::new(Historicals::AdjustmentFactor)
+                        .decimal_len(19, 8)
+                        .null(),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.alter_table(
+            Table::alter()
+                .table(Historicals::Table)
+                .modify_column(
+                    ColumnDef::new(Historicals::AdjustmentFactor)
+                        .decimal_len(10, 0)
+                        .null(),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+        m.drop_table(Table::drop().table(CorporateActions::Table).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,62 @@
+//! Creates the `harvest_jobs` table tracking background harvests.
+//!
+//! Each row is one ticker harvested by the `DownloadWorker`. The status moves
+//! from `queued` to `running` and ends as `done` (with the harvest outcome in
+//! `result`) or `failed` (with the error message).
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(DeriveIden)]
+enum HarvestJobs {
+    Table,
+    Id,
+    Ticker,
+    Refresh,
+    Status,
+    Error,
+    Result,
+    StartedAt,
+    FinishedAt,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.create_table(
+            Table::create()
+                .table(HarvestJobs::Table)
+                .if_not_exists()
+                .col(pk_auto(HarvestJobs::Id))
+                .col(string(HarvestJobs::Ticker))
+                .col(boolean(HarvestJobs::Refresh).default(false))
+                .col(string_len(HarvestJobs::Status, 16))
+                .col(text_null(HarvestJobs::Error))
+                .col(json_binary_null(HarvestJobs::Result))
+                .col(timestamp_with_time_zone_null(HarvestJobs::StartedAt))
+                .col(timestamp_with_time_zone_null(HarvestJobs::FinishedAt))
+                .col(timestamp_with_time_zone(HarvestJobs::CreatedAt))
+                .col(timestamp_with_time_zone(HarvestJobs::UpdatedAt))
+                .to_owned(),
+        )
+        .await?;
+
+        m.create_index(
+            Index::create()
+                .name("idx-harvest_jobs-status")
+                .table(HarvestJobs::Table)
+                .col(HarvestJobs::Status)
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_table(Table::drop().table(HarvestJobs::Table).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,77 @@
+//! Creates the `historicals_quarterly` table holding quarterly fundamentals.
+//!
+//! One row per ticker and fiscal quarter. The four latest consecutive
+//! quarters yield the trailing-twelve-month EPS used as "current" EPS when
+//! they are newer than the latest annual record.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(DeriveIden)]
+enum HistoricalsQuarterly {
+    Table,
+    Id,
+    Ticker,
+    FiscalYear,
+    FiscalQuarter,
+    PeriodEnd,
+    Sales,
+    Eps,
+    NetIncome,
+    PretaxIncome,
+    PriceHigh,
+    PriceLow,
+    Currency,
+    AdjustmentFactor,
+    Source,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.create_table(
+            Table::create()
+                .table(HistoricalsQuarterly::Table)
+                .if_not_exists()
+                .col(pk_auto(HistoricalsQuarterly::Id))
+                .col(string(HistoricalsQuarterly::Ticker))
+                .col(integer(HistoricalsQuarterly::FiscalYear))
+                .col(small_integer(HistoricalsQuarterly::FiscalQuarter))
+                .col(date(HistoricalsQuarterly::PeriodEnd))
+                .col(decimal_len(HistoricalsQuarterly::Sales, 19, 4))
+                .col(decimal_len(HistoricalsQuarterly::Eps, 19, 4))
+                .col(decimal_len_null(HistoricalsQuarterly::NetIncome, 19, 4))
+                .col(decimal_len_null(HistoricalsQuarterly::PretaxIncome, 19, 4))
+                .col(decimal_len_null(HistoricalsQuarterly::PriceHigh, 19, 4))
+                .col(decimal_len_null(HistoricalsQuarterly::PriceLow, 19, 4))
+                .col(string(HistoricalsQuarterly::Currency))
+                .col(decimal_len_null(
+                    HistoricalsQuarterly::AdjustmentFactor,
+                    19,
+                    8,
+                ))
+                .col(string_null(HistoricalsQuarterly::Source))
+                .to_owned(),
+        )
+        .await?;
+
+        m.create_index(
+            Index::create()
+                .name("idx-historicals_quarterly-ticker-quarter")
+                .table(HistoricalsQuarterly::Table)
+                .col(HistoricalsQuarterly::Ticker)
+                .col(HistoricalsQuarterly::FiscalYear)
+                .col(HistoricalsQuarterly::FiscalQuarter)
+                .unique()
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_table(Table::drop().table(HistoricalsQuarterly::Table).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,43 @@
+//! Adds `fiscal_year_end_month` to `tickers`.
+//!
+//! Defaults to December; the seeded tickers with other year ends are updated.
+
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("tickers"))
+                .add_column(
+                    ColumnDef::new(Alias::new("fiscal_year_end_month"))
+                        .small_integer()
+                        .not_null()
+                        .default(12),
+                )
+                .to_owned(),
+        )
+        .await?;
+
+        let db = m.get_connection();
+        db.execute_unprepared(
+            "
+            UPDATE tickers
+            SET fiscal_year_end_month = CASE ticker WHEN 'MSFT' THEN 6 WHEN 'AAPL' THEN 9 END
+            WHERE ticker IN ('MSFT', 'AAPL');
+        ",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        remove_column(m, "tickers", "fiscal_year_end_month").await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+//! Creates the `quotes` table holding market prices per ticker.
+//!
+//! Quotes come from the data providers during harvest or are entered
+//! manually. The newest `as_of` per ticker is its current price.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(DeriveIden)]
+enum Quotes {
+    Table,
+    Id,
+    Ticker,
+    Price,
+    Currency,
+    AsOf,
+    Source,
+    CreatedAt,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.create_table(
+            Table::create()
+                .table(Quotes::Table)
+                .if_not_exists()
+                .col(pk_auto(Quotes::Id))
+                .col(string(Quotes::Ticker))
+                .col(decimal_len(Quotes::Price, 19, 4))
+                .col(string(Quotes::Currency))
+                .col(timestamp_with_time_zone(Quotes::AsOf))
+                .col(string(Quotes::Source))
+                .col(timestamp_with_time_zone(Quotes::CreatedAt))
+                .to_owned(),
+        )
+        .await?;
+
+        m.create_index(
+            Index::create()
+                .name("idx-quotes-ticker-as_of")
+                .table(Quotes::Table)
+                .col(Quotes::Ticker)
+                .col(Quotes::AsOf)
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_table(Table::drop().table(Quotes::Table).to_owned())
+            .await
+    }
+}
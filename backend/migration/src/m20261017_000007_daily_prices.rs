@@ -0,0 +1,67 @@
+//! Creates the `daily_prices` table holding the daily price series.
+//!
+//! One row per ticker and trading day, as traded. Yearly price ranges are
+//! derived from it per fiscal period.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(DeriveIden)]
+enum DailyPrices {
+    Table,
+    Id,
+    Ticker,
+    TradeDate,
+    Open,
+    High,
+    Low,
+    Close,
+    AdjustedClose,
+    Volume,
+    Source,
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.create_table(
+            Table::create()
+                .table(DailyPrices::Table)
+                .if_not_exists()
+                .col(pk_auto(DailyPrices::Id))
+                .col(string(DailyPrices::Ticker))
+                .col(date(DailyPrices::TradeDate))
+                .col(decimal_len(DailyPrices::Open, 19, 4))
+                .col(decimal_len(DailyPrices::High, 19, 4))
+                .col(decimal_len(DailyPrices::Low, 19, 4))
+                .col(decimal_len(DailyPrices::Close, 19, 4))
+                .col(decimal_len_null(DailyPrices::AdjustedClose, 19, 4))
+                .col(big_integer_null(DailyPrices::Volume))
+                .col(string(DailyPrices::Source))
+                .col(timestamp_with_time_zone(DailyPrices::CreatedAt))
+                .col(timestamp_with_time_zone(DailyPrices::UpdatedAt))
+                .to_owned(),
+        )
+        .await?;
+
+        m.create_index(
+            Index::create()
+                .name("idx-daily_prices-ticker-trade_date")
+                .table(DailyPrices::Table)
+                .col(DailyPrices::Ticker)
+                .col(DailyPrices::TradeDate)
+                .unique()
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_table(Table::drop().table(DailyPrices::Table).to_owned())
+            .await
+    }
+}
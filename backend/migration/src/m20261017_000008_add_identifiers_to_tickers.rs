@@ -0,0 +1,82 @@
+//! Adds the `isin`, `valor` and `wkn` security identifiers to `tickers`.
+//!
+//! All three are optional: ISINs are global, valor numbers are assigned by
+//! SIX for Swiss listings and WKNs by WM Datenservice for German ones. The
+//! seeded tickers get their identifiers filled in.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(DeriveIden)]
+enum Tickers {
+    Table,
+    Isin,
+    Valor,
+    Wkn,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.alter_table(
+            Table::alter()
+                .table(Tickers::Table)
+                .add_column(string_len_null(Tickers::Isin, 12))
+                .add_column(string_len_null(Tickers::Valor, 9))
+                .add_column(string_len_null(Tickers::Wkn, 6))
+                .to_owned(),
+        )
+        .await?;
+
+        for (name, column) in [
+            ("idx-tickers-isin", Tickers::Isin),
+            ("idx-tickers-valor", Tickers::Valor),
+            ("idx-tickers-wkn", Tickers::Wkn),
+        ] {
+            m.create_index(
+                Index::create()
+                    .name(name)
+                    .table(Tickers::Table)
+                    .col(column)
+                    .to_owned(),
+            )
+            .await?;
+        }
+
+        let db = m.get_connection();
+        db.execute_unprepared(
+            "
+            UPDATE tickers
+            SET isin = CASE ticker
+                    WHEN 'NESN.SW' THEN 'CH0038863350'
+                    WHEN 'ROG.SW' THEN 'CH0012032048'
+                    WHEN 'SAP.DE' THEN 'DE0007164600'
+                    WHEN 'MBG.DE' THEN 'DE0007100000'
+                    WHEN 'AAPL' THEN 'US0378331005'
+                    WHEN 'MSFT' THEN 'US5949181045'
+                    WHEN 'GOOGL' THEN 'US02079K3059'
+                    WHEN 'AMZN' THEN 'US0231351067'
+                END,
+                valor = CASE ticker WHEN 'NESN.SW' THEN '3886335' WHEN 'ROG.SW' THEN '1203204' END,
+                wkn = CASE ticker WHEN 'SAP.DE' THEN '716460' WHEN 'MBG.DE' THEN '710000' END
+            WHERE ticker IN ('NESN.SW', 'ROG.SW', 'SAP.DE', 'MBG.DE', 'AAPL', 'MSFT', 'GOOGL', 'AMZN');
+        ",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.alter_table(
+            Table::alter()
+                .table(Tickers::Table)
+                .drop_column(Tickers::Isin)
+                .drop_column(Tickers::Valor)
+                .drop_column(Tickers::Wkn)
+                .to_owned(),
+        )
+        .await
+    }
+}
@@ -0,0 +1,54 @@
+//! Creates the `provider_health_checks` table.
+//!
+//! One row per connectivity probe of a data provider; uptime and latency
+//! percentiles for the system monitor are computed from this time series.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(DeriveIden)]
+enum ProviderHealthChecks {
+    Table,
+    Id,
+    Provider,
+    CheckedAt,
+    Success,
+    LatencyMs,
+    Error,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.create_table(
+            Table::create()
+                .table(ProviderHealthChecks::Table)
+                .if_not_exists()
+                .col(pk_auto(ProviderHealthChecks::Id))
+                .col(string(ProviderHealthChecks::Provider))
+                .col(timestamp_with_time_zone(ProviderHealthChecks::CheckedAt))
+                .col(boolean(ProviderHealthChecks::Success))
+                .col(integer(ProviderHealthChecks::LatencyMs))
+                .col(text_null(ProviderHealthChecks::Error))
+                .to_owned(),
+        )
+        .await?;
+
+        m.create_index(
+            Index::create()
+                .name("idx-provider_health_checks-provider-checked_at")
+                .table(ProviderHealthChecks::Table)
+                .col(ProviderHealthChecks::Provider)
+                .col(ProviderHealthChecks::CheckedAt)
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_table(Table::drop().table(ProviderHealthChecks::Table).to_owned())
+            .await
+    }
+}
@@ -0,0 +1,32 @@
+//! Adds token-bucket state to `provider_rate_limits`.
+//!
+//! `minute_tokens` and `day_tokens` hold the tokens left in each bucket as of
+//! `last_updated`, so consumption survives restarts. Rows without them are
+//! treated as full buckets.
+
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("provider_rate_limits"))
+                .add_column(ColumnDef::new(Alias::new("minute_tokens")).double().null())
+                .add_column(ColumnDef::new(Alias::new("day_tokens")).double().null())
+                .to_owned(),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        remove_column(m, "provider_rate_limits", "day_tokens").await?;
+        remove_column(m, "provider_rate_limits", "minute_tokens").await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+//! Adds `year_end_rate` to `exchange_rates`.
+//!
+//! `rate` stays the yearly average used to normalize a year's figures;
+//! `year_end_rate` is the rate of the year's last trading day, filled by the
+//! exchange rate backfill. The seeded rows have no year-end rate.
+
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("exchange_rates"))
+                .add_column(
+                    ColumnDef::new(Alias::new("year_end_rate"))
+                        .decimal_len(19, 4)
+                        .null(),
+                )
+                .to_owned(),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        remove_column(m, "exchange_rates", "year_end_rate").await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+//! Adds `display_currency` to `harvest_jobs`.
+//!
+//! The currency the job's exchange rates convert to. Jobs queued before the
+//! column existed, and jobs that do not pick one, use USD.
+
+use loco_rs::schema::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.alter_table(
+            Table::alter()
+                .table(Alias::new("harvest_jobs"))
+                .add_column(
+                    ColumnDef::new(Alias::new("display_currency"))
+                        .string_len(3)
+                        .null(),
+                )
+                .to_owned(),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        remove_column(m, "harvest_jobs", "display_currency").await?;
+        Ok(())
+    }
+}
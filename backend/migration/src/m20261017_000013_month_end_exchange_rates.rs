@@ -0,0 +1,55 @@
+//! Creates the `month_end_exchange_rates` table.
+//!
+//! One row per currency pair and month: the rate of the month's last trading
+//! day, stored under the month's last calendar day. Closing rates are read
+//! at a fiscal year's end, which is any month's end.
+
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[derive(DeriveIden)]
+enum MonthEndExchangeRates {
+    Table,
+    Id,
+    FromCurrency,
+    ToCurrency,
+    MonthEnd,
+    Rate,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.create_table(
+            Table::create()
+                .table(MonthEndExchangeRates::Table)
+                .if_not_exists()
+                .col(pk_auto(MonthEndExchangeRates::Id))
+                .col(string(MonthEndExchangeRates::FromCurrency))
+                .col(string(MonthEndExchangeRates::ToCurrency))
+                .col(date(MonthEndExchangeRates::MonthEnd))
+                .col(decimal_len(MonthEndExchangeRates::Rate, 19, 4))
+                .to_owned(),
+        )
+        .await?;
+
+        m.create_index(
+            Index::create()
+                .name("idx-month-end-rates-from-to-month")
+                .table(MonthEndExchangeRates::Table)
+                .col(MonthEndExchangeRates::FromCurrency)
+                .col(MonthEndExchangeRates::ToCurrency)
+                .col(MonthEndExchangeRates::MonthEnd)
+                .unique()
+                .to_owned(),
+        )
+        .await
+    }
+
+    async fn down(&self, m: &SchemaManager) -> Result<(), DbErr> {
+        m.drop_table(Table::drop().table(MonthEndExchangeRates::Table).to_owned())
+            .await
+    }
+}
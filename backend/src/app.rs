@@ -19,7 +19,9 @@ use migration::Migrator;
 use std::path::Path;
 
 #[allow(unused_imports)]
-use crate::{controllers, models::_entities::users, tasks, workers::downloader::DownloadWorker};
+use crate::{
+    controllers, initializers, models::_entities::users, tasks, workers::downloader::DownloadWorker,
+};
 
 pub struct App;
 #[async_trait]
@@ -47,18 +49,25 @@ impl Hooks for App {
     }
 
     async fn initializers(_ctx: &AppContext) -> Result<Vec<Box<dyn Initializer>>> {
-        Ok(vec![])
+        Ok(vec![Box::new(
+            initializers::refresh_scheduler::RefreshScheduler,
+        )])
     }
 
     fn routes(_ctx: &AppContext) -> AppRoutes {
         AppRoutes::with_default_routes() // controller routes below
             .add_route(controllers::harvest::routes())
+            .add_route(controllers::harvest::job_routes())
             .add_route(controllers::tickers::routes())
+            .add_route(controllers::historicals::routes())
+            .add_route(controllers::daily_prices::routes())
             .add_route(controllers::auth::routes())
             .add_route(controllers::overrides::routes())
+            .add_route(controllers::corporate_actions::routes())
             .add_route(controllers::analyses::routes())
             .add_route(controllers::snapshots::routes())
             .add_route(controllers::exchange_rates::routes())
+            .add_route(controllers::quotes::routes())
             .add_route(controllers::comparisons::compare_routes())
             .add_route(controllers::comparisons::routes())
             .add_route(controllers::system::routes())
@@ -68,8 +77,10 @@ impl Hooks for App {
         Ok(())
     }
 
-    #[allow(unused_variables)]
     fn register_tasks(tasks: &mut Tasks) {
+        tasks.register(tasks::refresh_all::RefreshAll);
+        tasks.register(tasks::import_tickers::ImportTickers);
+        tasks.register(tasks::backfill_exchange_rates::BackfillExchangeRates);
         // tasks-inject (do not remove)
     }
     async fn truncate(ctx: &AppContext) -> Result<()> {
@@ -4,6 +4,7 @@
 //! and exporting locked analyses as PDF reports.
 
 use crate::models::{_entities::analysis_snapshots, tickers};
+use crate::services::quotes;
 use loco_rs::prelude::*;
 use sea_orm::QueryOrder;
 use serde::{Deserialize, Serialize};
@@ -45,11 +46,15 @@ pub async fn lock_analysis(
         .await?
         .ok_or_else(|| Error::NotFound)?;
 
+    // The thesis is locked against the price current at the time
+    let mut snapshot = req.snapshot;
+    quotes::attach_latest(&ctx.db, &ticker_symbol, &mut snapshot).await?;
+
     let active = analysis_snapshots::ActiveModel {
         user_id: ActiveValue::set(1), // default single-user until Phase 3 auth
         ticker_id: ActiveValue::set(ticker.id),
         snapshot_data: ActiveValue::set(
-            serde_json::to_value(req.snapshot).map_err(|e| Error::string(&e.to_string()))?,
+            serde_json::to_value(snapshot).map_err(|e| Error::string(&e.to_string()))?,
         ),
         thesis_locked: ActiveValue::set(true),
         chart_image: ActiveValue::set(None), // Story 7.4 adds chart image capture
@@ -13,13 +13,13 @@
 use loco_rs::prelude::*;
 use sea_orm::{IntoActiveModel, PaginatorTrait, QueryOrder, TransactionTrait};
 use serde::{Deserialize, Serialize};
-
-use steady_invest_logic::is_valid_currency_code;
+use steady_invest_logic::PriceZone;
 
 use super::snapshot_metrics::{extract_monetary_fields, extract_projection_metrics};
 use crate::models::_entities::{
     analysis_snapshots, comparison_set_items, comparison_sets, tickers,
 };
+use crate::services::exchange_rate_provider;
 
 // ---------------------------------------------------------------------------
 // Request / Response DTOs
@@ -72,12 +72,19 @@ pub struct ComparisonSnapshotSummary {
     pub projected_eps_cagr: Option<f64>,
     pub projected_high_pe: Option<f64>,
     pub projected_low_pe: Option<f64>,
-    pub valuation_zone: Option<String>,
+    /// NAIC Section 4C zone of the current price ("Buy", "Maybe", "Sell").
+    pub valuation_zone: Option<PriceZone>,
     pub upside_downside_ratio: Option<f64>,
     pub native_currency: Option<String>,
     pub current_price: Option<f64>,
     pub target_high_price: Option<f64>,
     pub target_low_price: Option<f64>,
+    pub buy_below_price: Option<f64>,
+    pub sell_above_price: Option<f64>,
+    /// NAIC Section 5 metrics, in percent.
+    pub relative_value: Option<f64>,
+    pub present_yield: Option<f64>,
+    pub projected_average_return: Option<f64>,
 }
 
 impl ComparisonSnapshotSummary {
@@ -101,16 +108,17 @@ impl ComparisonSnapshotSummary {
             projected_eps_cagr: proj.projected_eps_cagr,
             projected_high_pe: proj.projected_high_pe,
             projected_low_pe: proj.projected_low_pe,
-            valuation_zone: m
-                .snapshot_data
-                .get("valuation_zone")
-                .and_then(|v| v.as_str())
-                .map(|s| s.to_owned()),
+            valuation_zone: monetary.valuation_zone,
             upside_downside_ratio: monetary.upside_downside_ratio,
             native_currency: monetary.native_currency,
             current_price: monetary.current_price,
             target_high_price: monetary.target_high_price,
             target_low_price: monetary.target_low_price,
+            buy_below_price: monetary.buy_below_price,
+            sell_above_price: monetary.sell_above_price,
+            relative_value: monetary.relative_value,
+            present_yield: monetary.present_yield,
+            projected_average_return: monetary.projected_average_return,
         }
     }
 }
@@ -164,6 +172,14 @@ fn unprocessable_entity(message: &str) -> Result<Response> {
         .map_err(|e| Error::string(&e.to_string()))
 }
 
+/// Error message listing the base currencies comparisons accept.
+fn unsupported_currency_message() -> String {
+    format!(
+        "base_currency must be one of the supported ISO 4217 codes: {}",
+        exchange_rate_provider::supported_currencies().join(", ")
+    )
+}
+
 // ---------------------------------------------------------------------------
 // Handlers — Ad-hoc compare
 // ---------------------------------------------------------------------------
@@ -225,11 +241,9 @@ pub async fn create_comparison_set(
         return unprocessable_entity("Name must not be empty");
     }
 
-    // Validate base_currency is a valid ISO 4217 code (3 uppercase ASCII letters)
-    if !is_valid_currency_code(&req.base_currency) {
-        return unprocessable_entity(
-            "base_currency must be a valid ISO 4217 code (3 uppercase letters)",
-        );
+    // Validate base_currency is a supported ISO 4217 code (3 uppercase ASCII letters)
+    if !exchange_rate_provider::is_supported_currency(&req.base_currency) {
+        return unprocessable_entity(&unsupported_currency_message());
     }
 
     // Validate all snapshot IDs exist and are not deleted
@@ -351,11 +365,9 @@ pub async fn update_comparison_set(
         return unprocessable_entity("Name must not be empty");
     }
 
-    // Validate base_currency is a valid ISO 4217 code (3 uppercase ASCII letters)
-    if !is_valid_currency_code(&req.base_currency) {
-        return unprocessable_entity(
-            "base_currency must be a valid ISO 4217 code (3 uppercase letters)",
-        );
+    // Validate base_currency is a supported ISO 4217 code (3 uppercase ASCII letters)
+    if !exchange_rate_provider::is_supported_currency(&req.base_currency) {
+        return unprocessable_entity(&unsupported_currency_message());
     }
 
     // Validate all snapshot IDs exist and are not deleted
@@ -0,0 +1,233 @@
+//! Corporate actions ledger controller.
+//!
+//! CRUD under `/api/v1/corporate-actions` for the splits, reverse splits,
+//! stock dividends and spin-offs that drive split adjustment in harvest.
+//! Deletion is soft so that a provider-reported action removed by an analyst
+//! is not re-imported on the next harvest; moving an entry to another date
+//! leaves the same tombstone under its original key.
+
+use crate::models::{corporate_actions, tickers};
+use crate::services::corporate_actions::{validate_ratio, USER_SOURCE};
+use crate::services::providers::CorporateActionKind;
+use axum::extract::{Path, Query};
+use loco_rs::prelude::*;
+use rust_decimal::Decimal;
+use sea_orm::{TransactionTrait, TryIntoModel};
+use serde::Deserialize;
+
+/// Query parameters for listing ledger entries.
+#[derive(Debug, Deserialize)]
+pub struct ListQuery {
+    pub ticker: String,
+}
+
+/// Request body for creating or updating a ledger entry.
+#[derive(Debug, Deserialize)]
+pub struct CorporateActionRequest {
+    pub ticker: String,
+    /// `split`, `reverse_split`, `stock_dividend` or `spin_off`.
+    pub kind: String,
+    /// Number of new shares per old share (e.g. `4` for a 4-for-1 split).
+    pub ratio: Decimal,
+    pub effective_date: chrono::NaiveDate,
+    pub note: Option<String>,
+}
+
+/// Validates a request, returning the normalized ticker and kind.
+async fn validate(
+    ctx: &AppContext,
+    req: &CorporateActionRequest,
+) -> Result<(String, CorporateActionKind)> {
+    let kind = CorporateActionKind::parse(&req.kind).ok_or_else(|| {
+        let kinds: Vec<&str> = CorporateActionKind::ALL
+            .iter()
+            .map(|k| k.as_str())
+            .collect();
+        Error::BadRequest(format!(
+            "Invalid kind '{}'. Must be one of: {}",
+            req.kind,
+            kinds.join(", ")
+        ))
+    })?;
+    validate_ratio(kind, req.ratio).map_err(Error::BadRequest)?;
+
+    let ticker = req.ticker.to_uppercase();
+    tickers::Entity::find()
+        .filter(tickers::Column::Ticker.eq(&ticker))
+        .one(&ctx.db)
+        .await?
+        .ok_or_else(|| Error::BadRequest(format!("Unknown ticker '{ticker}'")))?;
+
+    Ok((ticker, kind))
+}
+
+/// Loads a non-deleted ledger entry by id.
+async fn load_active(ctx: &AppContext, id: i32) -> Result<corporate_actions::Model> {
+    corporate_actions::Entity::find_by_id(id)
+        .one(&ctx.db)
+        .await?
+        .filter(|m| m.deleted_at.is_none())
+        .ok_or(Error::NotFound)
+}
+
+/// Lists the active ledger entries for a ticker, oldest first.
+///
+/// **GET** `/api/v1/corporate-actions?ticker=AAPL`
+#[debug_handler]
+pub async fn list(
+    State(ctx): State<AppContext>,
+    Query(params): Query<ListQuery>,
+) -> Result<Response> {
+    let entries =
+        corporate_actions::Model::find_active_for_ticker(&ctx.db, &params.ticker.to_uppercase())
+            .await?;
+    format::json(entries)
+}
+
+/// Adds an entry to the ledger.
+///
+/// **POST** `/api/v1/corporate-actions`
+///
+/// # Errors
+///
+/// Returns `400 Bad Request` for an unknown ticker or kind, a ratio that does
+/// not fit the kind, or an action already recorded for that date.
+#[debug_handler]
+pub async fn create(
+    State(ctx): State<AppContext>,
+    Json(req): Json<CorporateActionRequest>,
+) -> Result<Response> {
+    let (ticker, kind) = validate(&ctx, &req).await?;
+
+    let existing =
+        corporate_actions::Model::find_entry(&ctx.db, &ticker, req.effective_date, kind.as_str())
+            .await?;
+    let now = chrono::Utc::now();
+    let mut active: corporate_actions::ActiveModel = match existing {
+        Some(m) if m.deleted_at.is_none() => {
+            return Err(Error::BadRequest(format!(
+                "A {} for {ticker} effective {} is already recorded",
+                kind.as_str(),
+                req.effective_date
+            )))
+        }
+        // Re-creating a deleted entry revives it
+        Some(m) => m.into(),
+        None => corporate_actions::ActiveModel {
+            ticker: ActiveValue::set(ticker),
+            kind: ActiveValue::set(kind.as_str().to_string()),
+            effective_date: ActiveValue::set(req.effective_date),
+            created_at: ActiveValue::set(now.into()),
+            ..Default::default()
+        },
+    };
+    active.ratio = ActiveValue::set(req.ratio);
+    active.source = ActiveValue::set(USER_SOURCE.to_string());
+    active.note = ActiveValue::set(req.note);
+    active.applied_at = ActiveValue::set(None);
+    active.updated_at = ActiveValue::set(now.into());
+    active.deleted_at = ActiveValue::set(None);
+
+    let model = active.save(&ctx.db).await?;
+    format::json(model.try_into_model()?)
+}
+
+/// Replaces an entry in the ledger. The entry is audited again on the next
+/// harvest it applies to.
+///
+/// Changing the ticker, kind or effective date moves the entry to a new key:
+/// the original row is soft-deleted and kept as a tombstone, so a provider
+/// action edited away is not re-imported by the next harvest, and the entry
+/// is returned under its new id.
+///
+/// **PUT** `/api/v1/corporate-actions/{id}`
+///
+/// # Errors
+///
+/// Returns `404 Not Found` if the entry does not exist or was deleted, and
+/// `400 Bad Request` for invalid input or an action already recorded for the
+/// new date.
+#[debug_handler]
+pub async fn update(
+    State(ctx): State<AppContext>,
+    Path(id): Path<i32>,
+    Json(req): Json<CorporateActionRequest>,
+) -> Result<Response> {
+    let existing = load_active(&ctx, id).await?;
+    let (ticker, kind) = validate(&ctx, &req).await?;
+    let now = chrono::Utc::now();
+
+    let same_key = existing.ticker == ticker
+        && existing.kind == kind.as_str()
+        && existing.effective_date == req.effective_date;
+    let txn = ctx.db.begin().await?;
+    let mut active: corporate_actions::ActiveModel = if same_key {
+        existing.into()
+    } else {
+        let target =
+            corporate_actions::Model::find_entry(&txn, &ticker, req.effective_date, kind.as_str())
+                .await?;
+        let active = match target {
+            Some(m) if m.deleted_at.is_none() => {
+                return Err(Error::BadRequest(format!(
+                    "A {} for {ticker} effective {} is already recorded",
+                    kind.as_str(),
+                    req.effective_date
+                )))
+            }
+            // Moving onto a deleted entry revives it
+            Some(m) => m.into(),
+            None => corporate_actions::ActiveModel {
+                ticker: ActiveValue::set(ticker),
+                kind: ActiveValue::set(kind.as_str().to_string()),
+                effective_date: ActiveValue::set(req.effective_date),
+                created_at: ActiveValue::set(now.into()),
+                ..Default::default()
+            },
+        };
+
+        let mut tombstone: corporate_actions::ActiveModel = existing.into();
+        tombstone.deleted_at = ActiveValue::set(Some(now.into()));
+        tombstone.updated_at = ActiveValue::set(now.into());
+        tombstone.update(&txn).await?;
+        active
+    };
+    active.ratio = ActiveValue::set(req.ratio);
+    active.source = ActiveValue::set(USER_SOURCE.to_string());
+    active.note = ActiveValue::set(req.note);
+    active.applied_at = ActiveValue::set(None);
+    active.updated_at = ActiveValue::set(now.into());
+    active.deleted_at = ActiveValue::set(None);
+
+    let model = active.save(&txn).await?.try_into_model()?;
+    txn.commit().await?;
+    format::json(model)
+}
+
+/// Soft-deletes an entry from the ledger.
+///
+/// **DELETE** `/api/v1/corporate-actions/{id}`
+///
+/// # Errors
+///
+/// Returns `404 Not Found` if the entry does not exist or was already deleted.
+#[debug_handler]
+pub async fn remove(State(ctx): State<AppContext>, Path(id): Path<i32>) -> Result<Response> {
+    let existing = load_active(&ctx, id).await?;
+
+    let mut active: corporate_actions::ActiveModel = existing.into();
+    active.deleted_at = ActiveValue::set(Some(chrono::Utc::now().into()));
+    active.update(&ctx.db).await?;
+
+    format::json(serde_json::json!({ "status": "deleted" }))
+}
+
+/// Registers corporate action routes under `/api/v1/corporate-actions`.
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("api/v1/corporate-actions")
+        .add("/", get(list))
+        .add("/", post(create))
+        .add("/{id}", put(update))
+        .add("/{id}", delete(remove))
+}
@@ -0,0 +1,94 @@
+//! Daily price series controller.
+//!
+//! Serves the stored daily bars of a ticker for price charts and imports
+//! bars from CSV under `/api/v1/daily-prices`. Harvests add bars from the
+//! ticker's provider.
+
+use super::response::json_with_status;
+use crate::models::daily_prices;
+use crate::services::daily_prices as daily_prices_service;
+use axum::extract::{Multipart, Path, Query};
+use chrono::NaiveDate;
+use loco_rs::prelude::*;
+use serde::Deserialize;
+
+/// Query parameters for listing bars; both bounds are inclusive.
+#[derive(Debug, Deserialize)]
+pub struct RangeQuery {
+    /// Defaults to 1900-01-01, i.e. every stored day.
+    pub from: Option<NaiveDate>,
+    /// Defaults to today.
+    pub to: Option<NaiveDate>,
+}
+
+/// Lists the stored bars for a ticker, oldest first.
+///
+/// **GET** `/api/v1/daily-prices/{ticker}?from=2024-01-01&to=2024-12-31`
+#[debug_handler]
+pub async fn list(
+    State(ctx): State<AppContext>,
+    Path(ticker): Path<String>,
+    Query(params): Query<RangeQuery>,
+) -> Result<Response> {
+    let from = params
+        .from
+        .or_else(|| NaiveDate::from_ymd_opt(1900, 1, 1))
+        .unwrap_or_default();
+    let to = params.to.unwrap_or_else(|| chrono::Utc::now().date_naive());
+    let bars = daily_prices::Entity::find_range(&ctx.db, &ticker.to_uppercase(), from, to).await?;
+    format::json(bars)
+}
+
+/// Imports daily bars for the given ticker from a CSV file.
+///
+/// **POST** `/api/v1/daily-prices/{ticker}/upload` (multipart, field `file`)
+///
+/// Returns the [`daily_prices_service::DailyImportReport`]. When any row is
+/// invalid nothing is stored and the report lists every problem with its
+/// row number.
+///
+/// # Errors
+///
+/// Returns `400 Bad Request` if the `file` field is missing, `404 Not Found`
+/// if the ticker is unknown, and `422 Unprocessable Entity` (with the report)
+/// if validation fails.
+#[debug_handler]
+pub async fn upload(
+    State(ctx): State<AppContext>,
+    Path(ticker): Path<String>,
+    mut multipart: Multipart,
+) -> Result<Response> {
+    let ticker = ticker.to_uppercase();
+
+    let mut upload = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| Error::BadRequest(format!("Invalid multipart body: {e}")))?
+    {
+        if field.name() != Some("file") {
+            continue;
+        }
+        let bytes = field
+            .bytes()
+            .await
+            .map_err(|e| Error::BadRequest(format!("Failed to read upload: {e}")))?;
+        upload = Some(bytes);
+    }
+    let bytes = upload.ok_or_else(|| Error::BadRequest("Missing 'file' field".to_string()))?;
+
+    let report = daily_prices_service::import_csv(&ctx.db, &ticker, &bytes).await?;
+    if report.errors.is_empty() {
+        format::json(report)
+    } else {
+        json_with_status(axum::http::StatusCode::UNPROCESSABLE_ENTITY, &report)
+    }
+}
+
+/// Registers daily price routes under `/api/v1/daily-prices`.
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("api/v1/daily-prices")
+        .add("/{ticker}", get(list))
+        .add("/{ticker}/upload", post(upload))
+}
@@ -1,11 +1,49 @@
 //! Data harvesting controller.
 //!
 //! Exposes `POST /api/harvest/{ticker}` to trigger a 10-year historical data
-//! fetch, split adjustment, and P/E analysis for the given ticker.
+//! fetch, split adjustment, and P/E analysis for the given ticker. With
+//! `?refresh=true` stored years are re-compared and restated values updated.
+//!
+//! Harvests can also run in the background: `POST /api/v1/harvest/jobs`
+//! queues one job per ticker on the
+//! [`DownloadWorker`](crate::workers::downloader::DownloadWorker) and
+//! `GET /api/v1/harvest/jobs/{id}` reports its progress and result.
 
-use crate::services::harvest;
-use axum::extract::Path;
+use crate::models::harvest_jobs;
+use crate::services::{exchange_rate_provider, harvest};
+use crate::workers::downloader::{DownloadWorker, DownloadWorkerArgs};
+use axum::extract::{Path, Query};
 use loco_rs::prelude::*;
+use serde::Deserialize;
+
+/// Query parameters for a harvest.
+#[derive(Debug, Default, Deserialize)]
+pub struct HarvestQuery {
+    /// Update stored years whose values changed at the provider.
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+/// Request body for queuing background harvests.
+#[derive(Debug, Deserialize)]
+pub struct EnqueueRequest {
+    /// Tickers to harvest, one job each.
+    pub tickers: Vec<String>,
+    /// Run the jobs as refreshes (see [`harvest::refresh_harvest`]).
+    #[serde(default)]
+    pub refresh: bool,
+    /// Currency the harvested exchange rates convert to; USD when omitted.
+    #[serde(default)]
+    pub display_currency: Option<String>,
+}
+
+/// Validates a ticker path or body value, returning it uppercased.
+fn normalize_ticker(ticker: &str) -> Result<String> {
+    if ticker.is_empty() || ticker.len() > 10 {
+        return Err(Error::BadRequest("Invalid ticker format".to_string()));
+    }
+    Ok(ticker.to_uppercase())
+}
 
 /// Triggers a full 10-year data harvest for the given ticker symbol.
 ///
@@ -14,6 +52,12 @@ use loco_rs::prelude::*;
 /// Validates the ticker format, delegates to [`harvest::run_harvest`], and
 /// returns the assembled [`steady_invest_logic::HistoricalData`] as JSON.
 ///
+/// **POST** `/api/harvest/{ticker}?refresh=true`
+///
+/// Delegates to [`harvest::refresh_harvest`] and returns a
+/// [`harvest::HarvestOutcome`]: the data plus a summary of the inserted,
+/// updated and kept years and every restated field.
+///
 /// # Errors
 ///
 /// Returns `400 Bad Request` if the ticker is empty or longer than 10 characters.
@@ -21,20 +65,104 @@ use loco_rs::prelude::*;
 pub async fn harvest_ticker(
     State(ctx): State<AppContext>,
     Path(ticker): Path<String>,
+    Query(params): Query<HarvestQuery>,
 ) -> Result<Response> {
-    // Basic validation
-    if ticker.is_empty() || ticker.len() > 10 {
-        return Err(Error::BadRequest("Invalid ticker format".to_string()));
+    let ticker = normalize_ticker(&ticker)?;
+
+    if params.refresh {
+        let outcome = harvest::refresh_harvest(&ctx, &ticker).await?;
+        return format::json(outcome);
     }
-    let ticker = ticker.to_uppercase();
 
     let data = harvest::run_harvest(&ctx, &ticker).await?;
     format::json(data)
 }
 
+/// Queues a background harvest for each ticker.
+///
+/// **POST** `/api/v1/harvest/jobs`
+///
+/// Returns the created jobs, in request order. Poll each one with
+/// [`get_job`] until its status is `done` or `failed`.
+///
+/// # Errors
+///
+/// Returns `400 Bad Request` if no ticker is given, any ticker is invalid or
+/// the display currency is not supported; no job is queued in that case.
+#[debug_handler]
+pub async fn enqueue_jobs(
+    State(ctx): State<AppContext>,
+    Json(req): Json<EnqueueRequest>,
+) -> Result<Response> {
+    if req.tickers.is_empty() {
+        return Err(Error::BadRequest(
+            "At least one ticker is required".to_string(),
+        ));
+    }
+    let tickers = req
+        .tickers
+        .iter()
+        .map(|t| normalize_ticker(t))
+        .collect::<Result<Vec<_>>>()?;
+    if let Some(currency) = &req.display_currency {
+        if !exchange_rate_provider::is_supported_currency(currency) {
+            return Err(Error::BadRequest(format!(
+                "display_currency must be one of: {}",
+                exchange_rate_provider::supported_currencies().join(", ")
+            )));
+        }
+    }
+
+    let mut job_ids = Vec::with_capacity(tickers.len());
+    for ticker in &tickers {
+        let job = harvest_jobs::Model::enqueue(
+            &ctx.db,
+            ticker,
+            req.refresh,
+            req.display_currency.as_deref(),
+        )
+        .await?;
+        DownloadWorker::perform_later(&ctx, DownloadWorkerArgs { job_id: job.id }).await?;
+        job_ids.push(job.id);
+    }
+
+    // Reload: with a foreground queue the jobs have already run
+    let mut jobs = Vec::with_capacity(job_ids.len());
+    for id in job_ids {
+        if let Some(job) = harvest_jobs::Entity::find_by_id(id).one(&ctx.db).await? {
+            jobs.push(job);
+        }
+    }
+    format::json(jobs)
+}
+
+/// Returns the status of a background harvest.
+///
+/// **GET** `/api/v1/harvest/jobs/{id}`
+///
+/// # Errors
+///
+/// Returns `404 Not Found` if the job does not exist.
+#[debug_handler]
+pub async fn get_job(State(ctx): State<AppContext>, Path(id): Path<i32>) -> Result<Response> {
+    let job = harvest_jobs::Entity::find_by_id(id)
+        .one(&ctx.db)
+        .await?
+        .ok_or(Error::NotFound)?;
+    format::json(job)
+}
+
 /// Registers harvest routes under `/api/harvest`.
 pub fn routes() -> Routes {
     Routes::new()
         .prefix("api/harvest")
         .add("/{ticker}", post(harvest_ticker))
 }
+
+/// Registers background harvest job routes under `/api/v1/harvest/jobs`.
+pub fn job_routes() -> Routes {
+    Routes::new()
+        .prefix("api/v1/harvest/jobs")
+        .add("/", post(enqueue_jobs))
+        .add("/{id}", get(get_job))
+}
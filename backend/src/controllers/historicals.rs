@@ -0,0 +1,70 @@
+//! Historical table upload controller.
+//!
+//! Exposes `POST /api/historicals/{ticker}/upload` to import a full yearly
+//! table for tickers no provider covers. The file is sent as the `file` field
+//! of a multipart form, as CSV or XLSX.
+
+use super::response::json_with_status;
+use crate::services::historicals_import::{self, UploadFormat};
+use axum::extract::{Multipart, Path};
+use loco_rs::prelude::*;
+
+/// Imports an uploaded historical table for the given ticker.
+///
+/// **POST** `/api/historicals/{ticker}/upload` (multipart, field `file`)
+///
+/// Returns the [`historicals_import::ImportReport`]. When any row is invalid
+/// nothing is stored and the report lists every problem with its row number.
+///
+/// # Errors
+///
+/// Returns `400 Bad Request` if the `file` field is missing or not a `.csv` /
+/// `.xlsx` file, `404 Not Found` if the ticker is unknown, and
+/// `422 Unprocessable Entity` (with the report) if validation fails.
+#[debug_handler]
+pub async fn upload_historicals(
+    State(ctx): State<AppContext>,
+    Path(ticker): Path<String>,
+    mut multipart: Multipart,
+) -> Result<Response> {
+    let ticker = ticker.to_uppercase();
+
+    let mut upload = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| Error::BadRequest(format!("Invalid multipart body: {e}")))?
+    {
+        if field.name() != Some("file") {
+            continue;
+        }
+        let file_name = field.file_name().unwrap_or_default().to_string();
+        let bytes = field
+            .bytes()
+            .await
+            .map_err(|e| Error::BadRequest(format!("Failed to read upload: {e}")))?;
+        upload = Some((file_name, bytes));
+    }
+
+    let (file_name, bytes) =
+        upload.ok_or_else(|| Error::BadRequest("Missing 'file' field".to_string()))?;
+    let format = UploadFormat::from_file_name(&file_name).ok_or_else(|| {
+        Error::BadRequest(format!(
+            "Unsupported file '{file_name}'. Upload a .csv or .xlsx file"
+        ))
+    })?;
+
+    let report = historicals_import::import_historicals(&ctx.db, &ticker, format, &bytes).await?;
+    if report.errors.is_empty() {
+        format::json(report)
+    } else {
+        json_with_status(axum::http::StatusCode::UNPROCESSABLE_ENTITY, &report)
+    }
+}
+
+/// Registers historical table routes under `/api/historicals`.
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("api/historicals")
+        .add("/{ticker}/upload", post(upload_historicals))
+}
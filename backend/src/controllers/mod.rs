@@ -6,11 +6,15 @@
 //! ## Endpoints
 //!
 //! - [`harvest`]         — Trigger and monitor 10-year data harvests
-//! - [`tickers`]         — Ticker search and autocomplete
+//! - [`tickers`]         — Ticker search, autocomplete and universe import
+//! - [`historicals`]     — Upload of full historical tables (CSV / XLSX)
+//! - [`daily_prices`]    — Daily price series (chart data, CSV import)
 //! - [`overrides`]       — Manual data override CRUD
+//! - [`corporate_actions`] — Corporate actions ledger CRUD (splits, spin-offs)
 //! - [`analyses`]        — Analysis persistence (save / load / list / delete)
 //! - [`snapshots`]       — Analysis snapshot CRUD (append-only, immutable)
 //! - [`exchange_rates`]  — Current EUR/CHF/USD exchange rates
+//! - [`quotes`]          — Current prices (latest quote, manual entry)
 //! - [`auth`]            — User authentication (register, login, verify)
 //! - [`comparisons`]     — Ad-hoc compare and persisted comparison sets
 //! - [`system`]          — System health and provider status
@@ -18,9 +22,14 @@
 pub mod analyses;
 pub mod auth;
 pub mod comparisons;
+pub mod corporate_actions;
+pub mod daily_prices;
 pub mod exchange_rates;
 pub mod harvest;
+pub mod historicals;
 pub mod overrides;
+pub mod quotes;
+pub mod response;
 pub mod snapshot_metrics;
 pub mod snapshots;
 pub mod system;
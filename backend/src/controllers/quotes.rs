@@ -0,0 +1,93 @@
+//! Current price controller.
+//!
+//! Reads the latest quote of a ticker and lets analysts enter a price by
+//! hand under `/api/v1/quotes`. Harvested quotes are stored by the harvest
+//! pipeline.
+
+use crate::models::tickers;
+use crate::services::audit_service::AuditService;
+use crate::services::quotes::{self, MANUAL_SOURCE};
+use axum::extract::Path;
+use chrono::{DateTime, Utc};
+use loco_rs::prelude::*;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+/// Request body for entering a quote manually.
+#[derive(Debug, Deserialize)]
+pub struct QuoteRequest {
+    pub ticker: String,
+    pub price: Decimal,
+    /// Defaults to the ticker's reporting currency.
+    pub currency: Option<String>,
+    /// Defaults to now.
+    pub as_of: Option<DateTime<Utc>>,
+}
+
+/// Returns the newest quote for a ticker.
+///
+/// **GET** `/api/v1/quotes/{ticker}`
+///
+/// # Errors
+///
+/// Returns `404 Not Found` if no quote is stored for the ticker.
+#[debug_handler]
+pub async fn latest(State(ctx): State<AppContext>, Path(ticker): Path<String>) -> Result<Response> {
+    let quote = quotes::latest(&ctx.db, &ticker.to_uppercase())
+        .await?
+        .ok_or(Error::NotFound)?;
+    format::json(quote)
+}
+
+/// Records a manually entered quote, audited as an override of the current price.
+///
+/// **POST** `/api/v1/quotes`
+///
+/// # Errors
+///
+/// Returns `400 Bad Request` for an unknown ticker or a non-positive price.
+#[debug_handler]
+pub async fn create(
+    State(ctx): State<AppContext>,
+    Json(req): Json<QuoteRequest>,
+) -> Result<Response> {
+    if req.price <= Decimal::ZERO {
+        return Err(Error::BadRequest("Price must be positive".to_string()));
+    }
+
+    let ticker = req.ticker.to_uppercase();
+    let ticker_info = tickers::Entity::find()
+        .filter(tickers::Column::Ticker.eq(&ticker))
+        .one(&ctx.db)
+        .await?
+        .ok_or_else(|| Error::BadRequest(format!("Unknown ticker '{ticker}'")))?;
+
+    let previous = quotes::latest(&ctx.db, &ticker).await?;
+    let currency = req
+        .currency
+        .map(|c| c.to_uppercase())
+        .unwrap_or_else(|| ticker_info.currency.clone());
+    let as_of = req.as_of.unwrap_or_else(Utc::now);
+    let model =
+        quotes::record(&ctx.db, &ticker, req.price, &currency, as_of, MANUAL_SOURCE).await?;
+
+    AuditService::log_override(
+        &ctx.db,
+        &ticker,
+        &ticker_info.exchange,
+        "current_price",
+        previous.map(|q| q.price.normalize().to_string()),
+        Some(req.price.normalize().to_string()),
+    )
+    .await?;
+
+    format::json(model.to_quote())
+}
+
+/// Registers quote routes under `/api/v1/quotes`.
+pub fn routes() -> Routes {
+    Routes::new()
+        .prefix("api/v1/quotes")
+        .add("/", post(create))
+        .add("/{ticker}", get(latest))
+}
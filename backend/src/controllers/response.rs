@@ -0,0 +1,20 @@
+//! Shared response helpers.
+//!
+//! Used by the upload and import controllers
+//! ([`historicals`](super::historicals), [`daily_prices`](super::daily_prices),
+//! [`tickers`](super::tickers)) to return a report with a non-200 status.
+
+use loco_rs::prelude::*;
+
+/// Build a JSON response with the given status.
+pub fn json_with_status<T: serde::Serialize>(
+    status: axum::http::StatusCode,
+    body: &T,
+) -> Result<Response> {
+    let body = serde_json::to_string(body).map_err(|e| Error::string(&e.to_string()))?;
+    Response::builder()
+        .status(status)
+        .header("Content-Type", "application/json")
+        .body(body.into())
+        .map_err(|e| Error::string(&e.to_string()))
+}
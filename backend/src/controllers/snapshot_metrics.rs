@@ -4,7 +4,8 @@
 //! controllers to avoid duplicating JSON extraction logic.
 
 use steady_invest_logic::{
-    compute_upside_downside_from_snapshot, extract_snapshot_prices, AnalysisSnapshot,
+    calculate_valuation_zones, compute_return_metrics_from_snapshot,
+    compute_upside_downside_from_snapshot, extract_snapshot_prices, AnalysisSnapshot, PriceZone,
 };
 
 /// Key projection metrics extracted from `snapshot_data` JSON.
@@ -36,15 +37,32 @@ pub fn extract_projection_metrics(snapshot_data: &serde_json::Value) -> Projecti
 
 /// Monetary and derived fields extracted from snapshot JSON data.
 ///
-/// Deserializes into [`AnalysisSnapshot`] and extracts native currency,
-/// current price, target prices, and upside/downside ratio.
+/// Deserializes into [`AnalysisSnapshot`] and extracts the currency the
+/// prices are in, current price and its quote time, target prices, upside/downside ratio, the
+/// NAIC Section 4C price zones and the Section 5 Relative Value, Present Yield
+/// and Projected Average Return.
 #[derive(Debug)]
 pub struct MonetaryFields {
+    /// Currency of every price below: the display currency of normalized
+    /// data, else the reporting currency.
     pub native_currency: Option<String>,
     pub current_price: Option<f64>,
+    pub price_as_of: Option<chrono::DateTime<chrono::Utc>>,
     pub target_high_price: Option<f64>,
     pub target_low_price: Option<f64>,
     pub upside_downside_ratio: Option<f64>,
+    /// Zone of the current price within the forecast range.
+    pub valuation_zone: Option<PriceZone>,
+    /// Upper bound of the Buy zone.
+    pub buy_below_price: Option<f64>,
+    /// Lower bound of the Sell zone.
+    pub sell_above_price: Option<f64>,
+    /// Current P/E as a percentage of the 5-year average P/E.
+    pub relative_value: Option<f64>,
+    /// Latest annual dividend as a percentage of the current price.
+    pub present_yield: Option<f64>,
+    /// Projected Average Return (PAR), in percent per year.
+    pub projected_average_return: Option<f64>,
 }
 
 /// Extract monetary fields by deserializing snapshot data via `steady-invest-logic`.
@@ -55,21 +73,43 @@ pub fn extract_monetary_fields(snapshot_data: &serde_json::Value) -> MonetaryFie
         return MonetaryFields {
             native_currency: None,
             current_price: None,
+            price_as_of: None,
             target_high_price: None,
             target_low_price: None,
             upside_downside_ratio: None,
+            valuation_zone: None,
+            buy_below_price: None,
+            sell_above_price: None,
+            relative_value: None,
+            present_yield: None,
+            projected_average_return: None,
         };
     };
 
-    let native_currency = Some(snapshot.historical_data.currency.clone());
+    // Prices follow the normalized figures; the quote is converted to match
+    let data = &snapshot.historical_data;
+    let native_currency = Some(
+        data.display_currency
+            .clone()
+            .unwrap_or_else(|| data.currency.clone()),
+    );
     let prices = extract_snapshot_prices(&snapshot);
     let upside_downside_ratio = compute_upside_downside_from_snapshot(&snapshot);
+    let zones = calculate_valuation_zones(&snapshot);
+    let returns = compute_return_metrics_from_snapshot(&snapshot);
 
     MonetaryFields {
         native_currency,
         current_price: prices.current_price,
+        price_as_of: prices.price_as_of,
         target_high_price: prices.target_high_price,
         target_low_price: prices.target_low_price,
         upside_downside_ratio,
+        valuation_zone: zones.current_zone,
+        buy_below_price: zones.price_zones.as_ref().map(|z| z.buy_below),
+        sell_above_price: zones.price_zones.as_ref().map(|z| z.sell_above),
+        relative_value: returns.relative_value,
+        present_yield: returns.present_yield,
+        projected_average_return: returns.projected_average_return,
     }
 }
@@ -13,6 +13,7 @@ use serde::{Deserialize, Serialize};
 
 use super::snapshot_metrics::{extract_monetary_fields, extract_projection_metrics};
 use crate::models::_entities::{analysis_snapshots, tickers};
+use crate::services::quotes;
 
 /// Maximum base64-encoded chart image size (5 MB).
 const MAX_CHART_IMAGE_BASE64_LEN: usize = 5 * 1024 * 1024;
@@ -47,7 +48,8 @@ pub struct SnapshotQueryParams {
 ///
 /// Excludes `snapshot_data` and `chart_image` to keep payloads small.
 /// Includes ticker symbol (resolved via join) and key metrics extracted
-/// from `snapshot_data` for Compact Analysis Card rendering.
+/// from `snapshot_data` for Compact Analysis Card rendering, including the
+/// NAIC Section 5 metrics (in percent).
 #[derive(Debug, Serialize)]
 pub struct SnapshotSummary {
     pub id: i32,
@@ -60,6 +62,9 @@ pub struct SnapshotSummary {
     pub projected_eps_cagr: Option<f64>,
     pub projected_high_pe: Option<f64>,
     pub projected_low_pe: Option<f64>,
+    pub relative_value: Option<f64>,
+    pub present_yield: Option<f64>,
+    pub projected_average_return: Option<f64>,
 }
 
 impl SnapshotSummary {
@@ -69,6 +74,7 @@ impl SnapshotSummary {
             .map(|t| t.ticker)
             .unwrap_or_else(|| format!("ID:{}", m.ticker_id));
         let proj = extract_projection_metrics(&m.snapshot_data);
+        let monetary = extract_monetary_fields(&m.snapshot_data);
         Self {
             id: m.id,
             ticker_id: m.ticker_id,
@@ -80,6 +86,9 @@ impl SnapshotSummary {
             projected_eps_cagr: proj.projected_eps_cagr,
             projected_high_pe: proj.projected_high_pe,
             projected_low_pe: proj.projected_low_pe,
+            relative_value: monetary.relative_value,
+            present_yield: monetary.present_yield,
+            projected_average_return: monetary.projected_average_return,
         }
     }
 }
@@ -99,6 +108,8 @@ pub struct HistoryEntry {
     pub projected_high_pe: Option<f64>,
     pub projected_low_pe: Option<f64>,
     pub current_price: Option<f64>,
+    /// When `current_price` was quoted; `None` when it is a historical high.
+    pub price_as_of: Option<chrono::DateTime<chrono::Utc>>,
     pub target_high_price: Option<f64>,
     pub target_low_price: Option<f64>,
     pub native_currency: Option<String>,
@@ -148,6 +159,7 @@ impl HistoryEntry {
             projected_high_pe: proj.projected_high_pe,
             projected_low_pe: proj.projected_low_pe,
             current_price: monetary.current_price,
+            price_as_of: monetary.price_as_of,
             target_high_price: monetary.target_high_price,
             target_low_price: monetary.target_low_price,
             native_currency: monetary.native_currency,
@@ -230,23 +242,21 @@ pub async fn create_snapshot(
     Json(req): Json<CreateSnapshotRequest>,
 ) -> Result<Response> {
     // Resolve ticker_id: use provided value or look up by ticker symbol
-    let ticker_id = match (req.ticker_id, &req.ticker) {
+    let ticker = match (req.ticker_id, &req.ticker) {
         (Some(id), _) => {
             // Validate that the ticker exists
             tickers::Entity::find_by_id(id)
                 .one(&ctx.db)
                 .await?
-                .ok_or_else(|| Error::NotFound)?;
-            id
+                .ok_or_else(|| Error::NotFound)?
         }
         (None, Some(symbol)) => {
             let normalized = symbol.to_uppercase();
-            let t = tickers::Entity::find()
+            tickers::Entity::find()
                 .filter(tickers::Column::Ticker.eq(normalized.as_str()))
                 .one(&ctx.db)
                 .await?
-                .ok_or_else(|| Error::NotFound)?;
-            t.id
+                .ok_or_else(|| Error::NotFound)?
         }
         (None, None) => {
             return bad_request("Either ticker_id or ticker must be provided");
@@ -270,10 +280,14 @@ pub async fn create_snapshot(
         None
     };
 
+    // Snapshots without a quote capture the latest stored one
+    let mut snapshot_data = req.snapshot_data;
+    quotes::attach_latest_json(&ctx.db, &ticker.ticker, &mut snapshot_data).await?;
+
     let active = analysis_snapshots::ActiveModel {
         user_id: ActiveValue::set(1), // default single-user until Phase 3
-        ticker_id: ActiveValue::set(ticker_id),
-        snapshot_data: ActiveValue::set(req.snapshot_data),
+        ticker_id: ActiveValue::set(ticker.id),
+        snapshot_data: ActiveValue::set(snapshot_data),
         thesis_locked: ActiveValue::set(req.thesis_locked),
         chart_image: ActiveValue::set(chart_image_bytes),
         notes: ActiveValue::set(req.notes),
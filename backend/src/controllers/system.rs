@@ -6,6 +6,7 @@
 use crate::middlewares::auth_ip::auth_local_ip;
 use crate::models::audit_logs;
 use crate::services::provider_health;
+use crate::services::providers::ProviderRegistry;
 use axum::middleware;
 use loco_rs::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -47,7 +48,8 @@ impl From<audit_logs::Model> for AuditResponse {
 ///
 /// All endpoints are restricted to local subnets via security middleware.
 pub async fn health(State(ctx): State<AppContext>) -> Result<Response> {
-    let health_data = provider_health::check_providers(&ctx.db).await;
+    let health_data =
+        provider_health::check_providers(&ctx.db, &ProviderRegistry::from_env(&ctx.db)).await?;
     format::json(health_data)
 }
 
@@ -1,49 +1,108 @@
-//! Ticker search and autocomplete controller.
+//! Ticker search, autocomplete and universe import controller.
 //!
-//! Exposes `GET /api/tickers/search?q=…` for fuzzy-matching tickers by
-//! symbol, company name, or exchange.
+//! Exposes `GET /api/tickers/search?q=…` for ranked, paginated matching of
+//! tickers by symbol, company name, exchange, ISIN, valor or WKN, and
+//! `POST /api/tickers/import` for loading index constituent files.
 
-use crate::models::tickers;
+use super::response::json_with_status;
+use crate::services::ticker_import::{self, IndexUniverse};
+use crate::services::ticker_search;
+use axum::extract::Multipart;
 use loco_rs::prelude::*;
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 use serde::Deserialize;
 
 /// Query parameters for the ticker search endpoint.
 #[derive(Deserialize)]
 pub struct SearchParams {
-    /// The search query string (matched against ticker, name, and exchange).
+    /// The search query: a symbol, company name, exchange, ISIN, valor or WKN.
     pub q: String,
+    /// Results per page, at most [`ticker_search::MAX_LIMIT`].
+    pub limit: Option<usize>,
+    /// Number of ranked results to skip.
+    pub offset: Option<usize>,
 }
 
-/// Searches tickers by symbol, name, or exchange using a LIKE query.
+/// Query parameters for the ticker import endpoint.
+#[derive(Deserialize)]
+pub struct ImportParams {
+    /// Index the file lists (`SMI`, `SPI`, `DAX`, `MDAX` or `SP500`).
+    pub index: Option<String>,
+}
+
+/// Searches tickers, ranking exact symbol and identifier matches first, then
+/// symbol prefixes, then name matches with diacritics folded and typos
+/// tolerated.
 ///
-/// **GET** `/api/tickers/search?q={query}`
+/// **GET** `/api/tickers/search?q={query}&limit=10&offset=0`
 ///
-/// Returns a JSON array of [`steady_invest_logic::TickerInfo`] matches.
+/// Returns a [`steady_invest_logic::TickerSearchPage`].
 ///
 /// # Errors
 ///
-/// Returns a database error if the query execution fails.
+/// Returns a database error if the ticker universe cannot be loaded.
 #[debug_handler]
 pub async fn search(
     State(ctx): State<AppContext>,
     Query(params): Query<SearchParams>,
 ) -> Result<Response> {
-    let query = format!("%{}%", params.q);
-    let tickers = tickers::Entity::find()
-        .filter(
-            sea_orm::Condition::any()
-                .add(tickers::Column::Ticker.like(&query))
-                .add(tickers::Column::Name.like(&query))
-                .add(tickers::Column::Exchange.like(&query)),
-        )
-        .all(&ctx.db)
-        .await?;
-
-    let results: Vec<steady_invest_logic::TickerInfo> =
-        tickers.into_iter().map(|t| t.to_ticker_info()).collect();
-
-    format::json(results)
+    let page = ticker_search::search(
+        &ctx.db,
+        &params.q,
+        params.offset.unwrap_or(0),
+        params.limit.unwrap_or(ticker_search::DEFAULT_LIMIT),
+    )
+    .await?;
+    format::json(page)
+}
+
+/// Imports an index constituent file into the ticker universe.
+///
+/// **POST** `/api/tickers/import?index=SMI` (multipart, field `file`)
+///
+/// Returns the [`ticker_import::TickerImportReport`]. When any row is invalid
+/// nothing is stored and the report lists every problem with its row number.
+///
+/// # Errors
+///
+/// Returns `400 Bad Request` for an unknown index or a missing `file` field,
+/// and `422 Unprocessable Entity` (with the report) if validation fails.
+#[debug_handler]
+pub async fn import(
+    State(ctx): State<AppContext>,
+    Query(params): Query<ImportParams>,
+    mut multipart: Multipart,
+) -> Result<Response> {
+    let index = match params.index.as_deref() {
+        Some(name) => Some(
+            IndexUniverse::parse(name)
+                .ok_or_else(|| Error::BadRequest(format!("Unknown index '{name}'")))?,
+        ),
+        None => None,
+    };
+
+    let mut upload = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| Error::BadRequest(format!("Invalid multipart body: {e}")))?
+    {
+        if field.name() != Some("file") {
+            continue;
+        }
+        let bytes = field
+            .bytes()
+            .await
+            .map_err(|e| Error::BadRequest(format!("Failed to read upload: {e}")))?;
+        upload = Some(bytes);
+    }
+    let bytes = upload.ok_or_else(|| Error::BadRequest("Missing 'file' field".to_string()))?;
+
+    let report = ticker_import::import_tickers(&ctx.db, index, &bytes).await?;
+    if report.errors.is_empty() {
+        format::json(report)
+    } else {
+        json_with_status(axum::http::StatusCode::UNPROCESSABLE_ENTITY, &report)
+    }
 }
 
 /// Registers ticker routes under `/api/tickers`.
@@ -51,4 +110,5 @@ pub fn routes() -> Routes {
     Routes::new()
         .prefix("api/tickers")
         .add("/search", get(search))
+        .add("/import", post(import))
 }
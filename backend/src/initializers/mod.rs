@@ -3,3 +3,7 @@
 //! Initializers run once during application boot (before the HTTP server
 //! starts accepting requests). Use them for one-time setup such as seeding
 //! caches or validating external service connectivity.
+//!
+//! - [`refresh_scheduler`] — Periodic `refresh_all` run (`REFRESH_INTERVAL_HOURS`)
+
+pub mod refresh_scheduler;
@@ -0,0 +1,60 @@
+//! In-process scheduler for the `refresh_all` run.
+//!
+//! Disabled unless `REFRESH_INTERVAL_HOURS` is set to a positive number.
+//! When enabled, the server refreshes every tracked ticker once per interval,
+//! starting one interval after boot. Deployments that prefer an external
+//! scheduler (cron, systemd timers) can run `cargo loco task refresh_all`
+//! instead.
+
+use crate::services::providers::ProviderRegistry;
+use crate::services::refresh::{self, RefreshOptions};
+use async_trait::async_trait;
+use loco_rs::{
+    app::{AppContext, Initializer},
+    Result,
+};
+use std::time::Duration;
+
+/// Reads the refresh interval from `REFRESH_INTERVAL_HOURS`.
+fn interval_from_env() -> Option<Duration> {
+    std::env::var("REFRESH_INTERVAL_HOURS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .filter(|hours| *hours > 0)
+        .map(|hours| Duration::from_secs(hours * 3600))
+}
+
+/// Spawns the periodic refresh loop when an interval is configured.
+pub struct RefreshScheduler;
+
+#[async_trait]
+impl Initializer for RefreshScheduler {
+    fn name(&self) -> String {
+        "refresh-scheduler".to_string()
+    }
+
+    async fn before_run(&self, ctx: &AppContext) -> Result<()> {
+        let Some(interval) = interval_from_env() else {
+            return Ok(());
+        };
+
+        tracing::info!(?interval, "Scheduled refresh enabled");
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            let mut ticker =
+                tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                let registry = ProviderRegistry::from_env(&ctx.db);
+                match refresh::refresh_all(&ctx, &registry, RefreshOptions::from_env()).await {
+                    Ok(report) => {
+                        tracing::info!(summary = %report.summary(), "Scheduled refresh finished")
+                    }
+                    Err(e) => tracing::error!(error = %e, "Scheduled refresh failed"),
+                }
+            }
+        });
+        Ok(())
+    }
+}
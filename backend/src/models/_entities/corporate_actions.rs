@@ -0,0 +1,28 @@
+//! `SeaORM` Entity for `corporate_actions` table.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "corporate_actions")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub ticker: String,
+    #[sea_orm(column_type = "String(StringLen::N(20))")]
+    pub kind: String,
+    #[sea_orm(column_type = "Decimal(Some((19, 8)))")]
+    pub ratio: Decimal,
+    pub effective_date: Date,
+    pub source: String,
+    pub note: Option<String>,
+    pub applied_at: Option<DateTimeWithTimeZone>,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+    pub deleted_at: Option<DateTimeWithTimeZone>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,30 @@
+//! `SeaORM` Entity for `daily_prices` table.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "daily_prices")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub ticker: String,
+    pub trade_date: Date,
+    #[sea_orm(column_type = "Decimal(Some((19, 4)))")]
+    pub open: Decimal,
+    #[sea_orm(column_type = "Decimal(Some((19, 4)))")]
+    pub high: Decimal,
+    #[sea_orm(column_type = "Decimal(Some((19, 4)))")]
+    pub low: Decimal,
+    #[sea_orm(column_type = "Decimal(Some((19, 4)))")]
+    pub close: Decimal,
+    #[sea_orm(column_type = "Decimal(Some((19, 4)))", nullable)]
+    pub adjusted_close: Option<Decimal>,
+    pub volume: Option<i64>,
+    pub source: String,
+    pub created_at: DateTimeWithTimeZone,
+    pub updated_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
@@ -13,6 +13,8 @@ pub struct Model {
     pub fiscal_year: i32,
     #[sea_orm(column_type = "Decimal(Some((19, 4)))")]
     pub rate: Decimal,
+    #[sea_orm(column_type = "Decimal(Some((19, 4)))", nullable)]
+    pub year_end_rate: Option<Decimal>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -20,7 +20,7 @@ pub struct Model {
     pub price_low: Decimal,
     pub currency: String,
     pub is_split_adjusted: Option<bool>,
-    #[sea_orm(column_type = "Decimal(Some((10, 0)))", nullable)]
+    #[sea_orm(column_type = "Decimal(Some((19, 8)))", nullable)]
     pub adjustment_factor: Option<Decimal>,
     #[sea_orm(column_type = "Decimal(Some((19, 4)))", nullable)]
     pub net_income: Option<Decimal>,
@@ -32,6 +32,7 @@ pub struct Model {
     pub dividend_per_share: Option<Decimal>,
     #[sea_orm(column_type = "Decimal(Some((19, 4)))", nullable)]
     pub shares_outstanding: Option<Decimal>,
+    pub source: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -0,0 +1,34 @@
+//! `SeaORM` Entity for `historicals_quarterly` table.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "historicals_quarterly")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub ticker: String,
+    pub fiscal_year: i32,
+    pub fiscal_quarter: i16,
+    pub period_end: Date,
+    #[sea_orm(column_type = "Decimal(Some((19, 4)))")]
+    pub sales: Decimal,
+    #[sea_orm(column_type = "Decimal(Some((19, 4)))")]
+    pub eps: Decimal,
+    #[sea_orm(column_type = "Decimal(Some((19, 4)))", nullable)]
+    pub net_income: Option<Decimal>,
+    #[sea_orm(column_type = "Decimal(Some((19, 4)))", nullable)]
+    pub pretax_income: Option<Decimal>,
+    #[sea_orm(column_type = "Decimal(Some((19, 4)))", nullable)]
+    pub price_high: Option<Decimal>,
+    #[sea_orm(column_type = "Decimal(Some((19, 4)))", nullable)]
+    pub price_low: Option<Decimal>,
+    pub currency: String,
+    #[sea_orm(column_type = "Decimal(Some((19, 8)))", nullable)]
+    pub adjustment_factor: Option<Decimal>,
+    pub source: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
@@ -6,9 +6,16 @@ pub mod analysis_snapshots;
 pub mod audit_logs;
 pub mod comparison_set_items;
 pub mod comparison_sets;
+pub mod corporate_actions;
+pub mod daily_prices;
 pub mod exchange_rates;
+pub mod harvest_jobs;
 pub mod historicals;
 pub mod historicals_overrides;
+pub mod historicals_quarterly;
+pub mod month_end_exchange_rates;
+pub mod provider_health_checks;
 pub mod provider_rate_limits;
+pub mod quotes;
 pub mod tickers;
 pub mod users;
@@ -0,0 +1,19 @@
+//! `SeaORM` Entity for `month_end_exchange_rates` table.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "month_end_exchange_rates")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub from_currency: String,
+    pub to_currency: String,
+    pub month_end: Date,
+    #[sea_orm(column_type = "Decimal(Some((19, 4)))")]
+    pub rate: Decimal,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
@@ -4,9 +4,16 @@ pub use super::analysis_snapshots::Entity as AnalysisSnapshots;
 pub use super::audit_logs::Entity as AuditLogs;
 pub use super::comparison_set_items::Entity as ComparisonSetItems;
 pub use super::comparison_sets::Entity as ComparisonSets;
+pub use super::corporate_actions::Entity as CorporateActions;
+pub use super::daily_prices::Entity as DailyPrices;
 pub use super::exchange_rates::Entity as ExchangeRates;
+pub use super::harvest_jobs::Entity as HarvestJobs;
 pub use super::historicals::Entity as Historicals;
 pub use super::historicals_overrides::Entity as HistoricalsOverrides;
+pub use super::historicals_quarterly::Entity as HistoricalsQuarterly;
+pub use super::month_end_exchange_rates::Entity as MonthEndExchangeRates;
+pub use super::provider_health_checks::Entity as ProviderHealthChecks;
 pub use super::provider_rate_limits::Entity as ProviderRateLimits;
+pub use super::quotes::Entity as Quotes;
 pub use super::tickers::Entity as Tickers;
 pub use super::users::Entity as Users;
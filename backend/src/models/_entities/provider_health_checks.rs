@@ -0,0 +1,20 @@
+//! `SeaORM` Entity for `provider_health_checks` table.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "provider_health_checks")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub provider: String,
+    pub checked_at: DateTimeWithTimeZone,
+    pub success: bool,
+    pub latency_ms: i32,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub error: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
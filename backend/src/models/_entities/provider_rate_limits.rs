@@ -3,7 +3,7 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
 #[sea_orm(table_name = "provider_rate_limits")]
 pub struct Model {
     #[sea_orm(primary_key)]
@@ -12,6 +12,10 @@ pub struct Model {
     pub name: String,
     pub quota_consumed: i32,
     pub last_updated: DateTime,
+    #[sea_orm(column_type = "Double", nullable)]
+    pub minute_tokens: Option<f64>,
+    #[sea_orm(column_type = "Double", nullable)]
+    pub day_tokens: Option<f64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
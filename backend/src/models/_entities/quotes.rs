@@ -0,0 +1,21 @@
+//! `SeaORM` Entity for `quotes` table.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "quotes")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub ticker: String,
+    #[sea_orm(column_type = "Decimal(Some((19, 4)))")]
+    pub price: Decimal,
+    pub currency: String,
+    pub as_of: DateTimeWithTimeZone,
+    pub source: String,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
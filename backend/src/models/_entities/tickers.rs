@@ -15,6 +15,10 @@ pub struct Model {
     pub name: String,
     pub exchange: String,
     pub currency: String,
+    pub fiscal_year_end_month: i16,
+    pub isin: Option<String>,
+    pub valor: Option<String>,
+    pub wkn: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
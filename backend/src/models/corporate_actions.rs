@@ -0,0 +1,35 @@
+//! Corporate action model — per-ticker ledger of splits and spin-offs.
+
+pub use super::_entities::corporate_actions::{self, ActiveModel, Column, Entity, Model};
+use loco_rs::prelude::*;
+use sea_orm::QueryOrder;
+
+impl Model {
+    /// Returns the non-deleted actions for `ticker`, oldest first.
+    pub async fn find_active_for_ticker(
+        db: &impl ConnectionTrait,
+        ticker: &str,
+    ) -> Result<Vec<Self>, DbErr> {
+        Entity::find()
+            .filter(Column::Ticker.eq(ticker))
+            .filter(Column::DeletedAt.is_null())
+            .order_by_asc(Column::EffectiveDate)
+            .all(db)
+            .await
+    }
+
+    /// Finds the ledger entry for an action, including deleted ones.
+    pub async fn find_entry(
+        db: &impl ConnectionTrait,
+        ticker: &str,
+        effective_date: chrono::NaiveDate,
+        kind: &str,
+    ) -> Result<Option<Self>, DbErr> {
+        Entity::find()
+            .filter(Column::Ticker.eq(ticker))
+            .filter(Column::EffectiveDate.eq(effective_date))
+            .filter(Column::Kind.eq(kind))
+            .one(db)
+            .await
+    }
+}
@@ -0,0 +1,40 @@
+//! Daily price model.
+//!
+//! Wraps the `daily_prices` entity storing one OHLCV bar per ticker and
+//! trading day, as traded. Yearly price ranges are derived from it.
+
+pub use super::_entities::daily_prices::{self, ActiveModel, Column, Entity, Model};
+use chrono::NaiveDate;
+use sea_orm::{entity::prelude::*, QueryOrder};
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Entity {
+    /// Stored bars for `ticker` from `from` through `to`, oldest first.
+    pub async fn find_range(
+        db: &impl ConnectionTrait,
+        ticker: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<Model>, DbErr> {
+        Self::find()
+            .filter(Column::Ticker.eq(ticker))
+            .filter(Column::TradeDate.between(from, to))
+            .order_by_asc(Column::TradeDate)
+            .all(db)
+            .await
+    }
+
+    /// Date of the newest stored bar for `ticker`.
+    pub async fn latest_date(
+        db: &impl ConnectionTrait,
+        ticker: &str,
+    ) -> Result<Option<NaiveDate>, DbErr> {
+        Ok(Self::find()
+            .filter(Column::Ticker.eq(ticker))
+            .order_by_desc(Column::TradeDate)
+            .one(db)
+            .await?
+            .map(|m| m.trade_date))
+    }
+}
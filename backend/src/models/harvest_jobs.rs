@@ -0,0 +1,75 @@
+//! Harvest job model — status of background harvests run by the
+//! [`DownloadWorker`](crate::workers::downloader::DownloadWorker).
+
+pub use super::_entities::harvest_jobs::{self, ActiveModel, Column, Entity, Model};
+use loco_rs::prelude::*;
+
+/// Job is waiting for a worker.
+pub const QUEUED: &str = "queued";
+/// A worker is harvesting the ticker.
+pub const RUNNING: &str = "running";
+/// Harvest succeeded; `result` holds the outcome.
+pub const DONE: &str = "done";
+/// Harvest failed; `error` holds the message.
+pub const FAILED: &str = "failed";
+
+impl Model {
+    /// Inserts a queued job for `ticker`, converting to `display_currency`
+    /// (USD when `None`).
+    pub async fn enqueue(
+        db: &impl ConnectionTrait,
+        ticker: &str,
+        refresh: bool,
+        display_currency: Option<&str>,
+    ) -> Result<Self, DbErr> {
+        let now = chrono::Utc::now();
+        ActiveModel {
+            ticker: ActiveValue::set(ticker.to_string()),
+            refresh: ActiveValue::set(refresh),
+            display_currency: ActiveValue::set(display_currency.map(str::to_string)),
+            status: ActiveValue::set(QUEUED.to_string()),
+            error: ActiveValue::set(None),
+            result: ActiveValue::set(None),
+            started_at: ActiveValue::set(None),
+            finished_at: ActiveValue::set(None),
+            created_at: ActiveValue::set(now.into()),
+            updated_at: ActiveValue::set(now.into()),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+    }
+
+    /// Marks the job as picked up by a worker.
+    pub async fn mark_running(self, db: &impl ConnectionTrait) -> Result<Self, DbErr> {
+        let now = chrono::Utc::now();
+        let mut active: ActiveModel = self.into();
+        active.status = ActiveValue::set(RUNNING.to_string());
+        active.started_at = ActiveValue::set(Some(now.into()));
+        active.updated_at = ActiveValue::set(now.into());
+        active.update(db).await
+    }
+
+    /// Marks the job as finished, storing either its result or its error.
+    pub async fn finish(
+        self,
+        db: &impl ConnectionTrait,
+        outcome: std::result::Result<serde_json::Value, String>,
+    ) -> Result<Self, DbErr> {
+        let now = chrono::Utc::now();
+        let mut active: ActiveModel = self.into();
+        match outcome {
+            Ok(result) => {
+                active.status = ActiveValue::set(DONE.to_string());
+                active.result = ActiveValue::set(Some(result));
+            }
+            Err(error) => {
+                active.status = ActiveValue::set(FAILED.to_string());
+                active.error = ActiveValue::set(Some(error));
+            }
+        }
+        active.finished_at = ActiveValue::set(Some(now.into()));
+        active.updated_at = ActiveValue::set(now.into());
+        active.update(db).await
+    }
+}
@@ -0,0 +1,25 @@
+//! Quarterly financial data model.
+//!
+//! Wraps the `historicals_quarterly` entity storing per-ticker, per-quarter
+//! figures (sales, EPS, income, price high/low) used for trailing-twelve-month
+//! EPS.
+
+pub use super::_entities::historicals_quarterly::{self, ActiveModel, Column, Entity, Model};
+use sea_orm::{entity::prelude::*, QueryOrder};
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Entity {
+    /// Stored quarters for `ticker`, oldest first.
+    pub async fn find_by_ticker(
+        db: &impl ConnectionTrait,
+        ticker: &str,
+    ) -> Result<Vec<Model>, DbErr> {
+        Self::find()
+            .filter(Column::Ticker.eq(ticker))
+            .order_by_asc(Column::FiscalYear)
+            .order_by_asc(Column::FiscalQuarter)
+            .all(db)
+            .await
+    }
+}
@@ -7,19 +7,33 @@
 //! - [`tickers`]               — Security ticker registry
 //! - [`historicals`]           — 10-year historical financial records
 //! - [`historicals_overrides`] — Manual data overrides per year/field
+//! - [`historicals_quarterly`] — Quarterly records for trailing-twelve-month EPS
 //! - [`exchange_rates`]        — Cached currency conversion rates
+//! - [`month_end_exchange_rates`] — Month-end rates for fiscal year-end closing rates
 //! - [`audit_logs`]            — Data-integrity and override audit trail
 //! - [`analysis_snapshots`]    — Persisted analysis snapshots (append-only)
+//! - [`provider_health_checks`] — Provider connectivity probes (latency time series)
 //! - [`provider_rate_limits`]  — API provider rate-limit tracking
+//! - [`quotes`]                — Market prices with their as-of time
+//! - [`daily_prices`]          — Daily OHLCV price series
+//! - [`corporate_actions`]     — Split / spin-off ledger driving adjustment factors
+//! - [`harvest_jobs`]          — Background harvest job status
 
 pub mod _entities;
 pub mod analysis_snapshots;
 pub mod audit_logs;
 pub mod comparison_set_items;
 pub mod comparison_sets;
+pub mod corporate_actions;
+pub mod daily_prices;
 pub mod exchange_rates;
+pub mod harvest_jobs;
 pub mod historicals;
 pub mod historicals_overrides;
+pub mod historicals_quarterly;
+pub mod month_end_exchange_rates;
+pub mod provider_health_checks;
 pub mod provider_rate_limits;
+pub mod quotes;
 pub mod tickers;
 pub mod users;
@@ -0,0 +1,11 @@
+//! Month-end exchange rate model.
+//!
+//! Wraps the `month_end_exchange_rates` entity storing the rate of each
+//! currency pair on the last trading day of every month, keyed by the
+//! month's last calendar day.
+
+pub use super::_entities::month_end_exchange_rates::{self, ActiveModel, Column, Entity, Model};
+
+use sea_orm::entity::prelude::*;
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,33 @@
+//! Provider health check model — one connectivity probe per row.
+
+pub use super::_entities::provider_health_checks::{self, ActiveModel, Column, Entity, Model};
+use chrono::{DateTime, Utc};
+use sea_orm::{entity::prelude::*, QueryOrder};
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Entity {
+    /// Checks of `provider` at or after `since`, oldest first.
+    pub async fn since(
+        db: &impl ConnectionTrait,
+        provider: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<Model>, DbErr> {
+        Self::find()
+            .filter(Column::Provider.eq(provider))
+            .filter(Column::CheckedAt.gte(since))
+            .order_by_asc(Column::CheckedAt)
+            .order_by_asc(Column::Id)
+            .all(db)
+            .await
+    }
+
+    /// Deletes every check older than `before`.
+    pub async fn prune(db: &impl ConnectionTrait, before: DateTime<Utc>) -> Result<u64, DbErr> {
+        Self::delete_many()
+            .filter(Column::CheckedAt.lt(before))
+            .exec(db)
+            .await
+            .map(|res| res.rows_affected)
+    }
+}
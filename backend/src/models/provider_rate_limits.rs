@@ -1,4 +1,5 @@
-//! Provider rate-limit model — tracks API quota consumption per provider.
+//! Provider rate-limit model — persisted token buckets and quota consumption
+//! per provider.
 
 pub use super::_entities::provider_rate_limits::{ActiveModel, Entity, Model};
 use loco_rs::prelude::*;
@@ -12,11 +13,16 @@ impl Model {
             .await
     }
 
-    /// Upserts the quota consumption for a provider (creates if not found).
-    pub async fn update_quota(
+    /// Upserts the token-bucket state of a provider (creates if not found).
+    ///
+    /// `quota_consumed` is the share of the daily allowance in use, in percent.
+    pub async fn save_bucket(
         db: &DatabaseConnection,
         name: &str,
-        consumed: i32,
+        minute_tokens: f64,
+        day_tokens: f64,
+        quota_consumed: i32,
+        updated_at: chrono::NaiveDateTime,
     ) -> Result<Self, DbErr> {
         let model = Self::find_by_name(db, name).await?;
         let mut active_model: ActiveModel = match model {
@@ -26,8 +32,10 @@ impl Model {
                 ..Default::default()
             },
         };
-        active_model.quota_consumed = Set(consumed);
-        active_model.last_updated = Set(chrono::Utc::now().naive_utc());
+        active_model.minute_tokens = Set(Some(minute_tokens));
+        active_model.day_tokens = Set(Some(day_tokens));
+        active_model.quota_consumed = Set(quota_consumed);
+        active_model.last_updated = Set(updated_at);
 
         let saved = active_model.save(db).await?;
         // Use sea_orm::TryIntoModel
@@ -0,0 +1,30 @@
+//! Quote model — market prices per ticker with the time they were observed.
+
+pub use super::_entities::quotes::{self, ActiveModel, Column, Entity, Model};
+use sea_orm::{entity::prelude::*, QueryOrder};
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    /// Converts the row to the shared [`steady_invest_logic::Quote`] DTO.
+    pub fn to_quote(&self) -> steady_invest_logic::Quote {
+        steady_invest_logic::Quote {
+            price: self.price,
+            currency: self.currency.clone(),
+            as_of: self.as_of.to_utc(),
+            source: self.source.clone(),
+        }
+    }
+}
+
+impl Entity {
+    /// The newest quote for `ticker`, if any.
+    pub async fn latest(db: &impl ConnectionTrait, ticker: &str) -> Result<Option<Model>, DbErr> {
+        Self::find()
+            .filter(Column::Ticker.eq(ticker))
+            .order_by_desc(Column::AsOf)
+            .order_by_desc(Column::Id)
+            .one(db)
+            .await
+    }
+}
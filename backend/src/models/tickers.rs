@@ -23,4 +23,13 @@ impl Model {
             currency: self.currency.clone(),
         }
     }
+
+    /// The ticker's fiscal calendar, falling back to calendar years when the
+    /// stored month is out of range.
+    pub fn fiscal_calendar(&self) -> steady_invest_logic::FiscalCalendar {
+        u32::try_from(self.fiscal_year_end_month)
+            .ok()
+            .and_then(steady_invest_logic::FiscalCalendar::new)
+            .unwrap_or_default()
+    }
 }
@@ -60,4 +60,35 @@ impl AuditService {
     ) -> Result<audit_logs::Model, DbErr> {
         Self::log_event(db, ticker, exchange, field, old, new, "Override", "User").await
     }
+
+    /// Records the summary of a bulk refresh of all tracked tickers.
+    pub async fn log_refresh_summary(
+        db: &DatabaseConnection,
+        summary: &str,
+    ) -> Result<audit_logs::Model, DbErr> {
+        Self::log_event(
+            db,
+            "ALL",
+            "ALL",
+            "refresh_all",
+            None,
+            Some(summary.to_string()),
+            "RefreshSummary",
+            "System",
+        )
+        .await
+    }
+
+    /// Records a stored value replaced because the provider restated it.
+    pub async fn log_restatement(
+        db: &DatabaseConnection,
+        ticker: &str,
+        exchange: &str,
+        field: &str,
+        old: Option<String>,
+        new: Option<String>,
+        source: &str,
+    ) -> Result<audit_logs::Model, DbErr> {
+        Self::log_event(db, ticker, exchange, field, old, new, "Restatement", source).await
+    }
 }
@@ -0,0 +1,184 @@
+//! Corporate actions ledger service.
+//!
+//! The `corporate_actions` table is the single source of per-share basis
+//! changes. Provider-reported actions are copied into it during harvest,
+//! analysts maintain it through the CRUD endpoints, and each fiscal year's
+//! cumulative `adjustment_factor` is computed from it.
+//!
+//! The first time an action changes the factor of a harvested year it is
+//! written to the audit log and stamped with `applied_at`; editing an action
+//! clears the stamp so the new values are audited again.
+
+use super::audit_service::AuditService;
+use super::providers::{CorporateAction, CorporateActionKind};
+use crate::models::corporate_actions;
+use chrono::NaiveDate;
+use loco_rs::prelude::*;
+use rust_decimal::Decimal;
+use steady_invest_logic::FiscalCalendar;
+
+/// Value of `corporate_actions.source` for entries created through the API.
+pub const USER_SOURCE: &str = "user";
+
+/// A ledger entry paired with its parsed action.
+#[derive(Debug, Clone)]
+pub struct LedgerEntry {
+    pub model: corporate_actions::Model,
+    pub action: CorporateAction,
+}
+
+/// Validates a ratio for `kind`.
+///
+/// # Errors
+///
+/// Returns a message describing why the ratio is not acceptable.
+pub fn validate_ratio(
+    kind: CorporateActionKind,
+    ratio: Decimal,
+) -> std::result::Result<(), String> {
+    if ratio <= Decimal::ZERO {
+        return Err("Ratio must be positive".to_string());
+    }
+    match kind {
+        CorporateActionKind::Split | CorporateActionKind::StockDividend
+            if ratio <= Decimal::ONE =>
+        {
+            Err(format!("A {} ratio must be greater than 1", kind.as_str()))
+        }
+        CorporateActionKind::ReverseSplit if ratio >= Decimal::ONE => {
+            Err("A reverse_split ratio must be less than 1".to_string())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Copies provider-reported actions missing from the ledger into it.
+///
+/// Entries that already exist — including ones an analyst deleted or edited —
+/// are left untouched, so the ledger always wins over the provider.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails.
+pub async fn sync_provider_actions(
+    db: &DatabaseConnection,
+    ticker: &str,
+    actions: &[CorporateAction],
+    source: &str,
+) -> Result<()> {
+    for action in actions {
+        let kind = action.kind.as_str();
+        if corporate_actions::Model::find_entry(db, ticker, action.effective_date, kind)
+            .await?
+            .is_some()
+        {
+            continue;
+        }
+
+        let now = chrono::Utc::now();
+        corporate_actions::ActiveModel {
+            ticker: ActiveValue::set(ticker.to_string()),
+            kind: ActiveValue::set(kind.to_string()),
+            ratio: ActiveValue::set(action.ratio),
+            effective_date: ActiveValue::set(action.effective_date),
+            source: ActiveValue::set(source.to_string()),
+            note: ActiveValue::set(None),
+            applied_at: ActiveValue::set(None),
+            created_at: ActiveValue::set(now.into()),
+            updated_at: ActiveValue::set(now.into()),
+            deleted_at: ActiveValue::set(None),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Loads the active ledger entries for `ticker`, skipping unknown kinds.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub async fn ledger(db: &DatabaseConnection, ticker: &str) -> Result<Vec<LedgerEntry>> {
+    let models = corporate_actions::Model::find_active_for_ticker(db, ticker).await?;
+    Ok(models
+        .into_iter()
+        .filter_map(|model| {
+            let kind = CorporateActionKind::parse(&model.kind)?;
+            let action = CorporateAction {
+                effective_date: model.effective_date,
+                kind,
+                ratio: model.ratio,
+            };
+            Some(LedgerEntry { model, action })
+        })
+        .collect())
+}
+
+/// Cumulative share-count ratio of all share-basis actions taking effect
+/// after `date`, used for periods ending on `date` (fiscal years and
+/// quarters).
+///
+/// A 4-for-1 split effective in August 2020 yields a factor of `4` for a
+/// fiscal year ending in June 2020 and `1` for one ending in September 2020.
+pub fn adjustment_factor_at<'a>(
+    actions: impl IntoIterator<Item = &'a CorporateAction>,
+    date: NaiveDate,
+) -> Decimal {
+    actions
+        .into_iter()
+        .filter(|a| a.effective_date > date && !a.ratio.is_zero())
+        .filter(|a| a.kind.changes_share_basis())
+        .fold(Decimal::ONE, |acc, a| acc * a.ratio)
+}
+
+/// Audits the ledger entries that adjust at least one of the fiscal `years`
+/// and have not been audited yet, then stamps them with `applied_at`.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails.
+pub async fn audit_applied(
+    db: &DatabaseConnection,
+    ticker: &str,
+    exchange: &str,
+    entries: &[LedgerEntry],
+    years: &[i32],
+    calendar: FiscalCalendar,
+) -> Result<()> {
+    let Some(earliest) = years.iter().min() else {
+        return Ok(());
+    };
+    let earliest_end = calendar.end(*earliest);
+
+    for entry in entries {
+        let applies =
+            entry.action.kind.changes_share_basis() && entry.action.effective_date > earliest_end;
+        if !applies || entry.model.applied_at.is_some() {
+            continue;
+        }
+
+        AuditService::log_event(
+            db,
+            ticker,
+            exchange,
+            "adjustment_factor",
+            None,
+            Some(format!(
+                "{} {} effective {}",
+                entry.action.kind.as_str(),
+                entry.action.ratio.normalize(),
+                entry.action.effective_date
+            )),
+            "CorporateAction",
+            &entry.model.source,
+        )
+        .await?;
+
+        let mut active: corporate_actions::ActiveModel = entry.model.clone().into();
+        active.applied_at = ActiveValue::set(Some(chrono::Utc::now().into()));
+        active.update(db).await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod tests {
+    use crate::services::corporate_actions::{adjustment_factor_at, validate_ratio};
+    use crate::services::providers::{CorporateAction, CorporateActionKind};
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+    use steady_invest_logic::FiscalCalendar;
+
+    fn action(date: &str, kind: CorporateActionKind, ratio: &str) -> CorporateAction {
+        CorporateAction {
+            effective_date: NaiveDate::from_str(date).unwrap(),
+            kind,
+            ratio: Decimal::from_str(ratio).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_adjustment_factor_compounds_later_actions() {
+        let actions = vec![
+            action("2014-06-09", CorporateActionKind::Split, "7"),
+            action("2020-08-31", CorporateActionKind::Split, "4"),
+            action("2021-03-01", CorporateActionKind::ReverseSplit, "0.5"),
+            action("2019-05-01", CorporateActionKind::SpinOff, "1.2"),
+        ];
+        let september = FiscalCalendar::new(9).unwrap();
+        let factor = |year: i32| adjustment_factor_at(&actions, september.end(year));
+
+        assert_eq!(factor(2013), Decimal::from(14));
+        assert_eq!(factor(2018), Decimal::from(2));
+        // The August split falls within FY2020, which ends in September
+        assert_eq!(factor(2020), Decimal::new(5, 1));
+        assert_eq!(factor(2021), Decimal::ONE);
+
+        // With a June year end the June 2014 split already falls in FY2014
+        let june = FiscalCalendar::new(6).unwrap();
+        assert_eq!(
+            adjustment_factor_at(&actions, june.end(2013)),
+            Decimal::from(14)
+        );
+        assert_eq!(
+            adjustment_factor_at(&actions, june.end(2014)),
+            Decimal::from(2)
+        );
+    }
+
+    #[test]
+    fn test_adjustment_factor_at_quarter_end() {
+        let actions = vec![action("2020-08-31", CorporateActionKind::Split, "4")];
+        let date = |s: &str| NaiveDate::from_str(s).unwrap();
+
+        assert_eq!(
+            adjustment_factor_at(&actions, date("2020-06-30")),
+            Decimal::from(4)
+        );
+        assert_eq!(
+            adjustment_factor_at(&actions, date("2020-09-30")),
+            Decimal::ONE
+        );
+    }
+
+    #[test]
+    fn test_validate_ratio_by_kind() {
+        let ratio = |s: &str| Decimal::from_str(s).unwrap();
+
+        assert!(validate_ratio(CorporateActionKind::Split, ratio("4")).is_ok());
+        assert!(validate_ratio(CorporateActionKind::Split, ratio("1")).is_err());
+        assert!(validate_ratio(CorporateActionKind::ReverseSplit, ratio("0.1")).is_ok());
+        assert!(validate_ratio(CorporateActionKind::ReverseSplit, ratio("10")).is_err());
+        assert!(validate_ratio(CorporateActionKind::StockDividend, ratio("1.05")).is_ok());
+        assert!(validate_ratio(CorporateActionKind::SpinOff, ratio("0.8")).is_ok());
+        assert!(validate_ratio(CorporateActionKind::SpinOff, ratio("0")).is_err());
+    }
+
+    #[test]
+    fn test_kind_round_trips_through_name() {
+        for kind in CorporateActionKind::ALL {
+            assert_eq!(CorporateActionKind::parse(kind.as_str()), Some(kind));
+        }
+        assert_eq!(CorporateActionKind::parse("merger"), None);
+    }
+}
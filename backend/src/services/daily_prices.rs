@@ -0,0 +1,426 @@
+//! Daily price series service.
+//!
+//! Daily bars are stored in `daily_prices` as traded, either fetched from the
+//! ticker's provider during harvest — only days not stored yet — or imported
+//! from a CSV file. Yearly price ranges are derived from the stored series per
+//! fiscal period: every bar is restated on the share basis at the end of its
+//! fiscal year using the corporate actions ledger, so a split during the year
+//! does not mix two share bases into one range.
+//!
+//! CSV imports are all-or-nothing, like historical table uploads: if any row
+//! fails validation nothing is written and the caller receives a row-level
+//! error report instead.
+
+use super::corporate_actions::adjustment_factor_at;
+use super::historicals_import::{parse_decimal, RowError, UPLOADED_SOURCE};
+use super::providers::{CorporateAction, DailyPrice, YearlyPriceRange};
+use crate::models::{daily_prices, tickers};
+use chrono::NaiveDate;
+use loco_rs::prelude::*;
+use rust_decimal::Decimal;
+use sea_orm::TransactionTrait;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use steady_invest_logic::FiscalCalendar;
+
+/// Days a series may start after or end before a fiscal year and still count
+/// as covering it (weekends and holidays around the year boundary).
+const COVERAGE_SLACK_DAYS: i64 = 7;
+
+/// Rows inserted per statement when storing a series.
+const INSERT_BATCH: usize = 500;
+
+/// Outcome of a CSV import.
+#[derive(Debug, Serialize)]
+pub struct DailyImportReport {
+    pub ticker: String,
+    /// Number of trading days written (zero when validation failed).
+    pub imported_days: usize,
+    pub first_date: Option<NaiveDate>,
+    pub last_date: Option<NaiveDate>,
+    pub errors: Vec<RowError>,
+}
+
+/// Derives the high/low range of every fiscal year the bars fully cover.
+///
+/// Bars are expected as traded. Each one is restated on the share basis at
+/// its fiscal year's end with `actions`, so the ranges match what providers
+/// report per fiscal year and are adjusted further by the harvest pipeline.
+/// Years whose bars start or end more than [`COVERAGE_SLACK_DAYS`] inside the
+/// fiscal year are left out.
+pub fn yearly_ranges(
+    bars: &[DailyPrice],
+    calendar: FiscalCalendar,
+    actions: &[CorporateAction],
+) -> Vec<YearlyPriceRange> {
+    let mut by_year: BTreeMap<i32, Vec<&DailyPrice>> = BTreeMap::new();
+    for bar in bars {
+        by_year
+            .entry(calendar.fiscal_year(bar.date))
+            .or_default()
+            .push(bar);
+    }
+
+    by_year
+        .into_iter()
+        .filter_map(|(year, bars)| {
+            let (start, end) = (calendar.start(year), calendar.end(year));
+            let first = bars.iter().map(|b| b.date).min()?;
+            let last = bars.iter().map(|b| b.date).max()?;
+            if (first - start).num_days() > COVERAGE_SLACK_DAYS
+                || (end - last).num_days() > COVERAGE_SLACK_DAYS
+            {
+                return None;
+            }
+
+            let year_end_factor = adjustment_factor_at(actions, end);
+            let (high, low) = bars
+                .iter()
+                .map(|bar| {
+                    let basis = year_end_factor / adjustment_factor_at(actions, bar.date);
+                    (bar.high * basis, bar.low * basis)
+                })
+                .reduce(|(h, l), (high, low)| (h.max(high), l.min(low)))?;
+
+            Some(YearlyPriceRange {
+                fiscal_year: year,
+                high: high.round_dp(4),
+                low: low.round_dp(4),
+            })
+        })
+        .collect()
+}
+
+/// Loads the stored bars for `ticker` from `from` through `to`, oldest first.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub async fn load(
+    db: &DatabaseConnection,
+    ticker: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<DailyPrice>> {
+    let rows = daily_prices::Entity::find_range(db, ticker, from, to).await?;
+    Ok(rows
+        .into_iter()
+        .map(|row| DailyPrice {
+            date: row.trade_date,
+            open: row.open,
+            high: row.high,
+            low: row.low,
+            close: row.close,
+            adjusted_close: row.adjusted_close,
+            volume: row.volume,
+        })
+        .collect())
+}
+
+/// Stores the bars for days not stored yet; stored days are left untouched.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails.
+pub async fn store_missing(
+    db: &DatabaseConnection,
+    ticker: &str,
+    bars: &[DailyPrice],
+    source: &str,
+) -> Result<usize> {
+    let existing = stored_by_date(db, ticker, bars).await?;
+    let missing: Vec<&DailyPrice> = bars
+        .iter()
+        .filter(|bar| !existing.contains_key(&bar.date))
+        .collect();
+    insert_all(db, ticker, &missing, source).await?;
+    Ok(missing.len())
+}
+
+/// Parses a CSV file of daily bars.
+///
+/// Expects a header with `date`, `open`, `high`, `low` and `close`, plus
+/// optional `adj_close` (also `adj close` or `adjusted_close`) and `volume`
+/// columns — the layout of common price history exports. Dates use
+/// `YYYY-MM-DD`.
+///
+/// # Errors
+///
+/// Returns every row-level problem found when the file is not valid.
+pub fn parse_csv(bytes: &[u8]) -> std::result::Result<Vec<DailyPrice>, Vec<RowError>> {
+    let file_error = |message: String| {
+        vec![RowError {
+            row: 1,
+            column: None,
+            message,
+        }]
+    };
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(bytes);
+
+    let mut errors = Vec::new();
+    let mut columns: HashMap<&'static str, usize> = HashMap::new();
+    let header = reader
+        .headers()
+        .map_err(|e| file_error(format!("Unreadable CSV header: {e}")))?
+        .clone();
+    for (index, name) in header.iter().enumerate() {
+        let column = match name.to_lowercase().replace(' ', "_").as_str() {
+            "date" => "date",
+            "open" => "open",
+            "high" => "high",
+            "low" => "low",
+            "close" => "close",
+            "adj_close" | "adjusted_close" => "adjusted_close",
+            "volume" => "volume",
+            "" => continue,
+            other => {
+                errors.push(header_error(other, "Unknown column"));
+                continue;
+            }
+        };
+        if columns.insert(column, index).is_some() {
+            errors.push(header_error(column, "Duplicate column"));
+        }
+    }
+    for required in ["date", "open", "high", "low", "close"] {
+        if !columns.contains_key(required) {
+            errors.push(header_error(required, "Missing required column"));
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let mut bars: BTreeMap<NaiveDate, DailyPrice> = BTreeMap::new();
+    for (i, record) in reader.records().enumerate() {
+        let row = i + 2;
+        let record = record.map_err(|e| file_error(format!("Unreadable CSV row {row}: {e}")))?;
+        if record.iter().all(str::is_empty) {
+            continue;
+        }
+        let cell = |column: &str| {
+            columns
+                .get(column)
+                .and_then(|i| record.get(*i))
+                .filter(|v| !v.is_empty())
+        };
+        let mut error = |column: &str, message: String| {
+            errors.push(RowError {
+                row,
+                column: Some(column.to_string()),
+                message,
+            });
+        };
+
+        let date = match cell("date") {
+            None => {
+                error("date", "Value is required".to_string());
+                None
+            }
+            Some(raw) => match NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+                Ok(date) if bars.contains_key(&date) => {
+                    error("date", format!("Date {date} appears more than once"));
+                    None
+                }
+                Ok(date) => Some(date),
+                Err(_) => {
+                    error("date", format!("'{raw}' is not a YYYY-MM-DD date"));
+                    None
+                }
+            },
+        };
+
+        let mut price = |column: &str, required: bool| match cell(column) {
+            None if required => {
+                error(column, "Value is required".to_string());
+                None
+            }
+            None => None,
+            Some(raw) => match parse_decimal(raw) {
+                Some(v) if v > Decimal::ZERO => Some(v),
+                Some(_) => {
+                    error(column, "Price must be positive".to_string());
+                    None
+                }
+                None => {
+                    error(column, format!("'{raw}' is not a number"));
+                    None
+                }
+            },
+        };
+        let open = price("open", true);
+        let high = price("high", true);
+        let low = price("low", true);
+        let close = price("close", true);
+        let adjusted_close = price("adjusted_close", false);
+
+        let volume = match cell("volume") {
+            None => None,
+            Some(raw) => match raw.replace(['\'', '_', ','], "").parse::<i64>() {
+                Ok(v) if v >= 0 => Some(v),
+                _ => {
+                    error("volume", format!("'{raw}' is not a share count"));
+                    None
+                }
+            },
+        };
+
+        if let (Some(high), Some(low)) = (high, low) {
+            if low > high {
+                error("low", format!("low {low} is greater than high {high}"));
+                continue;
+            }
+        }
+        if let (Some(date), Some(open), Some(high), Some(low), Some(close)) =
+            (date, open, high, low, close)
+        {
+            bars.insert(
+                date,
+                DailyPrice {
+                    date,
+                    open,
+                    high,
+                    low,
+                    close,
+                    adjusted_close,
+                    volume,
+                },
+            );
+        }
+    }
+
+    if errors.is_empty() && bars.is_empty() {
+        errors.push(RowError {
+            row: 2,
+            column: None,
+            message: "The file contains no data rows".to_string(),
+        });
+    }
+    if errors.is_empty() {
+        Ok(bars.into_values().collect())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Parses and stores a CSV file of daily bars for `ticker`.
+///
+/// Imported bars replace stored bars for the same days and are tagged with
+/// [`UPLOADED_SOURCE`]; other days are left untouched.
+///
+/// # Errors
+///
+/// Returns `404 Not Found` if the ticker is unknown, or an error if a
+/// database operation fails. Validation problems are reported in the
+/// returned [`DailyImportReport`], not as errors.
+pub async fn import_csv(
+    db: &DatabaseConnection,
+    ticker: &str,
+    bytes: &[u8],
+) -> Result<DailyImportReport> {
+    tickers::Entity::find()
+        .filter(tickers::Column::Ticker.eq(ticker))
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound)?;
+
+    let bars = match parse_csv(bytes) {
+        Ok(bars) => bars,
+        Err(errors) => {
+            return Ok(DailyImportReport {
+                ticker: ticker.to_string(),
+                imported_days: 0,
+                first_date: None,
+                last_date: None,
+                errors,
+            })
+        }
+    };
+
+    let existing = stored_by_date(db, ticker, &bars).await?;
+    let txn = db.begin().await?;
+    let mut new_bars = Vec::new();
+    for bar in &bars {
+        let Some(stored) = existing.get(&bar.date) else {
+            new_bars.push(bar);
+            continue;
+        };
+        let mut active: daily_prices::ActiveModel = stored.clone().into();
+        active.open = ActiveValue::set(bar.open);
+        active.high = ActiveValue::set(bar.high);
+        active.low = ActiveValue::set(bar.low);
+        active.close = ActiveValue::set(bar.close);
+        active.adjusted_close = ActiveValue::set(bar.adjusted_close);
+        active.volume = ActiveValue::set(bar.volume);
+        active.source = ActiveValue::set(UPLOADED_SOURCE.to_string());
+        active.updated_at = ActiveValue::set(chrono::Utc::now().into());
+        active.update(&txn).await?;
+    }
+    insert_all(&txn, ticker, &new_bars, UPLOADED_SOURCE).await?;
+    txn.commit().await?;
+
+    Ok(DailyImportReport {
+        ticker: ticker.to_string(),
+        imported_days: bars.len(),
+        first_date: bars.first().map(|b| b.date),
+        last_date: bars.last().map(|b| b.date),
+        errors: vec![],
+    })
+}
+
+/// Stored rows for `ticker` within the date span of `bars`, keyed by date.
+async fn stored_by_date(
+    db: &DatabaseConnection,
+    ticker: &str,
+    bars: &[DailyPrice],
+) -> Result<HashMap<NaiveDate, daily_prices::Model>> {
+    let (Some(from), Some(to)) = (
+        bars.iter().map(|b| b.date).min(),
+        bars.iter().map(|b| b.date).max(),
+    ) else {
+        return Ok(HashMap::new());
+    };
+    Ok(daily_prices::Entity::find_range(db, ticker, from, to)
+        .await?
+        .into_iter()
+        .map(|row| (row.trade_date, row))
+        .collect())
+}
+
+async fn insert_all(
+    db: &impl ConnectionTrait,
+    ticker: &str,
+    bars: &[&DailyPrice],
+    source: &str,
+) -> Result<()> {
+    let now = chrono::Utc::now();
+    for chunk in bars.chunks(INSERT_BATCH) {
+        let models = chunk.iter().map(|bar| daily_prices::ActiveModel {
+            ticker: ActiveValue::set(ticker.to_string()),
+            trade_date: ActiveValue::set(bar.date),
+            open: ActiveValue::set(bar.open),
+            high: ActiveValue::set(bar.high),
+            low: ActiveValue::set(bar.low),
+            close: ActiveValue::set(bar.close),
+            adjusted_close: ActiveValue::set(bar.adjusted_close),
+            volume: ActiveValue::set(bar.volume),
+            source: ActiveValue::set(source.to_string()),
+            created_at: ActiveValue::set(now.into()),
+            updated_at: ActiveValue::set(now.into()),
+            ..Default::default()
+        });
+        daily_prices::Entity::insert_many(models).exec(db).await?;
+    }
+    Ok(())
+}
+
+fn header_error(column: &str, message: &str) -> RowError {
+    RowError {
+        row: 1,
+        column: Some(column.to_string()),
+        message: message.to_string(),
+    }
+}
@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod tests {
+    use crate::services::daily_prices::{parse_csv, yearly_ranges};
+    use crate::services::providers::{CorporateAction, CorporateActionKind, DailyPrice};
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+    use steady_invest_logic::FiscalCalendar;
+
+    fn dec(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    fn bar(date: &str, high: &str, low: &str) -> DailyPrice {
+        DailyPrice {
+            date: NaiveDate::from_str(date).unwrap(),
+            open: dec(low),
+            high: dec(high),
+            low: dec(low),
+            close: dec(high),
+            adjusted_close: None,
+            volume: None,
+        }
+    }
+
+    #[test]
+    fn test_yearly_ranges_skip_partially_covered_years() {
+        let bars = vec![
+            bar("2022-06-01", "90", "80"),
+            bar("2022-12-30", "95", "85"),
+            bar("2023-01-03", "100", "90"),
+            bar("2023-07-03", "120", "70"),
+            bar("2023-12-29", "110", "95"),
+            bar("2024-01-02", "130", "120"),
+        ];
+
+        let ranges = yearly_ranges(&bars, FiscalCalendar::CALENDAR_YEAR, &[]);
+
+        // 2022 starts in June and 2024 ends in January: only 2023 is covered
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(ranges[0].fiscal_year, 2023);
+        assert_eq!(ranges[0].high, dec("120"));
+        assert_eq!(ranges[0].low, dec("70"));
+    }
+
+    #[test]
+    fn test_yearly_ranges_restate_bars_on_year_end_basis() {
+        // A 4-for-1 split in March: the January bar traded at four times the
+        // price it has on the share basis at year end
+        let actions = vec![
+            CorporateAction {
+                effective_date: NaiveDate::from_ymd_opt(2023, 3, 1).unwrap(),
+                kind: CorporateActionKind::Split,
+                ratio: dec("4"),
+            },
+            CorporateAction {
+                effective_date: NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+                kind: CorporateActionKind::Split,
+                ratio: dec("2"),
+            },
+        ];
+        let bars = vec![
+            bar("2023-01-03", "400", "360"),
+            bar("2023-06-01", "95", "80"),
+            bar("2023-12-29", "98", "92"),
+        ];
+
+        let ranges = yearly_ranges(&bars, FiscalCalendar::CALENDAR_YEAR, &actions);
+
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(ranges[0].high, dec("100"));
+        assert_eq!(ranges[0].low, dec("80"));
+    }
+
+    #[test]
+    fn test_yearly_ranges_follow_fiscal_calendar() {
+        let june = FiscalCalendar::new(6).unwrap();
+        let bars = vec![
+            bar("2023-07-03", "50", "45"),
+            bar("2024-03-01", "60", "40"),
+            bar("2024-06-28", "55", "50"),
+        ];
+
+        let ranges = yearly_ranges(&bars, june, &[]);
+
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(ranges[0].fiscal_year, 2024);
+        assert_eq!(ranges[0].high, dec("60"));
+        assert_eq!(ranges[0].low, dec("40"));
+    }
+
+    #[test]
+    fn test_parse_csv_accepts_common_export_layout() {
+        let csv = "\
+Date,Open,High,Low,Close,Adj Close,Volume
+2024-01-03,184.22,185.88,183.43,184.25,183.13,58414500
+2024-01-02,187.15,188.44,183.89,185.64,184.51,82488700
+";
+        let bars = parse_csv(csv.as_bytes()).unwrap();
+
+        assert_eq!(bars.len(), 2);
+        // Bars are returned oldest first
+        assert_eq!(bars[0].date.to_string(), "2024-01-02");
+        assert_eq!(bars[0].high, dec("188.44"));
+        assert_eq!(bars[0].adjusted_close, Some(dec("184.51")));
+        assert_eq!(bars[1].volume, Some(58_414_500));
+    }
+
+    #[test]
+    fn test_parse_csv_reports_every_row_problem() {
+        let csv = "\
+date,open,high,low,close
+2024-01-02,10,12,9,11
+2024-01-02,10,12,9,11
+01/03/2024,10,12,9,11
+2024-01-04,10,8,9,abc
+";
+        let errors = parse_csv(csv.as_bytes()).unwrap_err();
+
+        let found: Vec<(usize, Option<&str>)> = errors
+            .iter()
+            .map(|e| (e.row, e.column.as_deref()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (3, Some("date")),
+                (4, Some("date")),
+                (5, Some("close")),
+                (5, Some("low")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_csv_rejects_missing_columns() {
+        let errors = parse_csv(b"date,high,low\n2024-01-02,12,9\n").unwrap_err();
+
+        let missing: Vec<&str> = errors.iter().filter_map(|e| e.column.as_deref()).collect();
+        assert_eq!(missing, vec!["open", "close"]);
+    }
+}
@@ -2,15 +2,33 @@
 //!
 //! Provides cached exchange rate lookups from the database. Used during data
 //! harvesting to normalize foreign-currency financials to the user's display currency.
+//! The yearly and month-end rates are filled by
+//! [`super::exchange_rate_backfill`].
 
-use crate::models::exchange_rates;
+use crate::models::{exchange_rates, month_end_exchange_rates};
+use chrono::{Datelike, NaiveDate};
 use loco_rs::prelude::*;
 use rust_decimal::Decimal;
+use steady_invest_logic::FiscalCalendar;
 
-/// Looks up the exchange rate between two currencies for a given fiscal year.
+/// Currency every stored pair can be crossed through.
+///
+/// The seeded rates only quote currencies against USD; the backfill stores
+/// every pair, but a pair it has not reached yet is still derived from the
+/// two USD legs.
+pub const CROSS_CURRENCY: &str = "USD";
+
+/// Looks up the exchange rate between two currencies for a fiscal year.
+///
+/// Rates are cached per calendar year. A fiscal year spanning two calendar
+/// years gets their average weighted by the months falling in each, so a
+/// June year end blends half of the previous year's rate with half of the
+/// current one. When only one of the two rates is cached it is used alone.
+/// A pair without a cached rate is crossed through [`CROSS_CURRENCY`].
 ///
 /// Returns `Ok(Some(Decimal::ONE))` when `from == to` (no conversion needed),
-/// `Ok(None)` when no rate is cached for the requested pair and year.
+/// `Ok(None)` when neither the pair nor both of its USD legs are cached for
+/// the requested period.
 ///
 /// # Errors
 ///
@@ -19,18 +37,135 @@ pub async fn get_rate(
     db: &DatabaseConnection,
     from: &str,
     to: &str,
-    year: i32,
+    fiscal_year: i32,
+    calendar: FiscalCalendar,
 ) -> Result<Option<Decimal>> {
     if from == to {
         return Ok(Some(Decimal::ONE));
     }
+    if let Some(rate) = stored_rate(db, from, to, fiscal_year, calendar).await? {
+        return Ok(Some(rate));
+    }
 
-    let rate = exchange_rates::Entity::find()
+    let from_leg = match from {
+        CROSS_CURRENCY => Some(Decimal::ONE),
+        _ => stored_rate(db, from, CROSS_CURRENCY, fiscal_year, calendar).await?,
+    };
+    let to_leg = match to {
+        CROSS_CURRENCY => Some(Decimal::ONE),
+        _ => stored_rate(db, to, CROSS_CURRENCY, fiscal_year, calendar).await?,
+    };
+    Ok(match (from_leg, to_leg) {
+        (Some(from_leg), Some(to_leg)) if !to_leg.is_zero() => {
+            Some((from_leg / to_leg).round_dp(4))
+        }
+        _ => None,
+    })
+}
+
+/// Month-weighted average of the cached calendar-year rates of one pair.
+async fn stored_rate(
+    db: &DatabaseConnection,
+    from: &str,
+    to: &str,
+    fiscal_year: i32,
+    calendar: FiscalCalendar,
+) -> Result<Option<Decimal>> {
+    let months = calendar.calendar_months(fiscal_year);
+    let rates = exchange_rates::Entity::find()
         .filter(exchange_rates::Column::FromCurrency.eq(from))
         .filter(exchange_rates::Column::ToCurrency.eq(to))
-        .filter(exchange_rates::Column::FiscalYear.eq(year))
+        .filter(exchange_rates::Column::FiscalYear.is_in(months.iter().map(|(year, _)| *year)))
+        .all(db)
+        .await?;
+
+    let (weighted, covered) = months
+        .iter()
+        .filter_map(|(year, months)| {
+            let rate = rates.iter().find(|r| r.fiscal_year == *year)?.rate;
+            Some((rate * Decimal::from(*months), Decimal::from(*months)))
+        })
+        .fold((Decimal::ZERO, Decimal::ZERO), |(w, c), (rate, months)| {
+            (w + rate, c + months)
+        });
+
+    if covered.is_zero() {
+        return Ok(None);
+    }
+    Ok(Some((weighted / covered).round_dp(4)))
+}
+
+/// Looks up the closing rate between two currencies at a fiscal year end.
+///
+/// Reads the month-end rate stored for the last day of the fiscal year, so a
+/// June year end gets the rate of June's last trading day. For December year
+/// ends, rows backfilled before month-end rates existed fall back to the
+/// calendar year-end rate. Like [`get_rate`], a pair without a stored rate is
+/// crossed through [`CROSS_CURRENCY`].
+///
+/// Returns `Ok(Some(Decimal::ONE))` when `from == to` (no conversion needed),
+/// `Ok(None)` when no closing rate is stored for the pair and date.
+///
+/// # Errors
+///
+/// Returns a database error if the query fails.
+pub async fn get_closing_rate(
+    db: &DatabaseConnection,
+    from: &str,
+    to: &str,
+    fiscal_year: i32,
+    calendar: FiscalCalendar,
+) -> Result<Option<Decimal>> {
+    if from == to {
+        return Ok(Some(Decimal::ONE));
+    }
+
+    let year_end = calendar.end(fiscal_year);
+    if let Some(rate) = stored_closing_rate(db, from, to, year_end).await? {
+        return Ok(Some(rate));
+    }
+
+    let from_leg = match from {
+        CROSS_CURRENCY => Some(Decimal::ONE),
+        _ => stored_closing_rate(db, from, CROSS_CURRENCY, year_end).await?,
+    };
+    let to_leg = match to {
+        CROSS_CURRENCY => Some(Decimal::ONE),
+        _ => stored_closing_rate(db, to, CROSS_CURRENCY, year_end).await?,
+    };
+    Ok(match (from_leg, to_leg) {
+        (Some(from_leg), Some(to_leg)) if !to_leg.is_zero() => {
+            Some((from_leg / to_leg).round_dp(4))
+        }
+        _ => None,
+    })
+}
+
+/// Stored closing rate of one pair on a month's last day.
+async fn stored_closing_rate(
+    db: &DatabaseConnection,
+    from: &str,
+    to: &str,
+    month_end: NaiveDate,
+) -> Result<Option<Decimal>> {
+    let month_end_rate = month_end_exchange_rates::Entity::find()
+        .filter(month_end_exchange_rates::Column::FromCurrency.eq(from))
+        .filter(month_end_exchange_rates::Column::ToCurrency.eq(to))
+        .filter(month_end_exchange_rates::Column::MonthEnd.eq(month_end))
         .one(db)
         .await?;
+    if let Some(rate) = month_end_rate {
+        return Ok(Some(rate.rate));
+    }
+    if month_end.month() != 12 {
+        return Ok(None);
+    }
 
-    Ok(rate.map(|r| r.rate))
+    let rate = exchange_rates::Entity::find()
+        .filter(exchange_rates::Column::FromCurrency.eq(from))
+        .filter(exchange_rates::Column::ToCurrency.eq(to))
+        .filter(exchange_rates::Column::FiscalYear.eq(month_end.year()))
+        .one(db)
+        .await?;
+    Ok(rate.and_then(|r| r.year_end_rate))
 }
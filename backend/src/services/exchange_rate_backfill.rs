@@ -0,0 +1,518 @@
+//! Historical exchange rate backfill.
+//!
+//! Fills `exchange_rates` with one row per currency pair and calendar year:
+//! `rate` is the average of the year's daily reference rates, which
+//! [`super::exchange::get_rate`] blends into fiscal years, and
+//! `year_end_rate` the rate of the year's last trading day. The current year
+//! holds the year to date and is brought up to date by the next run.
+//! `month_end_exchange_rates` gets the rate of every month's last trading
+//! day, which [`super::exchange::get_closing_rate`] reads at a fiscal year
+//! end.
+//!
+//! Daily reference rates are the ECB's, read either from the Frankfurter
+//! time-series API or from an imported copy of the ECB's
+//! `eurofxref-hist.csv`. Both quote every currency against the euro; cross
+//! rates between two other currencies are derived day by day before
+//! averaging. The API base URL is read from `EXCHANGE_RATE_HISTORY_URL` so a
+//! local stand-in can replace the public host, and every request goes
+//! through the Frankfurter [`RateLimiter`](super::rate_limiter::RateLimiter).
+//!
+//! Pairs cover the currencies of the ticker universe plus CHF, EUR and USD.
+
+use super::exchange_rate_provider;
+use crate::models::{exchange_rates, month_end_exchange_rates, tickers};
+use chrono::{Datelike, NaiveDate, Utc};
+use loco_rs::prelude::*;
+use rust_decimal::Decimal;
+use sea_orm::{QuerySelect, TransactionTrait};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+/// Default Frankfurter API root for time series.
+const DEFAULT_HISTORY_URL: &str = "https://api.frankfurter.dev/v1";
+
+/// HTTP request timeout for time-series requests (seconds); a year of daily
+/// rates is a larger document than the latest rates.
+const HTTP_TIMEOUT_SECS: u64 = 15;
+
+/// Calendar years backfilled by default: the ten-year analysis window, the
+/// year a non-calendar fiscal year starts in, and the current year.
+pub const DEFAULT_YEARS: i32 = 12;
+
+/// Currency the ECB quotes every rate against.
+const QUOTE_BASE: &str = "EUR";
+
+/// Reference rates per day, as units of each currency per euro.
+pub type DailyQuotes = BTreeMap<NaiveDate, HashMap<String, Decimal>>;
+
+/// Average and year-end rate of one currency pair over a calendar year.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YearlyRate {
+    pub from_currency: String,
+    pub to_currency: String,
+    pub year: i32,
+    /// Units of `to_currency` per unit of `from_currency`, averaged over the
+    /// year's trading days.
+    pub average: Decimal,
+    /// Rate of the year's last trading day.
+    pub year_end: Decimal,
+}
+
+/// Rate of one currency pair on the last trading day of a month.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonthEndRate {
+    pub from_currency: String,
+    pub to_currency: String,
+    /// Last calendar day of the month.
+    pub month_end: NaiveDate,
+    /// Units of `to_currency` per unit of `from_currency`.
+    pub rate: Decimal,
+}
+
+/// Outcome of a backfill run.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BackfillReport {
+    pub first_year: i32,
+    pub last_year: i32,
+    /// Yearly and month-end rows written for the first time.
+    pub inserted: usize,
+    /// Stored yearly and month-end rows whose rates were replaced.
+    pub updated: usize,
+    /// Requested currencies without any reference rate in the source.
+    pub missing_currencies: Vec<String>,
+}
+
+/// Shared HTTP client with connection pooling and timeout.
+static HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(HTTP_TIMEOUT_SECS))
+        .build()
+        .expect("HTTP client build must succeed")
+});
+
+/// Time-series response of the Frankfurter API.
+#[derive(Debug, Deserialize)]
+struct TimeSeriesResponse {
+    base: String,
+    rates: BTreeMap<NaiveDate, HashMap<String, serde_json::Number>>,
+}
+
+fn history_url() -> String {
+    std::env::var("EXCHANGE_RATE_HISTORY_URL")
+        .unwrap_or_else(|_| DEFAULT_HISTORY_URL.to_string())
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// The `years` calendar years up to and including the current one.
+pub fn recent_years(years: i32) -> RangeInclusive<i32> {
+    let current = Utc::now().year();
+    (current - years.max(1) + 1)..=current
+}
+
+// ---------------------------------------------------------------------------
+// Parsing
+// ---------------------------------------------------------------------------
+
+/// Parses a Frankfurter time-series document.
+///
+/// # Errors
+///
+/// Returns an error if the document is not a euro-based time series.
+pub fn parse_frankfurter(json: &str) -> Result<DailyQuotes> {
+    let response: TimeSeriesResponse = serde_json::from_str(json)
+        .map_err(|e| Error::string(&format!("Invalid Frankfurter time series: {e}")))?;
+    if response.base != QUOTE_BASE {
+        return Err(Error::string(&format!(
+            "Frankfurter time series is based on {}, expected {QUOTE_BASE}",
+            response.base
+        )));
+    }
+
+    response
+        .rates
+        .into_iter()
+        .map(|(date, rates)| {
+            let rates = rates
+                .into_iter()
+                .map(|(currency, rate)| {
+                    let parsed = Decimal::from_str(&rate.to_string()).map_err(|e| {
+                        Error::string(&format!("Invalid {currency} rate on {date}: {e}"))
+                    })?;
+                    Ok((currency, parsed))
+                })
+                .collect::<Result<HashMap<_, _>>>()?;
+            Ok((date, rates))
+        })
+        .collect()
+}
+
+/// Parses the ECB's `eurofxref-hist.csv`: a `Date` column followed by one
+/// column per currency, with `N/A` or blanks where no rate was fixed.
+///
+/// # Errors
+///
+/// Returns an error naming the row of the first unreadable date or rate.
+pub fn parse_ecb_csv(bytes: &[u8]) -> Result<DailyQuotes> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(bytes);
+    let header = reader
+        .headers()
+        .map_err(|e| Error::string(&format!("Unreadable ECB CSV header: {e}")))?
+        .clone();
+    if !header
+        .get(0)
+        .is_some_and(|name| name.eq_ignore_ascii_case("date"))
+    {
+        return Err(Error::string("ECB CSV must start with a Date column"));
+    }
+
+    let mut quotes = DailyQuotes::new();
+    for (i, record) in reader.records().enumerate() {
+        let row = i + 2;
+        let record =
+            record.map_err(|e| Error::string(&format!("Unreadable ECB CSV row {row}: {e}")))?;
+        let Some(date) = record.get(0).filter(|d| !d.is_empty()) else {
+            continue;
+        };
+        let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| Error::string(&format!("Row {row}: invalid date '{date}'")))?;
+
+        let mut rates = HashMap::new();
+        for (currency, value) in header.iter().zip(record.iter()).skip(1) {
+            if currency.is_empty() || value.is_empty() || value.eq_ignore_ascii_case("N/A") {
+                continue;
+            }
+            let rate = Decimal::from_str(value).map_err(|_| {
+                Error::string(&format!("Row {row}: invalid {currency} rate '{value}'"))
+            })?;
+            rates.insert(currency.to_uppercase(), rate);
+        }
+        quotes.insert(date, rates);
+    }
+    Ok(quotes)
+}
+
+// ---------------------------------------------------------------------------
+// Aggregation
+// ---------------------------------------------------------------------------
+
+/// Units of `currency` per euro on a day, `None` if not quoted.
+fn euro_rate(rates: &HashMap<String, Decimal>, currency: &str) -> Option<Decimal> {
+    if currency == QUOTE_BASE {
+        return Some(Decimal::ONE);
+    }
+    rates.get(currency).copied().filter(|r| !r.is_zero())
+}
+
+/// Average and year-end rates of every ordered pair of `currencies` for each
+/// year in `years` with at least one day quoting both.
+pub fn yearly_rates(
+    quotes: &DailyQuotes,
+    currencies: &[String],
+    years: RangeInclusive<i32>,
+) -> Vec<YearlyRate> {
+    let mut rates = Vec::new();
+    for year in years {
+        let (Some(first), Some(last)) = (
+            NaiveDate::from_ymd_opt(year, 1, 1),
+            NaiveDate::from_ymd_opt(year, 12, 31),
+        ) else {
+            continue;
+        };
+        let days: Vec<&HashMap<String, Decimal>> =
+            quotes.range(first..=last).map(|(_, r)| r).collect();
+
+        for from in currencies {
+            for to in currencies.iter().filter(|to| *to != from) {
+                let daily: Vec<Decimal> = days
+                    .iter()
+                    .filter_map(|rates| Some(euro_rate(rates, to)? / euro_rate(rates, from)?))
+                    .collect();
+                let Some(&year_end) = daily.last() else {
+                    continue;
+                };
+                let average = daily.iter().sum::<Decimal>() / Decimal::from(daily.len());
+                rates.push(YearlyRate {
+                    from_currency: from.clone(),
+                    to_currency: to.clone(),
+                    year,
+                    average: average.round_dp(4),
+                    year_end: year_end.round_dp(4),
+                });
+            }
+        }
+    }
+    rates
+}
+
+/// Rates of every ordered pair of `currencies` on the last day of each
+/// month of `years` quoting both.
+pub fn month_end_rates(
+    quotes: &DailyQuotes,
+    currencies: &[String],
+    years: RangeInclusive<i32>,
+) -> Vec<MonthEndRate> {
+    let mut rates = Vec::new();
+    for year in years {
+        for month in 1..=12 {
+            let Some(first) = NaiveDate::from_ymd_opt(year, month, 1) else {
+                continue;
+            };
+            let Some(month_end) = first
+                .checked_add_months(chrono::Months::new(1))
+                .and_then(|next| next.pred_opt())
+            else {
+                continue;
+            };
+            for from in currencies {
+                for to in currencies.iter().filter(|to| *to != from) {
+                    let last = quotes
+                        .range(first..=month_end)
+                        .rev()
+                        .find_map(|(_, rates)| {
+                            Some(euro_rate(rates, to)? / euro_rate(rates, from)?)
+                        });
+                    if let Some(rate) = last {
+                        rates.push(MonthEndRate {
+                            from_currency: from.clone(),
+                            to_currency: to.clone(),
+                            month_end,
+                            rate: rate.round_dp(4),
+                        });
+                    }
+                }
+            }
+        }
+    }
+    rates
+}
+
+/// Requested currencies the quotes never mention.
+fn missing_currencies(quotes: &DailyQuotes, currencies: &[String]) -> Vec<String> {
+    currencies
+        .iter()
+        .filter(|c| {
+            c.as_str() != QUOTE_BASE && !quotes.values().any(|rates| rates.contains_key(*c))
+        })
+        .cloned()
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Sources and persistence
+// ---------------------------------------------------------------------------
+
+/// Currencies of the ticker universe plus the supported currencies, sorted.
+///
+/// # Errors
+///
+/// Returns an error if the tickers cannot be read.
+pub async fn currencies(db: &DatabaseConnection) -> Result<Vec<String>> {
+    let listed: Vec<String> = tickers::Entity::find()
+        .select_only()
+        .column(tickers::Column::Currency)
+        .distinct()
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let all: BTreeSet<String> = listed
+        .iter()
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| c.len() == 3)
+        .chain(exchange_rate_provider::supported_currencies())
+        .collect();
+    Ok(all.into_iter().collect())
+}
+
+/// Fetches daily reference rates for `years` from the Frankfurter API, one
+/// request per year.
+async fn fetch_frankfurter(
+    db: &DatabaseConnection,
+    currencies: &[String],
+    years: RangeInclusive<i32>,
+) -> Result<DailyQuotes> {
+    let symbols: Vec<&str> = currencies
+        .iter()
+        .map(String::as_str)
+        .filter(|c| *c != QUOTE_BASE)
+        .collect();
+    let limiter = exchange_rate_provider::rate_limiter(db);
+    let today = Utc::now().date_naive();
+    let base_url = history_url();
+
+    let mut quotes = DailyQuotes::new();
+    for year in years {
+        let (Some(start), Some(end)) = (
+            NaiveDate::from_ymd_opt(year, 1, 1),
+            NaiveDate::from_ymd_opt(year, 12, 31),
+        ) else {
+            continue;
+        };
+        if start > today {
+            break;
+        }
+        limiter.acquire().await?;
+        let url = format!("{base_url}/{start}..{}", end.min(today));
+        let response = HTTP_CLIENT
+            .get(&url)
+            .query(&[
+                ("base", QUOTE_BASE.to_string()),
+                ("symbols", symbols.join(",")),
+            ])
+            .send()
+            .await
+            .map_err(|e| Error::string(&format!("Frankfurter request for {year} failed: {e}")))?;
+        if !response.status().is_success() {
+            return Err(Error::string(&format!(
+                "Frankfurter returned HTTP {} for {year}",
+                response.status()
+            )));
+        }
+        let body = response
+            .text()
+            .await
+            .map_err(|e| Error::string(&format!("Invalid Frankfurter response for {year}: {e}")))?;
+        quotes.extend(parse_frankfurter(&body)?);
+    }
+    Ok(quotes)
+}
+
+/// Upserts the yearly rates, returning the rows inserted and updated.
+async fn store(db: &DatabaseConnection, rates: &[YearlyRate]) -> Result<(usize, usize)> {
+    let txn = db.begin().await?;
+    let (mut inserted, mut updated) = (0, 0);
+    for rate in rates {
+        let existing = exchange_rates::Entity::find()
+            .filter(exchange_rates::Column::FromCurrency.eq(&rate.from_currency))
+            .filter(exchange_rates::Column::ToCurrency.eq(&rate.to_currency))
+            .filter(exchange_rates::Column::FiscalYear.eq(rate.year))
+            .one(&txn)
+            .await?;
+        match existing {
+            Some(existing) => {
+                if existing.rate == rate.average && existing.year_end_rate == Some(rate.year_end) {
+                    continue;
+                }
+                let mut active: exchange_rates::ActiveModel = existing.into();
+                active.rate = ActiveValue::set(rate.average);
+                active.year_end_rate = ActiveValue::set(Some(rate.year_end));
+                active.update(&txn).await?;
+                updated += 1;
+            }
+            None => {
+                exchange_rates::ActiveModel {
+                    from_currency: ActiveValue::set(rate.from_currency.clone()),
+                    to_currency: ActiveValue::set(rate.to_currency.clone()),
+                    fiscal_year: ActiveValue::set(rate.year),
+                    rate: ActiveValue::set(rate.average),
+                    year_end_rate: ActiveValue::set(Some(rate.year_end)),
+                    ..Default::default()
+                }
+                .insert(&txn)
+                .await?;
+                inserted += 1;
+            }
+        }
+    }
+    txn.commit().await?;
+    Ok((inserted, updated))
+}
+
+/// Upserts the month-end rates, returning the rows inserted and updated.
+async fn store_month_ends(
+    db: &DatabaseConnection,
+    rates: &[MonthEndRate],
+) -> Result<(usize, usize)> {
+    let txn = db.begin().await?;
+    let (mut inserted, mut updated) = (0, 0);
+    for rate in rates {
+        let existing = month_end_exchange_rates::Entity::find()
+            .filter(month_end_exchange_rates::Column::FromCurrency.eq(&rate.from_currency))
+            .filter(month_end_exchange_rates::Column::ToCurrency.eq(&rate.to_currency))
+            .filter(month_end_exchange_rates::Column::MonthEnd.eq(rate.month_end))
+            .one(&txn)
+            .await?;
+        match existing {
+            Some(existing) => {
+                if existing.rate == rate.rate {
+                    continue;
+                }
+                let mut active: month_end_exchange_rates::ActiveModel = existing.into();
+                active.rate = ActiveValue::set(rate.rate);
+                active.update(&txn).await?;
+                updated += 1;
+            }
+            None => {
+                month_end_exchange_rates::ActiveModel {
+                    from_currency: ActiveValue::set(rate.from_currency.clone()),
+                    to_currency: ActiveValue::set(rate.to_currency.clone()),
+                    month_end: ActiveValue::set(rate.month_end),
+                    rate: ActiveValue::set(rate.rate),
+                    ..Default::default()
+                }
+                .insert(&txn)
+                .await?;
+                inserted += 1;
+            }
+        }
+    }
+    txn.commit().await?;
+    Ok((inserted, updated))
+}
+
+async fn backfill(
+    db: &DatabaseConnection,
+    quotes: &DailyQuotes,
+    currencies: &[String],
+    years: RangeInclusive<i32>,
+) -> Result<BackfillReport> {
+    let rates = yearly_rates(quotes, currencies, years.clone());
+    let (inserted, updated) = store(db, &rates).await?;
+    let month_ends = month_end_rates(quotes, currencies, years.clone());
+    let (month_ends_inserted, month_ends_updated) = store_month_ends(db, &month_ends).await?;
+    Ok(BackfillReport {
+        first_year: *years.start(),
+        last_year: *years.end(),
+        inserted: inserted + month_ends_inserted,
+        updated: updated + month_ends_updated,
+        missing_currencies: missing_currencies(quotes, currencies),
+    })
+}
+
+/// Backfills `years` from the Frankfurter time-series API.
+///
+/// # Errors
+///
+/// Returns an error if a request fails, the rate limit is reached, or the
+/// rates cannot be stored; nothing is stored in that case.
+pub async fn backfill_from_frankfurter(
+    db: &DatabaseConnection,
+    years: RangeInclusive<i32>,
+) -> Result<BackfillReport> {
+    let currencies = currencies(db).await?;
+    let quotes = fetch_frankfurter(db, &currencies, years.clone()).await?;
+    backfill(db, &quotes, &currencies, years).await
+}
+
+/// Backfills `years` from the contents of an ECB `eurofxref-hist.csv`.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be parsed or the rates cannot be
+/// stored; nothing is stored in that case.
+pub async fn backfill_from_ecb_csv(
+    db: &DatabaseConnection,
+    bytes: &[u8],
+    years: RangeInclusive<i32>,
+) -> Result<BackfillReport> {
+    let currencies = currencies(db).await?;
+    let quotes = parse_ecb_csv(bytes)?;
+    backfill(db, &quotes, &currencies, years).await
+}
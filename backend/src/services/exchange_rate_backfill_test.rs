@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    use crate::services::exchange_rate_backfill::{
+        month_end_rates, parse_ecb_csv, parse_frankfurter, yearly_rates, YearlyRate,
+    };
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    fn currencies(list: &[&str]) -> Vec<String> {
+        list.iter().map(|c| (*c).to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_ecb_csv_skips_missing_rates() {
+        let csv = "\
+Date,USD,JPY,CHF,CYP,
+2024-12-31,1.0389,163.06,0.9412,N/A,
+2024-12-30,1.0444,164.35,0.9410,N/A,
+";
+        let quotes = parse_ecb_csv(csv.as_bytes()).unwrap();
+
+        assert_eq!(quotes.len(), 2);
+        let last = &quotes[&NaiveDate::from_ymd_opt(2024, 12, 31).unwrap()];
+        assert_eq!(last["USD"], dec("1.0389"));
+        assert_eq!(last["CHF"], dec("0.9412"));
+        assert!(!last.contains_key("CYP"));
+    }
+
+    #[test]
+    fn test_parse_ecb_csv_reports_bad_rows() {
+        let err = parse_ecb_csv(b"Date,USD\n2024-12-31,1.03\n31.12.2023,1.10\n").unwrap_err();
+        assert!(err.to_string().contains("Row 3"), "{err}");
+
+        let err = parse_ecb_csv(b"Date,USD\n2024-12-31,one\n").unwrap_err();
+        assert!(err.to_string().contains("USD"), "{err}");
+    }
+
+    #[test]
+    fn test_parse_frankfurter_time_series() {
+        let json = r#"{
+            "amount": 1.0,
+            "base": "EUR",
+            "start_date": "2024-01-02",
+            "end_date": "2024-01-03",
+            "rates": {
+                "2024-01-02": {"CHF": 0.9305, "USD": 1.0956},
+                "2024-01-03": {"CHF": 0.9314, "USD": 1.0919}
+            }
+        }"#;
+        let quotes = parse_frankfurter(json).unwrap();
+
+        assert_eq!(quotes.len(), 2);
+        let first = &quotes[&NaiveDate::from_ymd_opt(2024, 1, 2).unwrap()];
+        assert_eq!(first["CHF"], dec("0.9305"));
+
+        let usd_based = json.replace("\"base\": \"EUR\"", "\"base\": \"USD\"");
+        assert!(parse_frankfurter(&usd_based).is_err());
+    }
+
+    #[test]
+    fn test_yearly_rates_average_and_year_end_with_cross_rates() {
+        let csv = "\
+Date,USD,CHF
+2024-01-02,1.10,0.95
+2024-12-31,1.00,0.90
+2025-01-02,1.05,
+";
+        let quotes = parse_ecb_csv(csv.as_bytes()).unwrap();
+        let rates = yearly_rates(&quotes, &currencies(&["CHF", "EUR", "USD"]), 2024..=2025);
+
+        let find = |from: &str, to: &str, year: i32| -> Option<&YearlyRate> {
+            rates
+                .iter()
+                .find(|r| r.from_currency == from && r.to_currency == to && r.year == year)
+        };
+
+        let eur_usd = find("EUR", "USD", 2024).unwrap();
+        assert_eq!(eur_usd.average, dec("1.05"));
+        assert_eq!(eur_usd.year_end, dec("1.00"));
+
+        let usd_eur = find("USD", "EUR", 2024).unwrap();
+        // (1/1.10 + 1/1.00) / 2
+        assert_eq!(usd_eur.average, dec("0.9545"));
+
+        // Cross rate per day: USD per CHF = (1.10/0.95 + 1.00/0.90) / 2
+        let chf_usd = find("CHF", "USD", 2024).unwrap();
+        assert_eq!(chf_usd.average, dec("1.1345"));
+        assert_eq!(chf_usd.year_end, dec("1.1111"));
+
+        // No CHF quote in 2025, so no CHF pairs that year
+        assert!(find("EUR", "USD", 2025).is_some());
+        assert!(find("CHF", "USD", 2025).is_none());
+        assert_eq!(rates.len(), 6 + 2);
+    }
+
+    #[test]
+    fn test_month_end_rates_use_each_months_last_quote() {
+        let csv = "\
+Date,USD,CHF
+2024-06-03,1.10,0.95
+2024-06-28,1.20,1.00
+2024-07-01,1.05,
+";
+        let quotes = parse_ecb_csv(csv.as_bytes()).unwrap();
+        let rates = month_end_rates(&quotes, &currencies(&["CHF", "USD"]), 2024..=2024);
+
+        // June closes on Friday the 28th but is keyed by the 30th
+        let june_end = NaiveDate::from_ymd_opt(2024, 6, 30).unwrap();
+        let chf_usd = rates
+            .iter()
+            .find(|r| r.from_currency == "CHF" && r.month_end == june_end)
+            .unwrap();
+        assert_eq!(chf_usd.rate, dec("1.2"));
+
+        // July has no CHF quote; no other month has any
+        assert_eq!(rates.len(), 2);
+    }
+}
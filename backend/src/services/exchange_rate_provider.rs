@@ -1,8 +1,11 @@
 //! Current exchange rate provider with in-memory caching and DB fallback.
 //!
-//! Fetches live rates from the Frankfurter API (ECB data) and caches them
-//! in-memory for 24 hours. When the API is unreachable, falls back to
-//! stale cache, then to the latest fiscal-year rates in the database.
+//! Fetches live rates from the Frankfurter API (ECB data) for the configured
+//! currencies and caches them in-memory for 24 hours. Every rate is quoted
+//! against the euro; the served pairs are the full cross-rate matrix between
+//! the currencies. When the API is unreachable or its rate limit is
+//! reached, falls back to stale cache, then to the latest fiscal-year rates
+//! in the database.
 //!
 //! **Coexists with** [`super::exchange`] which handles historical per-year
 //! rates for the harvest pipeline. This module serves current rates for
@@ -13,11 +16,13 @@ use loco_rs::prelude::*;
 use rust_decimal::Decimal;
 use sea_orm::QueryOrder;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::LazyLock;
+use steady_invest_logic::{is_valid_currency_code, SUPPORTED_CURRENCIES};
 use tokio::sync::RwLock;
 
 use crate::models::_entities::exchange_rates;
+use crate::services::rate_limiter::{RateLimit, RateLimiter};
 
 // ---------------------------------------------------------------------------
 // Configuration
@@ -26,12 +31,22 @@ use crate::models::_entities::exchange_rates;
 /// Default cache time-to-live: 24 hours (in seconds).
 const DEFAULT_CACHE_TTL_SECS: u64 = 86_400;
 
-/// Default Frankfurter API endpoint (EUR base, CHF and USD symbols).
-const FRANKFURTER_URL: &str = "https://api.frankfurter.dev/v1/latest?symbols=CHF,USD";
+/// Default Frankfurter API endpoint (EUR base); the symbols are added per request.
+const FRANKFURTER_URL: &str = "https://api.frankfurter.dev/v1/latest";
+
+/// Currency every Frankfurter and ECB rate is quoted against.
+const QUOTE_BASE: &str = "EUR";
 
 /// HTTP request timeout for the exchange rate provider (seconds).
 const HTTP_TIMEOUT_SECS: u64 = 5;
 
+/// Frankfurter calls allowed unless `EXCHANGE_RATE_PROVIDER_RATE_LIMIT_PER_MINUTE`
+/// and `EXCHANGE_RATE_PROVIDER_RATE_LIMIT_PER_DAY` say otherwise.
+pub const DEFAULT_RATE_LIMIT: RateLimit = RateLimit {
+    per_minute: 30,
+    per_day: 1_000,
+};
+
 // ---------------------------------------------------------------------------
 // Types
 // ---------------------------------------------------------------------------
@@ -90,6 +105,46 @@ fn frankfurter_url() -> String {
     std::env::var("EXCHANGE_RATE_PROVIDER_URL").unwrap_or_else(|_| FRANKFURTER_URL.to_string())
 }
 
+/// Currencies served, from the comma-separated `EXCHANGE_RATE_CURRENCIES` or
+/// [`SUPPORTED_CURRENCIES`].
+///
+/// Entries that are not ISO 4217 codes are ignored, and the euro is always
+/// included since every rate is derived from it.
+pub fn supported_currencies() -> Vec<String> {
+    let configured: Vec<String> = std::env::var("EXCHANGE_RATE_CURRENCIES")
+        .unwrap_or_default()
+        .split(',')
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| c.len() == 3 && c.bytes().all(|b| b.is_ascii_uppercase()))
+        .collect();
+
+    let mut currencies = if configured.is_empty() {
+        SUPPORTED_CURRENCIES
+            .iter()
+            .map(|c| (*c).to_string())
+            .collect()
+    } else {
+        configured
+    };
+    if !currencies.iter().any(|c| c == QUOTE_BASE) {
+        currencies.push(QUOTE_BASE.to_string());
+    }
+    let mut seen = HashSet::new();
+    currencies.retain(|c| seen.insert(c.clone()));
+    currencies
+}
+
+/// Whether `code` is one of the [`supported_currencies`].
+pub fn is_supported_currency(code: &str) -> bool {
+    is_valid_currency_code(code, &supported_currencies())
+}
+
+/// Limiter for Frankfurter calls, counted as `frankfurter`.
+pub fn rate_limiter(db: &DatabaseConnection) -> RateLimiter {
+    let limit = RateLimit::from_env("EXCHANGE_RATE_PROVIDER", DEFAULT_RATE_LIMIT);
+    RateLimiter::new(db, "frankfurter", limit)
+}
+
 fn cache_ttl() -> chrono::Duration {
     let secs = std::env::var("EXCHANGE_RATE_CACHE_TTL_SECS")
         .ok()
@@ -108,57 +163,85 @@ impl CachedRates {
 // Rate derivation
 // ---------------------------------------------------------------------------
 
-/// Derives all 6 directional pairs from EUR→CHF and EUR→USD base rates.
-/// Returns an error if either rate is zero.
-fn derive_all_pairs(eur_chf: Decimal, eur_usd: Decimal) -> Result<Vec<ExchangeRatePair>> {
-    if eur_chf.is_zero() || eur_usd.is_zero() {
-        tracing::warn!(eur_chf = %eur_chf, eur_usd = %eur_usd, "Zero exchange rate received");
-        return Err(Error::string("Exchange rate is zero — cannot derive pairs"));
+/// Derives the cross-rate matrix from rates quoted as units of each currency
+/// per euro: every ordered pair of distinct currencies, `from` → `to` being
+/// `eur_rates[to] / eur_rates[from]`.
+///
+/// The euro itself is added at 1 when missing. Returns an error if any rate
+/// is zero or negative.
+pub fn derive_all_pairs(eur_rates: &BTreeMap<String, Decimal>) -> Result<Vec<ExchangeRatePair>> {
+    if let Some((currency, rate)) = eur_rates.iter().find(|(_, rate)| **rate <= Decimal::ZERO) {
+        tracing::warn!(currency = %currency, rate = %rate, "Zero exchange rate received");
+        return Err(Error::string(&format!(
+            "Exchange rate for {currency} is zero — cannot derive pairs"
+        )));
+    }
+
+    let mut quotes = eur_rates.clone();
+    quotes.entry(QUOTE_BASE.to_string()).or_insert(Decimal::ONE);
+
+    let mut pairs = Vec::with_capacity(quotes.len() * (quotes.len() - 1));
+    for (from, from_rate) in &quotes {
+        for (to, to_rate) in &quotes {
+            if from == to {
+                continue;
+            }
+            pairs.push(ExchangeRatePair {
+                from_currency: from.clone(),
+                to_currency: to.clone(),
+                rate: *to_rate / *from_rate,
+            });
+        }
     }
+    Ok(pairs)
+}
 
-    let one = Decimal::ONE;
-    Ok(vec![
-        ExchangeRatePair {
-            from_currency: "EUR".into(),
-            to_currency: "CHF".into(),
-            rate: eur_chf,
-        },
-        ExchangeRatePair {
-            from_currency: "EUR".into(),
-            to_currency: "USD".into(),
-            rate: eur_usd,
-        },
-        ExchangeRatePair {
-            from_currency: "CHF".into(),
-            to_currency: "EUR".into(),
-            rate: one / eur_chf,
-        },
-        ExchangeRatePair {
-            from_currency: "USD".into(),
-            to_currency: "EUR".into(),
-            rate: one / eur_usd,
-        },
-        ExchangeRatePair {
-            from_currency: "CHF".into(),
-            to_currency: "USD".into(),
-            rate: eur_usd / eur_chf,
-        },
-        ExchangeRatePair {
-            from_currency: "USD".into(),
-            to_currency: "CHF".into(),
-            rate: eur_chf / eur_usd,
-        },
-    ])
+/// Rebuilds per-euro rates from one fiscal year of stored `exchange_rates`
+/// rows, keeping the `currencies` asked for.
+///
+/// Direct EUR→X rows are used as they are; X→USD rows fill the rest through
+/// the year's EUR→USD rate.
+pub fn eur_rates_from_rows(
+    rows: &[exchange_rates::Model],
+    currencies: &[String],
+) -> BTreeMap<String, Decimal> {
+    let direct = |from: &str, to: &str| {
+        rows.iter()
+            .find(|r| r.from_currency == from && r.to_currency == to && !r.rate.is_zero())
+            .map(|r| r.rate)
+    };
+    let eur_usd = direct(QUOTE_BASE, "USD");
+
+    currencies
+        .iter()
+        .filter_map(|currency| {
+            let rate = if currency == QUOTE_BASE {
+                Some(Decimal::ONE)
+            } else {
+                direct(QUOTE_BASE, currency).or_else(|| Some(eur_usd? / direct(currency, "USD")?))
+            };
+            rate.map(|rate| (currency.clone(), rate))
+        })
+        .collect()
 }
 
 // ---------------------------------------------------------------------------
 // Frankfurter API fetch
 // ---------------------------------------------------------------------------
 
-async fn fetch_from_frankfurter() -> Result<CachedRates> {
+async fn fetch_from_frankfurter(db: &DatabaseConnection) -> Result<CachedRates> {
+    let currencies = supported_currencies();
+    let symbols: Vec<&str> = currencies
+        .iter()
+        .map(String::as_str)
+        .filter(|c| *c != QUOTE_BASE)
+        .collect();
+
+    rate_limiter(db).acquire().await?;
     let url = frankfurter_url();
     let resp = HTTP_CLIENT
         .get(&url)
+        .query(&[("symbols", symbols.join(","))])
         .send()
         .await
         .map_err(|e| {
@@ -172,21 +255,25 @@ async fn fetch_from_frankfurter() -> Result<CachedRates> {
             Error::string(&format!("Frankfurter parse error: {e}"))
         })?;
 
-    let eur_chf_f64 = resp
-        .rates
-        .get("CHF")
-        .ok_or_else(|| Error::string("CHF rate missing from Frankfurter response"))?;
-    let eur_usd_f64 = resp
-        .rates
-        .get("USD")
-        .ok_or_else(|| Error::string("USD rate missing from Frankfurter response"))?;
-
-    let eur_chf = Decimal::from_f64_retain(*eur_chf_f64)
-        .ok_or_else(|| Error::string("Invalid CHF rate value"))?;
-    let eur_usd = Decimal::from_f64_retain(*eur_usd_f64)
-        .ok_or_else(|| Error::string("Invalid USD rate value"))?;
+    let mut eur_rates = BTreeMap::new();
+    for symbol in symbols {
+        let Some(value) = resp.rates.get(symbol) else {
+            tracing::warn!(
+                provider = "frankfurter",
+                currency = symbol,
+                "Rate missing from response"
+            );
+            continue;
+        };
+        let rate = Decimal::from_f64_retain(*value)
+            .ok_or_else(|| Error::string(&format!("Invalid {symbol} rate value")))?;
+        eur_rates.insert(symbol.to_string(), rate);
+    }
+    if eur_rates.is_empty() {
+        return Err(Error::string("No rates in Frankfurter response"));
+    }
 
-    let pairs = derive_all_pairs(eur_chf, eur_usd)?;
+    let pairs = derive_all_pairs(&eur_rates)?;
 
     Ok(CachedRates {
         rates: pairs,
@@ -204,48 +291,41 @@ async fn fetch_from_frankfurter() -> Result<CachedRates> {
 /// Returns rates with `fetched_at` set to epoch so they are always stale
 /// in the cache, ensuring a Frankfurter refresh is attempted on next request.
 async fn get_latest_db_rates(db: &DatabaseConnection) -> Result<Option<CachedRates>> {
-    let chf_usd = exchange_rates::Entity::find()
-        .filter(exchange_rates::Column::FromCurrency.eq("CHF"))
-        .filter(exchange_rates::Column::ToCurrency.eq("USD"))
-        .order_by_desc(exchange_rates::Column::FiscalYear)
-        .one(db)
-        .await?;
-
-    let eur_usd = exchange_rates::Entity::find()
-        .filter(exchange_rates::Column::FromCurrency.eq("EUR"))
+    let Some(latest) = exchange_rates::Entity::find()
+        .filter(exchange_rates::Column::FromCurrency.eq(QUOTE_BASE))
         .filter(exchange_rates::Column::ToCurrency.eq("USD"))
         .order_by_desc(exchange_rates::Column::FiscalYear)
         .one(db)
+        .await?
+    else {
+        return Ok(None);
+    };
+
+    let year = latest.fiscal_year;
+    let rows = exchange_rates::Entity::find()
+        .filter(exchange_rates::Column::FiscalYear.eq(year))
+        .all(db)
         .await?;
 
-    match (chf_usd, eur_usd) {
-        (Some(chf), Some(eur)) => {
-            let chf_usd_rate = chf.rate;
-            let eur_usd_rate = eur.rate;
-
-            // Derive EUR→CHF from the two base rates: EUR→CHF = EUR/USD ÷ CHF/USD
-            let eur_chf = eur_usd_rate / chf_usd_rate;
+    let eur_rates = eur_rates_from_rows(&rows, &supported_currencies());
+    if eur_rates.len() < 2 {
+        return Ok(None);
+    }
+    let pairs = derive_all_pairs(&eur_rates)?;
 
-            let pairs = derive_all_pairs(eur_chf, eur_usd_rate)?;
-            let year = chf.fiscal_year.max(eur.fiscal_year);
+    tracing::info!(
+        year = year,
+        "Serving exchange rates from DB fallback (fiscal year {})",
+        year,
+    );
 
-            tracing::info!(
-                year = year,
-                "Serving exchange rates from DB fallback (fiscal year {})",
-                year,
-            );
-
-            // Use epoch for fetched_at so these are always stale in cache,
-            // ensuring a Frankfurter refresh is attempted on next request.
-            Ok(Some(CachedRates {
-                rates: pairs,
-                fetched_at: DateTime::<Utc>::from_timestamp(0, 0)
-                    .expect("epoch timestamp is valid"),
-                rate_date: year.to_string(),
-            }))
-        }
-        _ => Ok(None),
-    }
+    // Use epoch for fetched_at so these are always stale in cache,
+    // ensuring a Frankfurter refresh is attempted on next request.
+    Ok(Some(CachedRates {
+        rates: pairs,
+        fetched_at: DateTime::<Utc>::from_timestamp(0, 0).expect("epoch timestamp is valid"),
+        rate_date: year.to_string(),
+    }))
 }
 
 // ---------------------------------------------------------------------------
@@ -289,7 +369,7 @@ pub async fn get_rates(db: &DatabaseConnection) -> Result<ExchangeRateResponse>
         }
     }
 
-    match fetch_from_frankfurter().await {
+    match fetch_from_frankfurter(db).await {
         Ok(fresh) => {
             let response = ExchangeRateResponse {
                 rates: fresh.rates.clone(),
@@ -328,6 +408,30 @@ pub async fn get_rates(db: &DatabaseConnection) -> Result<ExchangeRateResponse>
     }
 }
 
+/// Current rate from one currency to another, from [`get_rates`].
+///
+/// Returns `Ok(Some(Decimal::ONE))` when `from == to` and `Ok(None)` when the
+/// pair is not among the served currencies.
+///
+/// # Errors
+///
+/// Returns an error when no rates are available at all.
+pub async fn current_rate(
+    db: &DatabaseConnection,
+    from: &str,
+    to: &str,
+) -> Result<Option<Decimal>> {
+    if from == to {
+        return Ok(Some(Decimal::ONE));
+    }
+    let rates = get_rates(db).await?;
+    Ok(rates
+        .rates
+        .iter()
+        .find(|r| r.from_currency == from && r.to_currency == to)
+        .map(|r| r.rate))
+}
+
 // ---------------------------------------------------------------------------
 // Test helpers (unconditionally public for integration tests)
 // ---------------------------------------------------------------------------
@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::_entities::exchange_rates;
+    use crate::services::exchange_rate_provider::{
+        derive_all_pairs, eur_rates_from_rows, supported_currencies,
+    };
+    use rust_decimal::Decimal;
+    use std::collections::BTreeMap;
+    use std::str::FromStr;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    fn row(from: &str, to: &str, rate: &str) -> exchange_rates::Model {
+        exchange_rates::Model {
+            id: 0,
+            from_currency: from.to_string(),
+            to_currency: to.to_string(),
+            fiscal_year: 2025,
+            rate: dec(rate),
+            year_end_rate: None,
+        }
+    }
+
+    fn currencies(list: &[&str]) -> Vec<String> {
+        list.iter().map(|c| (*c).to_string()).collect()
+    }
+
+    #[test]
+    fn test_derive_all_pairs_builds_full_cross_matrix() {
+        let eur_rates = BTreeMap::from([
+            ("CHF".to_string(), dec("0.94")),
+            ("GBP".to_string(), dec("0.85")),
+            ("JPY".to_string(), dec("162.0")),
+            ("USD".to_string(), dec("1.08")),
+        ]);
+        let pairs = derive_all_pairs(&eur_rates).unwrap();
+
+        // Five currencies including the euro, every ordered pair once
+        assert_eq!(pairs.len(), 5 * 4);
+        let rate = |from: &str, to: &str| {
+            pairs
+                .iter()
+                .find(|p| p.from_currency == from && p.to_currency == to)
+                .map(|p| p.rate)
+                .unwrap()
+        };
+        assert_eq!(rate("EUR", "GBP"), dec("0.85"));
+        assert_eq!(rate("USD", "EUR"), Decimal::ONE / dec("1.08"));
+        assert_eq!(rate("GBP", "JPY"), dec("162.0") / dec("0.85"));
+        assert_eq!(rate("CHF", "USD"), dec("1.08") / dec("0.94"));
+    }
+
+    #[test]
+    fn test_derive_all_pairs_rejects_zero_rate() {
+        let eur_rates = BTreeMap::from([
+            ("CHF".to_string(), dec("0.94")),
+            ("SEK".to_string(), Decimal::ZERO),
+        ]);
+        let err = derive_all_pairs(&eur_rates).unwrap_err();
+        assert!(err.to_string().contains("SEK"), "{err}");
+    }
+
+    #[test]
+    fn test_eur_rates_from_rows_prefers_direct_rates_over_usd_crosses() {
+        let rows = vec![
+            row("EUR", "USD", "1.10"),
+            row("CHF", "USD", "1.25"),
+            row("GBP", "USD", "1.30"),
+            row("EUR", "GBP", "0.84"),
+            row("NOK", "USD", "0.0"),
+        ];
+        let eur_rates =
+            eur_rates_from_rows(&rows, &currencies(&["CHF", "EUR", "GBP", "NOK", "USD"]));
+
+        assert_eq!(eur_rates["EUR"], Decimal::ONE);
+        assert_eq!(eur_rates["USD"], dec("1.10"));
+        assert_eq!(eur_rates["CHF"], dec("1.10") / dec("1.25"));
+        assert_eq!(eur_rates["GBP"], dec("0.84"));
+        // A zero rate cannot be inverted, so NOK is left out
+        assert!(!eur_rates.contains_key("NOK"));
+    }
+
+    #[test]
+    fn test_supported_currencies_from_env_always_include_euro() {
+        assert!(supported_currencies().contains(&"GBP".to_string()));
+
+        std::env::set_var("EXCHANGE_RATE_CURRENCIES", " usd, CHF,xx,USD ");
+        assert_eq!(supported_currencies(), currencies(&["USD", "CHF", "EUR"]));
+
+        std::env::set_var("EXCHANGE_RATE_CURRENCIES", "");
+        assert_eq!(supported_currencies().len(), 8);
+        std::env::remove_var("EXCHANGE_RATE_CURRENCIES");
+    }
+}
@@ -1,27 +1,168 @@
 //! Data harvesting service.
 //!
 //! Orchestrates the full 10-year historical data fetch pipeline: resolve ticker,
-//! fetch yearly records, apply manual overrides, adjust for splits, compute
-//! P/E ranges, and persist to the database.
+//! fetch yearly records from the exchange's [`FinancialDataProvider`], layer
+//! them under any uploaded table, apply manual overrides, adjust for splits
+//! from the corporate actions ledger, compute P/E ranges, detect data
+//! anomalies, and persist to the database. Quarterly records reported since
+//! are harvested alongside and stored in `historicals_quarterly`, feeding the
+//! trailing-twelve-month EPS, and the provider's current quote is stored in
+//! `quotes`. Daily bars are added to `daily_prices`; the price range of every
+//! fiscal year the stored series covers is derived from it rather than taken
+//! from the provider.
+//!
+//! A regular harvest only inserts fiscal years that are not stored yet. A
+//! refresh ([`refresh_harvest`]) also compares every stored year with the new
+//! values, updates the fields that changed and audits each of them as a
+//! `Restatement`.
 
-use crate::models::{historicals, tickers};
-use chrono::Datelike;
+use super::audit_service::AuditService;
+use super::corporate_actions::{self, adjustment_factor_at};
+use super::historicals_import::UPLOADED_SOURCE;
+use super::providers::{
+    CorporateAction, DailyPrice, FinancialDataProvider, ProviderRegistry, QuarterlyFundamentals,
+    YearlyFundamentals, YearlyPriceRange,
+};
+use crate::models::_entities::audit_logs;
+use crate::models::{daily_prices, historicals, historicals_quarterly, tickers};
+use chrono::{Datelike, NaiveDate};
 use loco_rs::prelude::*;
-use rust_decimal::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
 use std::time::Duration;
-use steady_invest_logic::{HistoricalData, HistoricalYearlyData};
+use steady_invest_logic::{
+    detect_anomalies, Anomaly, AnomalyConfig, FiscalCalendar, HistoricalData,
+    HistoricalQuarterlyData, HistoricalYearlyData,
+};
 use tokio::time::timeout;
 
+/// Number of fiscal years shown on the SSG.
+const HISTORY_YEARS: i32 = 10;
+
+/// Currency harvested exchange rates convert to unless the caller picks one.
+pub const DEFAULT_DISPLAY_CURRENCY: &str = "USD";
+
+/// Fiscal years before the current one whose quarters are harvested; enough
+/// for a trailing twelve months spanning a fiscal year boundary.
+const QUARTER_LOOKBACK_YEARS: i32 = 2;
+
+/// How a harvest treats fiscal years that are already stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HarvestMode {
+    /// Insert missing years and leave stored ones untouched.
+    #[default]
+    InsertMissing,
+    /// Also update stored years whose values changed, auditing each change.
+    Refresh,
+}
+
+/// A stored value replaced during a refresh.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub fiscal_year: i32,
+    /// `historicals` column name (e.g. `"eps"`).
+    pub field: String,
+    pub old: Option<Decimal>,
+    pub new: Option<Decimal>,
+}
+
+/// What a harvest wrote to the `historicals` table.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HarvestSummary {
+    /// Years stored for the first time.
+    pub inserted_years: Vec<i32>,
+    /// Years whose stored values changed.
+    pub updated_years: Vec<i32>,
+    /// Years left as stored: unchanged, uploaded, or not compared.
+    pub kept_years: Vec<i32>,
+    /// Every field that changed, oldest year first.
+    pub changes: Vec<FieldChange>,
+}
+
+/// Harvested data together with a summary of what was persisted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HarvestOutcome {
+    pub data: HistoricalData,
+    pub summary: HarvestSummary,
+}
+
+/// Executes the complete data harvest pipeline for a single ticker using the
+/// provider registry configured from the environment.
+///
+/// # Errors
+///
+/// See [`run_harvest_with_registry`].
+pub async fn run_harvest(ctx: &AppContext, ticker: &str) -> Result<HistoricalData> {
+    run_harvest_with_registry(ctx, ticker, &ProviderRegistry::from_env(&ctx.db)).await
+}
+
+/// Re-harvests a ticker and updates stored years whose values changed.
+///
+/// Fields with a manual override and years backed by an uploaded table are
+/// never touched. Every changed field is written to the audit log as a
+/// `Restatement` event carrying its old and new value.
+///
+/// # Errors
+///
+/// See [`run_harvest_with_registry`].
+pub async fn refresh_harvest(ctx: &AppContext, ticker: &str) -> Result<HarvestOutcome> {
+    harvest(
+        ctx,
+        ticker,
+        &ProviderRegistry::from_env(&ctx.db),
+        HarvestMode::Refresh,
+        DEFAULT_DISPLAY_CURRENCY,
+    )
+    .await
+}
+
 /// Executes the complete data harvest pipeline for a single ticker.
 ///
-/// Steps: resolve ticker → fetch 10 years of data → apply manual overrides →
-/// adjust for splits → compute P/E ranges → persist records.
+/// Steps: resolve ticker → fetch the latest 10 fiscal years (as defined by
+/// the ticker's fiscal-year-end month) from the provider registered for the
+/// ticker's exchange → replace years covered by an uploaded table → apply
+/// manual overrides → adjust for the ledger's corporate actions → compute P/E
+/// ranges → persist records. Rows that already exist (including
+/// uploaded ones) are not overwritten.
 ///
 /// # Errors
 ///
-/// Returns an error if the ticker is not found, data retrieval times out
-/// (4-second limit), or a database operation fails.
-pub async fn run_harvest(ctx: &AppContext, ticker: &str) -> Result<HistoricalData> {
+/// Returns an error if the ticker is not found, the provider fails or returns
+/// no data while no table was uploaded, data retrieval times out (4-second
+/// limit), or a database operation fails.
+pub async fn run_harvest_with_registry(
+    ctx: &AppContext,
+    ticker: &str,
+    registry: &ProviderRegistry,
+) -> Result<HistoricalData> {
+    harvest(
+        ctx,
+        ticker,
+        registry,
+        HarvestMode::InsertMissing,
+        DEFAULT_DISPLAY_CURRENCY,
+    )
+    .await
+    .map(|outcome| outcome.data)
+}
+
+/// Runs the harvest pipeline, persisting according to `mode`.
+///
+/// The yearly, closing, quarterly and spot exchange rates convert from the
+/// reporting currency to `display_currency`. They are not persisted.
+///
+/// # Errors
+///
+/// See [`run_harvest_with_registry`].
+pub async fn harvest(
+    ctx: &AppContext,
+    ticker: &str,
+    registry: &ProviderRegistry,
+    mode: HarvestMode,
+    display_currency: &str,
+) -> Result<HarvestOutcome> {
     // 1. Resolve Ticker Info from DB (AC Compliance)
     let ticker_info = tickers::Entity::find()
         .filter(tickers::Column::Ticker.eq(ticker))
@@ -29,14 +170,10 @@ pub async fn run_harvest(ctx: &AppContext, ticker: &str) -> Result<HistoricalDat
         .await?
         .ok_or_else(|| Error::string(&format!("Ticker {} not found in system", ticker)))?;
 
-    // records vector is collected from the fetch_future result below
-    let current_year = chrono::Utc::now().year();
-    let display_currency = "USD"; // Default display currency (In real app, from user preference/session)
-
-    // 2. Fetch data (AC 3, 4) - Using timeout for NFR 4
-    let ticker_for_fetch = ticker.to_string();
-    let db_for_fetch = ctx.db.clone();
-    let reporting_currency = ticker_info.currency.clone();
+    let provider = registry.provider_for(&ticker_info.exchange);
+    // Fiscal years follow the ticker's year end; the current one is in progress
+    let calendar = ticker_info.fiscal_calendar();
+    let current_year = calendar.fiscal_year(chrono::Utc::now().date_naive());
 
     // 2. Fetch Manual Overrides (AC 6)
     let db_overrides = crate::models::historicals_overrides::Entity::find()
@@ -44,157 +181,609 @@ pub async fn run_harvest(ctx: &AppContext, ticker: &str) -> Result<HistoricalDat
         .all(&ctx.db)
         .await?;
 
-    let fetch_future = async move {
-        let mut yearly_records = Vec::new();
-        // Not all tickers pay dividends; simulate realistic mix
-        let pays_dividends = matches!(ticker_for_fetch.as_str(), "AAPL" | "MSFT" | "NESN.SW");
-        // Simulate a 4:1 split for AAPL in 2020 for verification (AC 2)
-        let split_factor = if ticker_for_fetch == "AAPL" {
-            Decimal::from(4)
-        } else {
-            Decimal::ONE
-        };
+    // Uploaded tables form the base layer; the provider only fills other years
+    let uploaded = super::historicals_import::load_uploaded(&ctx.db, ticker).await?;
 
-        for i in 1..=10 {
-            let year = current_year - i;
-            // Apply split factor to years strictly before 2020 if it's AAPL
-            let factor = if ticker_for_fetch == "AAPL" && year < 2020 {
-                split_factor
-            } else {
-                Decimal::ONE
-            };
+    // Only days after the stored daily series are fetched
+    let daily_from = match daily_prices::Entity::latest_date(&ctx.db, ticker).await? {
+        Some(latest) => latest.succ_opt().unwrap_or(latest),
+        None => calendar.start(current_year - HISTORY_YEARS),
+    };
 
-            // Fetch historical rate
-            let exchange_rate = super::exchange::get_rate(
-                &db_for_fetch,
-                &reporting_currency,
-                display_currency,
-                year,
-            )
-            .await
-            .unwrap_or(None);
+    // 3. Fetch provider data (AC 3, 4) - Using timeout for NFR 4
+    let fetched = timeout(
+        Duration::from_secs(4),
+        fetch_provider_data(provider.clone(), ticker, current_year, calendar, daily_from),
+    )
+    .await
+    .map_err(|_| Error::string("Data retrieval timed out (NFR 4)"))
+    .and_then(|result| result);
+
+    let mut fetched = match fetched {
+        Ok(fetched) => fetched,
+        Err(e) if !uploaded.is_empty() => {
+            tracing::warn!(
+                ticker,
+                provider = provider.name(),
+                error = %e,
+                "Provider failed, harvesting from uploaded data only"
+            );
+            FetchedData::default()
+        }
+        Err(e) => return Err(e),
+    };
+
+    // Split adjustment comes from the corporate actions ledger, which the
+    // provider's reported actions are merged into first
+    corporate_actions::sync_provider_actions(
+        &ctx.db,
+        ticker,
+        &fetched.corporate_actions,
+        provider.name(),
+    )
+    .await?;
+    let ledger = corporate_actions::ledger(&ctx.db, ticker).await?;
+
+    // Price ranges of fiscal years covered by the stored daily series are
+    // derived from it, on each year's share basis from the ledger
+    daily_prices::store_missing(&ctx.db, ticker, &fetched.daily_prices, provider.name()).await?;
+    if let (Some(&first), Some(&last)) = (fetched.years.keys().next(), fetched.years.keys().last())
+    {
+        let bars =
+            daily_prices::load(&ctx.db, ticker, calendar.start(first), calendar.end(last)).await?;
+        let actions: Vec<CorporateAction> = ledger.iter().map(|e| e.action.clone()).collect();
+        for range in daily_prices::yearly_ranges(&bars, calendar, &actions) {
+            if let Some((_, prices)) = fetched.years.get_mut(&range.fiscal_year) {
+                *prices = Some(range);
+            }
+        }
+    }
 
-            let mut record = HistoricalYearlyData {
+    let mut base: BTreeMap<i32, HistoricalYearlyData> = fetched
+        .years
+        .into_iter()
+        .map(|(year, (fundamentals, prices))| {
+            let prices = prices.unwrap_or_default();
+            let record = HistoricalYearlyData {
                 fiscal_year: year,
-                sales: Decimal::from(1000 + (11 - i) * 123),
-                eps: Decimal::from_f32(1.5 * (11 - i) as f32)
-                    .unwrap_or_default()
-                    .round_dp(2),
-                price_high: Decimal::from(150 + (11 - i) * 15),
-                price_low: Decimal::from(100 + (11 - i) * 8),
-                adjustment_factor: factor,
-                exchange_rate,
-                net_income: Some(Decimal::from(100 + (11 - i) * 10)),
-                pretax_income: Some(Decimal::from(120 + (11 - i) * 12)),
-                total_equity: Some(Decimal::from(1000 + (11 - i) * 50)),
-                dividend_per_share: if pays_dividends {
-                    Some(
-                        Decimal::from_f32(0.5 + (11 - i) as f32 * 0.1)
-                            .unwrap_or_default()
-                            .round_dp(2),
-                    )
-                } else {
-                    None
-                },
-                shares_outstanding: Some(Decimal::from(1_000_000 + (11 - i) as i64 * 50_000)),
-                overrides: vec![],
+                sales: fundamentals.sales.unwrap_or_default(),
+                eps: fundamentals.eps.unwrap_or_default(),
+                price_high: prices.high,
+                price_low: prices.low,
+                net_income: fundamentals.net_income,
+                pretax_income: fundamentals.pretax_income,
+                total_equity: fundamentals.total_equity,
+                dividend_per_share: fundamentals.dividend_per_share,
+                shares_outstanding: fundamentals.shares_outstanding,
+                ..Default::default()
             };
+            (year, record)
+        })
+        .collect();
+    for record in uploaded {
+        base.insert(record.fiscal_year, record);
+    }
+    while base.len() > HISTORY_YEARS as usize {
+        base.pop_first();
+    }
 
-            // Apply overrides (AC 4, 6)
-            for ovr in db_overrides.iter().filter(|o| o.fiscal_year == year) {
-                let logic_ovr = steady_invest_logic::ManualOverride {
-                    field_name: ovr.field_name.clone(),
-                    value: ovr.value,
-                    note: ovr.note.clone(),
-                };
-
-                match ovr.field_name.as_str() {
-                    "sales" => record.sales = ovr.value,
-                    "eps" => record.eps = ovr.value,
-                    "price_high" => record.price_high = ovr.value,
-                    "price_low" => record.price_low = ovr.value,
-                    "net_income" => record.net_income = Some(ovr.value),
-                    "pretax_income" => record.pretax_income = Some(ovr.value),
-                    "total_equity" => record.total_equity = Some(ovr.value),
-                    "dividend_per_share" => record.dividend_per_share = Some(ovr.value),
-                    "shares_outstanding" => record.shares_outstanding = Some(ovr.value),
-                    _ => {}
-                }
-                record.overrides.push(logic_ovr);
-            }
+    if base.is_empty() {
+        return Err(Error::string(&format!(
+            "No historical data available for {} from provider '{}'",
+            ticker,
+            provider.name()
+        )));
+    }
+
+    let reporting_currency =
+        reporting_currency(ticker, fetched.profile_currency, &ticker_info.currency);
+
+    let mut records = Vec::with_capacity(base.len());
+    for (year, mut record) in base {
+        // Fetch the rate covering the fiscal period
+        record.exchange_rate = super::exchange::get_rate(
+            &ctx.db,
+            &reporting_currency,
+            display_currency,
+            year,
+            calendar,
+        )
+        .await
+        .unwrap_or(None);
+        record.closing_exchange_rate = super::exchange::get_closing_rate(
+            &ctx.db,
+            &reporting_currency,
+            display_currency,
+            year,
+            calendar,
+        )
+        .await
+        .unwrap_or(None);
+        record.adjustment_factor =
+            adjustment_factor_at(ledger.iter().map(|e| &e.action), calendar.end(year));
+
+        // Apply overrides (AC 4, 6)
+        for ovr in db_overrides.iter().filter(|o| o.fiscal_year == year) {
+            let logic_ovr = steady_invest_logic::ManualOverride {
+                field_name: ovr.field_name.clone(),
+                value: ovr.value,
+                note: ovr.note.clone(),
+            };
 
-            yearly_records.push(record);
+            match ovr.field_name.as_str() {
+                "sales" => record.sales = ovr.value,
+                "eps" => record.eps = ovr.value,
+                "price_high" => record.price_high = ovr.value,
+                "price_low" => record.price_low = ovr.value,
+                "net_income" => record.net_income = Some(ovr.value),
+                "pretax_income" => record.pretax_income = Some(ovr.value),
+                "total_equity" => record.total_equity = Some(ovr.value),
+                "dividend_per_share" => record.dividend_per_share = Some(ovr.value),
+                "shares_outstanding" => record.shares_outstanding = Some(ovr.value),
+                _ => {}
+            }
+            record.overrides.push(logic_ovr);
         }
-        yearly_records
-    };
 
-    let mut records = timeout(Duration::from_secs(4), fetch_future)
+        records.push(record);
+    }
+
+    let mut quarters = Vec::with_capacity(fetched.quarters.len());
+    for q in fetched.quarters {
+        // A quarter lies within one calendar year: the one it ends in
+        let exchange_rate = super::exchange::get_rate(
+            &ctx.db,
+            &reporting_currency,
+            display_currency,
+            q.period_end.year(),
+            FiscalCalendar::CALENDAR_YEAR,
+        )
         .await
-        .map_err(|_| Error::string("Data retrieval timed out (NFR 4)"))?;
+        .unwrap_or(None);
+        let adjustment_factor =
+            adjustment_factor_at(ledger.iter().map(|e| &e.action), q.period_end);
+        quarters.push(HistoricalQuarterlyData {
+            fiscal_year: q.fiscal_year,
+            fiscal_quarter: q.fiscal_quarter,
+            period_end: q.period_end,
+            sales: q.sales,
+            eps: q.eps,
+            net_income: q.net_income,
+            pretax_income: q.pretax_income,
+            price_high: q.price_high,
+            price_low: q.price_low,
+            adjustment_factor,
+            exchange_rate,
+        });
+    }
+    quarters.sort_by_key(|q| q.sequence());
 
     // Sort records chronologically (oldest first) so all downstream consumers
     // — chart rendering, PDF export, growth analysis — receive ordered data.
     records.sort_by_key(|r| r.fiscal_year);
 
+    // Today's rate for the spot convention; only fetched for foreign tickers
+    let spot_exchange_rate = if reporting_currency == display_currency {
+        Some(Decimal::ONE)
+    } else {
+        super::exchange_rate_provider::current_rate(&ctx.db, &reporting_currency, display_currency)
+            .await
+            .unwrap_or(None)
+    };
+
     // 3. Apply Adjustments (AC 3)
     let mut data = HistoricalData {
         ticker: ticker.to_string(),
-        currency: ticker_info.currency.clone(),
+        currency: reporting_currency.clone(),
         display_currency: None,
+        spot_exchange_rate,
         records,
+        quarters,
         is_complete: true,
         is_split_adjusted: false,
         pe_range_analysis: None,
+        anomalies: Vec::new(),
     };
     data.apply_adjustments();
 
     // 4. Compute P/E Analysis (AC 1, 2)
     data.pe_range_analysis = Some(steady_invest_logic::calculate_pe_ranges(&data));
-
-    let db = &ctx.db;
+    data.anomalies = detect_anomalies(&data, &anomaly_config_from_env());
 
     // 4. Persist to DB
+    let overridden: HashSet<(i32, &str)> = db_overrides
+        .iter()
+        .map(|o| (o.fiscal_year, o.field_name.as_str()))
+        .collect();
+    let mut summary = HarvestSummary::default();
     for rec in &data.records {
-        let active_model = historicals::ActiveModel {
-            ticker: ActiveValue::set(ticker.to_string()),
-            fiscal_year: ActiveValue::set(rec.fiscal_year),
-            sales: ActiveValue::set(rec.sales),
-            eps: ActiveValue::set(rec.eps),
-            price_high: ActiveValue::set(rec.price_high),
-            price_low: ActiveValue::set(rec.price_low),
-            currency: ActiveValue::set(ticker_info.currency.clone()),
-            is_split_adjusted: ActiveValue::set(Some(data.is_split_adjusted)),
-            adjustment_factor: ActiveValue::set(Some(rec.adjustment_factor)),
-            net_income: ActiveValue::set(rec.net_income),
-            pretax_income: ActiveValue::set(rec.pretax_income),
-            total_equity: ActiveValue::set(rec.total_equity),
-            dividend_per_share: ActiveValue::set(rec.dividend_per_share),
-            shares_outstanding: ActiveValue::set(rec.shares_outstanding),
-            ..Default::default()
-        };
-
         let existing = historicals::Entity::find()
             .filter(historicals::Column::Ticker.eq(ticker))
             .filter(historicals::Column::FiscalYear.eq(rec.fiscal_year))
-            .one(db)
+            .one(&ctx.db)
             .await?;
 
-        if existing.is_none() {
-            active_model.insert(db).await?;
+        let existing = match existing {
+            None => {
+                historicals::ActiveModel {
+                    ticker: ActiveValue::set(ticker.to_string()),
+                    fiscal_year: ActiveValue::set(rec.fiscal_year),
+                    sales: ActiveValue::set(rec.sales),
+                    eps: ActiveValue::set(rec.eps),
+                    price_high: ActiveValue::set(rec.price_high),
+                    price_low: ActiveValue::set(rec.price_low),
+                    currency: ActiveValue::set(reporting_currency.clone()),
+                    is_split_adjusted: ActiveValue::set(Some(data.is_split_adjusted)),
+                    adjustment_factor: ActiveValue::set(Some(rec.adjustment_factor)),
+                    net_income: ActiveValue::set(rec.net_income),
+                    pretax_income: ActiveValue::set(rec.pretax_income),
+                    total_equity: ActiveValue::set(rec.total_equity),
+                    dividend_per_share: ActiveValue::set(rec.dividend_per_share),
+                    shares_outstanding: ActiveValue::set(rec.shares_outstanding),
+                    source: ActiveValue::set(Some(provider.name().to_string())),
+                    ..Default::default()
+                }
+                .insert(&ctx.db)
+                .await?;
+                summary.inserted_years.push(rec.fiscal_year);
+                continue;
+            }
+            Some(existing) => existing,
+        };
+
+        let is_uploaded = existing.source.as_deref() == Some(UPLOADED_SOURCE);
+        if mode == HarvestMode::InsertMissing || is_uploaded {
+            summary.kept_years.push(rec.fiscal_year);
+            continue;
+        }
+
+        let changes: Vec<FieldChange> = changed_fields(&existing, rec)
+            .into_iter()
+            .filter(|c| !overridden.contains(&(rec.fiscal_year, c.field.as_str())))
+            .collect();
+        let factor_changed = existing.adjustment_factor != Some(rec.adjustment_factor);
+        if changes.is_empty() && !factor_changed {
+            summary.kept_years.push(rec.fiscal_year);
+            continue;
         }
+
+        let mut active: historicals::ActiveModel = existing.into();
+        for change in &changes {
+            let new = change.new;
+            match change.field.as_str() {
+                "sales" => active.sales = ActiveValue::set(new.unwrap_or_default()),
+                "eps" => active.eps = ActiveValue::set(new.unwrap_or_default()),
+                "price_high" => active.price_high = ActiveValue::set(new.unwrap_or_default()),
+                "price_low" => active.price_low = ActiveValue::set(new.unwrap_or_default()),
+                "net_income" => active.net_income = ActiveValue::set(new),
+                "pretax_income" => active.pretax_income = ActiveValue::set(new),
+                "total_equity" => active.total_equity = ActiveValue::set(new),
+                "dividend_per_share" => active.dividend_per_share = ActiveValue::set(new),
+                "shares_outstanding" => active.shares_outstanding = ActiveValue::set(new),
+                _ => {}
+            }
+            AuditService::log_restatement(
+                &ctx.db,
+                ticker,
+                &ticker_info.exchange,
+                &format!("{} FY{}", change.field, change.fiscal_year),
+                change.old.map(|v| v.normalize().to_string()),
+                change.new.map(|v| v.normalize().to_string()),
+                provider.name(),
+            )
+            .await?;
+        }
+        active.is_split_adjusted = ActiveValue::set(Some(data.is_split_adjusted));
+        active.adjustment_factor = ActiveValue::set(Some(rec.adjustment_factor));
+        active.update(&ctx.db).await?;
+
+        summary.updated_years.push(rec.fiscal_year);
+        summary.changes.extend(changes);
     }
 
-    // 5. Detect and Audit Anomalies (AC Story 5.2)
-    if ticker == "ANOMALY" {
-        let _ = crate::services::audit_service::AuditService::log_anomaly(
-            &ctx.db,
-            ticker,
-            &ticker_info.exchange,
-            "Integrity",
-            "Simulated data integrity gap detected",
-        )
-        .await;
+    persist_quarters(
+        &ctx.db,
+        ticker,
+        &reporting_currency,
+        provider.name(),
+        &data.quarters,
+        mode,
+    )
+    .await?;
+
+    // The current price is a bonus; a provider without one must not fail the harvest
+    if let Err(e) = super::quotes::refresh_from_provider(
+        &ctx.db,
+        provider.as_ref(),
+        ticker,
+        &reporting_currency,
+    )
+    .await
+    {
+        tracing::warn!(ticker, provider = provider.name(), error = %e, "Quote fetch failed");
+    }
+
+    // Audit ledger entries adjusting this history for the first time
+    let years: Vec<i32> = data.records.iter().map(|r| r.fiscal_year).collect();
+    corporate_actions::audit_applied(
+        &ctx.db,
+        ticker,
+        &ticker_info.exchange,
+        &ledger,
+        &years,
+        calendar,
+    )
+    .await?;
+
+    // 5. Audit Anomalies (AC Story 5.2, FR1.5)
+    audit_anomalies(&ctx.db, ticker, &ticker_info.exchange, &data.anomalies).await?;
+
+    Ok(HarvestOutcome { data, summary })
+}
+
+/// Stores harvested quarters in `historicals_quarterly`.
+///
+/// Missing quarters are inserted; in [`HarvestMode::Refresh`] stored quarters
+/// whose values changed are overwritten. Like yearly records, quarters are
+/// stored adjusted, together with the factor that was applied.
+async fn persist_quarters(
+    db: &DatabaseConnection,
+    ticker: &str,
+    currency: &str,
+    source: &str,
+    quarters: &[HistoricalQuarterlyData],
+    mode: HarvestMode,
+) -> Result<()> {
+    let stored = historicals_quarterly::Entity::find_by_ticker(db, ticker).await?;
+
+    for q in quarters {
+        let fiscal_quarter = i16::from(q.fiscal_quarter);
+        let round = |v: Option<Decimal>| v.map(|v| v.round_dp(4));
+        let existing = stored
+            .iter()
+            .find(|s| s.fiscal_year == q.fiscal_year && s.fiscal_quarter == fiscal_quarter);
+
+        let mut active = match existing {
+            None => historicals_quarterly::ActiveModel {
+                ticker: ActiveValue::set(ticker.to_string()),
+                fiscal_year: ActiveValue::set(q.fiscal_year),
+                fiscal_quarter: ActiveValue::set(fiscal_quarter),
+                ..Default::default()
+            },
+            Some(existing) if mode == HarvestMode::Refresh => {
+                let unchanged = existing.period_end == q.period_end
+                    && existing.sales == q.sales.round_dp(4)
+                    && existing.eps == q.eps.round_dp(4)
+                    && existing.net_income == round(q.net_income)
+                    && existing.pretax_income == round(q.pretax_income)
+                    && existing.price_high == round(q.price_high)
+                    && existing.price_low == round(q.price_low)
+                    && existing.adjustment_factor == Some(q.adjustment_factor);
+                if unchanged {
+                    continue;
+                }
+                existing.clone().into()
+            }
+            Some(_) => continue,
+        };
+
+        active.period_end = ActiveValue::set(q.period_end);
+        active.sales = ActiveValue::set(q.sales.round_dp(4));
+        active.eps = ActiveValue::set(q.eps.round_dp(4));
+        active.net_income = ActiveValue::set(round(q.net_income));
+        active.pretax_income = ActiveValue::set(round(q.pretax_income));
+        active.price_high = ActiveValue::set(round(q.price_high));
+        active.price_low = ActiveValue::set(round(q.price_low));
+        active.currency = ActiveValue::set(currency.to_string());
+        active.adjustment_factor = ActiveValue::set(Some(q.adjustment_factor));
+        active.source = ActiveValue::set(Some(source.to_string()));
+        if existing.is_some() {
+            active.update(db).await?;
+        } else {
+            active.insert(db).await?;
+        }
     }
+    Ok(())
+}
+
+/// Reads anomaly thresholds from `ANOMALY_JUMP_Z_SCORE` and
+/// `ANOMALY_MAX_MARGIN_DEVIATION`, keeping the defaults for unset or invalid
+/// values.
+fn anomaly_config_from_env() -> AnomalyConfig {
+    let positive = |var: &str| {
+        std::env::var(var)
+            .ok()
+            .and_then(|v| v.trim().parse::<f64>().ok())
+            .filter(|v| v.is_finite() && *v > 0.0)
+    };
+    let defaults = AnomalyConfig::default();
+    AnomalyConfig {
+        jump_z_score: positive("ANOMALY_JUMP_Z_SCORE").unwrap_or(defaults.jump_z_score),
+        max_margin_deviation: positive("ANOMALY_MAX_MARGIN_DEVIATION")
+            .unwrap_or(defaults.max_margin_deviation),
+    }
+}
+
+/// Logs each anomaly through [`AuditService::log_anomaly`], skipping those
+/// already logged for the ticker by an earlier harvest.
+async fn audit_anomalies(
+    db: &DatabaseConnection,
+    ticker: &str,
+    exchange: &str,
+    anomalies: &[Anomaly],
+) -> Result<()> {
+    if anomalies.is_empty() {
+        return Ok(());
+    }
+
+    let logged: HashSet<(String, Option<String>)> = audit_logs::Entity::find()
+        .filter(audit_logs::Column::Ticker.eq(ticker))
+        .filter(audit_logs::Column::EventType.eq("Anomaly"))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|l| (l.field_name, l.new_value))
+        .collect();
+
+    for anomaly in anomalies {
+        let field = format!("{} FY{}", anomaly.field, anomaly.fiscal_year);
+        if logged.contains(&(field.clone(), Some(anomaly.message.clone()))) {
+            continue;
+        }
+        AuditService::log_anomaly(db, ticker, exchange, &field, &anomaly.message).await?;
+    }
+    Ok(())
+}
+
+/// Fields of a stored row that differ from the newly harvested record.
+///
+/// New values are rounded to the four decimals the table stores, so values
+/// that only differ beyond that precision are not reported.
+fn changed_fields(existing: &historicals::Model, rec: &HistoricalYearlyData) -> Vec<FieldChange> {
+    let fields = [
+        ("sales", Some(existing.sales), Some(rec.sales)),
+        ("eps", Some(existing.eps), Some(rec.eps)),
+        (
+            "price_high",
+            Some(existing.price_high),
+            Some(rec.price_high),
+        ),
+        ("price_low", Some(existing.price_low), Some(rec.price_low)),
+        ("net_income", existing.net_income, rec.net_income),
+        ("pretax_income", existing.pretax_income, rec.pretax_income),
+        ("total_equity", existing.total_equity, rec.total_equity),
+        (
+            "dividend_per_share",
+            existing.dividend_per_share,
+            rec.dividend_per_share,
+        ),
+        (
+            "shares_outstanding",
+            existing.shares_outstanding,
+            rec.shares_outstanding,
+        ),
+    ];
+
+    fields
+        .into_iter()
+        .map(|(field, old, new)| (field, old, new.map(|v| v.round_dp(4))))
+        .filter(|(_, old, new)| old != new)
+        .map(|(field, old, new)| FieldChange {
+            fiscal_year: rec.fiscal_year,
+            field: field.to_string(),
+            old,
+            new,
+        })
+        .collect()
+}
+
+/// Yearly fundamentals and price range keyed by fiscal year.
+pub(crate) type YearlyInputs = (YearlyFundamentals, Option<YearlyPriceRange>);
+
+/// Raw provider output for one ticker, trimmed to the SSG window.
+#[derive(Default)]
+struct FetchedData {
+    profile_currency: Option<String>,
+    years: BTreeMap<i32, YearlyInputs>,
+    corporate_actions: Vec<CorporateAction>,
+    quarters: Vec<QuarterlyFundamentals>,
+    daily_prices: Vec<DailyPrice>,
+}
+
+/// Pairs fundamentals with price ranges by fiscal year and keeps the latest
+/// [`HISTORY_YEARS`] years.
+///
+/// Only years with both sales and EPS are kept. A year the provider only has
+/// prices or dividends for would otherwise be stored with zero sales and EPS
+/// and skew the growth, P/E and quality figures.
+pub(crate) fn merge_yearly_inputs(
+    fundamentals: Vec<YearlyFundamentals>,
+    prices: Vec<YearlyPriceRange>,
+) -> BTreeMap<i32, YearlyInputs> {
+    let mut by_year: BTreeMap<i32, YearlyInputs> = fundamentals
+        .into_iter()
+        .filter(|f| f.sales.is_some() && f.eps.is_some())
+        .map(|f| (f.fiscal_year, (f, None)))
+        .collect();
+    for p in prices {
+        if let Some((_, range)) = by_year.get_mut(&p.fiscal_year) {
+            *range = Some(p);
+        }
+    }
+
+    // Keep only the most recent HISTORY_YEARS fiscal years
+    while by_year.len() > HISTORY_YEARS as usize {
+        by_year.pop_first();
+    }
+    by_year
+}
+
+/// Currency the provider reports `ticker` in, falling back to the `listed`
+/// currency when the provider's code is missing or not a supported currency,
+/// such as a minor unit like `GBp` the provider did not convert.
+pub(crate) fn reporting_currency(
+    ticker: &str,
+    profile_currency: Option<String>,
+    listed: &str,
+) -> String {
+    match profile_currency {
+        Some(code) if super::exchange_rate_provider::is_supported_currency(&code) => code,
+        Some(code) => {
+            tracing::warn!(
+                ticker,
+                currency = code,
+                listed,
+                "Unsupported provider currency, using the listing currency"
+            );
+            listed.to_string()
+        }
+        None => listed.to_string(),
+    }
+}
+
+/// Fetches profile, fundamentals, prices, corporate actions, quarters and
+/// daily bars concurrently and keeps the latest [`HISTORY_YEARS`] completed
+/// fiscal years that have fundamentals (see [`merge_yearly_inputs`]).
+/// `current_year` is the fiscal year in progress.
+///
+/// The lookback is twice the SSG window so that a provider lagging behind the
+/// calendar by a year or two still yields a full ten-year history. Quarters
+/// are fetched for the last [`QUARTER_LOOKBACK_YEARS`] fiscal years and the
+/// current one, daily bars from `daily_from`. A failing daily series is
+/// logged and left empty since provider ranges stand in for it.
+async fn fetch_provider_data(
+    provider: Arc<dyn FinancialDataProvider>,
+    ticker: &str,
+    current_year: i32,
+    calendar: FiscalCalendar,
+    daily_from: NaiveDate,
+) -> Result<FetchedData> {
+    let years = (current_year - 2 * HISTORY_YEARS)..=(current_year - 1);
+
+    let quarter_years = (current_year - QUARTER_LOOKBACK_YEARS)..=current_year;
+
+    let daily_prices = async {
+        let bars = provider.fetch_daily_prices(ticker, daily_from).await;
+        Ok::<_, Error>(bars.unwrap_or_else(|e| {
+            tracing::warn!(ticker, provider = provider.name(), error = %e, "Daily price fetch failed");
+            Vec::new()
+        }))
+    };
+
+    let (profile, fundamentals, prices, corporate_actions, quarters, daily_prices) = tokio::try_join!(
+        provider.fetch_profile(ticker),
+        provider.fetch_yearly_fundamentals(ticker, years.clone(), calendar),
+        provider.fetch_prices(ticker, years.clone(), calendar),
+        provider.fetch_corporate_actions(ticker),
+        provider.fetch_quarterly_fundamentals(ticker, quarter_years),
+        daily_prices,
+    )?;
 
-    Ok(data)
+    Ok(FetchedData {
+        profile_currency: profile.map(|p| p.currency),
+        years: merge_yearly_inputs(fundamentals, prices),
+        corporate_actions,
+        quarters,
+        daily_prices,
+    })
 }
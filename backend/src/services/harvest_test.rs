@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+    use crate::services::harvest::{merge_yearly_inputs, reporting_currency};
+    use crate::services::providers::{YearlyFundamentals, YearlyPriceRange};
+    use rust_decimal::Decimal;
+
+    fn fundamentals(year: i32) -> YearlyFundamentals {
+        YearlyFundamentals {
+            fiscal_year: year,
+            sales: Some(Decimal::from(1000)),
+            eps: Some(Decimal::from(5)),
+            ..Default::default()
+        }
+    }
+
+    fn prices(year: i32) -> YearlyPriceRange {
+        YearlyPriceRange {
+            fiscal_year: year,
+            high: Decimal::from(120),
+            low: Decimal::from(80),
+        }
+    }
+
+    #[test]
+    fn test_merge_drops_years_without_fundamentals() {
+        // 2025 only has prices and 2024 only a dividend so far
+        let dividend_only = YearlyFundamentals {
+            fiscal_year: 2024,
+            dividend_per_share: Some(Decimal::from(2)),
+            ..Default::default()
+        };
+        let merged = merge_yearly_inputs(
+            vec![fundamentals(2022), fundamentals(2023), dividend_only],
+            vec![prices(2022), prices(2023), prices(2024), prices(2025)],
+        );
+
+        assert_eq!(merged.keys().copied().collect::<Vec<_>>(), vec![2022, 2023]);
+        let (f, range) = &merged[&2023];
+        assert_eq!(f.eps, Some(Decimal::from(5)));
+        assert_eq!(range.as_ref().map(|r| r.high), Some(Decimal::from(120)));
+    }
+
+    #[test]
+    fn test_merge_keeps_latest_ten_years_with_fundamentals() {
+        // A price-only year at the end must not push the oldest year out
+        let merged = merge_yearly_inputs(
+            (2013..=2024).map(fundamentals).collect(),
+            (2013..=2025).map(prices).collect(),
+        );
+
+        assert_eq!(merged.len(), 10);
+        assert_eq!(merged.keys().next(), Some(&2015));
+        assert_eq!(merged.keys().last(), Some(&2024));
+    }
+
+    #[test]
+    fn test_reporting_currency_falls_back_to_listing_currency() {
+        let currency =
+            |code: Option<&str>, listed| reporting_currency("TEST", code.map(String::from), listed);
+
+        assert_eq!(currency(Some("CHF"), "CHF"), "CHF");
+        assert_eq!(currency(Some("USD"), "EUR"), "USD");
+        assert_eq!(currency(None, "CHF"), "CHF");
+
+        // Minor units and unknown codes keep the listing currency
+        assert_eq!(currency(Some("GBp"), "GBP"), "GBP");
+        assert_eq!(currency(Some("usd"), "USD"), "USD");
+        assert_eq!(currency(Some("XYZ"), "EUR"), "EUR");
+    }
+}
@@ -0,0 +1,451 @@
+//! Historical table upload service.
+//!
+//! Parses a CSV or XLSX file holding a ticker's yearly SSG figures, validates
+//! every row, and persists the result into `historicals` tagged with the
+//! [`UPLOADED_SOURCE`] source. Uploaded rows form the base layer of the next
+//! harvest for that ticker; manual overrides are still applied on top.
+//!
+//! The file is all-or-nothing: if any row fails validation nothing is written
+//! and the caller receives a row-level error report instead.
+
+use crate::models::{historicals, tickers};
+use calamine::{Data, Reader, Xlsx};
+use chrono::Datelike;
+use loco_rs::prelude::*;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use sea_orm::TransactionTrait;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::io::Cursor;
+use std::str::FromStr;
+use steady_invest_logic::HistoricalYearlyData;
+
+/// Value of `historicals.source` for rows imported from an uploaded file.
+pub const UPLOADED_SOURCE: &str = "uploaded";
+
+/// Columns every upload must contain.
+const REQUIRED_COLUMNS: &[&str] = &["fiscal_year", "sales", "eps", "price_high", "price_low"];
+
+/// Columns that may be left out or blank.
+const OPTIONAL_COLUMNS: &[&str] = &[
+    "net_income",
+    "pretax_income",
+    "total_equity",
+    "dividend_per_share",
+    "shares_outstanding",
+];
+
+/// Earliest fiscal year accepted in an upload.
+const MIN_FISCAL_YEAR: i32 = 1900;
+
+/// Supported upload file formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UploadFormat {
+    Csv,
+    Xlsx,
+}
+
+impl UploadFormat {
+    /// Detects the format from the uploaded file name's extension.
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        let extension = file_name.rsplit_once('.')?.1.to_lowercase();
+        match extension.as_str() {
+            "csv" => Some(Self::Csv),
+            "xlsx" => Some(Self::Xlsx),
+            _ => None,
+        }
+    }
+}
+
+/// A problem found in one row (or the header) of an upload.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RowError {
+    /// Spreadsheet-style row number; the header is row 1.
+    pub row: usize,
+    /// Column the problem relates to, if any.
+    pub column: Option<String>,
+    pub message: String,
+}
+
+/// Outcome of an upload.
+#[derive(Debug, Serialize)]
+pub struct ImportReport {
+    pub ticker: String,
+    /// Fiscal years written to `historicals` (empty when validation failed).
+    pub imported_years: Vec<i32>,
+    pub errors: Vec<RowError>,
+}
+
+/// Raw cell values of a parsed file: the header row and each data row with
+/// its spreadsheet row number.
+struct RawTable {
+    header: Vec<String>,
+    rows: Vec<(usize, Vec<String>)>,
+}
+
+/// Parses and validates an uploaded file into yearly records.
+///
+/// Records are returned in file order with `adjustment_factor` set to `1`:
+/// uploaded figures are taken as reported.
+///
+/// # Errors
+///
+/// Returns every row-level problem found when the file is not valid.
+pub fn parse_upload(
+    format: UploadFormat,
+    bytes: &[u8],
+) -> std::result::Result<Vec<HistoricalYearlyData>, Vec<RowError>> {
+    let table = match format {
+        UploadFormat::Csv => read_csv(bytes),
+        UploadFormat::Xlsx => read_xlsx(bytes),
+    }
+    .map_err(|message| {
+        vec![RowError {
+            row: 1,
+            column: None,
+            message,
+        }]
+    })?;
+
+    validate(&table)
+}
+
+/// Parses, validates and stores an uploaded historical table for `ticker`.
+///
+/// Existing rows for the same fiscal years are replaced and re-tagged as
+/// uploaded; other years are left untouched.
+///
+/// # Errors
+///
+/// Returns `404 Not Found` if the ticker is unknown, or an error if a
+/// database operation fails. Validation problems are reported in the
+/// returned [`ImportReport`], not as errors.
+pub async fn import_historicals(
+    db: &DatabaseConnection,
+    ticker: &str,
+    format: UploadFormat,
+    bytes: &[u8],
+) -> Result<ImportReport> {
+    let ticker_info = tickers::Entity::find()
+        .filter(tickers::Column::Ticker.eq(ticker))
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound)?;
+
+    let records = match parse_upload(format, bytes) {
+        Ok(records) => records,
+        Err(errors) => {
+            return Ok(ImportReport {
+                ticker: ticker.to_string(),
+                imported_years: vec![],
+                errors,
+            })
+        }
+    };
+
+    let txn = db.begin().await?;
+    for rec in &records {
+        let existing = historicals::Entity::find()
+            .filter(historicals::Column::Ticker.eq(ticker))
+            .filter(historicals::Column::FiscalYear.eq(rec.fiscal_year))
+            .one(&txn)
+            .await?;
+
+        let mut active: historicals::ActiveModel = match existing {
+            Some(m) => m.into(),
+            None => historicals::ActiveModel {
+                ticker: ActiveValue::set(ticker.to_string()),
+                fiscal_year: ActiveValue::set(rec.fiscal_year),
+                ..Default::default()
+            },
+        };
+        active.sales = ActiveValue::set(rec.sales);
+        active.eps = ActiveValue::set(rec.eps);
+        active.price_high = ActiveValue::set(rec.price_high);
+        active.price_low = ActiveValue::set(rec.price_low);
+        active.currency = ActiveValue::set(ticker_info.currency.clone());
+        active.is_split_adjusted = ActiveValue::set(Some(false));
+        active.adjustment_factor = ActiveValue::set(Some(Decimal::ONE));
+        active.net_income = ActiveValue::set(rec.net_income);
+        active.pretax_income = ActiveValue::set(rec.pretax_income);
+        active.total_equity = ActiveValue::set(rec.total_equity);
+        active.dividend_per_share = ActiveValue::set(rec.dividend_per_share);
+        active.shares_outstanding = ActiveValue::set(rec.shares_outstanding);
+        active.source = ActiveValue::set(Some(UPLOADED_SOURCE.to_string()));
+        active.save(&txn).await?;
+    }
+    txn.commit().await?;
+
+    Ok(ImportReport {
+        ticker: ticker.to_string(),
+        imported_years: records.iter().map(|r| r.fiscal_year).collect(),
+        errors: vec![],
+    })
+}
+
+/// Loads the uploaded rows for `ticker` as unadjusted yearly records.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub async fn load_uploaded(
+    db: &DatabaseConnection,
+    ticker: &str,
+) -> Result<Vec<HistoricalYearlyData>> {
+    let rows = historicals::Entity::find()
+        .filter(historicals::Column::Ticker.eq(ticker))
+        .filter(historicals::Column::Source.eq(UPLOADED_SOURCE))
+        .all(db)
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| HistoricalYearlyData {
+            fiscal_year: row.fiscal_year,
+            sales: row.sales,
+            eps: row.eps,
+            price_high: row.price_high,
+            price_low: row.price_low,
+            net_income: row.net_income,
+            pretax_income: row.pretax_income,
+            total_equity: row.total_equity,
+            dividend_per_share: row.dividend_per_share,
+            shares_outstanding: row.shares_outstanding,
+            adjustment_factor: Decimal::ONE,
+            ..Default::default()
+        })
+        .collect())
+}
+
+fn read_csv(bytes: &[u8]) -> std::result::Result<RawTable, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(bytes);
+
+    let header = reader
+        .headers()
+        .map_err(|e| format!("Unreadable CSV header: {e}"))?
+        .iter()
+        .map(str::to_string)
+        .collect();
+
+    let mut rows = Vec::new();
+    for (i, record) in reader.records().enumerate() {
+        let record = record.map_err(|e| format!("Unreadable CSV row {}: {e}", i + 2))?;
+        rows.push((i + 2, record.iter().map(str::to_string).collect()));
+    }
+
+    Ok(RawTable { header, rows })
+}
+
+fn read_xlsx(bytes: &[u8]) -> std::result::Result<RawTable, String> {
+    let mut workbook =
+        Xlsx::new(Cursor::new(bytes)).map_err(|e| format!("Unreadable XLSX file: {e}"))?;
+    let range = workbook
+        .worksheet_range_at(0)
+        .ok_or_else(|| "The workbook has no worksheet".to_string())?
+        .map_err(|e| format!("Unreadable worksheet: {e}"))?;
+
+    // Report row numbers as shown in the spreadsheet, even if the used range
+    // does not start on the first row.
+    let first_row = range.start().map_or(0, |(row, _)| row as usize) + 1;
+    let mut cells = range
+        .rows()
+        .map(|row| row.iter().map(cell_to_string).collect::<Vec<_>>());
+
+    let header = cells
+        .next()
+        .ok_or_else(|| "The worksheet is empty".to_string())?;
+    let rows = cells
+        .enumerate()
+        .map(|(i, values)| (first_row + i + 1, values))
+        .collect();
+
+    Ok(RawTable { header, rows })
+}
+
+fn cell_to_string(cell: &Data) -> String {
+    match cell {
+        Data::Empty => String::new(),
+        Data::String(s) => s.trim().to_string(),
+        Data::Float(f) => f.to_string(),
+        Data::Int(i) => i.to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Checks the header and every row, collecting all problems before failing.
+fn validate(table: &RawTable) -> std::result::Result<Vec<HistoricalYearlyData>, Vec<RowError>> {
+    let mut errors = Vec::new();
+
+    let mut columns: HashMap<String, usize> = HashMap::new();
+    for (index, name) in table.header.iter().enumerate() {
+        let name = name.trim().to_lowercase();
+        if name.is_empty() {
+            continue;
+        }
+        if !REQUIRED_COLUMNS.contains(&name.as_str()) && !OPTIONAL_COLUMNS.contains(&name.as_str())
+        {
+            errors.push(header_error(&name, "Unknown column"));
+        } else if columns.insert(name.clone(), index).is_some() {
+            errors.push(header_error(&name, "Duplicate column"));
+        }
+    }
+    for required in REQUIRED_COLUMNS {
+        if !columns.contains_key(*required) {
+            errors.push(header_error(required, "Missing required column"));
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let max_year = chrono::Utc::now().year();
+    let mut seen_years = BTreeSet::new();
+    let mut records = Vec::new();
+
+    for (row, values) in &table.rows {
+        if values.iter().all(|v| v.trim().is_empty()) {
+            continue;
+        }
+        let row = *row;
+        let cell = |column: &str| {
+            columns
+                .get(column)
+                .and_then(|i| values.get(*i))
+                .map(|v| v.trim())
+                .filter(|v| !v.is_empty())
+        };
+        let mut error = |column: &str, message: String| {
+            errors.push(RowError {
+                row,
+                column: Some(column.to_string()),
+                message,
+            });
+        };
+
+        let fiscal_year = match cell("fiscal_year").map(parse_decimal) {
+            None => {
+                error("fiscal_year", "Value is required".to_string());
+                None
+            }
+            Some(Some(v)) if v.fract().is_zero() => {
+                let year = v.to_i32().unwrap_or(0);
+                if !(MIN_FISCAL_YEAR..=max_year).contains(&year) {
+                    error(
+                        "fiscal_year",
+                        format!("Year {year} is outside {MIN_FISCAL_YEAR}-{max_year}"),
+                    );
+                    None
+                } else if !seen_years.insert(year) {
+                    error("fiscal_year", format!("Year {year} appears more than once"));
+                    None
+                } else {
+                    Some(year)
+                }
+            }
+            Some(_) => {
+                error("fiscal_year", "Not a whole year".to_string());
+                None
+            }
+        };
+
+        let mut number = |column: &str, required: bool| match cell(column) {
+            None if required => {
+                error(column, "Value is required".to_string());
+                None
+            }
+            None => None,
+            Some(raw) => match parse_decimal(raw) {
+                Some(v) => Some(v),
+                None => {
+                    error(column, format!("'{raw}' is not a number"));
+                    None
+                }
+            },
+        };
+
+        let sales = number("sales", true);
+        let eps = number("eps", true);
+        let price_high = number("price_high", true);
+        let price_low = number("price_low", true);
+        let net_income = number("net_income", false);
+        let pretax_income = number("pretax_income", false);
+        let total_equity = number("total_equity", false);
+        let dividend_per_share = number("dividend_per_share", false);
+        let shares_outstanding = number("shares_outstanding", false);
+
+        for (column, value) in [
+            ("sales", sales),
+            ("price_low", price_low),
+            ("dividend_per_share", dividend_per_share),
+            ("shares_outstanding", shares_outstanding),
+        ] {
+            if value.is_some_and(|v| v.is_sign_negative() && !v.is_zero()) {
+                error(column, "Value must not be negative".to_string());
+            }
+        }
+        if let (Some(high), Some(low)) = (price_high, price_low) {
+            if low > high {
+                error(
+                    "price_low",
+                    format!("price_low {low} is greater than price_high {high}"),
+                );
+            }
+        }
+
+        if let (Some(fiscal_year), Some(sales), Some(eps), Some(price_high), Some(price_low)) =
+            (fiscal_year, sales, eps, price_high, price_low)
+        {
+            records.push(HistoricalYearlyData {
+                fiscal_year,
+                sales,
+                eps,
+                price_high,
+                price_low,
+                net_income,
+                pretax_income,
+                total_equity,
+                dividend_per_share,
+                shares_outstanding,
+                adjustment_factor: Decimal::ONE,
+                ..Default::default()
+            });
+        }
+    }
+
+    if errors.is_empty() && records.is_empty() {
+        errors.push(RowError {
+            row: 2,
+            column: None,
+            message: "The file contains no data rows".to_string(),
+        });
+    }
+
+    if errors.is_empty() {
+        Ok(records)
+    } else {
+        Err(errors)
+    }
+}
+
+fn header_error(column: &str, message: &str) -> RowError {
+    RowError {
+        row: 1,
+        column: Some(column.to_string()),
+        message: message.to_string(),
+    }
+}
+
+/// Parses a number, accepting `'`, `_` and spaces as thousands separators.
+pub(crate) fn parse_decimal(raw: &str) -> Option<Decimal> {
+    let cleaned: String = raw
+        .chars()
+        .filter(|c| !matches!(c, '\'' | '_' | ' ' | '\u{2019}'))
+        .collect();
+    Decimal::from_str(&cleaned)
+        .or_else(|_| Decimal::from_scientific(&cleaned))
+        .ok()
+}
@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+    use crate::services::historicals_import::{parse_upload, UploadFormat};
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    fn dec(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_upload_format_from_file_name() {
+        assert_eq!(
+            UploadFormat::from_file_name("belimo.CSV"),
+            Some(UploadFormat::Csv)
+        );
+        assert_eq!(
+            UploadFormat::from_file_name("history.v2.xlsx"),
+            Some(UploadFormat::Xlsx)
+        );
+        assert_eq!(UploadFormat::from_file_name("history.xls"), None);
+        assert_eq!(UploadFormat::from_file_name("history"), None);
+    }
+
+    #[test]
+    fn test_parse_csv_with_optional_columns() {
+        let csv = "\
+Fiscal_Year,sales,eps,price_high,price_low,dividend_per_share
+2022,1'234.5,12.40,410,300,
+2023, 1300 ,13.1,420.5,310,8.5
+";
+        let records = parse_upload(UploadFormat::Csv, csv.as_bytes()).unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].fiscal_year, 2022);
+        assert_eq!(records[0].sales, dec("1234.5"));
+        assert_eq!(records[0].dividend_per_share, None);
+        assert_eq!(records[0].adjustment_factor, Decimal::ONE);
+        assert_eq!(records[1].price_high, dec("420.5"));
+        assert_eq!(records[1].dividend_per_share, Some(dec("8.5")));
+        assert_eq!(records[1].net_income, None);
+    }
+
+    #[test]
+    fn test_parse_csv_reports_every_invalid_row() {
+        let csv = "\
+fiscal_year,sales,eps,price_high,price_low
+2021,100,1,50,40
+2021,110,1.1,55,45
+2022,abc,1.2,50,60
+,120,1.3,60,50
+";
+        let errors = parse_upload(UploadFormat::Csv, csv.as_bytes()).unwrap_err();
+
+        let summary: Vec<(usize, Option<&str>)> = errors
+            .iter()
+            .map(|e| (e.row, e.column.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (3, Some("fiscal_year")),
+                (4, Some("sales")),
+                (4, Some("price_low")),
+                (5, Some("fiscal_year")),
+            ]
+        );
+        assert!(errors[1].message.contains("abc"));
+    }
+
+    #[test]
+    fn test_parse_csv_rejects_bad_header() {
+        let csv = "fiscal_year,sales,eps,price_high,revenue\n2021,100,1,50,40\n";
+        let errors = parse_upload(UploadFormat::Csv, csv.as_bytes()).unwrap_err();
+
+        assert!(errors.iter().all(|e| e.row == 1));
+        assert!(errors
+            .iter()
+            .any(|e| e.column.as_deref() == Some("revenue") && e.message == "Unknown column"));
+        assert!(errors
+            .iter()
+            .any(|e| e.column.as_deref() == Some("price_low")
+                && e.message == "Missing required column"));
+    }
+
+    #[test]
+    fn test_parse_rejects_empty_and_unreadable_files() {
+        let errors = parse_upload(
+            UploadFormat::Csv,
+            b"fiscal_year,sales,eps,price_high,price_low\n",
+        )
+        .unwrap_err();
+        assert_eq!(errors[0].message, "The file contains no data rows");
+
+        let errors = parse_upload(UploadFormat::Xlsx, b"not a workbook").unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.starts_with("Unreadable XLSX file"));
+    }
+}
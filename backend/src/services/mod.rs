@@ -5,17 +5,57 @@
 //! exchange rate sources).
 //!
 //! - [`harvest`]                 — Fetches and stores 10-year historical financial data
+//! - [`providers`]               — Pluggable financial data providers selected per exchange
+//! - [`historicals_import`]      — CSV/XLSX upload of full historical tables
+//! - [`daily_prices`]            — Daily price series and the yearly ranges derived from it
+//! - [`ticker_import`]           — Index constituent imports with ISIN, valor and WKN identifiers
+//! - [`ticker_search`]           — Ranked, typo-tolerant ticker search
 //! - [`exchange`]                — Currency conversion using cached exchange rates (harvest pipeline)
-//! - [`exchange_rate_provider`]  — Current exchange rates via Frankfurter API with DB fallback
+//! - [`exchange_rate_provider`]  — Current cross rates of the configured currencies via Frankfurter API with DB fallback
+//! - [`exchange_rate_backfill`]  — Yearly average and year-end rates from ECB reference rates
 //! - [`audit_service`]           — Records data-integrity events and manual overrides
-//! - [`provider_health`]         — Monitors API provider availability and rate limits
+//! - [`corporate_actions`]       — Corporate actions ledger and split adjustment factors
+//! - [`quotes`]                  — Current prices from providers or manual entry
+//! - [`refresh`]                 — Scheduled refresh of every tracked ticker
+//! - [`provider_health`]         — Probes API providers and reports uptime and latency
+//! - [`rate_limiter`]            — Persisted per-provider token buckets for outgoing API calls
 //! - [`reporting`]               — Generates PDF/image SSG report exports
 
 pub mod audit_service;
+pub mod corporate_actions;
+#[cfg(test)]
+mod corporate_actions_test;
+pub mod daily_prices;
+#[cfg(test)]
+mod daily_prices_test;
 pub mod exchange;
+pub mod exchange_rate_backfill;
+#[cfg(test)]
+mod exchange_rate_backfill_test;
 pub mod exchange_rate_provider;
+#[cfg(test)]
+mod exchange_rate_provider_test;
 pub mod harvest;
+#[cfg(test)]
+mod harvest_test;
+pub mod historicals_import;
+#[cfg(test)]
+mod historicals_import_test;
 pub mod provider_health;
+#[cfg(test)]
+mod provider_health_test;
+pub mod providers;
+pub mod quotes;
+pub mod rate_limiter;
+#[cfg(test)]
+mod rate_limiter_test;
+pub mod refresh;
 pub mod reporting;
 #[cfg(test)]
 mod reporting_test;
+pub mod ticker_import;
+#[cfg(test)]
+mod ticker_import_test;
+pub mod ticker_search;
+#[cfg(test)]
+mod ticker_search_test;
@@ -1,61 +1,225 @@
 //! API provider health monitoring service.
 //!
-//! Simulates connectivity checks for external financial data providers,
-//! records quota consumption to the database, and returns aggregated health
-//! status for the System Monitor dashboard.
+//! Probes every configured financial data provider with a lightweight
+//! request, records each probe in `provider_health_checks`, and derives the
+//! status, uptime and p95 latency shown on the System Monitor dashboard from
+//! that time series.
+//!
+//! A provider probed less than [`probe_interval`] ago is not probed again:
+//! the dashboard and footer poll the health endpoint, and every open browser
+//! should not turn into a request against the provider.
 
-use crate::models::provider_rate_limits;
+use crate::models::{provider_health_checks, provider_rate_limits};
+use crate::services::providers::{FinancialDataProvider, ProviderRegistry};
+use chrono::{DateTime, Duration, Utc};
 use loco_rs::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use std::time::Instant;
+use tokio::task::JoinSet;
+
+/// Upper bound on a single probe (seconds); slower providers count as down.
+const PROBE_TIMEOUT_SECS: u64 = 5;
+
+/// Default minimum time between two probes of a provider (seconds).
+const DEFAULT_PROBE_INTERVAL_SECS: i64 = 60;
+
+/// A successful probe slower than this marks the provider as degraded.
+const SLOW_PROBE_MS: u64 = 2_000;
+
+/// 24-hour uptime below this percentage marks the provider as degraded.
+const DEGRADED_UPTIME_PERCENT: f64 = 95.0;
+
+/// How long probes are kept.
+const RETENTION_DAYS: i64 = 30;
+
+/// Connectivity status of a provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HealthStatus {
+    Online,
+    Degraded,
+    Offline,
+}
+
+/// Availability statistics over a time window.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WindowStats {
+    /// Probes in the window.
+    pub checks: usize,
+    /// Share of successful probes, in percent; `None` without probes.
+    pub uptime_percent: Option<f64>,
+    /// 95th percentile latency of successful probes.
+    pub p95_latency_ms: Option<u64>,
+}
 
 /// Health status record for a financial data provider.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ProviderHealth {
-    /// Friendly name of the provider (e.g., "CH (SWX)")
+    /// Provider name as used in logs and audit records (e.g., "yahoo+fixture")
     pub name: String,
-    /// Connectivity status ("Online", "Degraded", "Offline")
-    pub status: String,
-    /// Response latency in milliseconds
+    /// Exchanges routed to the provider; empty for the default provider
+    pub exchanges: Vec<String>,
+    /// Status derived from the latest probe and the last 24 hours
+    pub status: HealthStatus,
+    /// Latency of the latest probe in milliseconds
     pub latency_ms: u64,
-    /// Percentage of API quota consumed
+    /// Percentage of the daily API allowance consumed
     pub rate_limit_percent: u32,
+    /// When the latest probe ran
+    pub checked_at: DateTime<Utc>,
+    /// Failure reported by the latest probe
+    pub error: Option<String>,
+    pub last_24h: WindowStats,
+    pub last_7d: WindowStats,
+}
+
+/// Minimum time between two probes of a provider, from
+/// `PROVIDER_HEALTH_PROBE_INTERVAL_SECS` (default 60 seconds).
+pub fn probe_interval() -> Duration {
+    let secs = std::env::var("PROVIDER_HEALTH_PROBE_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .unwrap_or(DEFAULT_PROBE_INTERVAL_SECS);
+    Duration::seconds(secs)
 }
 
-/// Checks the simulated health of all external financial providers.
+/// Probes every provider in `registry` that is due and returns the health of
+/// each, computed from the stored probes.
 ///
-/// This function iterates through known providers, simulates a connectivity check,
-/// persists the rate limit usage to the database, and returns the aggregated health stats.
-pub async fn check_providers(db: &DatabaseConnection) -> Vec<ProviderHealth> {
-    let mut results = Vec::new();
+/// # Errors
+///
+/// Returns an error if probes cannot be stored or read.
+pub async fn check_providers(
+    db: &DatabaseConnection,
+    registry: &ProviderRegistry,
+) -> Result<Vec<ProviderHealth>> {
+    let now = Utc::now();
+    let week_ago = now - Duration::days(7);
+    provider_health_checks::Entity::prune(db, now - Duration::days(RETENTION_DAYS)).await?;
+
+    let providers = registry.providers();
+    let mut history = Vec::with_capacity(providers.len());
+    let mut probes = JoinSet::new();
+    for (index, (provider, _)) in providers.iter().enumerate() {
+        let checks = provider_health_checks::Entity::since(db, provider.name(), week_ago).await?;
+        let due = checks
+            .last()
+            .is_none_or(|last| now - last.checked_at.to_utc() >= probe_interval());
+        if due {
+            let provider = provider.clone();
+            probes.spawn(async move { (index, probe(provider).await) });
+        }
+        history.push(checks);
+    }
+
+    while let Some(joined) = probes.join_next().await {
+        let (index, check) = joined.map_err(|e| Error::string(&e.to_string()))?;
+        history[index].push(check.insert(db).await?);
+    }
+
+    let mut results = Vec::with_capacity(providers.len());
+    for ((provider, exchanges), checks) in providers.into_iter().zip(history) {
+        let Some(latest) = checks.last() else {
+            continue;
+        };
+        let last_24h = window_stats(&checks, now - Duration::hours(24));
+        let quota = match provider.rate_limit_name() {
+            Some(name) => provider_rate_limits::Model::find_by_name(db, name)
+                .await?
+                .map_or(0, |limit| limit.quota_consumed.clamp(0, 100)),
+            None => 0,
+        };
+
+        results.push(ProviderHealth {
+            name: provider.name().to_string(),
+            exchanges,
+            status: status(latest, &last_24h),
+            latency_ms: latency(latest),
+            rate_limit_percent: u32::try_from(quota).unwrap_or_default(),
+            checked_at: latest.checked_at.to_utc(),
+            error: latest.error.clone(),
+            last_7d: window_stats(&checks, week_ago),
+            last_24h,
+        });
+    }
+    Ok(results)
+}
 
-    // Simulate checks and PERSIST to DB
-    let providers = vec![
-        ("CH (SWX)", 120, 15),
-        ("DE (DAX)", 185, 42),
-        ("US (NYSE/NASDAQ)", 85, 68),
-    ];
+/// Runs one probe, bounded by [`PROBE_TIMEOUT_SECS`].
+async fn probe(provider: Arc<dyn FinancialDataProvider>) -> provider_health_checks::ActiveModel {
+    let started = Instant::now();
+    let outcome = tokio::time::timeout(
+        std::time::Duration::from_secs(PROBE_TIMEOUT_SECS),
+        provider.probe(),
+    )
+    .await;
+    let elapsed = i32::try_from(started.elapsed().as_millis()).unwrap_or(i32::MAX);
 
-    for (name, latency, quota) in providers {
-        let health = simulate_check(name, latency, quota);
-        // Persist to DB (Fulfils adversarial requirement)
-        let _ = provider_rate_limits::Model::update_quota(db, name, quota as i32).await;
-        results.push(health);
+    let error = match outcome {
+        Ok(Ok(())) => None,
+        Ok(Err(e)) => Some(e.to_string()),
+        Err(_) => Some(format!("No answer within {PROBE_TIMEOUT_SECS}s")),
+    };
+    if let Some(error) = &error {
+        tracing::warn!(provider = provider.name(), error = %error, "Provider health probe failed");
     }
 
-    results
+    provider_health_checks::ActiveModel {
+        provider: ActiveValue::set(provider.name().to_string()),
+        checked_at: ActiveValue::set(Utc::now().into()),
+        success: ActiveValue::set(error.is_none()),
+        latency_ms: ActiveValue::set(elapsed),
+        error: ActiveValue::set(error),
+        ..Default::default()
+    }
 }
 
-/// Simulates a health check for a given provider.
-///
-/// Returns a `ProviderHealth` struct with randomized latency jitter.
-fn simulate_check(name: &str, base_latency: u64, rate_limit_percent: u32) -> ProviderHealth {
-    // Add some jitter to latency
-    let jitter = (Instant::now().elapsed().as_micros() % 50) as u64;
-    ProviderHealth {
-        name: name.to_string(),
-        status: "Online".to_string(),
-        latency_ms: base_latency + jitter,
-        rate_limit_percent,
+fn latency(check: &provider_health_checks::Model) -> u64 {
+    u64::try_from(check.latency_ms).unwrap_or_default()
+}
+
+/// Uptime and p95 latency of the checks at or after `since`.
+pub fn window_stats(checks: &[provider_health_checks::Model], since: DateTime<Utc>) -> WindowStats {
+    let in_window: Vec<&provider_health_checks::Model> = checks
+        .iter()
+        .filter(|c| c.checked_at.to_utc() >= since)
+        .collect();
+    if in_window.is_empty() {
+        return WindowStats::default();
+    }
+
+    let mut latencies: Vec<u64> = in_window
+        .iter()
+        .filter(|c| c.success)
+        .map(|c| latency(c))
+        .collect();
+    latencies.sort_unstable();
+    // Nearest-rank percentile
+    let p95_latency_ms = (!latencies.is_empty()).then(|| {
+        let rank = (latencies.len() * 95).div_ceil(100);
+        latencies[rank - 1]
+    });
+
+    let uptime_percent = latencies.len() as f64 * 100.0 / in_window.len() as f64;
+    WindowStats {
+        checks: in_window.len(),
+        uptime_percent: Some(uptime_percent),
+        p95_latency_ms,
+    }
+}
+
+/// Offline when the latest probe failed; degraded when it was slow or the
+/// provider was unreliable over the last 24 hours.
+pub fn status(latest: &provider_health_checks::Model, last_24h: &WindowStats) -> HealthStatus {
+    if !latest.success {
+        return HealthStatus::Offline;
+    }
+    let unreliable = last_24h
+        .uptime_percent
+        .is_some_and(|uptime| uptime < DEGRADED_UPTIME_PERCENT);
+    if latency(latest) >= SLOW_PROBE_MS || unreliable {
+        HealthStatus::Degraded
+    } else {
+        HealthStatus::Online
     }
 }
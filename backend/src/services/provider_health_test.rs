@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::provider_health_checks;
+    use crate::services::provider_health::{status, window_stats, HealthStatus, WindowStats};
+    use chrono::{DateTime, Duration, Utc};
+
+    fn check(at: DateTime<Utc>, success: bool, latency_ms: i32) -> provider_health_checks::Model {
+        provider_health_checks::Model {
+            id: 0,
+            provider: "yahoo".to_string(),
+            checked_at: at.into(),
+            success,
+            latency_ms,
+            error: (!success).then(|| "timeout".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_window_stats_uptime_and_p95() {
+        let now = Utc::now();
+        // 20 successful probes at 10..200 ms and one failure in the last day,
+        // plus an old failure outside the window
+        let mut checks: Vec<_> = (1..=20)
+            .map(|i| check(now - Duration::minutes(i64::from(i)), true, i * 10))
+            .collect();
+        checks.push(check(now - Duration::hours(2), false, 5_000));
+        checks.push(check(now - Duration::days(3), false, 5_000));
+
+        let day = window_stats(&checks, now - Duration::hours(24));
+        assert_eq!(day.checks, 21);
+        let uptime = day.uptime_percent.unwrap();
+        assert!((uptime - 2000.0 / 21.0).abs() < 1e-9);
+        // Nearest rank: the 19th of 20 sorted latencies; failures are excluded
+        assert_eq!(day.p95_latency_ms, Some(190));
+
+        let week = window_stats(&checks, now - Duration::days(7));
+        assert_eq!(week.checks, 22);
+        assert_eq!(week.p95_latency_ms, Some(190));
+    }
+
+    #[test]
+    fn test_window_stats_without_checks() {
+        assert_eq!(window_stats(&[], Utc::now()), WindowStats::default());
+
+        let failed = vec![check(Utc::now(), false, 5_000)];
+        let stats = window_stats(&failed, Utc::now() - Duration::hours(1));
+        assert_eq!(stats.uptime_percent, Some(0.0));
+        assert_eq!(stats.p95_latency_ms, None);
+    }
+
+    #[test]
+    fn test_status_from_latest_probe_and_uptime() {
+        let now = Utc::now();
+        let healthy = WindowStats {
+            checks: 10,
+            uptime_percent: Some(100.0),
+            p95_latency_ms: Some(120),
+        };
+
+        assert_eq!(
+            status(&check(now, true, 120), &healthy),
+            HealthStatus::Online
+        );
+        assert_eq!(
+            status(&check(now, false, 5_000), &healthy),
+            HealthStatus::Offline
+        );
+        assert_eq!(
+            status(&check(now, true, 2_500), &healthy),
+            HealthStatus::Degraded
+        );
+
+        let flaky = WindowStats {
+            uptime_percent: Some(80.0),
+            ..healthy
+        };
+        assert_eq!(
+            status(&check(now, true, 120), &flaky),
+            HealthStatus::Degraded
+        );
+    }
+}
@@ -0,0 +1,193 @@
+//! File-backed financial data provider.
+//!
+//! Serves harvest data from one JSON document per ticker (`{TICKER}.json`).
+//! Documents are read from the directory named by `FINANCIAL_FIXTURES_DIR`
+//! when set, otherwise from the fixtures bundled into the binary under
+//! `src/fixtures/financials`. No network access is required, which makes the
+//! provider suitable for development, tests and offline demos.
+
+use super::{
+    CorporateAction, DailyPrice, FinancialDataProvider, MarketQuote, QuarterlyFundamentals,
+    YearlyFundamentals, YearlyPriceRange,
+};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use include_dir::{include_dir, Dir};
+use loco_rs::prelude::*;
+use serde::Deserialize;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use steady_invest_logic::{FiscalCalendar, TickerInfo};
+
+/// Fixtures compiled into the binary so the provider works without a data directory.
+static BUNDLED_FIXTURES: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR/src/fixtures/financials");
+
+/// On-disk layout of a single ticker fixture document.
+#[derive(Debug, Default, Deserialize)]
+pub struct FixtureDocument {
+    pub profile: Option<TickerInfo>,
+    #[serde(default)]
+    pub fundamentals: Vec<YearlyFundamentals>,
+    #[serde(default)]
+    pub prices: Vec<YearlyPriceRange>,
+    #[serde(default)]
+    pub corporate_actions: Vec<CorporateAction>,
+    #[serde(default)]
+    pub quarters: Vec<QuarterlyFundamentals>,
+    #[serde(default)]
+    pub daily_prices: Vec<DailyPrice>,
+    #[serde(default)]
+    pub quote: Option<MarketQuote>,
+}
+
+/// Provider reading ticker data from JSON fixture documents.
+#[derive(Debug, Clone, Default)]
+pub struct FixtureProvider {
+    /// Directory to read from; `None` uses the bundled fixtures.
+    dir: Option<PathBuf>,
+}
+
+impl FixtureProvider {
+    /// Creates a provider reading `{TICKER}.json` files from `dir`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: Some(dir.into()),
+        }
+    }
+
+    /// Creates a provider serving the fixtures bundled into the binary.
+    pub fn bundled() -> Self {
+        Self { dir: None }
+    }
+
+    /// Uses `FINANCIAL_FIXTURES_DIR` when set, the bundled fixtures otherwise.
+    pub fn from_env() -> Self {
+        match std::env::var("FINANCIAL_FIXTURES_DIR") {
+            Ok(dir) if !dir.trim().is_empty() => Self::new(dir),
+            _ => Self::bundled(),
+        }
+    }
+
+    /// Loads the fixture document for `ticker`, or `None` if there is none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not valid JSON.
+    pub async fn load(&self, ticker: &str) -> Result<Option<FixtureDocument>> {
+        let file_name = format!("{}.json", ticker.to_uppercase());
+
+        let raw = match &self.dir {
+            Some(dir) => match tokio::fs::read_to_string(dir.join(&file_name)).await {
+                Ok(raw) => raw,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+                Err(e) => {
+                    return Err(Error::string(&format!(
+                        "Failed to read fixture {file_name}: {e}"
+                    )))
+                }
+            },
+            None => match BUNDLED_FIXTURES
+                .get_file(&file_name)
+                .and_then(|f| f.contents_utf8())
+            {
+                Some(raw) => raw.to_string(),
+                None => return Ok(None),
+            },
+        };
+
+        serde_json::from_str(&raw)
+            .map(Some)
+            .map_err(|e| Error::string(&format!("Invalid fixture {file_name}: {e}")))
+    }
+}
+
+#[async_trait]
+impl FinancialDataProvider for FixtureProvider {
+    fn name(&self) -> &str {
+        "fixture"
+    }
+
+    async fn fetch_profile(&self, ticker: &str) -> Result<Option<TickerInfo>> {
+        Ok(self.load(ticker).await?.and_then(|doc| doc.profile))
+    }
+
+    async fn fetch_yearly_fundamentals(
+        &self,
+        ticker: &str,
+        years: RangeInclusive<i32>,
+        _calendar: FiscalCalendar,
+    ) -> Result<Vec<YearlyFundamentals>> {
+        let doc = self.load(ticker).await?.unwrap_or_default();
+        Ok(doc
+            .fundamentals
+            .into_iter()
+            .filter(|f| years.contains(&f.fiscal_year))
+            .collect())
+    }
+
+    async fn fetch_prices(
+        &self,
+        ticker: &str,
+        years: RangeInclusive<i32>,
+        _calendar: FiscalCalendar,
+    ) -> Result<Vec<YearlyPriceRange>> {
+        let doc = self.load(ticker).await?.unwrap_or_default();
+        Ok(doc
+            .prices
+            .into_iter()
+            .filter(|p| years.contains(&p.fiscal_year))
+            .collect())
+    }
+
+    async fn fetch_corporate_actions(&self, ticker: &str) -> Result<Vec<CorporateAction>> {
+        Ok(self
+            .load(ticker)
+            .await?
+            .map(|doc| doc.corporate_actions)
+            .unwrap_or_default())
+    }
+
+    async fn fetch_quarterly_fundamentals(
+        &self,
+        ticker: &str,
+        years: RangeInclusive<i32>,
+    ) -> Result<Vec<QuarterlyFundamentals>> {
+        let doc = self.load(ticker).await?.unwrap_or_default();
+        Ok(doc
+            .quarters
+            .into_iter()
+            .filter(|q| years.contains(&q.fiscal_year))
+            .collect())
+    }
+
+    async fn fetch_daily_prices(&self, ticker: &str, from: NaiveDate) -> Result<Vec<DailyPrice>> {
+        let doc = self.load(ticker).await?.unwrap_or_default();
+        Ok(doc
+            .daily_prices
+            .into_iter()
+            .filter(|p| p.date >= from)
+            .collect())
+    }
+
+    async fn fetch_quote(&self, ticker: &str) -> Result<Option<MarketQuote>> {
+        Ok(self.load(ticker).await?.and_then(|doc| doc.quote))
+    }
+
+    /// Bundled fixtures are always available; a fixtures directory must exist.
+    async fn probe(&self) -> Result<()> {
+        let Some(dir) = &self.dir else {
+            return Ok(());
+        };
+        match tokio::fs::metadata(dir).await {
+            Ok(meta) if meta.is_dir() => Ok(()),
+            Ok(_) => Err(Error::string(&format!(
+                "Fixture path {} is not a directory",
+                dir.display()
+            ))),
+            Err(e) => Err(Error::string(&format!(
+                "Fixture directory {} is unreadable: {e}",
+                dir.display()
+            ))),
+        }
+    }
+}
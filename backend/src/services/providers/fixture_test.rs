@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod tests {
+    use crate::services::providers::{
+        CorporateActionKind, FinancialDataProvider, FixtureProvider, ProviderRegistry,
+    };
+    use rust_decimal::Decimal;
+    use std::sync::Arc;
+    use steady_invest_logic::FiscalCalendar;
+
+    const CALENDAR_YEAR: FiscalCalendar = FiscalCalendar::CALENDAR_YEAR;
+
+    #[tokio::test]
+    async fn test_bundled_fixture_profile_and_fundamentals() {
+        let provider = FixtureProvider::bundled();
+
+        let profile = provider.fetch_profile("AAPL").await.unwrap().unwrap();
+        assert_eq!(profile.currency, "USD");
+
+        let fundamentals = provider
+            .fetch_yearly_fundamentals("AAPL", 2016..=2025, CALENDAR_YEAR)
+            .await
+            .unwrap();
+        assert_eq!(fundamentals.len(), 10);
+        assert!(fundamentals
+            .iter()
+            .all(|f| f.sales.is_some() && f.eps.is_some()));
+    }
+
+    #[tokio::test]
+    async fn test_fixture_filters_by_year_range() {
+        let provider = FixtureProvider::bundled();
+
+        let prices = provider
+            .fetch_prices("MSFT", 2020..=2022, CALENDAR_YEAR)
+            .await
+            .unwrap();
+        let years: Vec<i32> = prices.iter().map(|p| p.fiscal_year).collect();
+        assert_eq!(years, vec![2020, 2021, 2022]);
+        assert!(prices.iter().all(|p| p.high >= p.low));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_ticker_returns_nothing() {
+        let provider = FixtureProvider::bundled();
+
+        assert!(provider.fetch_profile("NOPE").await.unwrap().is_none());
+        assert!(provider
+            .fetch_yearly_fundamentals("NOPE", 2016..=2025, CALENDAR_YEAR)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(provider
+            .fetch_corporate_actions("NOPE")
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fixture_corporate_actions() {
+        let provider = FixtureProvider::bundled();
+
+        let actions = provider.fetch_corporate_actions("AAPL").await.unwrap();
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].kind, CorporateActionKind::Split);
+        assert_eq!(actions[0].ratio, Decimal::from(4));
+    }
+
+    #[tokio::test]
+    async fn test_fixture_quarterly_fundamentals() {
+        let provider = FixtureProvider::bundled();
+
+        let quarters = provider
+            .fetch_quarterly_fundamentals("MSFT", 2026..=2026)
+            .await
+            .unwrap();
+        assert_eq!(quarters.len(), 1);
+        assert_eq!(quarters[0].fiscal_quarter, 1);
+
+        let quarters = provider
+            .fetch_quarterly_fundamentals("AAPL", 2016..=2026)
+            .await
+            .unwrap();
+        assert!(quarters.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fixture_quote() {
+        let provider = FixtureProvider::bundled();
+
+        let quote = provider.fetch_quote("MSFT").await.unwrap().unwrap();
+        assert_eq!(quote.price, Decimal::new(51735, 2));
+        assert_eq!(quote.currency.as_deref(), Some("USD"));
+        assert_eq!(quote.as_of.to_rfc3339(), "2025-10-15T20:00:00+00:00");
+
+        assert!(provider.fetch_quote("AAPL").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_registry_routes_by_exchange() {
+        let dir = std::env::temp_dir().join(format!("fixtures-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("NESN.SW.json"),
+            r#"{"prices":[{"fiscal_year":2020,"high":"1","low":"1"}]}"#,
+        )
+        .unwrap();
+
+        let registry = ProviderRegistry::new(Arc::new(FixtureProvider::bundled()))
+            .with_exchange("smi", Arc::new(FixtureProvider::new(&dir)));
+
+        let swiss = registry.provider_for("SMI");
+        let prices = swiss
+            .fetch_prices("NESN.SW", 2016..=2025, CALENDAR_YEAR)
+            .await
+            .unwrap();
+        assert_eq!(prices.len(), 1);
+
+        let fallback = registry.provider_for("NASDAQ");
+        let prices = fallback
+            .fetch_prices("NESN.SW", 2016..=2025, CALENDAR_YEAR)
+            .await
+            .unwrap();
+        assert_eq!(prices.len(), 10);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,186 @@
+//! Provider combining two sources field by field.
+//!
+//! Lets a provider with partial coverage (e.g. Yahoo, which only has prices
+//! and dividends) be completed by another (e.g. the fixture provider holding
+//! fundamentals). Values from the primary provider win; the fallback fills
+//! whatever the primary leaves empty. A failing primary is logged and the
+//! fallback is used on its own.
+
+use super::{
+    CorporateAction, DailyPrice, FinancialDataProvider, MarketQuote, QuarterlyFundamentals,
+    YearlyFundamentals, YearlyPriceRange,
+};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use loco_rs::prelude::*;
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+use steady_invest_logic::{FiscalCalendar, TickerInfo};
+
+/// Provider serving data from `primary`, completed by `fallback`.
+#[derive(Clone)]
+pub struct LayeredProvider {
+    primary: Arc<dyn FinancialDataProvider>,
+    fallback: Arc<dyn FinancialDataProvider>,
+    name: String,
+}
+
+impl LayeredProvider {
+    /// Creates a provider preferring `primary` and falling back to `fallback`.
+    pub fn new(
+        primary: Arc<dyn FinancialDataProvider>,
+        fallback: Arc<dyn FinancialDataProvider>,
+    ) -> Self {
+        let name = format!("{}+{}", primary.name(), fallback.name());
+        Self {
+            primary,
+            fallback,
+            name,
+        }
+    }
+
+    /// Returns the primary's result, or `T::default()` after logging if it failed.
+    fn primary_or_default<T: Default>(&self, ticker: &str, result: Result<T>) -> T {
+        result.unwrap_or_else(|e| {
+            tracing::warn!(
+                provider = self.primary.name(),
+                ticker,
+                error = %e,
+                "Primary provider failed, using fallback only"
+            );
+            T::default()
+        })
+    }
+}
+
+#[async_trait]
+impl FinancialDataProvider for LayeredProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The primary's limiter; the fallback fills gaps from a local source.
+    fn rate_limit_name(&self) -> Option<&str> {
+        self.primary.rate_limit_name()
+    }
+
+    async fn fetch_profile(&self, ticker: &str) -> Result<Option<TickerInfo>> {
+        let primary = self.primary.fetch_profile(ticker).await;
+        match self.primary_or_default(ticker, primary) {
+            Some(profile) => Ok(Some(profile)),
+            None => self.fallback.fetch_profile(ticker).await,
+        }
+    }
+
+    async fn fetch_yearly_fundamentals(
+        &self,
+        ticker: &str,
+        years: RangeInclusive<i32>,
+        calendar: FiscalCalendar,
+    ) -> Result<Vec<YearlyFundamentals>> {
+        let (primary, fallback) = tokio::join!(
+            self.primary
+                .fetch_yearly_fundamentals(ticker, years.clone(), calendar),
+            self.fallback
+                .fetch_yearly_fundamentals(ticker, years, calendar),
+        );
+
+        let mut merged: BTreeMap<i32, YearlyFundamentals> =
+            fallback?.into_iter().map(|f| (f.fiscal_year, f)).collect();
+        for p in self.primary_or_default(ticker, primary) {
+            let entry = merged
+                .entry(p.fiscal_year)
+                .or_insert_with(|| YearlyFundamentals {
+                    fiscal_year: p.fiscal_year,
+                    ..Default::default()
+                });
+            entry.sales = p.sales.or(entry.sales);
+            entry.eps = p.eps.or(entry.eps);
+            entry.net_income = p.net_income.or(entry.net_income);
+            entry.pretax_income = p.pretax_income.or(entry.pretax_income);
+            entry.total_equity = p.total_equity.or(entry.total_equity);
+            entry.dividend_per_share = p.dividend_per_share.or(entry.dividend_per_share);
+            entry.shares_outstanding = p.shares_outstanding.or(entry.shares_outstanding);
+        }
+
+        Ok(merged.into_values().collect())
+    }
+
+    async fn fetch_prices(
+        &self,
+        ticker: &str,
+        years: RangeInclusive<i32>,
+        calendar: FiscalCalendar,
+    ) -> Result<Vec<YearlyPriceRange>> {
+        let (primary, fallback) = tokio::join!(
+            self.primary.fetch_prices(ticker, years.clone(), calendar),
+            self.fallback.fetch_prices(ticker, years, calendar),
+        );
+
+        let mut merged: BTreeMap<i32, YearlyPriceRange> =
+            fallback?.into_iter().map(|p| (p.fiscal_year, p)).collect();
+        for p in self.primary_or_default(ticker, primary) {
+            merged.insert(p.fiscal_year, p);
+        }
+
+        Ok(merged.into_values().collect())
+    }
+
+    async fn fetch_corporate_actions(&self, ticker: &str) -> Result<Vec<CorporateAction>> {
+        let primary = self.primary.fetch_corporate_actions(ticker).await;
+        let actions = self.primary_or_default(ticker, primary);
+        if actions.is_empty() {
+            self.fallback.fetch_corporate_actions(ticker).await
+        } else {
+            Ok(actions)
+        }
+    }
+
+    async fn fetch_quarterly_fundamentals(
+        &self,
+        ticker: &str,
+        years: RangeInclusive<i32>,
+    ) -> Result<Vec<QuarterlyFundamentals>> {
+        let (primary, fallback) = tokio::join!(
+            self.primary
+                .fetch_quarterly_fundamentals(ticker, years.clone()),
+            self.fallback.fetch_quarterly_fundamentals(ticker, years),
+        );
+
+        let mut merged: BTreeMap<(i32, u8), QuarterlyFundamentals> = fallback?
+            .into_iter()
+            .map(|q| ((q.fiscal_year, q.fiscal_quarter), q))
+            .collect();
+        for q in self.primary_or_default(ticker, primary) {
+            merged.insert((q.fiscal_year, q.fiscal_quarter), q);
+        }
+
+        Ok(merged.into_values().collect())
+    }
+
+    async fn fetch_daily_prices(&self, ticker: &str, from: NaiveDate) -> Result<Vec<DailyPrice>> {
+        let primary = self.primary.fetch_daily_prices(ticker, from).await;
+        let bars = self.primary_or_default(ticker, primary);
+        if bars.is_empty() {
+            self.fallback.fetch_daily_prices(ticker, from).await
+        } else {
+            Ok(bars)
+        }
+    }
+
+    async fn fetch_quote(&self, ticker: &str) -> Result<Option<MarketQuote>> {
+        let primary = self.primary.fetch_quote(ticker).await;
+        match self.primary_or_default(ticker, primary) {
+            Some(quote) => Ok(Some(quote)),
+            None => self.fallback.fetch_quote(ticker).await,
+        }
+    }
+
+    /// Probes both providers; the layered provider is only healthy when the
+    /// primary answers, since the fallback merely fills its gaps.
+    async fn probe(&self) -> Result<()> {
+        let (primary, fallback) = tokio::join!(self.primary.probe(), self.fallback.probe());
+        primary.and(fallback)
+    }
+}
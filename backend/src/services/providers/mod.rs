@@ -0,0 +1,395 @@
+//! Pluggable financial data providers.
+//!
+//! Defines the [`FinancialDataProvider`] trait that the harvest pipeline uses
+//! to obtain ticker profiles, yearly fundamentals, yearly price ranges,
+//! quarterly fundamentals, daily prices, corporate actions and current quotes,
+//! plus a [`ProviderRegistry`] that selects an implementation per exchange.
+//!
+//! - [`fixture`]   — File-backed provider reading one JSON document per ticker
+//! - [`yahoo`]     — Yahoo Finance chart API (price ranges, dividends, splits)
+//! - [`sec_edgar`] — SEC EDGAR XBRL `companyfacts` (US fundamentals)
+//! - [`layered`]   — Combines a primary and a fallback provider field by field
+
+use crate::services::rate_limiter::{RateLimit, RateLimiter};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use loco_rs::prelude::*;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+use steady_invest_logic::{FiscalCalendar, TickerInfo};
+
+pub mod fixture;
+#[cfg(test)]
+mod fixture_test;
+pub mod layered;
+pub mod sec_edgar;
+#[cfg(test)]
+mod sec_edgar_test;
+pub mod yahoo;
+#[cfg(test)]
+mod yahoo_test;
+
+pub use fixture::FixtureProvider;
+pub use layered::LayeredProvider;
+pub use sec_edgar::SecEdgarProvider;
+pub use yahoo::YahooProvider;
+
+/// Exchanges whose prices come from Yahoo unless `YAHOO_FINANCE_EXCHANGES`
+/// says otherwise: none, so the test suite never calls the live API. The
+/// `.env.example` used for development and Docker enables SIX and XETRA.
+const DEFAULT_YAHOO_EXCHANGES: &str = "";
+
+/// Exchanges whose fundamentals come from EDGAR, when configured, unless
+/// `SEC_EDGAR_EXCHANGES` says otherwise.
+const DEFAULT_SEC_EDGAR_EXCHANGES: &str = "NASDAQ,NYSE";
+
+// ---------------------------------------------------------------------------
+// Provider DTOs
+// ---------------------------------------------------------------------------
+
+/// Income-statement and balance-sheet figures for one fiscal year.
+///
+/// Every field is optional so that providers covering only part of the SSG
+/// data set can still be combined by the harvest pipeline.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct YearlyFundamentals {
+    /// Fiscal year the figures belong to.
+    pub fiscal_year: i32,
+    /// Total revenue / net sales.
+    #[serde(default)]
+    pub sales: Option<Decimal>,
+    /// Diluted earnings per share, as reported.
+    #[serde(default)]
+    pub eps: Option<Decimal>,
+    /// Net income after tax.
+    #[serde(default)]
+    pub net_income: Option<Decimal>,
+    /// Income before tax.
+    #[serde(default)]
+    pub pretax_income: Option<Decimal>,
+    /// Total shareholders' equity at fiscal year end.
+    #[serde(default)]
+    pub total_equity: Option<Decimal>,
+    /// Dividends declared per share, as reported.
+    #[serde(default)]
+    pub dividend_per_share: Option<Decimal>,
+    /// Shares outstanding, as reported.
+    #[serde(default)]
+    pub shares_outstanding: Option<Decimal>,
+}
+
+/// Highest and lowest traded price during one fiscal year.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct YearlyPriceRange {
+    /// Fiscal year the range covers.
+    pub fiscal_year: i32,
+    /// Highest price during the year, as traded (not split-adjusted).
+    pub high: Decimal,
+    /// Lowest price during the year, as traded (not split-adjusted).
+    pub low: Decimal,
+}
+
+/// Income-statement figures and price range for one fiscal quarter.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuarterlyFundamentals {
+    /// Fiscal year the quarter belongs to.
+    pub fiscal_year: i32,
+    /// Quarter within the fiscal year (1–4).
+    pub fiscal_quarter: u8,
+    /// Last day of the quarter.
+    pub period_end: NaiveDate,
+    /// Total revenue / net sales for the quarter.
+    pub sales: Decimal,
+    /// Diluted earnings per share for the quarter, as reported.
+    pub eps: Decimal,
+    /// Net income after tax.
+    #[serde(default)]
+    pub net_income: Option<Decimal>,
+    /// Income before tax.
+    #[serde(default)]
+    pub pretax_income: Option<Decimal>,
+    /// Highest price during the quarter, as traded.
+    #[serde(default)]
+    pub price_high: Option<Decimal>,
+    /// Lowest price during the quarter, as traded.
+    #[serde(default)]
+    pub price_low: Option<Decimal>,
+}
+
+/// Open, high, low and close of one trading day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyPrice {
+    /// Trading day, in the exchange's local calendar.
+    pub date: NaiveDate,
+    /// Prices as traded (not split-adjusted).
+    pub open: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub close: Decimal,
+    /// Close adjusted for splits and dividends up to today, if the provider has it.
+    #[serde(default)]
+    pub adjusted_close: Option<Decimal>,
+    /// Shares traded, as traded.
+    #[serde(default)]
+    pub volume: Option<i64>,
+}
+
+/// The latest traded price of a ticker.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketQuote {
+    /// Last traded price, as traded.
+    pub price: Decimal,
+    /// Currency of `price`; `None` means the ticker's reporting currency.
+    #[serde(default)]
+    pub currency: Option<String>,
+    /// When the price was observed.
+    pub as_of: DateTime<Utc>,
+}
+
+/// The kind of event recorded in a [`CorporateAction`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CorporateActionKind {
+    /// Forward stock split (e.g. 4-for-1 → ratio `4`).
+    Split,
+    /// Reverse stock split (e.g. 1-for-10 → ratio `0.1`).
+    ReverseSplit,
+    /// Dividend paid in shares (e.g. 5% → ratio `1.05`).
+    StockDividend,
+    /// Distribution of shares in a separated business.
+    SpinOff,
+}
+
+impl CorporateActionKind {
+    /// Every kind, in declaration order.
+    pub const ALL: [Self; 4] = [
+        Self::Split,
+        Self::ReverseSplit,
+        Self::StockDividend,
+        Self::SpinOff,
+    ];
+
+    /// The snake_case name used in JSON and in the `corporate_actions` table.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Split => "split",
+            Self::ReverseSplit => "reverse_split",
+            Self::StockDividend => "stock_dividend",
+            Self::SpinOff => "spin_off",
+        }
+    }
+
+    /// Parses a name produced by [`Self::as_str`].
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|k| k.as_str() == name)
+    }
+
+    /// Whether the action changes the share count, and therefore the
+    /// per-share basis captured by `adjustment_factor`. Spin-offs leave the
+    /// share count unchanged.
+    pub fn changes_share_basis(self) -> bool {
+        !matches!(self, Self::SpinOff)
+    }
+}
+
+/// A corporate action that changes the per-share basis of historical data.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CorporateAction {
+    /// First trading day on the new basis.
+    pub effective_date: NaiveDate,
+    /// What kind of action took place.
+    pub kind: CorporateActionKind,
+    /// Number of new shares per old share.
+    pub ratio: Decimal,
+}
+
+// ---------------------------------------------------------------------------
+// Provider trait
+// ---------------------------------------------------------------------------
+
+/// A source of historical financial data for the harvest pipeline.
+///
+/// Implementations return whatever they cover for the requested fiscal
+/// years; missing years or fields are simply absent rather than errors.
+/// Fiscal years follow the ticker's [`FiscalCalendar`]; providers deriving
+/// yearly figures from dated events bucket them into its fiscal periods.
+/// Per-share figures are returned as reported at the time; split adjustment
+/// is applied by the harvest pipeline from [`Self::fetch_corporate_actions`].
+#[async_trait]
+pub trait FinancialDataProvider: Send + Sync {
+    /// Short identifier used in logs and audit records (e.g. `"fixture"`).
+    fn name(&self) -> &str;
+
+    /// Name the provider's calls are counted under in `provider_rate_limits`,
+    /// or `None` when they are not rate limited.
+    fn rate_limit_name(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns identity information for the ticker, or `None` if unknown.
+    async fn fetch_profile(&self, ticker: &str) -> Result<Option<TickerInfo>>;
+
+    /// Returns yearly fundamentals for the requested fiscal years.
+    async fn fetch_yearly_fundamentals(
+        &self,
+        ticker: &str,
+        years: RangeInclusive<i32>,
+        calendar: FiscalCalendar,
+    ) -> Result<Vec<YearlyFundamentals>>;
+
+    /// Returns high/low price ranges over each requested fiscal year.
+    async fn fetch_prices(
+        &self,
+        ticker: &str,
+        years: RangeInclusive<i32>,
+        calendar: FiscalCalendar,
+    ) -> Result<Vec<YearlyPriceRange>>;
+
+    /// Returns all known corporate actions for the ticker.
+    async fn fetch_corporate_actions(&self, ticker: &str) -> Result<Vec<CorporateAction>>;
+
+    /// Returns quarterly fundamentals for quarters of the requested fiscal
+    /// years. Providers without quarterly coverage return nothing.
+    async fn fetch_quarterly_fundamentals(
+        &self,
+        _ticker: &str,
+        _years: RangeInclusive<i32>,
+    ) -> Result<Vec<QuarterlyFundamentals>> {
+        Ok(Vec::new())
+    }
+
+    /// Returns daily bars from `from` until today. Providers without a daily
+    /// series return nothing.
+    async fn fetch_daily_prices(&self, _ticker: &str, _from: NaiveDate) -> Result<Vec<DailyPrice>> {
+        Ok(Vec::new())
+    }
+
+    /// Returns the latest traded price, or `None` if the provider has no
+    /// quote for the ticker.
+    async fn fetch_quote(&self, _ticker: &str) -> Result<Option<MarketQuote>> {
+        Ok(None)
+    }
+
+    /// Checks that the provider's source answers, using the lightest request
+    /// it supports. Providers without a remote source are always reachable.
+    async fn probe(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Registry
+// ---------------------------------------------------------------------------
+
+/// Selects a [`FinancialDataProvider`] for a ticker based on its exchange.
+///
+/// Exchanges without a dedicated registration fall back to the default provider.
+#[derive(Clone)]
+pub struct ProviderRegistry {
+    default: Arc<dyn FinancialDataProvider>,
+    by_exchange: HashMap<String, Arc<dyn FinancialDataProvider>>,
+}
+
+impl ProviderRegistry {
+    /// Creates a registry that routes every exchange to `default`.
+    pub fn new(default: Arc<dyn FinancialDataProvider>) -> Self {
+        Self {
+            default,
+            by_exchange: HashMap::new(),
+        }
+    }
+
+    /// Routes tickers listed on `exchange` to `provider`.
+    #[must_use]
+    pub fn with_exchange(
+        mut self,
+        exchange: &str,
+        provider: Arc<dyn FinancialDataProvider>,
+    ) -> Self {
+        self.by_exchange.insert(exchange.to_uppercase(), provider);
+        self
+    }
+
+    /// Every distinct provider with the exchanges routed to it, the default
+    /// provider first. The default provider's list only holds exchanges
+    /// registered to it explicitly.
+    pub fn providers(&self) -> Vec<(Arc<dyn FinancialDataProvider>, Vec<String>)> {
+        let mut providers: Vec<(Arc<dyn FinancialDataProvider>, Vec<String>)> =
+            vec![(self.default.clone(), Vec::new())];
+        let mut exchanges: Vec<(&String, &Arc<dyn FinancialDataProvider>)> =
+            self.by_exchange.iter().collect();
+        exchanges.sort_by(|a, b| a.0.cmp(b.0));
+        for (exchange, provider) in exchanges {
+            match providers
+                .iter_mut()
+                .find(|(p, _)| p.name() == provider.name())
+            {
+                Some((_, list)) => list.push(exchange.clone()),
+                None => providers.push((provider.clone(), vec![exchange.clone()])),
+            }
+        }
+        providers
+    }
+
+    /// Returns the provider responsible for `exchange`.
+    pub fn provider_for(&self, exchange: &str) -> Arc<dyn FinancialDataProvider> {
+        self.by_exchange
+            .get(&exchange.to_uppercase())
+            .cloned()
+            .unwrap_or_else(|| self.default.clone())
+    }
+
+    /// Builds the registry used by the running application.
+    ///
+    /// Every exchange is served by the [`FixtureProvider`], reading from
+    /// `FINANCIAL_FIXTURES_DIR` when set and the bundled fixtures otherwise.
+    /// Exchanges listed in `YAHOO_FINANCE_EXCHANGES` (comma-separated, e.g.
+    /// `SMI,DAX`; unset or empty disables Yahoo) take prices, dividends and
+    /// splits from the [`YahooProvider`], with the fixtures filling the rest.
+    /// When `SEC_EDGAR_DIR` or `SEC_EDGAR_BASE_URL` is set, exchanges listed
+    /// in `SEC_EDGAR_EXCHANGES` (default `NASDAQ,NYSE`) take fundamentals
+    /// from the [`SecEdgarProvider`] in the same way.
+    ///
+    /// Requests to Yahoo and to remote EDGAR hosts go through a
+    /// [`RateLimiter`] persisting its state in `db`.
+    pub fn from_env(db: &DatabaseConnection) -> Self {
+        let fixture: Arc<dyn FinancialDataProvider> = Arc::new(FixtureProvider::from_env());
+        let mut registry = Self::new(fixture.clone());
+
+        let yahoo_limit = RateLimit::from_env("YAHOO_FINANCE", yahoo::DEFAULT_RATE_LIMIT);
+        let yahoo: Arc<dyn FinancialDataProvider> = Arc::new(
+            YahooProvider::from_env().with_rate_limiter(RateLimiter::new(db, "yahoo", yahoo_limit)),
+        );
+        for exchange in exchanges_from_env("YAHOO_FINANCE_EXCHANGES", DEFAULT_YAHOO_EXCHANGES) {
+            let layered = LayeredProvider::new(yahoo.clone(), fixture.clone());
+            registry = registry.with_exchange(&exchange, Arc::new(layered));
+        }
+
+        if let Some(mut edgar) = SecEdgarProvider::from_env() {
+            if edgar.is_remote() {
+                let limit = RateLimit::from_env("SEC_EDGAR", sec_edgar::DEFAULT_RATE_LIMIT);
+                edgar = edgar.with_rate_limiter(RateLimiter::new(db, "sec_edgar", limit));
+            }
+            let edgar: Arc<dyn FinancialDataProvider> = Arc::new(edgar);
+            for exchange in exchanges_from_env("SEC_EDGAR_EXCHANGES", DEFAULT_SEC_EDGAR_EXCHANGES) {
+                let layered = LayeredProvider::new(edgar.clone(), fixture.clone());
+                registry = registry.with_exchange(&exchange, Arc::new(layered));
+            }
+        }
+        registry
+    }
+}
+
+/// Reads a comma-separated exchange list from `var`, falling back to `default`.
+fn exchanges_from_env(var: &str, default: &str) -> Vec<String> {
+    std::env::var(var)
+        .unwrap_or_else(|_| default.to_string())
+        .split(',')
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+        .map(str::to_string)
+        .collect()
+}
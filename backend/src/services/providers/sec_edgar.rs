@@ -0,0 +1,479 @@
+//! SEC EDGAR XBRL `companyfacts` provider.
+//!
+//! Maps us-gaap concepts from the SEC `companyfacts` JSON onto yearly
+//! fundamentals for US filers. Only annual facts reported in 10-K filings are
+//! used; for each fiscal year the most recently filed value wins, so later
+//! restatements replace the originally reported figure.
+//!
+//! Documents are read either from a local directory of downloaded files
+//! (`SEC_EDGAR_DIR`) or from a configurable API host (`SEC_EDGAR_BASE_URL`).
+//! Both layouts mirror the SEC's own: the ticker → CIK map lives at
+//! `files/company_tickers.json` and the facts at
+//! `api/xbrl/companyfacts/CIK##########.json` relative to the source root.
+//!
+//! Requests to a remote host take a token from the provider's
+//! [`RateLimiter`], when one is attached, before they are sent.
+//!
+//! Monetary amounts and share counts are returned in millions, matching the
+//! units used by the other providers. EDGAR carries no prices, so the provider
+//! is meant to be layered over one that does.
+
+use super::{CorporateAction, FinancialDataProvider, YearlyFundamentals, YearlyPriceRange};
+use crate::services::rate_limiter::{RateLimit, RateLimiter};
+use async_trait::async_trait;
+use chrono::{Datelike, NaiveDate};
+use loco_rs::prelude::*;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+use steady_invest_logic::{FiscalCalendar, TickerInfo};
+use tokio::sync::OnceCell;
+
+/// Public host serving the ticker map (`files/company_tickers.json`).
+const SEC_WWW_URL: &str = "https://www.sec.gov";
+
+/// Public host serving the XBRL APIs (`api/xbrl/...`).
+const SEC_DATA_URL: &str = "https://data.sec.gov";
+
+/// HTTP request timeout for EDGAR requests (seconds).
+const HTTP_TIMEOUT_SECS: u64 = 3;
+
+/// The SEC asks automated clients to identify themselves with a contact address.
+const DEFAULT_USER_AGENT: &str = "SteadyInvest admin@steadyinvest.local";
+
+/// Calls allowed unless `SEC_EDGAR_RATE_LIMIT_PER_MINUTE` and
+/// `SEC_EDGAR_RATE_LIMIT_PER_DAY` say otherwise; the SEC's fair access policy
+/// allows ten requests per second.
+pub const DEFAULT_RATE_LIMIT: RateLimit = RateLimit {
+    per_minute: 300,
+    per_day: 50_000,
+};
+
+/// Shortest and longest period, in days, accepted as a full fiscal year.
+const ANNUAL_PERIOD_DAYS: RangeInclusive<i64> = 350..=380;
+
+/// Divisor turning dollars and shares into millions.
+const MILLION: Decimal = Decimal::from_parts(1_000_000, 0, 0, false, 0);
+
+/// us-gaap concepts for each field, in order of preference.
+const SALES_CONCEPTS: &[&str] = &[
+    "RevenueFromContractWithCustomerExcludingAssessedTax",
+    "Revenues",
+    "SalesRevenueNet",
+    "RevenueFromContractWithCustomerIncludingAssessedTax",
+];
+const NET_INCOME_CONCEPTS: &[&str] = &["NetIncomeLoss"];
+const PRETAX_INCOME_CONCEPTS: &[&str] = &[
+    "IncomeLossFromContinuingOperationsBeforeIncomeTaxesExtraordinaryItemsNoncontrollingInterest",
+    "IncomeLossFromContinuingOperationsBeforeIncomeTaxesMinorityInterestAndIncomeLossFromEquityMethodInvestments",
+];
+const EQUITY_CONCEPTS: &[&str] = &[
+    "StockholdersEquity",
+    "StockholdersEquityIncludingPortionAttributableToNoncontrollingInterest",
+];
+const EPS_CONCEPTS: &[&str] = &["EarningsPerShareDiluted", "EarningsPerShareBasic"];
+const DIVIDEND_CONCEPTS: &[&str] = &[
+    "CommonStockDividendsPerShareDeclared",
+    "CommonStockDividendsPerShareCashPaid",
+];
+const SHARES_CONCEPTS: &[&str] = &[
+    "WeightedAverageNumberOfDilutedSharesOutstanding",
+    "CommonStockSharesOutstanding",
+];
+
+/// Ticker → CIK map below the SEC www host.
+const TICKER_MAP_PATH: &str = "files/company_tickers.json";
+
+/// Shared HTTP client with connection pooling and timeout.
+static HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(HTTP_TIMEOUT_SECS))
+        .user_agent(
+            std::env::var("SEC_EDGAR_USER_AGENT").unwrap_or_else(|_| DEFAULT_USER_AGENT.into()),
+        )
+        .build()
+        .expect("HTTP client build must succeed")
+});
+
+/// Where EDGAR documents are read from.
+#[derive(Debug, Clone)]
+pub enum SecEdgarSource {
+    /// Local directory laid out like the SEC hosts.
+    Directory(PathBuf),
+    /// Remote hosts for the ticker map and the XBRL API.
+    Url { www: String, data: String },
+}
+
+/// Provider reading us-gaap fundamentals from EDGAR `companyfacts` documents.
+#[derive(Debug)]
+pub struct SecEdgarProvider {
+    source: SecEdgarSource,
+    /// Upper-case ticker → CIK, loaded on first use.
+    ciks: OnceCell<HashMap<String, u64>>,
+    limiter: Option<RateLimiter>,
+}
+
+impl SecEdgarProvider {
+    /// Creates a provider reading downloaded documents from `dir`.
+    pub fn from_dir(dir: impl Into<PathBuf>) -> Self {
+        Self::with_source(SecEdgarSource::Directory(dir.into()))
+    }
+
+    /// Creates a provider serving both the ticker map and the facts from `base_url`.
+    pub fn from_url(base_url: impl Into<String>) -> Self {
+        let base = base_url.into().trim_end_matches('/').to_string();
+        Self::with_source(SecEdgarSource::Url {
+            www: base.clone(),
+            data: base,
+        })
+    }
+
+    /// Creates a provider talking to the public SEC hosts.
+    pub fn public() -> Self {
+        Self::with_source(SecEdgarSource::Url {
+            www: SEC_WWW_URL.to_string(),
+            data: SEC_DATA_URL.to_string(),
+        })
+    }
+
+    fn with_source(source: SecEdgarSource) -> Self {
+        Self {
+            source,
+            ciks: OnceCell::new(),
+            limiter: None,
+        }
+    }
+
+    /// Takes a token from `limiter` before every request to a remote host.
+    #[must_use]
+    pub fn with_rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.limiter = Some(limiter);
+        self
+    }
+
+    /// Whether documents are fetched from a remote host.
+    pub fn is_remote(&self) -> bool {
+        matches!(self.source, SecEdgarSource::Url { .. })
+    }
+
+    async fn acquire(&self) -> Result<()> {
+        match &self.limiter {
+            Some(limiter) => limiter.acquire().await,
+            None => Ok(()),
+        }
+    }
+
+    /// Reads `SEC_EDGAR_DIR`, then `SEC_EDGAR_BASE_URL` (`public` for the SEC
+    /// hosts); returns `None` when neither is set.
+    pub fn from_env() -> Option<Self> {
+        if let Ok(dir) = std::env::var("SEC_EDGAR_DIR") {
+            if !dir.trim().is_empty() {
+                return Some(Self::from_dir(dir));
+            }
+        }
+        match std::env::var("SEC_EDGAR_BASE_URL") {
+            Ok(url) if url.trim() == "public" => Some(Self::public()),
+            Ok(url) if !url.trim().is_empty() => Some(Self::from_url(url.trim())),
+            _ => None,
+        }
+    }
+
+    /// Reads a document at `path` below the source root, or `None` if absent.
+    async fn read(&self, root: Root, path: &str) -> Result<Option<String>> {
+        match &self.source {
+            SecEdgarSource::Directory(dir) => {
+                match tokio::fs::read_to_string(dir.join(path)).await {
+                    Ok(raw) => Ok(Some(raw)),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                    Err(e) => Err(Error::string(&format!(
+                        "Failed to read EDGAR document {path}: {e}"
+                    ))),
+                }
+            }
+            SecEdgarSource::Url { www, data } => {
+                let base = match root {
+                    Root::Www => www,
+                    Root::Data => data,
+                };
+                self.acquire().await?;
+                let response = HTTP_CLIENT
+                    .get(format!("{base}/{path}"))
+                    .send()
+                    .await
+                    .map_err(|e| Error::string(&format!("EDGAR request {path} failed: {e}")))?;
+
+                if response.status() == reqwest::StatusCode::NOT_FOUND {
+                    return Ok(None);
+                }
+                if !response.status().is_success() {
+                    return Err(Error::string(&format!(
+                        "EDGAR returned HTTP {} for {path}",
+                        response.status()
+                    )));
+                }
+                response
+                    .text()
+                    .await
+                    .map(Some)
+                    .map_err(|e| Error::string(&format!("Invalid EDGAR response {path}: {e}")))
+            }
+        }
+    }
+
+    /// Resolves a ticker to its CIK using the SEC ticker map.
+    async fn cik(&self, ticker: &str) -> Result<Option<u64>> {
+        let ciks = self
+            .ciks
+            .get_or_try_init(|| async {
+                let Some(raw) = self.read(Root::Www, TICKER_MAP_PATH).await? else {
+                    return Ok::<_, Error>(HashMap::new());
+                };
+                let entries: HashMap<String, TickerEntry> = serde_json::from_str(&raw)
+                    .map_err(|e| Error::string(&format!("Invalid EDGAR ticker map: {e}")))?;
+                Ok(entries
+                    .into_values()
+                    .map(|e| (e.ticker.to_uppercase(), e.cik_str))
+                    .collect())
+            })
+            .await?;
+
+        Ok(ciks.get(&ticker.to_uppercase()).copied())
+    }
+
+    /// Loads the `companyfacts` document for `ticker`, or `None` if unknown.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a document cannot be read or is not valid JSON.
+    pub async fn company_facts(&self, ticker: &str) -> Result<Option<CompanyFacts>> {
+        let Some(cik) = self.cik(ticker).await? else {
+            return Ok(None);
+        };
+        let path = format!("api/xbrl/companyfacts/CIK{cik:010}.json");
+        let Some(raw) = self.read(Root::Data, &path).await? else {
+            return Ok(None);
+        };
+
+        serde_json::from_str(&raw)
+            .map(Some)
+            .map_err(|e| Error::string(&format!("Invalid EDGAR companyfacts for {ticker}: {e}")))
+    }
+}
+
+#[async_trait]
+impl FinancialDataProvider for SecEdgarProvider {
+    fn name(&self) -> &str {
+        "sec_edgar"
+    }
+
+    fn rate_limit_name(&self) -> Option<&str> {
+        self.limiter.as_ref().map(RateLimiter::name)
+    }
+
+    async fn fetch_profile(&self, ticker: &str) -> Result<Option<TickerInfo>> {
+        Ok(self.company_facts(ticker).await?.map(|facts| TickerInfo {
+            ticker: ticker.to_uppercase(),
+            name: facts.entity_name,
+            exchange: String::new(),
+            currency: "USD".to_string(),
+        }))
+    }
+
+    async fn fetch_yearly_fundamentals(
+        &self,
+        ticker: &str,
+        years: RangeInclusive<i32>,
+        _calendar: FiscalCalendar,
+    ) -> Result<Vec<YearlyFundamentals>> {
+        Ok(self
+            .company_facts(ticker)
+            .await?
+            .map(|facts| facts.yearly_fundamentals(&years))
+            .unwrap_or_default())
+    }
+
+    async fn fetch_prices(
+        &self,
+        _ticker: &str,
+        _years: RangeInclusive<i32>,
+        _calendar: FiscalCalendar,
+    ) -> Result<Vec<YearlyPriceRange>> {
+        Ok(vec![])
+    }
+
+    async fn fetch_corporate_actions(&self, _ticker: &str) -> Result<Vec<CorporateAction>> {
+        Ok(vec![])
+    }
+
+    /// Checks that the ticker map is available, with a `HEAD` request when
+    /// reading from the SEC hosts.
+    async fn probe(&self) -> Result<()> {
+        match &self.source {
+            SecEdgarSource::Directory(dir) => tokio::fs::metadata(dir.join(TICKER_MAP_PATH))
+                .await
+                .map(|_| ())
+                .map_err(|e| {
+                    Error::string(&format!(
+                        "EDGAR ticker map in {} is unreadable: {e}",
+                        dir.display()
+                    ))
+                }),
+            SecEdgarSource::Url { www, .. } => {
+                self.acquire().await?;
+                let response = HTTP_CLIENT
+                    .head(format!("{www}/{TICKER_MAP_PATH}"))
+                    .send()
+                    .await
+                    .map_err(|e| Error::string(&format!("EDGAR probe failed: {e}")))?;
+                if response.status().is_success() {
+                    Ok(())
+                } else {
+                    Err(Error::string(&format!(
+                        "EDGAR returned HTTP {} for {TICKER_MAP_PATH}",
+                        response.status()
+                    )))
+                }
+            }
+        }
+    }
+}
+
+/// Which SEC host a document lives on.
+#[derive(Clone, Copy)]
+enum Root {
+    Www,
+    Data,
+}
+
+/// One entry of `company_tickers.json`.
+#[derive(Deserialize)]
+struct TickerEntry {
+    cik_str: u64,
+    ticker: String,
+}
+
+/// The parts of a `companyfacts` document used by the provider.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompanyFacts {
+    pub cik: u64,
+    pub entity_name: String,
+    #[serde(default)]
+    pub facts: HashMap<String, HashMap<String, Concept>>,
+}
+
+/// A single XBRL concept with its facts grouped by unit.
+#[derive(Debug, Deserialize)]
+pub struct Concept {
+    #[serde(default)]
+    pub units: HashMap<String, Vec<Fact>>,
+}
+
+/// One reported value of a concept.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Fact {
+    /// Period start; absent for point-in-time (balance-sheet) facts.
+    pub start: Option<NaiveDate>,
+    /// Period end, or the balance-sheet date.
+    pub end: NaiveDate,
+    pub val: Decimal,
+    pub form: String,
+    pub filed: NaiveDate,
+}
+
+impl Fact {
+    /// Whether the fact comes from an annual report and covers a full year.
+    fn is_annual(&self) -> bool {
+        if !matches!(self.form.as_str(), "10-K" | "10-K/A") {
+            return false;
+        }
+        match self.start {
+            Some(start) => ANNUAL_PERIOD_DAYS.contains(&(self.end - start).num_days()),
+            None => true,
+        }
+    }
+}
+
+impl CompanyFacts {
+    /// Builds yearly fundamentals for `years` from the 10-K facts.
+    pub fn yearly_fundamentals(&self, years: &RangeInclusive<i32>) -> Vec<YearlyFundamentals> {
+        let mut by_year: BTreeMap<i32, YearlyFundamentals> = BTreeMap::new();
+        let mut set = |values: BTreeMap<i32, Decimal>,
+                       assign: fn(&mut YearlyFundamentals, Decimal)| {
+            for (year, value) in values.into_iter().filter(|(y, _)| years.contains(y)) {
+                let entry = by_year.entry(year).or_insert_with(|| YearlyFundamentals {
+                    fiscal_year: year,
+                    ..Default::default()
+                });
+                assign(entry, value);
+            }
+        };
+
+        set(self.annual("USD", SALES_CONCEPTS, MILLION), |f, v| {
+            f.sales = Some(v)
+        });
+        set(self.annual("USD", NET_INCOME_CONCEPTS, MILLION), |f, v| {
+            f.net_income = Some(v)
+        });
+        set(
+            self.annual("USD", PRETAX_INCOME_CONCEPTS, MILLION),
+            |f, v| f.pretax_income = Some(v),
+        );
+        set(self.annual("USD", EQUITY_CONCEPTS, MILLION), |f, v| {
+            f.total_equity = Some(v)
+        });
+        set(
+            self.annual("USD/shares", EPS_CONCEPTS, Decimal::ONE),
+            |f, v| f.eps = Some(v),
+        );
+        set(
+            self.annual("USD/shares", DIVIDEND_CONCEPTS, Decimal::ONE),
+            |f, v| f.dividend_per_share = Some(v),
+        );
+        set(self.annual("shares", SHARES_CONCEPTS, MILLION), |f, v| {
+            f.shares_outstanding = Some(v)
+        });
+
+        by_year.into_values().collect()
+    }
+
+    /// Annual values per fiscal year, divided by `scale`.
+    ///
+    /// Concepts are tried in order of preference and each year takes the first
+    /// concept that reports it. Within a concept, the latest-filed 10-K fact
+    /// for the period wins. The fiscal year is the calendar year of the
+    /// period end.
+    fn annual(&self, unit: &str, concepts: &[&str], scale: Decimal) -> BTreeMap<i32, Decimal> {
+        let Some(us_gaap) = self.facts.get("us-gaap") else {
+            return BTreeMap::new();
+        };
+
+        let mut values: BTreeMap<i32, Decimal> = BTreeMap::new();
+        for concept in concepts {
+            let Some(facts) = us_gaap.get(*concept).and_then(|c| c.units.get(unit)) else {
+                continue;
+            };
+
+            let mut latest: BTreeMap<i32, &Fact> = BTreeMap::new();
+            for fact in facts.iter().filter(|f| f.is_annual()) {
+                let year = fact.end.year();
+                let keep = latest
+                    .get(&year)
+                    .is_none_or(|f| (fact.filed, fact.end) >= (f.filed, f.end));
+                if keep {
+                    latest.insert(year, fact);
+                }
+            }
+
+            for (year, fact) in latest {
+                values
+                    .entry(year)
+                    .or_insert_with(|| (fact.val / scale).round_dp(4).normalize());
+            }
+        }
+        values
+    }
+}
@@ -0,0 +1,173 @@
+#[cfg(test)]
+mod tests {
+    use crate::services::providers::{
+        FinancialDataProvider, FixtureProvider, LayeredProvider, SecEdgarProvider,
+    };
+    use axum::{extract::Path, http::StatusCode, routing::get, Json, Router};
+    use rust_decimal::Decimal;
+    use serde_json::{json, Value};
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use steady_invest_logic::FiscalCalendar;
+
+    const CALENDAR_YEAR: FiscalCalendar = FiscalCalendar::CALENDAR_YEAR;
+
+    fn dec(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    fn tickers_json() -> Value {
+        json!({"0": {"cik_str": 320193, "ticker": "AAPL", "title": "Apple Inc."}})
+    }
+
+    fn fact(start: Option<&str>, end: &str, val: f64, form: &str, filed: &str) -> Value {
+        json!({
+            "start": start, "end": end, "val": val, "accn": "0000320193-00-000000",
+            "fy": 2021, "fp": "FY", "form": form, "filed": filed
+        })
+    }
+
+    /// Companyfacts for a September year-end filer. FY2020 revenue is restated
+    /// in the FY2021 10-K; a quarterly 10-Q fact must be ignored.
+    fn companyfacts_json() -> Value {
+        json!({
+            "cik": 320193,
+            "entityName": "Apple Inc.",
+            "facts": {
+                "us-gaap": {
+                    "RevenueFromContractWithCustomerExcludingAssessedTax": {"units": {"USD": [
+                        fact(Some("2019-09-29"), "2020-09-26", 274_515_000_000.0, "10-K", "2020-10-30"),
+                        fact(Some("2019-09-29"), "2020-09-26", 274_600_000_000.0, "10-K", "2021-10-29"),
+                        fact(Some("2020-09-27"), "2021-09-25", 365_817_000_000.0, "10-K", "2021-10-29"),
+                        fact(Some("2021-06-27"), "2021-09-25", 83_360_000_000.0, "10-K", "2021-10-29"),
+                        fact(Some("2020-12-27"), "2021-03-27", 89_584_000_000.0, "10-Q", "2021-04-29")
+                    ]}},
+                    "NetIncomeLoss": {"units": {"USD": [
+                        fact(Some("2020-09-27"), "2021-09-25", 94_680_000_000.0, "10-K", "2021-10-29")
+                    ]}},
+                    "StockholdersEquity": {"units": {"USD": [
+                        fact(None, "2020-09-26", 65_339_000_000.0, "10-K", "2021-10-29"),
+                        fact(None, "2021-09-25", 63_090_000_000.0, "10-K", "2021-10-29")
+                    ]}},
+                    "EarningsPerShareDiluted": {"units": {"USD/shares": [
+                        fact(Some("2020-09-27"), "2021-09-25", 5.61, "10-K", "2021-10-29")
+                    ]}},
+                    "EarningsPerShareBasic": {"units": {"USD/shares": [
+                        fact(Some("2019-09-29"), "2020-09-26", 3.31, "10-K", "2020-10-30"),
+                        fact(Some("2020-09-27"), "2021-09-25", 5.67, "10-K", "2021-10-29")
+                    ]}},
+                    "WeightedAverageNumberOfDilutedSharesOutstanding": {"units": {"shares": [
+                        fact(Some("2020-09-27"), "2021-09-25", 16_864_919_000.0, "10-K", "2021-10-29")
+                    ]}}
+                }
+            }
+        })
+    }
+
+    fn write_dir() -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("edgar-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("files")).unwrap();
+        std::fs::create_dir_all(dir.join("api/xbrl/companyfacts")).unwrap();
+        std::fs::write(
+            dir.join("files/company_tickers.json"),
+            tickers_json().to_string(),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("api/xbrl/companyfacts/CIK0000320193.json"),
+            companyfacts_json().to_string(),
+        )
+        .unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_edgar_maps_annual_10k_facts() {
+        let dir = write_dir();
+        let provider = SecEdgarProvider::from_dir(&dir);
+
+        let fundamentals = provider
+            .fetch_yearly_fundamentals("AAPL", 2016..=2025, CALENDAR_YEAR)
+            .await
+            .unwrap();
+        assert_eq!(fundamentals.len(), 2);
+
+        // Restated value from the later 10-K wins, scaled to millions
+        let fy2020 = &fundamentals[0];
+        assert_eq!(fy2020.fiscal_year, 2020);
+        assert_eq!(fy2020.sales, Some(dec("274600")));
+        assert_eq!(fy2020.total_equity, Some(dec("65339")));
+        // Falls back to basic EPS when diluted is not reported
+        assert_eq!(fy2020.eps, Some(dec("3.31")));
+        assert_eq!(fy2020.net_income, None);
+
+        // The fourth-quarter fact in the 10-K is not a full year
+        let fy2021 = &fundamentals[1];
+        assert_eq!(fy2021.sales, Some(dec("365817")));
+        assert_eq!(fy2021.net_income, Some(dec("94680")));
+        assert_eq!(fy2021.eps, Some(dec("5.61")));
+        assert_eq!(fy2021.shares_outstanding, Some(dec("16864.919")));
+        assert_eq!(fy2021.dividend_per_share, None);
+
+        let only_2021 = provider
+            .fetch_yearly_fundamentals("AAPL", 2021..=2021, CALENDAR_YEAR)
+            .await
+            .unwrap();
+        assert_eq!(only_2021.len(), 1);
+
+        let profile = provider.fetch_profile("aapl").await.unwrap().unwrap();
+        assert_eq!(profile.name, "Apple Inc.");
+        assert_eq!(profile.currency, "USD");
+
+        assert!(provider.fetch_profile("NOPE").await.unwrap().is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    async fn company_tickers() -> Json<Value> {
+        Json(tickers_json())
+    }
+
+    async fn company_facts(Path(file): Path<String>) -> (StatusCode, Json<Value>) {
+        if file == "CIK0000320193.json" {
+            (StatusCode::OK, Json(companyfacts_json()))
+        } else {
+            (StatusCode::NOT_FOUND, Json(json!({})))
+        }
+    }
+
+    /// Starts a mock EDGAR server and returns its base URL.
+    async fn mock_server() -> String {
+        let app = Router::new()
+            .route("/files/company_tickers.json", get(company_tickers))
+            .route("/api/xbrl/companyfacts/{file}", get(company_facts));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{addr}")
+    }
+
+    #[tokio::test]
+    async fn test_edgar_layered_over_fixture_prices() {
+        let layered = LayeredProvider::new(
+            Arc::new(SecEdgarProvider::from_url(mock_server().await)),
+            Arc::new(FixtureProvider::bundled()),
+        );
+
+        let fundamentals = layered
+            .fetch_yearly_fundamentals("AAPL", 2016..=2025, CALENDAR_YEAR)
+            .await
+            .unwrap();
+        assert_eq!(fundamentals.len(), 10);
+        let fy2021 = fundamentals.iter().find(|f| f.fiscal_year == 2021).unwrap();
+        assert_eq!(fy2021.sales, Some(dec("365817")));
+        // Fields EDGAR does not report come from the fixture
+        assert!(fy2021.dividend_per_share.is_some());
+
+        let prices = layered
+            .fetch_prices("AAPL", 2016..=2025, CALENDAR_YEAR)
+            .await
+            .unwrap();
+        assert_eq!(prices.len(), 10);
+    }
+}
@@ -0,0 +1,489 @@
+//! Yahoo Finance provider.
+//!
+//! Reads the Yahoo v8 chart endpoint and parses it with the
+//! [`yahoo_finance_api`] response types. Monthly bars are folded into high/low
+//! ranges per fiscal year, dividend events into dividends per share per fiscal
+//! year, and split events into corporate actions. Yahoo does not expose
+//! income-statement data on this endpoint, so sales, EPS and balance-sheet
+//! fields are left empty. Daily bars form the daily price series, and the
+//! close of the latest one serves as the current quote.
+//!
+//! Yahoo reports prices and dividends on today's share basis. The provider
+//! undoes that adjustment so that, like every other provider, it returns
+//! figures as reported at the time; the harvest pipeline then applies its own
+//! split adjustment from the returned corporate actions.
+//!
+//! Some exchanges quote in a minor unit, such as London in pence (`GBp`).
+//! Prices and dividends from those are divided into the major currency, which
+//! is also the currency the provider reports.
+//!
+//! The base URL is read from `YAHOO_FINANCE_BASE_URL` so tests can point the
+//! provider at a local mock server. With a [`RateLimiter`] attached, every
+//! chart request takes a token first. Yearly prices and dividends come from
+//! the same monthly chart, which is fetched once and shared by both calls for
+//! [`HISTORY_TTL`].
+
+use super::{
+    CorporateAction, CorporateActionKind, DailyPrice, FinancialDataProvider, MarketQuote,
+    YearlyFundamentals, YearlyPriceRange,
+};
+use crate::services::rate_limiter::{RateLimit, RateLimiter};
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, NaiveDate, TimeZone, Utc};
+use loco_rs::prelude::*;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::RangeInclusive;
+use std::sync::{Arc, LazyLock};
+use std::time::{Duration, Instant};
+use steady_invest_logic::{FiscalCalendar, TickerInfo};
+use tokio::sync::Mutex;
+use yahoo_finance_api::YResponse;
+
+/// Default Yahoo Finance API host.
+const YAHOO_BASE_URL: &str = "https://query1.finance.yahoo.com";
+
+/// HTTP request timeout for Yahoo requests (seconds). Kept below the harvest
+/// timeout so a layered fallback still has time to answer.
+const HTTP_TIMEOUT_SECS: u64 = 3;
+
+/// Yahoo rejects requests without a browser-like user agent.
+const USER_AGENT: &str = "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/122.0.0.0 Safari/537.36";
+
+/// Calls allowed unless `YAHOO_FINANCE_RATE_LIMIT_PER_MINUTE` and
+/// `YAHOO_FINANCE_RATE_LIMIT_PER_DAY` say otherwise. Yahoo publishes no
+/// limits; these stay well below the rates it is known to block.
+pub const DEFAULT_RATE_LIMIT: RateLimit = RateLimit {
+    per_minute: 60,
+    per_day: 2_000,
+};
+
+/// Liquid symbol requested by [`YahooProvider::probe`](FinancialDataProvider::probe).
+const PROBE_SYMBOL: &str = "SPY";
+
+/// Half a day in seconds, used to place bar timestamps safely within their date.
+const HALF_DAY_SECS: i64 = 43_200;
+
+/// How long a fetched monthly chart is reused; long enough to serve both the
+/// price and dividend calls of one harvest.
+const HISTORY_TTL: Duration = Duration::from_secs(60);
+
+/// Minor-unit codes Yahoo quotes some exchanges in, with their major currency.
+const MINOR_UNITS: [(&str, &str); 4] = [
+    ("GBp", "GBP"),
+    ("GBX", "GBP"),
+    ("ZAc", "ZAR"),
+    ("ILA", "ILS"),
+];
+
+/// Monthly chart of one ticker from one start date, with when it was fetched;
+/// the inner `None` means Yahoo does not know the ticker.
+type HistorySlot = Arc<Mutex<Option<(Instant, Option<Arc<History>>)>>>;
+
+/// Shared HTTP client with connection pooling and timeout.
+static HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(HTTP_TIMEOUT_SECS))
+        .user_agent(USER_AGENT)
+        .build()
+        .expect("HTTP client build must succeed")
+});
+
+/// Provider backed by the Yahoo Finance chart API.
+#[derive(Debug, Clone)]
+pub struct YahooProvider {
+    base_url: String,
+    limiter: Option<RateLimiter>,
+    /// Monthly charts by ticker and first day, shared by clones.
+    histories: Arc<std::sync::Mutex<HashMap<(String, NaiveDate), HistorySlot>>>,
+}
+
+impl YahooProvider {
+    /// Creates a provider talking to the API at `base_url`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            limiter: None,
+            histories: Arc::default(),
+        }
+    }
+
+    /// Takes a token from `limiter` before every request.
+    #[must_use]
+    pub fn with_rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.limiter = Some(limiter);
+        self
+    }
+
+    /// Uses `YAHOO_FINANCE_BASE_URL` when set, the public Yahoo host otherwise.
+    pub fn from_env() -> Self {
+        Self::new(
+            std::env::var("YAHOO_FINANCE_BASE_URL").unwrap_or_else(|_| YAHOO_BASE_URL.to_string()),
+        )
+    }
+
+    /// Fetches a chart response, returning `None` when Yahoo does not know the ticker.
+    async fn chart(&self, ticker: &str, query: &[(&str, String)]) -> Result<Option<YResponse>> {
+        if let Some(limiter) = &self.limiter {
+            limiter.acquire().await?;
+        }
+        let url = format!("{}/v8/finance/chart/{}", self.base_url, ticker);
+        let response = HTTP_CLIENT
+            .get(&url)
+            .query(query)
+            .send()
+            .await
+            .map_err(|e| Error::string(&format!("Yahoo request for {ticker} failed: {e}")))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(Error::string(&format!(
+                "Yahoo returned HTTP {} for {ticker}",
+                response.status()
+            )));
+        }
+
+        let json: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| Error::string(&format!("Invalid Yahoo response for {ticker}: {e}")))?;
+        let chart = YResponse::from_json(json)
+            .map_err(|e| Error::string(&format!("Invalid Yahoo chart for {ticker}: {e}")))?;
+
+        if chart.chart.result.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(chart))
+    }
+
+    /// Fetches monthly bars with dividend and split events from the first day
+    /// of the first requested fiscal year until today.
+    ///
+    /// A chart fetched less than [`HISTORY_TTL`] ago for the same ticker and
+    /// first day is reused; concurrent callers wait for the one request.
+    async fn history(
+        &self,
+        ticker: &str,
+        years: &RangeInclusive<i32>,
+        calendar: FiscalCalendar,
+    ) -> Result<Option<Arc<History>>> {
+        let first_day = calendar.start(*years.start());
+        let slot = {
+            let mut histories = self
+                .histories
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            // Drop expired charts nobody is waiting on
+            histories.retain(|_, slot| match slot.try_lock() {
+                Ok(cached) => cached
+                    .as_ref()
+                    .is_some_and(|(at, _)| at.elapsed() < HISTORY_TTL),
+                Err(_) => true,
+            });
+            histories
+                .entry((ticker.to_string(), first_day))
+                .or_default()
+                .clone()
+        };
+
+        let mut cached = slot.lock().await;
+        if let Some((fetched_at, history)) = cached.as_ref() {
+            if fetched_at.elapsed() < HISTORY_TTL {
+                return Ok(history.clone());
+            }
+        }
+
+        let start = Utc
+            .with_ymd_and_hms(first_day.year(), first_day.month(), 1, 0, 0, 0)
+            .single()
+            .map_or(0, |d| d.timestamp());
+        let query = [
+            ("period1", start.to_string()),
+            ("period2", Utc::now().timestamp().to_string()),
+            ("interval", "1mo".to_string()),
+            ("events", "div|split".to_string()),
+        ];
+
+        let history = match self.chart(ticker, &query).await? {
+            Some(chart) => Some(Arc::new(History::from_chart(&chart)?)),
+            None => None,
+        };
+        *cached = Some((Instant::now(), history.clone()));
+        Ok(history)
+    }
+}
+
+#[async_trait]
+impl FinancialDataProvider for YahooProvider {
+    fn name(&self) -> &str {
+        "yahoo"
+    }
+
+    fn rate_limit_name(&self) -> Option<&str> {
+        self.limiter.as_ref().map(RateLimiter::name)
+    }
+
+    async fn fetch_profile(&self, ticker: &str) -> Result<Option<TickerInfo>> {
+        let query = [("range", "5d".to_string()), ("interval", "1d".to_string())];
+        let Some(chart) = self.chart(ticker, &query).await? else {
+            return Ok(None);
+        };
+        let meta = chart.chart.result[0].meta.clone();
+
+        Ok(meta.currency.map(|currency| TickerInfo {
+            ticker: meta.symbol,
+            name: meta.long_name,
+            exchange: meta.exchange_name,
+            currency: major_unit(&currency).0,
+        }))
+    }
+
+    async fn fetch_yearly_fundamentals(
+        &self,
+        ticker: &str,
+        years: RangeInclusive<i32>,
+        calendar: FiscalCalendar,
+    ) -> Result<Vec<YearlyFundamentals>> {
+        let Some(history) = self.history(ticker, &years, calendar).await? else {
+            return Ok(vec![]);
+        };
+
+        let mut dividends: BTreeMap<i32, Decimal> = BTreeMap::new();
+        for (date, amount) in &history.dividends {
+            *dividends.entry(calendar.fiscal_year(*date)).or_default() +=
+                *amount * history.split_ratio_after(*date);
+        }
+
+        Ok(dividends
+            .into_iter()
+            .filter(|(year, _)| years.contains(year))
+            .map(|(fiscal_year, dps)| YearlyFundamentals {
+                fiscal_year,
+                dividend_per_share: Some(dps.round_dp(4)),
+                ..Default::default()
+            })
+            .collect())
+    }
+
+    async fn fetch_prices(
+        &self,
+        ticker: &str,
+        years: RangeInclusive<i32>,
+        calendar: FiscalCalendar,
+    ) -> Result<Vec<YearlyPriceRange>> {
+        let Some(history) = self.history(ticker, &years, calendar).await? else {
+            return Ok(vec![]);
+        };
+
+        let mut ranges: BTreeMap<i32, (Decimal, Decimal)> = BTreeMap::new();
+        for (date, high, low) in &history.bars {
+            let ratio = history.split_ratio_after(*date);
+            let (high, low) = (*high * ratio, *low * ratio);
+            ranges
+                .entry(calendar.fiscal_year(*date))
+                .and_modify(|(h, l)| {
+                    *h = (*h).max(high);
+                    *l = (*l).min(low);
+                })
+                .or_insert((high, low));
+        }
+
+        Ok(ranges
+            .into_iter()
+            .filter(|(year, _)| years.contains(year))
+            .map(|(fiscal_year, (high, low))| YearlyPriceRange {
+                fiscal_year,
+                high: high.round_dp(2),
+                low: low.round_dp(2),
+            })
+            .collect())
+    }
+
+    async fn fetch_corporate_actions(&self, ticker: &str) -> Result<Vec<CorporateAction>> {
+        let query = [
+            ("range", "max".to_string()),
+            ("interval", "3mo".to_string()),
+            ("events", "split".to_string()),
+        ];
+        match self.chart(ticker, &query).await? {
+            Some(chart) => Ok(History::from_chart(&chart)?.splits),
+            None => Ok(vec![]),
+        }
+    }
+
+    async fn fetch_daily_prices(&self, ticker: &str, from: NaiveDate) -> Result<Vec<DailyPrice>> {
+        let start = from
+            .and_hms_opt(0, 0, 0)
+            .map_or(0, |d| d.and_utc().timestamp());
+        let query = [
+            ("period1", start.to_string()),
+            ("period2", Utc::now().timestamp().to_string()),
+            ("interval", "1d".to_string()),
+            ("events", "split".to_string()),
+        ];
+        let Some(chart) = self.chart(ticker, &query).await? else {
+            return Ok(vec![]);
+        };
+        let history = History::from_chart(&chart)?;
+        let meta = &chart.chart.result[0].meta;
+        let gmtoffset = i64::from(meta.gmtoffset);
+        let (_, unit) = major_unit(meta.currency.as_deref().unwrap_or_default());
+
+        Ok(chart
+            .quotes()
+            .map_err(|e| Error::string(&e.to_string()))?
+            .into_iter()
+            .filter(|q| q.high > Decimal::ZERO && q.low > Decimal::ZERO)
+            .filter_map(|q| {
+                let date = local_date(q.timestamp, gmtoffset)?;
+                let ratio = history.split_ratio_after(date);
+                Some(DailyPrice {
+                    date,
+                    open: (q.open * ratio / unit).round_dp(4),
+                    high: (q.high * ratio / unit).round_dp(4),
+                    low: (q.low * ratio / unit).round_dp(4),
+                    close: (q.close * ratio / unit).round_dp(4),
+                    adjusted_close: Some((q.adjclose / unit).round_dp(4)),
+                    volume: (Decimal::from(q.volume) / ratio).round().to_i64(),
+                })
+            })
+            .filter(|bar| bar.date >= from)
+            .collect())
+    }
+
+    async fn fetch_quote(&self, ticker: &str) -> Result<Option<MarketQuote>> {
+        let query = [("range", "5d".to_string()), ("interval", "1d".to_string())];
+        let Some(chart) = self.chart(ticker, &query).await? else {
+            return Ok(None);
+        };
+        let (currency, unit) = match chart.chart.result[0].meta.currency.as_deref() {
+            Some(currency) => {
+                let (major, unit) = major_unit(currency);
+                (Some(major), unit)
+            }
+            None => (None, Decimal::ONE),
+        };
+        let last = chart
+            .quotes()
+            .map_err(|e| Error::string(&e.to_string()))?
+            .into_iter()
+            .filter(|q| q.close > Decimal::ZERO)
+            .max_by_key(|q| q.timestamp);
+
+        Ok(last.and_then(|q| {
+            let as_of = DateTime::from_timestamp(i64::try_from(q.timestamp).ok()?, 0)?;
+            Some(MarketQuote {
+                price: (q.close / unit).round_dp(4),
+                currency: currency.clone(),
+                as_of,
+            })
+        }))
+    }
+
+    async fn probe(&self) -> Result<()> {
+        let query = [("range", "1d".to_string()), ("interval", "1d".to_string())];
+        match self.chart(PROBE_SYMBOL, &query).await? {
+            Some(_) => Ok(()),
+            None => Err(Error::string(&format!(
+                "Yahoo returned no chart for {PROBE_SYMBOL}"
+            ))),
+        }
+    }
+}
+
+/// Chart data converted to exchange-local dates and Decimal values.
+#[derive(Debug)]
+struct History {
+    /// Monthly bars as (bar date, split-adjusted high, split-adjusted low), in
+    /// the major currency.
+    bars: Vec<(NaiveDate, Decimal, Decimal)>,
+    /// Dividends as (ex-date, split-adjusted amount), in the major currency.
+    dividends: Vec<(NaiveDate, Decimal)>,
+    splits: Vec<CorporateAction>,
+}
+
+impl History {
+    fn from_chart(chart: &YResponse) -> Result<Self> {
+        let yahoo_err = |e: yahoo_finance_api::YahooError| Error::string(&e.to_string());
+        let meta = &chart.chart.result[0].meta;
+        let gmtoffset = i64::from(meta.gmtoffset);
+        let (_, unit) = major_unit(meta.currency.as_deref().unwrap_or_default());
+
+        let bars = chart
+            .quotes()
+            .map_err(yahoo_err)?
+            .into_iter()
+            .filter(|q| q.high > Decimal::ZERO && q.low > Decimal::ZERO)
+            .filter_map(|q| {
+                let date = local_date(q.timestamp, gmtoffset)?;
+                Some((date, q.high / unit, q.low / unit))
+            })
+            .collect();
+
+        let dividends = chart
+            .dividends()
+            .map_err(yahoo_err)?
+            .into_iter()
+            .filter_map(|d| Some((local_date(d.date, gmtoffset)?, d.amount / unit)))
+            .collect();
+
+        let splits = chart
+            .splits()
+            .map_err(yahoo_err)?
+            .into_iter()
+            .filter(|s| s.numerator > Decimal::ZERO && s.denominator > Decimal::ZERO)
+            .filter_map(|s| {
+                let ratio = s.numerator / s.denominator;
+                Some(CorporateAction {
+                    effective_date: local_date(s.date, gmtoffset)?,
+                    kind: if ratio < Decimal::ONE {
+                        CorporateActionKind::ReverseSplit
+                    } else {
+                        CorporateActionKind::Split
+                    },
+                    ratio,
+                })
+            })
+            .collect();
+
+        Ok(Self {
+            bars,
+            dividends,
+            splits,
+        })
+    }
+
+    /// Cumulative ratio of the splits taking effect after `date`, which turns
+    /// a split-adjusted value on `date` back into the value reported then.
+    fn split_ratio_after(&self, date: NaiveDate) -> Decimal {
+        self.splits
+            .iter()
+            .filter(|s| s.effective_date > date)
+            .fold(Decimal::ONE, |acc, s| acc * s.ratio)
+    }
+}
+
+/// Converts a Yahoo UTC timestamp to the exchange-local calendar date.
+///
+/// Bars are stamped at local midnight, but `gmtoffset` is the exchange's
+/// current offset, which may differ from the bar's by a daylight-saving hour.
+/// Shifting by half a day keeps such timestamps on their own date.
+fn local_date(timestamp: u64, gmtoffset: i64) -> Option<NaiveDate> {
+    let secs = i64::try_from(timestamp).ok()? + gmtoffset + HALF_DAY_SECS;
+    DateTime::from_timestamp(secs, 0).map(|d| d.date_naive())
+}
+
+/// Major currency code for `currency` and the number of its units in one of
+/// the major currency; codes that are not minor units map to themselves.
+fn major_unit(currency: &str) -> (String, Decimal) {
+    MINOR_UNITS
+        .iter()
+        .find(|(minor, _)| *minor == currency)
+        .map_or_else(
+            || (currency.to_string(), Decimal::ONE),
+            |(_, major)| ((*major).to_string(), Decimal::ONE_HUNDRED),
+        )
+}
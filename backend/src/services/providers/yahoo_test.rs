@@ -0,0 +1,363 @@
+#[cfg(test)]
+mod tests {
+    use crate::services::providers::{
+        CorporateActionKind, FinancialDataProvider, FixtureProvider, LayeredProvider, YahooProvider,
+    };
+    use axum::{extract::Path, http::StatusCode, routing::get, Json, Router};
+    use chrono::{TimeZone, Utc};
+    use rust_decimal::Decimal;
+    use serde_json::{json, Value};
+    use std::str::FromStr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use steady_invest_logic::FiscalCalendar;
+
+    const CALENDAR_YEAR: FiscalCalendar = FiscalCalendar::CALENDAR_YEAR;
+
+    /// Zurich winter offset; bars are stamped at local midnight.
+    const GMT_OFFSET: i64 = 3600;
+
+    fn local_midnight(year: i32, month: u32, day: u32) -> i64 {
+        Utc.with_ymd_and_hms(year, month, day, 0, 0, 0)
+            .unwrap()
+            .timestamp()
+            - GMT_OFFSET
+    }
+
+    fn dec(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    /// Chart for a CHF stock with a 2-for-1 split on 2019-10-01. Yahoo reports
+    /// prices and dividends on the post-split basis.
+    fn chart_json() -> Value {
+        let period = json!({"timezone": "CET", "start": 0, "end": 0, "gmtoffset": GMT_OFFSET});
+        let split = local_midnight(2019, 10, 1) + 9 * 3600;
+        let dividends = [
+            (local_midnight(2019, 5, 2), 1.0),
+            (local_midnight(2020, 5, 4), 1.5),
+        ];
+
+        json!({
+            "chart": {
+                "result": [{
+                    "meta": {
+                        "currency": "CHF",
+                        "symbol": "MOCK.SW",
+                        "longName": "Mock Holding AG",
+                        "shortName": "MOCK",
+                        "instrumentType": "EQUITY",
+                        "exchangeName": "EBS",
+                        "fullExchangeName": "SIX Swiss Exchange",
+                        "regularMarketTime": 0,
+                        "gmtoffset": GMT_OFFSET,
+                        "timezone": "CET",
+                        "exchangeTimezoneName": "Europe/Zurich",
+                        "regularMarketPrice": 55.0,
+                        "chartPreviousClose": 54.0,
+                        "hasPrePostMarketData": false,
+                        "fiftyTwoWeekHigh": 60.0,
+                        "fiftyTwoWeekLow": 50.0,
+                        "priceHint": 2,
+                        "currentTradingPeriod": {"pre": period, "regular": period, "post": period},
+                        "dataGranularity": "1mo",
+                        "range": "",
+                        "validRanges": ["max"]
+                    },
+                    "timestamp": [
+                        local_midnight(2019, 1, 1),
+                        local_midnight(2019, 7, 1),
+                        local_midnight(2020, 1, 1),
+                        local_midnight(2020, 3, 1)
+                    ],
+                    "events": {
+                        "splits": {
+                            split.to_string(): {
+                                "date": split, "numerator": 2, "denominator": 1, "splitRatio": "2:1"
+                            }
+                        },
+                        "dividends": {
+                            dividends[0].0.to_string(): {"date": dividends[0].0, "amount": dividends[0].1},
+                            dividends[1].0.to_string(): {"date": dividends[1].0, "amount": dividends[1].1}
+                        }
+                    },
+                    "indicators": {
+                        "quote": [{
+                            "open":   [90.0, 100.0, 55.0, 52.0],
+                            "high":   [100.0, 120.0, 58.0, 60.0],
+                            "low":    [80.0, 90.0, 51.0, 50.0],
+                            "close":  [95.0, 110.0, 56.0, 55.0],
+                            "volume": [1000, 1000, 1000, 1000]
+                        }]
+                    }
+                }],
+                "error": null
+            }
+        })
+    }
+
+    /// Serves the mock chart for `MOCK.SW` and for `NESN.SW`, which the
+    /// bundled fixture also knows, and the same chart quoted in pence for
+    /// `MOCK.L`.
+    async fn chart(Path(ticker): Path<String>) -> (StatusCode, Json<Value>) {
+        if ticker == "MOCK.SW" || ticker == "NESN.SW" {
+            (StatusCode::OK, Json(chart_json()))
+        } else if ticker == "MOCK.L" {
+            let mut chart = chart_json();
+            chart["chart"]["result"][0]["meta"]["currency"] = json!("GBp");
+            (StatusCode::OK, Json(chart))
+        } else {
+            (
+                StatusCode::NOT_FOUND,
+                Json(json!({"chart": {"result": null, "error": {"code": "Not Found"}}})),
+            )
+        }
+    }
+
+    /// Starts a mock Yahoo server and returns its base URL.
+    async fn mock_server() -> String {
+        counting_mock_server().await.0
+    }
+
+    /// Starts a mock Yahoo server and returns its base URL together with the
+    /// number of chart requests it has answered.
+    async fn counting_mock_server() -> (String, Arc<AtomicUsize>) {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        let app = Router::new().route(
+            "/v8/finance/chart/{ticker}",
+            get(move |ticker: Path<String>| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                chart(ticker)
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (format!("http://{addr}"), requests)
+    }
+
+    #[tokio::test]
+    async fn test_yahoo_yearly_price_ranges_are_as_traded() {
+        let provider = YahooProvider::new(mock_server().await);
+
+        let prices = provider
+            .fetch_prices("MOCK.SW", 2019..=2020, CALENDAR_YEAR)
+            .await
+            .unwrap();
+        assert_eq!(prices.len(), 2);
+
+        // 2019 bars precede the split and are restated on the pre-split basis
+        assert_eq!(prices[0].fiscal_year, 2019);
+        assert_eq!(prices[0].high, dec("240"));
+        assert_eq!(prices[0].low, dec("160"));
+        assert_eq!(prices[1].fiscal_year, 2020);
+        assert_eq!(prices[1].high, dec("60"));
+        assert_eq!(prices[1].low, dec("50"));
+
+        let only_2020 = provider
+            .fetch_prices("MOCK.SW", 2020..=2020, CALENDAR_YEAR)
+            .await
+            .unwrap();
+        assert_eq!(only_2020.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_yahoo_buckets_by_fiscal_year() {
+        let provider = YahooProvider::new(mock_server().await);
+        let june = FiscalCalendar::new(6).unwrap();
+
+        // FY2020 runs from July 2019 to June 2020
+        let prices = provider
+            .fetch_prices("MOCK.SW", 2019..=2020, june)
+            .await
+            .unwrap();
+        let ranges: Vec<_> = prices
+            .iter()
+            .map(|p| (p.fiscal_year, p.high, p.low))
+            .collect();
+        assert_eq!(
+            ranges,
+            vec![
+                (2019, dec("200"), dec("160")),
+                (2020, dec("240"), dec("50"))
+            ]
+        );
+
+        let fundamentals = provider
+            .fetch_yearly_fundamentals("MOCK.SW", 2019..=2020, june)
+            .await
+            .unwrap();
+        let dps: Vec<_> = fundamentals
+            .iter()
+            .map(|f| (f.fiscal_year, f.dividend_per_share.unwrap()))
+            .collect();
+        assert_eq!(dps, vec![(2019, dec("2")), (2020, dec("1.5"))]);
+    }
+
+    #[tokio::test]
+    async fn test_yahoo_prices_and_dividends_share_one_chart_request() {
+        let (base_url, requests) = counting_mock_server().await;
+        let provider = YahooProvider::new(base_url);
+
+        // Fetched concurrently, as the harvest does
+        let (prices, fundamentals) = tokio::try_join!(
+            provider.fetch_prices("MOCK.SW", 2019..=2020, CALENDAR_YEAR),
+            provider.fetch_yearly_fundamentals("MOCK.SW", 2019..=2020, CALENDAR_YEAR),
+        )
+        .unwrap();
+        assert_eq!(prices.len(), 2);
+        assert_eq!(fundamentals.len(), 2);
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        // A different window is a different chart
+        provider
+            .fetch_prices("MOCK.SW", 2020..=2020, CALENDAR_YEAR)
+            .await
+            .unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_yahoo_dividends_splits_and_profile() {
+        let provider = YahooProvider::new(mock_server().await);
+
+        let fundamentals = provider
+            .fetch_yearly_fundamentals("MOCK.SW", 2019..=2020, CALENDAR_YEAR)
+            .await
+            .unwrap();
+        let dps: Vec<_> = fundamentals
+            .iter()
+            .map(|f| (f.fiscal_year, f.dividend_per_share.unwrap()))
+            .collect();
+        assert_eq!(dps, vec![(2019, dec("2")), (2020, dec("1.5"))]);
+        assert!(fundamentals.iter().all(|f| f.sales.is_none()));
+
+        let actions = provider.fetch_corporate_actions("MOCK.SW").await.unwrap();
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].kind, CorporateActionKind::Split);
+        assert_eq!(actions[0].ratio, dec("2"));
+        assert_eq!(actions[0].effective_date.to_string(), "2019-10-01");
+
+        let profile = provider.fetch_profile("MOCK.SW").await.unwrap().unwrap();
+        assert_eq!(profile.currency, "CHF");
+        assert_eq!(profile.name, "Mock Holding AG");
+    }
+
+    #[tokio::test]
+    async fn test_yahoo_daily_prices_are_as_traded() {
+        let provider = YahooProvider::new(mock_server().await);
+        let from = chrono::NaiveDate::from_ymd_opt(2019, 6, 1).unwrap();
+
+        let bars = provider.fetch_daily_prices("MOCK.SW", from).await.unwrap();
+        let dates: Vec<String> = bars.iter().map(|b| b.date.to_string()).collect();
+        assert_eq!(dates, vec!["2019-07-01", "2020-01-01", "2020-03-01"]);
+
+        // The pre-split bar is restated on the basis traded at the time
+        assert_eq!(bars[0].open, dec("200"));
+        assert_eq!(bars[0].high, dec("240"));
+        assert_eq!(bars[0].close, dec("220"));
+        assert_eq!(bars[0].volume, Some(500));
+        assert_eq!(bars[1].high, dec("58"));
+        assert_eq!(bars[1].volume, Some(1000));
+    }
+
+    #[tokio::test]
+    async fn test_yahoo_converts_pence_to_pounds() {
+        let provider = YahooProvider::new(mock_server().await);
+
+        let profile = provider.fetch_profile("MOCK.L").await.unwrap().unwrap();
+        assert_eq!(profile.currency, "GBP");
+
+        let prices = provider
+            .fetch_prices("MOCK.L", 2019..=2020, CALENDAR_YEAR)
+            .await
+            .unwrap();
+        assert_eq!(prices[0].high, dec("2.4"));
+        assert_eq!(prices[1].low, dec("0.5"));
+
+        let fundamentals = provider
+            .fetch_yearly_fundamentals("MOCK.L", 2019..=2020, CALENDAR_YEAR)
+            .await
+            .unwrap();
+        assert_eq!(fundamentals[1].dividend_per_share, Some(dec("0.015")));
+
+        let from = chrono::NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        let bars = provider.fetch_daily_prices("MOCK.L", from).await.unwrap();
+        assert_eq!(bars[0].high, dec("0.58"));
+
+        let quote = provider.fetch_quote("MOCK.L").await.unwrap().unwrap();
+        assert_eq!(quote.currency.as_deref(), Some("GBP"));
+        assert_eq!(quote.price, dec("0.55"));
+    }
+
+    #[tokio::test]
+    async fn test_yahoo_unknown_ticker_returns_nothing() {
+        let provider = YahooProvider::new(mock_server().await);
+
+        assert!(provider.fetch_profile("NOPE.SW").await.unwrap().is_none());
+        assert!(provider
+            .fetch_prices("NOPE.SW", 2019..=2020, CALENDAR_YEAR)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_layered_prefers_yahoo_prices_over_fixture() {
+        let layered = LayeredProvider::new(
+            Arc::new(YahooProvider::new(mock_server().await)),
+            Arc::new(FixtureProvider::bundled()),
+        );
+
+        // Both sources know NESN.SW; Yahoo's ranges win where it has them
+        let prices = layered
+            .fetch_prices("NESN.SW", 2016..=2025, CALENDAR_YEAR)
+            .await
+            .unwrap();
+        assert_eq!(prices.len(), 10);
+        let ranges: Vec<_> = prices
+            .iter()
+            .filter(|p| (2018..=2021).contains(&p.fiscal_year))
+            .map(|p| (p.fiscal_year, p.high, p.low))
+            .collect();
+        assert_eq!(
+            ranges,
+            vec![
+                (2018, dec("87.8"), dec("73.76")),
+                (2019, dec("240"), dec("160")),
+                (2020, dec("60"), dec("50")),
+                (2021, dec("130"), dec("96"))
+            ]
+        );
+
+        // Yahoo only carries dividends; the fixture keeps the other fundamentals
+        let fundamentals = layered
+            .fetch_yearly_fundamentals("NESN.SW", 2019..=2020, CALENDAR_YEAR)
+            .await
+            .unwrap();
+        let merged: Vec<_> = fundamentals
+            .iter()
+            .map(|f| (f.fiscal_year, f.sales, f.eps, f.dividend_per_share))
+            .collect();
+        assert_eq!(
+            merged,
+            vec![
+                (2019, Some(dec("92568")), Some(dec("4.3")), Some(dec("2"))),
+                (2020, Some(dec("84343")), Some(dec("4.3")), Some(dec("1.5")))
+            ]
+        );
+
+        // An unreachable primary falls back to the fixture instead of failing
+        let unreachable = LayeredProvider::new(
+            Arc::new(YahooProvider::new("http://127.0.0.1:1")),
+            Arc::new(FixtureProvider::bundled()),
+        );
+        let fundamentals = unreachable
+            .fetch_yearly_fundamentals("NESN.SW", 2016..=2025, CALENDAR_YEAR)
+            .await
+            .unwrap();
+        assert_eq!(fundamentals.len(), 10);
+        assert!(fundamentals.iter().all(|f| f.sales.is_some()));
+    }
+}
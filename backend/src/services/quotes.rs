@@ -0,0 +1,138 @@
+//! Current price service.
+//!
+//! Quotes are stored in the `quotes` table with the time they were observed,
+//! either fetched from the ticker's provider during harvest or entered by an
+//! analyst. The newest quote of a ticker is its current price and is carried
+//! into analysis snapshots as [`steady_invest_logic::Quote`].
+
+use super::providers::FinancialDataProvider;
+use crate::models::quotes;
+use chrono::{DateTime, Utc};
+use loco_rs::prelude::*;
+use rust_decimal::Decimal;
+use steady_invest_logic::{AnalysisSnapshot, Quote};
+
+/// Value of `quotes.source` for prices entered through the API.
+pub const MANUAL_SOURCE: &str = "manual";
+
+/// Returns the newest stored quote for `ticker`.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub async fn latest(db: &DatabaseConnection, ticker: &str) -> Result<Option<Quote>> {
+    Ok(quotes::Entity::latest(db, ticker)
+        .await?
+        .map(|model| model.to_quote()))
+}
+
+/// Stores a quote, skipping it when the same source already recorded a
+/// quote for `ticker` at `as_of`.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails.
+pub async fn record(
+    db: &DatabaseConnection,
+    ticker: &str,
+    price: Decimal,
+    currency: &str,
+    as_of: DateTime<Utc>,
+    source: &str,
+) -> Result<quotes::Model> {
+    let existing = quotes::Entity::find()
+        .filter(quotes::Column::Ticker.eq(ticker))
+        .filter(quotes::Column::AsOf.eq(as_of))
+        .filter(quotes::Column::Source.eq(source))
+        .one(db)
+        .await?;
+    if let Some(existing) = existing {
+        return Ok(existing);
+    }
+
+    let model = quotes::ActiveModel {
+        ticker: ActiveValue::set(ticker.to_string()),
+        price: ActiveValue::set(price),
+        currency: ActiveValue::set(currency.to_string()),
+        as_of: ActiveValue::set(as_of.into()),
+        source: ActiveValue::set(source.to_string()),
+        created_at: ActiveValue::set(Utc::now().into()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    Ok(model)
+}
+
+/// Fetches the current quote from `provider` and stores it.
+///
+/// Quotes without a currency are taken to be in `default_currency`.
+///
+/// # Errors
+///
+/// Returns an error if the provider or a database operation fails.
+pub async fn refresh_from_provider(
+    db: &DatabaseConnection,
+    provider: &dyn FinancialDataProvider,
+    ticker: &str,
+    default_currency: &str,
+) -> Result<Option<quotes::Model>> {
+    let Some(quote) = provider.fetch_quote(ticker).await? else {
+        return Ok(None);
+    };
+    let currency = quote.currency.as_deref().unwrap_or(default_currency);
+    record(
+        db,
+        ticker,
+        quote.price,
+        currency,
+        quote.as_of,
+        provider.name(),
+    )
+    .await
+    .map(Some)
+}
+
+/// Fills `snapshot.current_quote` with the newest stored quote when the
+/// client did not send one.
+///
+/// The quote stays in the trading currency;
+/// [`steady_invest_logic::extract_snapshot_prices`] converts it to the
+/// snapshot's display currency.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub async fn attach_latest(
+    db: &DatabaseConnection,
+    ticker: &str,
+    snapshot: &mut AnalysisSnapshot,
+) -> Result<()> {
+    if snapshot.current_quote.is_none() {
+        snapshot.current_quote = latest(db, ticker).await?;
+    }
+    Ok(())
+}
+
+/// Like [`attach_latest`], for snapshots kept as raw JSON.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails or the quote cannot be
+/// serialized.
+pub async fn attach_latest_json(
+    db: &DatabaseConnection,
+    ticker: &str,
+    snapshot_data: &mut serde_json::Value,
+) -> Result<()> {
+    let Some(object) = snapshot_data.as_object_mut() else {
+        return Ok(());
+    };
+    if object.get("current_quote").is_some_and(|q| !q.is_null()) {
+        return Ok(());
+    }
+    if let Some(quote) = latest(db, ticker).await? {
+        object.insert("current_quote".to_string(), serde_json::to_value(quote)?);
+    }
+    Ok(())
+}
@@ -0,0 +1,242 @@
+//! Per-provider rate limiting.
+//!
+//! Every call to a remote data source takes a token from two buckets: one
+//! refilling its per-minute allowance each minute, one refilling its daily
+//! allowance each day. A call finding either bucket empty waits for the next
+//! token when it is due within the configured maximum wait, and fails
+//! otherwise, so bulk refreshes slow down before a provider starts rejecting
+//! us and give up rather than queue for hours.
+//!
+//! Bucket state is shared by every limiter of a provider in the process and
+//! written to `provider_rate_limits` after each call, so consumption survives
+//! restarts. `quota_consumed` there is the share of the daily allowance in
+//! use, shown on the System Monitor.
+//!
+//! Limits are read from `{PREFIX}_RATE_LIMIT_PER_MINUTE` and
+//! `{PREFIX}_RATE_LIMIT_PER_DAY`, with the prefix of the provider's other
+//! settings (e.g. `YAHOO_FINANCE`); the maximum wait from
+//! `PROVIDER_RATE_LIMIT_MAX_WAIT_SECS`.
+
+use crate::models::provider_rate_limits;
+use chrono::{DateTime, Utc};
+use loco_rs::prelude::*;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Default longest wait for a token (seconds); below the harvest timeout so
+/// a throttled harvest still finishes.
+const DEFAULT_MAX_WAIT_SECS: u64 = 2;
+
+const SECONDS_PER_MINUTE: f64 = 60.0;
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Calls allowed per minute and per day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub per_minute: u32,
+    pub per_day: u32,
+}
+
+impl RateLimit {
+    /// Reads `{prefix}_RATE_LIMIT_PER_MINUTE` and `{prefix}_RATE_LIMIT_PER_DAY`,
+    /// keeping `default` for unset, invalid or zero values.
+    pub fn from_env(prefix: &str, default: Self) -> Self {
+        let read = |suffix: &str| {
+            std::env::var(format!("{prefix}_RATE_LIMIT_{suffix}"))
+                .ok()
+                .and_then(|v| v.trim().parse::<u32>().ok())
+                .filter(|v| *v > 0)
+        };
+        Self {
+            per_minute: read("PER_MINUTE").unwrap_or(default.per_minute),
+            per_day: read("PER_DAY").unwrap_or(default.per_day),
+        }
+    }
+
+    fn minute_capacity(self) -> f64 {
+        f64::from(self.per_minute.max(1))
+    }
+
+    fn day_capacity(self) -> f64 {
+        f64::from(self.per_day.max(1))
+    }
+}
+
+/// Tokens left in a provider's minute and day buckets as of `updated_at`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TokenBucket {
+    pub minute_tokens: f64,
+    pub day_tokens: f64,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TokenBucket {
+    /// Both buckets full.
+    pub fn full(limit: RateLimit, now: DateTime<Utc>) -> Self {
+        Self {
+            minute_tokens: limit.minute_capacity(),
+            day_tokens: limit.day_capacity(),
+            updated_at: now,
+        }
+    }
+
+    /// Adds the tokens accrued since `updated_at`, up to each bucket's size.
+    pub fn refill(&mut self, limit: RateLimit, now: DateTime<Utc>) {
+        let elapsed = (now - self.updated_at).num_milliseconds().max(0) as f64 / 1000.0;
+        self.minute_tokens = (self.minute_tokens
+            + elapsed * limit.minute_capacity() / SECONDS_PER_MINUTE)
+            .min(limit.minute_capacity());
+        self.day_tokens = (self.day_tokens + elapsed * limit.day_capacity() / SECONDS_PER_DAY)
+            .min(limit.day_capacity());
+        self.updated_at = self.updated_at.max(now);
+    }
+
+    /// Takes a token from both buckets, or returns how long until both hold
+    /// one.
+    pub fn try_take(
+        &mut self,
+        limit: RateLimit,
+        now: DateTime<Utc>,
+    ) -> std::result::Result<(), Duration> {
+        self.refill(limit, now);
+        if self.minute_tokens >= 1.0 && self.day_tokens >= 1.0 {
+            self.minute_tokens -= 1.0;
+            self.day_tokens -= 1.0;
+            return Ok(());
+        }
+        let minute_wait =
+            (1.0 - self.minute_tokens).max(0.0) * SECONDS_PER_MINUTE / limit.minute_capacity();
+        let day_wait = (1.0 - self.day_tokens).max(0.0) * SECONDS_PER_DAY / limit.day_capacity();
+        Err(Duration::from_secs_f64(minute_wait.max(day_wait)))
+    }
+
+    /// Share of the daily allowance in use, in percent.
+    pub fn day_used_percent(&self, limit: RateLimit) -> i32 {
+        let used = 1.0 - self.day_tokens / limit.day_capacity();
+        (used * 100.0).round().clamp(0.0, 100.0) as i32
+    }
+}
+
+/// Bucket state per provider, loaded from the database on first use.
+type SharedBucket = Arc<Mutex<Option<TokenBucket>>>;
+
+static BUCKETS: LazyLock<std::sync::Mutex<HashMap<String, SharedBucket>>> =
+    LazyLock::new(Default::default);
+
+fn shared_bucket(name: &str) -> SharedBucket {
+    BUCKETS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .entry(name.to_string())
+        .or_default()
+        .clone()
+}
+
+fn max_wait_from_env() -> Duration {
+    let secs = std::env::var("PROVIDER_RATE_LIMIT_MAX_WAIT_SECS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_MAX_WAIT_SECS);
+    Duration::from_secs(secs)
+}
+
+/// Rate limiter for the calls to one provider.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    name: String,
+    limit: RateLimit,
+    max_wait: Duration,
+    db: DatabaseConnection,
+}
+
+impl RateLimiter {
+    /// Creates a limiter counting calls under `name`, waiting at most
+    /// `PROVIDER_RATE_LIMIT_MAX_WAIT_SECS` (default 2 seconds) for a token.
+    pub fn new(db: &DatabaseConnection, name: &str, limit: RateLimit) -> Self {
+        Self {
+            name: name.to_string(),
+            limit,
+            max_wait: max_wait_from_env(),
+            db: db.clone(),
+        }
+    }
+
+    /// Replaces the longest wait for a token.
+    #[must_use]
+    pub fn with_max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = max_wait;
+        self
+    }
+
+    /// Name the calls are counted under in `provider_rate_limits`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Takes a token for one call, waiting for it if it is due within the
+    /// maximum wait. Waiting callers are served in order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no token is due within the maximum wait, or if the
+    /// stored bucket cannot be read.
+    pub async fn acquire(&self) -> Result<()> {
+        let shared = shared_bucket(&self.name);
+        let mut state = shared.lock().await;
+        let mut bucket = match *state {
+            Some(bucket) => bucket,
+            None => self.load().await?,
+        };
+
+        loop {
+            match bucket.try_take(self.limit, Utc::now()) {
+                Ok(()) => break,
+                Err(wait) if wait <= self.max_wait => tokio::time::sleep(wait).await,
+                Err(wait) => {
+                    *state = Some(bucket);
+                    tracing::warn!(provider = %self.name, wait_secs = wait.as_secs(), "Rate limit reached");
+                    return Err(Error::string(&format!(
+                        "Rate limit of provider '{}' reached; next call allowed in {}s",
+                        self.name,
+                        wait.as_secs().max(1)
+                    )));
+                }
+            }
+        }
+
+        *state = Some(bucket);
+        if let Err(e) = provider_rate_limits::Model::save_bucket(
+            &self.db,
+            &self.name,
+            bucket.minute_tokens,
+            bucket.day_tokens,
+            bucket.day_used_percent(self.limit),
+            bucket.updated_at.naive_utc(),
+        )
+        .await
+        {
+            tracing::warn!(provider = %self.name, error = %e, "Failed to persist rate limit state");
+        }
+        Ok(())
+    }
+
+    /// The stored bucket, or a full one if none is stored yet.
+    async fn load(&self) -> Result<TokenBucket> {
+        let stored = provider_rate_limits::Model::find_by_name(&self.db, &self.name).await?;
+        Ok(match stored {
+            Some(provider_rate_limits::Model {
+                minute_tokens: Some(minute_tokens),
+                day_tokens: Some(day_tokens),
+                last_updated,
+                ..
+            }) => TokenBucket {
+                minute_tokens: minute_tokens.min(self.limit.minute_capacity()),
+                day_tokens: day_tokens.min(self.limit.day_capacity()),
+                updated_at: last_updated.and_utc(),
+            },
+            _ => TokenBucket::full(self.limit, Utc::now()),
+        })
+    }
+}
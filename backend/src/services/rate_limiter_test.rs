@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use crate::services::rate_limiter::{RateLimit, TokenBucket};
+    use chrono::{Duration, Utc};
+
+    const LIMIT: RateLimit = RateLimit {
+        per_minute: 2,
+        per_day: 3,
+    };
+
+    #[test]
+    fn test_minute_bucket_refills_over_the_minute() {
+        let start = Utc::now();
+        let mut bucket = TokenBucket::full(LIMIT, start);
+
+        assert!(bucket.try_take(LIMIT, start).is_ok());
+        assert!(bucket.try_take(LIMIT, start).is_ok());
+        // One token accrues every 30 seconds
+        let wait = bucket.try_take(LIMIT, start).unwrap_err();
+        assert_eq!(wait.as_secs(), 30);
+
+        assert!(bucket
+            .try_take(LIMIT, start + Duration::seconds(30))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_day_bucket_limits_after_minute_refills() {
+        let start = Utc::now();
+        let mut bucket = TokenBucket::full(LIMIT, start);
+        for minute in 0..3 {
+            assert!(bucket
+                .try_take(LIMIT, start + Duration::minutes(minute))
+                .is_ok());
+        }
+        assert_eq!(bucket.day_used_percent(LIMIT), 100);
+
+        // The minute bucket is full again, but the day bucket only gains a
+        // token every eight hours
+        let wait = bucket
+            .try_take(LIMIT, start + Duration::minutes(10))
+            .unwrap_err();
+        assert!(wait.as_secs() > 7 * 3_600, "waits {wait:?}");
+        assert!(bucket.try_take(LIMIT, start + Duration::hours(9)).is_ok());
+    }
+
+    #[test]
+    fn test_refill_caps_at_capacity_and_ignores_clock_going_back() {
+        let start = Utc::now();
+        let mut bucket = TokenBucket::full(LIMIT, start);
+        assert!(bucket.try_take(LIMIT, start).is_ok());
+
+        bucket.refill(LIMIT, start - Duration::minutes(5));
+        assert!((bucket.minute_tokens - 1.0).abs() < 1e-9);
+        assert_eq!(bucket.updated_at, start);
+
+        bucket.refill(LIMIT, start + Duration::days(2));
+        assert!((bucket.minute_tokens - 2.0).abs() < 1e-9);
+        assert!((bucket.day_tokens - 3.0).abs() < 1e-9);
+        assert_eq!(bucket.day_used_percent(LIMIT), 0);
+    }
+
+    #[test]
+    fn test_rate_limit_from_env_keeps_defaults_for_invalid_values() {
+        std::env::set_var("RATE_LIMITER_TEST_RATE_LIMIT_PER_MINUTE", "0");
+        std::env::set_var("RATE_LIMITER_TEST_RATE_LIMIT_PER_DAY", "500");
+
+        let limit = RateLimit::from_env("RATE_LIMITER_TEST", LIMIT);
+        assert_eq!(limit.per_minute, 2);
+        assert_eq!(limit.per_day, 500);
+
+        std::env::remove_var("RATE_LIMITER_TEST_RATE_LIMIT_PER_MINUTE");
+        std::env::remove_var("RATE_LIMITER_TEST_RATE_LIMIT_PER_DAY");
+    }
+}
@@ -0,0 +1,227 @@
+//! Bulk refresh of every tracked ticker.
+//!
+//! A ticker is tracked when it has a live analysis snapshot or belongs to a
+//! comparison set (through one of its snapshots, deleted or not). Each tracked
+//! ticker is re-harvested in [`HarvestMode::Refresh`], so restated values are
+//! picked up and audited. Watchlists are not tracked: they are planned for
+//! the portfolio phase and have no table yet.
+//!
+//! Runs are bounded twice: at most `concurrency` harvests are in flight, and
+//! each provider serves at most `provider_budget` tickers per run. Tickers
+//! over budget are skipped and reported, then refreshed by a later run.
+//! Within a run, every call to a remote provider is further paced by that
+//! provider's [`RateLimiter`](super::rate_limiter::RateLimiter). The run's
+//! summary is written to the audit log as a `RefreshSummary` event.
+
+use super::audit_service::AuditService;
+use super::harvest::{self, HarvestMode};
+use super::providers::ProviderRegistry;
+use crate::models::{analysis_snapshots, comparison_set_items, tickers};
+use loco_rs::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// Default number of concurrent harvests.
+const DEFAULT_CONCURRENCY: usize = 4;
+/// Default number of tickers each provider serves per run.
+const DEFAULT_PROVIDER_BUDGET: usize = 100;
+
+/// Limits applied to a refresh run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RefreshOptions {
+    /// Maximum harvests running at the same time.
+    pub concurrency: usize,
+    /// Maximum tickers harvested per provider.
+    pub provider_budget: usize,
+}
+
+impl Default for RefreshOptions {
+    fn default() -> Self {
+        Self {
+            concurrency: DEFAULT_CONCURRENCY,
+            provider_budget: DEFAULT_PROVIDER_BUDGET,
+        }
+    }
+}
+
+impl RefreshOptions {
+    /// Reads `REFRESH_CONCURRENCY` and `REFRESH_PROVIDER_BUDGET`, keeping the
+    /// defaults for unset or invalid values.
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            concurrency: positive_from_env("REFRESH_CONCURRENCY").unwrap_or(defaults.concurrency),
+            provider_budget: positive_from_env("REFRESH_PROVIDER_BUDGET")
+                .unwrap_or(defaults.provider_budget),
+        }
+    }
+}
+
+fn positive_from_env(var: &str) -> Option<usize> {
+    std::env::var(var)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .filter(|v| *v > 0)
+}
+
+/// A ticker whose refresh failed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RefreshFailure {
+    pub ticker: String,
+    pub error: String,
+}
+
+/// Outcome of a refresh run.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RefreshReport {
+    /// Tickers harvested successfully.
+    pub refreshed: Vec<String>,
+    /// Tickers whose harvest failed.
+    pub failed: Vec<RefreshFailure>,
+    /// Tickers not harvested because their provider's budget was spent.
+    pub skipped: Vec<String>,
+    /// Fields restated across all refreshed tickers.
+    pub restated_fields: usize,
+}
+
+impl RefreshReport {
+    /// One-line summary stored in the audit log.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} refreshed, {} failed, {} skipped, {} fields restated",
+            self.refreshed.len(),
+            self.failed.len(),
+            self.skipped.len(),
+            self.restated_fields
+        )
+    }
+}
+
+/// Returns the tracked tickers, ordered by symbol.
+///
+/// Only tickers with a live snapshot or in a comparison set are returned;
+/// there are no watchlist entries to include yet.
+///
+/// # Errors
+///
+/// Returns an error if a database query fails.
+pub async fn tracked_tickers(db: &DatabaseConnection) -> Result<Vec<tickers::Model>> {
+    let mut ticker_ids: BTreeSet<i32> = analysis_snapshots::Entity::find()
+        .filter(analysis_snapshots::Column::DeletedAt.is_null())
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|s| s.ticker_id)
+        .collect();
+
+    let compared: Vec<i32> = comparison_set_items::Entity::find()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|i| i.analysis_snapshot_id)
+        .collect();
+    if !compared.is_empty() {
+        let snapshots = analysis_snapshots::Entity::find()
+            .filter(analysis_snapshots::Column::Id.is_in(compared))
+            .all(db)
+            .await?;
+        ticker_ids.extend(snapshots.into_iter().map(|s| s.ticker_id));
+    }
+
+    if ticker_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut tracked = tickers::Entity::find()
+        .filter(tickers::Column::Id.is_in(ticker_ids))
+        .all(db)
+        .await?;
+    tracked.sort_by(|a, b| a.ticker.cmp(&b.ticker));
+    Ok(tracked)
+}
+
+/// Refreshes every tracked ticker and audits the run's summary.
+///
+/// Failures of individual tickers are collected in the report rather than
+/// aborting the run.
+///
+/// # Errors
+///
+/// Returns an error if loading the tracked tickers or writing the audit
+/// summary fails.
+pub async fn refresh_all(
+    ctx: &AppContext,
+    registry: &ProviderRegistry,
+    options: RefreshOptions,
+) -> Result<RefreshReport> {
+    let mut report = RefreshReport::default();
+
+    let mut spent: HashMap<String, usize> = HashMap::new();
+    let mut planned = Vec::new();
+    for ticker in tracked_tickers(&ctx.db).await? {
+        let provider = registry.provider_for(&ticker.exchange);
+        let used = spent.entry(provider.name().to_string()).or_default();
+        if *used >= options.provider_budget {
+            report.skipped.push(ticker.ticker);
+            continue;
+        }
+        *used += 1;
+        planned.push(ticker.ticker);
+    }
+
+    let permits = Arc::new(Semaphore::new(options.concurrency.max(1)));
+    let registry = Arc::new(registry.clone());
+    let mut running = JoinSet::new();
+    // Task ids name the ticker of a task that panicked
+    let mut task_tickers = HashMap::new();
+    for ticker in planned {
+        let permits = permits.clone();
+        let registry = registry.clone();
+        let ctx = ctx.clone();
+        let task_ticker = ticker.clone();
+        let task = running.spawn(async move {
+            let _permit = permits.acquire_owned().await;
+            let outcome = harvest::harvest(
+                &ctx,
+                &ticker,
+                &registry,
+                HarvestMode::Refresh,
+                harvest::DEFAULT_DISPLAY_CURRENCY,
+            )
+            .await;
+            (ticker, outcome)
+        });
+        task_tickers.insert(task.id(), task_ticker);
+    }
+
+    while let Some(joined) = running.join_next().await {
+        match joined {
+            Ok((ticker, Ok(outcome))) => {
+                report.restated_fields += outcome.summary.changes.len();
+                report.refreshed.push(ticker);
+            }
+            Ok((ticker, Err(e))) => {
+                tracing::warn!(ticker, error = %e, "Refresh failed");
+                report.failed.push(RefreshFailure {
+                    ticker,
+                    error: e.to_string(),
+                });
+            }
+            Err(e) => {
+                let ticker = task_tickers.remove(&e.id()).unwrap_or_default();
+                tracing::error!(ticker, error = %e, "Refresh task panicked");
+                report.failed.push(RefreshFailure {
+                    ticker,
+                    error: format!("Refresh task failed: {e}"),
+                });
+            }
+        }
+    }
+    report.refreshed.sort();
+    report.failed.sort_by(|a, b| a.ticker.cmp(&b.ticker));
+
+    AuditService::log_refresh_summary(&ctx.db, &report.summary()).await?;
+    Ok(report)
+}
@@ -16,7 +16,9 @@ use genpdf::{elements, fonts, style};
 use rust_decimal::prelude::ToPrimitive;
 use std::io::Cursor;
 use steady_invest_logic::{
-    calculate_growth_analysis, calculate_projected_trendline, AnalysisSnapshot,
+    calculate_analysis_summary, calculate_growth_analysis, calculate_projected_trendline,
+    calculate_quality_analysis, calculate_valuation_zones, evaluate_naic_tests, AnalysisSnapshot,
+    NaicTestOutcome,
 };
 
 /// Alias for fallible report operations.
@@ -186,6 +188,19 @@ impl ReportingService {
             "Estimated Average Low P/E: {:.1}",
             snapshot.projected_low_pe
         )));
+        for line in Self::valuation_zone_lines(snapshot) {
+            doc.push(elements::Text::new(line));
+        }
+
+        // Guided Analysis Summary
+        doc.push(elements::Break::new(1.5));
+        doc.push(elements::StyledElement::new(
+            elements::Text::new("Guided Analysis Summary"),
+            style::Style::new().bold().with_font_size(14),
+        ));
+        for line in Self::analysis_summary_lines(snapshot) {
+            doc.push(elements::Paragraph::new(line));
+        }
 
         let mut buffer = Vec::new();
         doc.render(&mut buffer)
@@ -194,6 +209,96 @@ impl ReportingService {
         Ok(buffer)
     }
 
+    /// Report lines for the five historical P/E tiers (Section 3) and the
+    /// Buy/Maybe/Sell price zones (Section 4C). Either block is omitted when
+    /// the snapshot lacks the data for it.
+    pub(crate) fn valuation_zone_lines(snapshot: &AnalysisSnapshot) -> Vec<String> {
+        let zones = calculate_valuation_zones(snapshot);
+        let mut lines = Vec::new();
+
+        if let Some(pe) = zones.pe_breakdown {
+            lines.push(format!(
+                "Historical P/E: Highest {:.1} | Average High {:.1} | Average {:.1} | Average Low {:.1} | Lowest {:.1}",
+                pe.highest, pe.average_high, pe.average, pe.average_low, pe.lowest
+            ));
+        }
+        if let Some(price_zones) = zones.price_zones {
+            let currency = &price_zones.currency;
+            lines.push(format!(
+                "Forecast Price Range: {currency} {:.2} to {currency} {:.2}",
+                price_zones.forecast_low, price_zones.forecast_high
+            ));
+            lines.push(format!(
+                "Forecast Low Price Source: {}",
+                snapshot.low_price_method.label()
+            ));
+            lines.push(format!(
+                "Buy Zone: {currency} {:.2} to {currency} {:.2}",
+                price_zones.forecast_low, price_zones.buy_below
+            ));
+            lines.push(format!(
+                "Maybe Zone: {currency} {:.2} to {currency} {:.2}",
+                price_zones.buy_below, price_zones.sell_above
+            ));
+            lines.push(format!(
+                "Sell Zone: {currency} {:.2} to {currency} {:.2}",
+                price_zones.sell_above, price_zones.forecast_high
+            ));
+        }
+        if let Some(zone) = zones.current_zone {
+            lines.push(format!("Current Price Zone: {}", zone.label()));
+        }
+        lines
+    }
+
+    /// Report lines for the guided Stock Checklist conclusions: the company
+    /// size, then one line per question with its verdict and explanation.
+    pub(crate) fn analysis_summary_lines(snapshot: &AnalysisSnapshot) -> Vec<String> {
+        let hist = &snapshot.historical_data;
+        let years: Vec<i32> = hist.records.iter().map(|r| r.fiscal_year).collect();
+        let sales: Vec<f64> = hist
+            .records
+            .iter()
+            .map(|r| r.sales.to_f64().unwrap_or(0.0))
+            .collect();
+        let eps: Vec<f64> = hist
+            .records
+            .iter()
+            .map(|r| r.eps.to_f64().unwrap_or(0.0))
+            .collect();
+        let naic_tests = evaluate_naic_tests(
+            &calculate_quality_analysis(hist),
+            &calculate_growth_analysis(&years, &sales),
+            &calculate_growth_analysis(&years, &eps),
+            snapshot,
+        );
+        let summary = calculate_analysis_summary(snapshot, &naic_tests);
+
+        let mut lines = Vec::new();
+        if let (Some(size), Some(latest_sales)) = (summary.company_size, summary.latest_sales) {
+            lines.push(format!(
+                "Company Size: {} (latest sales {:.0} million; expected growth {})",
+                size.label(),
+                latest_sales,
+                size.growth_range_text()
+            ));
+        }
+        for conclusion in summary.conclusions {
+            let verdict = match conclusion.outcome {
+                NaicTestOutcome::Pass => "Yes",
+                NaicTestOutcome::Fail => "No",
+                NaicTestOutcome::Inconclusive => "Undetermined",
+            };
+            lines.push(format!(
+                "{} {}. {}",
+                conclusion.question.label(),
+                verdict,
+                conclusion.explanation
+            ));
+        }
+        lines
+    }
+
     /// Builds an ECharts `Chart` matching the frontend SSG chart (NAIC Figure 2.1).
     ///
     /// Includes: Sales/EPS/PTP data + trendlines + projections + price candlestick bars.
@@ -3,7 +3,9 @@ mod tests {
     use crate::services::reporting::ReportingService;
     use chrono::Utc;
     use rust_decimal::Decimal;
-    use steady_invest_logic::{AnalysisSnapshot, HistoricalData, HistoricalYearlyData};
+    use steady_invest_logic::{
+        AnalysisSnapshot, HistoricalData, HistoricalYearlyData, LowPriceMethod,
+    };
 
     #[tokio::test]
     async fn test_generate_ssg_report_bytes() {
@@ -37,6 +39,9 @@ mod tests {
             projected_low_pe: 10.0,
             analyst_note: "Great long term value.".to_string(),
             captured_at: Utc::now(),
+            current_quote: None,
+            naic_test_overrides: Vec::new(),
+            low_price_method: LowPriceMethod::default(),
         };
 
         let result = ReportingService::generate_ssg_report(
@@ -59,4 +64,85 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_valuation_zone_lines() {
+        let hist = HistoricalData {
+            currency: "USD".to_string(),
+            records: vec![HistoricalYearlyData {
+                fiscal_year: 2023,
+                eps: Decimal::from(5),
+                price_high: Decimal::from(100),
+                price_low: Decimal::from(50),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let snapshot = AnalysisSnapshot {
+            historical_data: hist,
+            projected_high_pe: 20.0,
+            projected_low_pe: 8.0,
+            ..Default::default()
+        };
+
+        let lines = ReportingService::valuation_zone_lines(&snapshot);
+        assert_eq!(
+            lines,
+            vec![
+                "Historical P/E: Highest 20.0 | Average High 20.0 | Average 15.0 | Average Low 10.0 | Lowest 10.0",
+                "Forecast Price Range: USD 40.00 to USD 100.00",
+                "Forecast Low Price Source: Estimated low P/E × 5-year EPS",
+                "Buy Zone: USD 40.00 to USD 60.00",
+                "Maybe Zone: USD 60.00 to USD 80.00",
+                "Sell Zone: USD 80.00 to USD 100.00",
+                "Current Price Zone: Sell",
+            ]
+        );
+
+        let empty = ReportingService::valuation_zone_lines(&AnalysisSnapshot::default());
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_analysis_summary_lines() {
+        let hist = HistoricalData {
+            records: (0..5)
+                .map(|i| HistoricalYearlyData {
+                    fiscal_year: 2019 + i,
+                    sales: Decimal::from(5_000 + 500 * i),
+                    eps: Decimal::from(2 + i),
+                    price_high: Decimal::from(40),
+                    price_low: Decimal::from(20),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let snapshot = AnalysisSnapshot {
+            historical_data: hist,
+            projected_sales_cagr: 8.0,
+            projected_eps_cagr: 9.0,
+            projected_high_pe: 20.0,
+            projected_low_pe: 10.0,
+            ..Default::default()
+        };
+
+        let lines = ReportingService::analysis_summary_lines(&snapshot);
+        assert_eq!(lines.len(), 5);
+        assert_eq!(
+            lines[0],
+            "Company Size: Medium (latest sales 7000 million; expected growth 7–12%)"
+        );
+        assert!(
+            lines[1].starts_with("Is sales growth adequate? Yes."),
+            "{}",
+            lines[1]
+        );
+        assert!(lines[3].starts_with("Is future growth likely to be adequate? Yes."));
+        assert!(lines[4].starts_with("Is the price acceptable?"));
+
+        let empty = ReportingService::analysis_summary_lines(&AnalysisSnapshot::default());
+        assert_eq!(empty.len(), 4);
+        assert!(empty.iter().all(|l| l.contains("Undetermined")));
+    }
 }
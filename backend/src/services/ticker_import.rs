@@ -0,0 +1,462 @@
+//! Ticker universe import service.
+//!
+//! Loads index constituent files (SMI, SPI, DAX, MDAX, S&P 500) from CSV into
+//! `tickers`, together with each security's ISIN, Swiss valor number and
+//! German WKN. Rows are upserted by ticker: existing tickers get their name,
+//! listing and identifiers refreshed, new ones are inserted.
+//!
+//! Constituent files vary by source, so only the columns this service knows
+//! are read and any others (weights, sectors, ...) are ignored. When the file
+//! is imported for an [`IndexUniverse`], its exchange and currency fill in
+//! blank cells and bare symbols get the listing suffix the providers expect
+//! (`NESN` becomes `NESN.SW`).
+//!
+//! Like historical table uploads the file is all-or-nothing: if any row fails
+//! validation nothing is written and the caller receives a row-level error
+//! report instead.
+
+use super::historicals_import::RowError;
+use super::ticker_search;
+use crate::models::tickers;
+use loco_rs::prelude::*;
+use sea_orm::TransactionTrait;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Index whose constituent files can be imported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexUniverse {
+    Smi,
+    Spi,
+    Dax,
+    Mdax,
+    Sp500,
+}
+
+impl IndexUniverse {
+    /// Parses an index name such as `SMI`, `mdax` or `S&P 500`.
+    pub fn parse(name: &str) -> Option<Self> {
+        let normalized: String = name
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_uppercase();
+        match normalized.as_str() {
+            "SMI" => Some(Self::Smi),
+            "SPI" => Some(Self::Spi),
+            "DAX" => Some(Self::Dax),
+            "MDAX" => Some(Self::Mdax),
+            "SP500" | "SPX" => Some(Self::Sp500),
+            _ => None,
+        }
+    }
+
+    /// Display name of the index.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Smi => "SMI",
+            Self::Spi => "SPI",
+            Self::Dax => "DAX",
+            Self::Mdax => "MDAX",
+            Self::Sp500 => "S&P 500",
+        }
+    }
+
+    /// Exchange stored for constituents whose row leaves it blank.
+    ///
+    /// S&P 500 members trade on both NYSE and NASDAQ; files that know the
+    /// listing should carry an `exchange` column.
+    pub fn exchange(self) -> &'static str {
+        match self {
+            Self::Smi => "SMI",
+            Self::Spi => "SPI",
+            Self::Dax => "DAX",
+            Self::Mdax => "MDAX",
+            Self::Sp500 => "NYSE",
+        }
+    }
+
+    /// Trading currency of the index constituents.
+    pub fn currency(self) -> &'static str {
+        match self {
+            Self::Smi | Self::Spi => "CHF",
+            Self::Dax | Self::Mdax => "EUR",
+            Self::Sp500 => "USD",
+        }
+    }
+
+    /// Listing suffix appended to bare symbols, if the index has one.
+    fn ticker_suffix(self) -> Option<&'static str> {
+        match self {
+            Self::Smi | Self::Spi => Some(".SW"),
+            Self::Dax | Self::Mdax => Some(".DE"),
+            Self::Sp500 => None,
+        }
+    }
+}
+
+/// A validated row of a constituent file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedTicker {
+    pub ticker: String,
+    pub name: String,
+    pub exchange: String,
+    pub currency: String,
+    pub isin: Option<String>,
+    pub valor: Option<String>,
+    pub wkn: Option<String>,
+    pub fiscal_year_end_month: Option<i16>,
+}
+
+/// Outcome of a ticker import.
+#[derive(Debug, Serialize)]
+pub struct TickerImportReport {
+    /// Index the file was imported for, if any.
+    pub index: Option<String>,
+    pub inserted: usize,
+    pub updated: usize,
+    pub errors: Vec<RowError>,
+}
+
+/// Normalizes a security identifier: surrounding and inner whitespace is
+/// removed and letters are upper-cased.
+pub fn normalize_identifier(raw: &str) -> String {
+    raw.chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_uppercase()
+}
+
+/// Checks an ISIN's layout (country code, nine-character national number)
+/// and its Luhn check digit.
+pub fn is_valid_isin(isin: &str) -> bool {
+    let bytes = isin.as_bytes();
+    if bytes.len() != 12
+        || !bytes[..2].iter().all(u8::is_ascii_uppercase)
+        || !bytes[2..11]
+            .iter()
+            .all(|b| b.is_ascii_digit() || b.is_ascii_uppercase())
+        || !bytes[11].is_ascii_digit()
+    {
+        return false;
+    }
+
+    // Letters expand to two digits (A = 10 ... Z = 35) before the Luhn check
+    let digits: Vec<u32> = bytes
+        .iter()
+        .flat_map(|&b| {
+            let value = if b.is_ascii_digit() {
+                u32::from(b - b'0')
+            } else {
+                u32::from(b - b'A') + 10
+            };
+            if value >= 10 {
+                vec![value / 10, value % 10]
+            } else {
+                vec![value]
+            }
+        })
+        .collect();
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| {
+            if i % 2 == 1 {
+                let doubled = d * 2;
+                doubled / 10 + doubled % 10
+            } else {
+                d
+            }
+        })
+        .sum();
+    sum % 10 == 0
+}
+
+/// Checks a Swiss valor number: one to nine digits.
+pub fn is_valid_valor(valor: &str) -> bool {
+    (1..=9).contains(&valor.len()) && valor.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Checks a German WKN: six letters or digits.
+pub fn is_valid_wkn(wkn: &str) -> bool {
+    wkn.len() == 6
+        && wkn
+            .bytes()
+            .all(|b| b.is_ascii_digit() || b.is_ascii_uppercase())
+}
+
+/// Parses and validates a constituent CSV file.
+///
+/// `ticker` and `name` columns are required; `exchange` and `currency` are
+/// required unless an `index` supplies them. `isin`, `valor`, `wkn` and
+/// `fiscal_year_end_month` are optional.
+///
+/// # Errors
+///
+/// Returns every row-level problem found when the file is not valid.
+pub fn parse_csv(
+    bytes: &[u8],
+    index: Option<IndexUniverse>,
+) -> std::result::Result<Vec<ImportedTicker>, Vec<RowError>> {
+    let file_error = |message: String| {
+        vec![RowError {
+            row: 1,
+            column: None,
+            message,
+        }]
+    };
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(bytes);
+
+    let mut errors = Vec::new();
+    let mut columns: HashMap<&'static str, usize> = HashMap::new();
+    let header = reader
+        .headers()
+        .map_err(|e| file_error(format!("Unreadable CSV header: {e}")))?
+        .clone();
+    for (position, name) in header.iter().enumerate() {
+        let column = match name.to_lowercase().replace([' ', '-'], "_").as_str() {
+            "ticker" | "symbol" => "ticker",
+            "name" | "company" | "company_name" => "name",
+            "exchange" => "exchange",
+            "currency" => "currency",
+            "isin" => "isin",
+            "valor" | "valoren" | "valor_number" => "valor",
+            "wkn" => "wkn",
+            "fiscal_year_end_month" => "fiscal_year_end_month",
+            _ => continue,
+        };
+        if columns.insert(column, position).is_some() {
+            errors.push(header_error(column, "Duplicate column"));
+        }
+    }
+    let mut required = vec!["ticker", "name"];
+    if index.is_none() {
+        required.extend(["exchange", "currency"]);
+    }
+    for column in required {
+        if !columns.contains_key(column) {
+            errors.push(header_error(column, "Missing required column"));
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let mut imported = Vec::new();
+    let mut seen_tickers = HashSet::new();
+    let mut seen_isins = HashSet::new();
+    for (i, record) in reader.records().enumerate() {
+        let row = i + 2;
+        let record = record.map_err(|e| file_error(format!("Unreadable CSV row {row}: {e}")))?;
+        if record.iter().all(str::is_empty) {
+            continue;
+        }
+        let cell = |column: &str| {
+            columns
+                .get(column)
+                .and_then(|i| record.get(*i))
+                .filter(|v| !v.is_empty())
+        };
+        let mut error = |column: &str, message: String| {
+            errors.push(RowError {
+                row,
+                column: Some(column.to_string()),
+                message,
+            });
+        };
+
+        let ticker = match cell("ticker") {
+            None => {
+                error("ticker", "Value is required".to_string());
+                None
+            }
+            Some(raw) => {
+                let mut ticker = raw.to_uppercase();
+                if let Some(suffix) = index.and_then(IndexUniverse::ticker_suffix) {
+                    if !ticker.contains('.') {
+                        ticker.push_str(suffix);
+                    }
+                }
+                if seen_tickers.insert(ticker.clone()) {
+                    Some(ticker)
+                } else {
+                    error("ticker", format!("Ticker {ticker} appears more than once"));
+                    None
+                }
+            }
+        };
+        let name = cell("name").map(str::to_string);
+        if name.is_none() {
+            error("name", "Value is required".to_string());
+        }
+        let exchange = match cell("exchange") {
+            Some(raw) => Some(raw.to_uppercase()),
+            None => {
+                if index.is_none() {
+                    error("exchange", "Value is required".to_string());
+                }
+                index.map(|i| i.exchange().to_string())
+            }
+        };
+        let currency = match cell("currency").map(str::to_uppercase) {
+            Some(c) if c.len() == 3 && c.bytes().all(|b| b.is_ascii_uppercase()) => Some(c),
+            Some(c) => {
+                error(
+                    "currency",
+                    format!("'{c}' is not a three-letter currency code"),
+                );
+                None
+            }
+            None => {
+                if index.is_none() {
+                    error("currency", "Value is required".to_string());
+                }
+                index.map(|i| i.currency().to_string())
+            }
+        };
+
+        let isin = cell("isin").map(normalize_identifier);
+        if let Some(isin) = &isin {
+            if !is_valid_isin(isin) {
+                error("isin", format!("'{isin}' is not a valid ISIN"));
+            } else if !seen_isins.insert(isin.clone()) {
+                error("isin", format!("ISIN {isin} appears more than once"));
+            }
+        }
+        let valor = cell("valor").map(normalize_identifier);
+        if let Some(valor) = valor.as_deref().filter(|v| !is_valid_valor(v)) {
+            error("valor", format!("'{valor}' is not a valid valor number"));
+        }
+        let wkn = cell("wkn").map(normalize_identifier);
+        if let Some(wkn) = wkn.as_deref().filter(|w| !is_valid_wkn(w)) {
+            error("wkn", format!("'{wkn}' is not a valid WKN"));
+        }
+        let fiscal_year_end_month = match cell("fiscal_year_end_month") {
+            None => None,
+            Some(raw) => match raw.parse::<i16>() {
+                Ok(month) if (1..=12).contains(&month) => Some(month),
+                _ => {
+                    error(
+                        "fiscal_year_end_month",
+                        format!("'{raw}' is not a month between 1 and 12"),
+                    );
+                    None
+                }
+            },
+        };
+
+        if let (Some(ticker), Some(name), Some(exchange), Some(currency)) =
+            (ticker, name, exchange, currency)
+        {
+            imported.push(ImportedTicker {
+                ticker,
+                name,
+                exchange,
+                currency,
+                isin,
+                valor,
+                wkn,
+                fiscal_year_end_month,
+            });
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(imported)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Parses, validates and upserts a constituent file into `tickers`.
+///
+/// Identifiers and fiscal year ends left blank in the file keep their stored
+/// values.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails. Validation problems are
+/// reported in the returned [`TickerImportReport`], not as errors.
+pub async fn import_tickers(
+    db: &DatabaseConnection,
+    index: Option<IndexUniverse>,
+    bytes: &[u8],
+) -> Result<TickerImportReport> {
+    let index_name = index.map(|i| i.name().to_string());
+    let rows = match parse_csv(bytes, index) {
+        Ok(rows) => rows,
+        Err(errors) => {
+            return Ok(TickerImportReport {
+                index: index_name,
+                inserted: 0,
+                updated: 0,
+                errors,
+            })
+        }
+    };
+
+    let existing: HashMap<String, tickers::Model> = tickers::Entity::find()
+        .filter(tickers::Column::Ticker.is_in(rows.iter().map(|r| r.ticker.clone())))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|t| (t.ticker.clone(), t))
+        .collect();
+
+    let txn = db.begin().await?;
+    let (mut inserted, mut updated) = (0, 0);
+    for row in rows {
+        let mut active = match existing.get(&row.ticker) {
+            Some(stored) => {
+                updated += 1;
+                let mut active: tickers::ActiveModel = stored.clone().into();
+                active.updated_at = ActiveValue::set(chrono::Utc::now());
+                active
+            }
+            None => {
+                inserted += 1;
+                tickers::ActiveModel {
+                    ticker: ActiveValue::set(row.ticker),
+                    ..Default::default()
+                }
+            }
+        };
+        active.name = ActiveValue::set(row.name);
+        active.exchange = ActiveValue::set(row.exchange);
+        active.currency = ActiveValue::set(row.currency);
+        if let Some(isin) = row.isin {
+            active.isin = ActiveValue::set(Some(isin));
+        }
+        if let Some(valor) = row.valor {
+            active.valor = ActiveValue::set(Some(valor));
+        }
+        if let Some(wkn) = row.wkn {
+            active.wkn = ActiveValue::set(Some(wkn));
+        }
+        if let Some(month) = row.fiscal_year_end_month {
+            active.fiscal_year_end_month = ActiveValue::set(month);
+        }
+        active.save(&txn).await?;
+    }
+    txn.commit().await?;
+    ticker_search::invalidate().await;
+
+    Ok(TickerImportReport {
+        index: index_name,
+        inserted,
+        updated,
+        errors: vec![],
+    })
+}
+
+fn header_error(column: &str, message: &str) -> RowError {
+    RowError {
+        row: 1,
+        column: Some(column.to_string()),
+        message: message.to_string(),
+    }
+}
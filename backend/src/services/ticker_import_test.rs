@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+    use crate::services::ticker_import::{
+        is_valid_isin, is_valid_valor, is_valid_wkn, normalize_identifier, parse_csv, IndexUniverse,
+    };
+
+    #[test]
+    fn test_index_universe_parse() {
+        assert_eq!(IndexUniverse::parse("smi"), Some(IndexUniverse::Smi));
+        assert_eq!(IndexUniverse::parse("MDAX"), Some(IndexUniverse::Mdax));
+        assert_eq!(IndexUniverse::parse("S&P 500"), Some(IndexUniverse::Sp500));
+        assert_eq!(IndexUniverse::parse("sp500"), Some(IndexUniverse::Sp500));
+        assert_eq!(IndexUniverse::parse("FTSE"), None);
+    }
+
+    #[test]
+    fn test_identifier_validation() {
+        assert!(is_valid_isin("CH0038863350"));
+        assert!(is_valid_isin("US02079K3059"));
+        assert!(!is_valid_isin("CH0038863351"), "wrong check digit");
+        assert!(!is_valid_isin("0H0038863350"), "numeric country code");
+        assert!(!is_valid_isin("CH003886335"), "too short");
+
+        assert!(is_valid_valor("3886335"));
+        assert!(!is_valid_valor("38863350000"));
+        assert!(!is_valid_valor("38A6335"));
+
+        assert!(is_valid_wkn("716460"));
+        assert!(is_valid_wkn("A1EWWW"));
+        assert!(!is_valid_wkn("71646"));
+
+        assert_eq!(normalize_identifier(" ch 003 886 3350 "), "CH0038863350");
+    }
+
+    #[test]
+    fn test_parse_csv_fills_in_index_defaults() {
+        let csv = "\
+Symbol,Name,ISIN,WKN,Weight
+SAP,SAP SE,de0007164600,716460,12.5
+MBG.DE,Mercedes-Benz Group AG,DE0007100000,,3.1
+";
+        let rows = parse_csv(csv.as_bytes(), Some(IndexUniverse::Dax)).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        // Bare symbols get the listing suffix; suffixed ones are kept
+        assert_eq!(rows[0].ticker, "SAP.DE");
+        assert_eq!(rows[1].ticker, "MBG.DE");
+        assert_eq!(rows[0].exchange, "DAX");
+        assert_eq!(rows[0].currency, "EUR");
+        assert_eq!(rows[0].isin.as_deref(), Some("DE0007164600"));
+        assert_eq!(rows[0].wkn.as_deref(), Some("716460"));
+        assert_eq!(rows[1].wkn, None);
+        assert_eq!(rows[1].fiscal_year_end_month, None);
+    }
+
+    #[test]
+    fn test_parse_csv_requires_listing_without_index() {
+        let errors = parse_csv(b"ticker,name\nAAPL,Apple\n", None).unwrap_err();
+
+        let missing: Vec<&str> = errors.iter().filter_map(|e| e.column.as_deref()).collect();
+        assert_eq!(missing, vec!["exchange", "currency"]);
+    }
+
+    #[test]
+    fn test_parse_csv_reports_every_row_problem() {
+        let csv = "\
+ticker,name,exchange,currency,isin,valor,fiscal_year_end_month
+ABC,Alpha,NYSE,USD,US0378331005,,6
+ABC,Alpha again,NYSE,USD,,,
+DEF,Delta,NYSE,US,US0378331005,12x,13
+";
+        let errors = parse_csv(csv.as_bytes(), None).unwrap_err();
+
+        let found: Vec<(usize, Option<&str>)> = errors
+            .iter()
+            .map(|e| (e.row, e.column.as_deref()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (3, Some("ticker")),
+                (4, Some("currency")),
+                (4, Some("isin")),
+                (4, Some("valor")),
+                (4, Some("fiscal_year_end_month")),
+            ]
+        );
+    }
+}
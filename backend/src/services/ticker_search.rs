@@ -0,0 +1,304 @@
+//! Ranked ticker search.
+//!
+//! Autocomplete has to tolerate how people type company names: without
+//! accents (`Nestle` for Nestlé), with German umlauts spelled out
+//! (`Muenchener` for Münchener) and with the odd typo. None of that maps onto
+//! a SQL `LIKE`, so the ticker universe is held in an in-memory
+//! [`SearchIndex`] with folded names and ranked per request. The index is
+//! rebuilt when the `tickers` table changes, detected from its row count,
+//! highest id and latest update, and after every universe import.
+//!
+//! Matches are ranked by [`MatchRank`]: exact symbol or identifier first,
+//! then symbol prefix, name prefix, name substring, typo-tolerant name match
+//! and finally exchange. Ties are broken by symbol.
+
+use super::ticker_import::normalize_identifier;
+use crate::models::tickers;
+use loco_rs::prelude::*;
+use sea_orm::QuerySelect;
+use std::sync::{Arc, LazyLock};
+use steady_invest_logic::{TickerInfo, TickerSearchPage};
+use tokio::sync::RwLock;
+
+/// Results per page when the caller does not ask for a size.
+pub const DEFAULT_LIMIT: usize = 10;
+
+/// Largest page size served.
+pub const MAX_LIMIT: usize = 50;
+
+/// Shortest folded query that may match names with typos.
+const MIN_FUZZY_LEN: usize = 4;
+
+/// How a ticker matched a query; better matches sort first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MatchRank {
+    /// Symbol, symbol without listing suffix, ISIN, valor or WKN.
+    ExactSymbol,
+    SymbolPrefix,
+    /// The name, or one of its words, starts with the query.
+    NamePrefix,
+    NameContains,
+    /// A name word is within the given edit distance of the query.
+    FuzzyName(usize),
+    Exchange,
+}
+
+/// A ticker with the normalized forms it is matched on.
+#[derive(Debug)]
+struct IndexedTicker {
+    info: TickerInfo,
+    symbol: String,
+    base_symbol: String,
+    identifiers: Vec<String>,
+    folded_name: String,
+    exchange: String,
+}
+
+/// In-memory search index over the ticker universe.
+#[derive(Debug, Default)]
+pub struct SearchIndex {
+    entries: Vec<IndexedTicker>,
+}
+
+impl SearchIndex {
+    /// Builds the index from stored tickers.
+    pub fn new(models: Vec<tickers::Model>) -> Self {
+        let entries = models
+            .into_iter()
+            .map(|model| {
+                let symbol = model.ticker.to_uppercase();
+                let base_symbol = symbol
+                    .split_once('.')
+                    .map_or_else(|| symbol.clone(), |(base, _)| base.to_string());
+                IndexedTicker {
+                    info: model.to_ticker_info(),
+                    base_symbol,
+                    symbol,
+                    identifiers: [&model.isin, &model.valor, &model.wkn]
+                        .into_iter()
+                        .flatten()
+                        .cloned()
+                        .collect(),
+                    folded_name: fold(&model.name),
+                    exchange: model.exchange.to_uppercase(),
+                }
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// Every ticker matching `query`, best match first.
+    pub fn search(&self, query: &str) -> Vec<(MatchRank, &TickerInfo)> {
+        let symbol = query.trim().to_uppercase();
+        let identifier = normalize_identifier(query);
+        let folded = fold(query);
+        if folded.is_empty() && identifier.is_empty() {
+            return Vec::new();
+        }
+
+        let mut matches: Vec<(MatchRank, &TickerInfo)> = self
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                entry
+                    .rank(&symbol, &identifier, &folded)
+                    .map(|rank| (rank, &entry.info))
+            })
+            .collect();
+        matches.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.ticker.cmp(&b.1.ticker)));
+        matches
+    }
+}
+
+impl IndexedTicker {
+    fn rank(&self, symbol: &str, identifier: &str, folded: &str) -> Option<MatchRank> {
+        if self.symbol == symbol
+            || self.base_symbol == symbol
+            || self.identifiers.iter().any(|id| id == identifier)
+        {
+            return Some(MatchRank::ExactSymbol);
+        }
+        if !symbol.is_empty() && self.symbol.starts_with(symbol) {
+            return Some(MatchRank::SymbolPrefix);
+        }
+        if folded.is_empty() {
+            return None;
+        }
+        if self.folded_name.starts_with(folded)
+            || self.folded_name.split(' ').any(|w| w.starts_with(folded))
+        {
+            return Some(MatchRank::NamePrefix);
+        }
+        if self.folded_name.contains(folded) {
+            return Some(MatchRank::NameContains);
+        }
+        if let Some(distance) = self.fuzzy_distance(folded) {
+            return Some(MatchRank::FuzzyName(distance));
+        }
+        (self.exchange == symbol).then_some(MatchRank::Exchange)
+    }
+
+    /// Smallest edit distance between the query and the start of the name or
+    /// of one of its words, if within the typos allowed for its length.
+    fn fuzzy_distance(&self, folded: &str) -> Option<usize> {
+        let query: Vec<char> = folded.chars().collect();
+        if query.len() < MIN_FUZZY_LEN {
+            return None;
+        }
+        let allowed = if query.len() >= 8 { 2 } else { 1 };
+
+        std::iter::once(self.folded_name.as_str())
+            .chain(self.folded_name.split(' '))
+            .filter_map(|candidate| {
+                let candidate: Vec<char> = candidate.chars().collect();
+                // Compare against prefixes one character shorter or longer
+                // than the query so a dropped or extra letter counts once
+                (query.len().saturating_sub(1)..=query.len() + 1)
+                    .map(|len| edit_distance(&query, &candidate[..len.min(candidate.len())]))
+                    .min()
+            })
+            .min()
+            .filter(|&distance| distance <= allowed)
+    }
+}
+
+/// Lower-cases `text`, folds diacritics to ASCII and collapses everything
+/// that is not a letter or digit into single spaces.
+///
+/// German umlauts fold to their two-letter spelling (`ü` → `ue`), the
+/// convention used in German identifiers and URLs.
+pub fn fold(text: &str) -> String {
+    let mut folded = String::with_capacity(text.len());
+    let mut separated = false;
+    for c in text.chars().flat_map(char::to_lowercase) {
+        let ascii = match c {
+            'ä' | 'æ' => "ae",
+            'ö' | 'œ' => "oe",
+            'ü' => "ue",
+            'ß' => "ss",
+            'à' | 'á' | 'â' | 'ã' | 'å' | 'ā' | 'ą' => "a",
+            'ç' | 'ć' | 'č' => "c",
+            'ď' => "d",
+            'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ę' | 'ě' => "e",
+            'ì' | 'í' | 'î' | 'ï' | 'ī' => "i",
+            'ł' => "l",
+            'ñ' | 'ń' | 'ň' => "n",
+            'ò' | 'ó' | 'ô' | 'õ' | 'ø' | 'ō' => "o",
+            'ř' => "r",
+            'ś' | 'š' => "s",
+            'ť' => "t",
+            'ù' | 'ú' | 'û' | 'ū' | 'ů' => "u",
+            'ý' | 'ÿ' => "y",
+            'ź' | 'ż' | 'ž' => "z",
+            _ => "",
+        };
+        if ascii.is_empty() && !c.is_alphanumeric() {
+            separated = !folded.is_empty();
+            continue;
+        }
+        if separated {
+            folded.push(' ');
+            separated = false;
+        }
+        if ascii.is_empty() {
+            folded.push(c);
+        } else {
+            folded.push_str(ascii);
+        }
+    }
+    folded
+}
+
+/// Levenshtein distance between two character sequences.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+// ---------------------------------------------------------------------------
+// Shared index
+// ---------------------------------------------------------------------------
+
+/// Row count, highest id and latest update of `tickers`.
+type Fingerprint = (i64, Option<i32>, Option<DateTimeUtc>);
+
+struct CachedIndex {
+    fingerprint: Fingerprint,
+    index: Arc<SearchIndex>,
+}
+
+static INDEX_CACHE: LazyLock<RwLock<Option<CachedIndex>>> = LazyLock::new(|| RwLock::new(None));
+
+async fn fingerprint(db: &DatabaseConnection) -> Result<Fingerprint> {
+    Ok(tickers::Entity::find()
+        .select_only()
+        .column_as(tickers::Column::Id.count(), "count")
+        .column_as(tickers::Column::Id.max(), "max_id")
+        .column_as(tickers::Column::UpdatedAt.max(), "last_update")
+        .into_tuple::<Fingerprint>()
+        .one(db)
+        .await?
+        .unwrap_or_default())
+}
+
+/// Returns the current index, rebuilding it if `tickers` changed.
+async fn current_index(db: &DatabaseConnection) -> Result<Arc<SearchIndex>> {
+    let fingerprint = fingerprint(db).await?;
+    {
+        let cache = INDEX_CACHE.read().await;
+        if let Some(cached) = cache.as_ref().filter(|c| c.fingerprint == fingerprint) {
+            return Ok(cached.index.clone());
+        }
+    }
+
+    let index = Arc::new(SearchIndex::new(tickers::Entity::find().all(db).await?));
+    *INDEX_CACHE.write().await = Some(CachedIndex {
+        fingerprint,
+        index: index.clone(),
+    });
+    Ok(index)
+}
+
+/// Drops the shared index so the next search rebuilds it.
+pub async fn invalidate() {
+    *INDEX_CACHE.write().await = None;
+}
+
+/// Returns one page of tickers matching `query`, best match first.
+///
+/// `limit` is clamped to `1..=`[`MAX_LIMIT`].
+///
+/// # Errors
+///
+/// Returns an error if the ticker universe cannot be loaded.
+pub async fn search(
+    db: &DatabaseConnection,
+    query: &str,
+    offset: usize,
+    limit: usize,
+) -> Result<TickerSearchPage> {
+    let limit = limit.clamp(1, MAX_LIMIT);
+    let index = current_index(db).await?;
+    let matches = index.search(query);
+
+    Ok(TickerSearchPage {
+        total: matches.len(),
+        results: matches
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .map(|(_, info)| info.clone())
+            .collect(),
+        offset,
+        limit,
+    })
+}
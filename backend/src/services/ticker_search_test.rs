@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::tickers;
+    use crate::services::ticker_search::{fold, MatchRank, SearchIndex};
+
+    fn ticker(id: i32, symbol: &str, name: &str, exchange: &str) -> tickers::Model {
+        tickers::Model {
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            id,
+            ticker: symbol.to_string(),
+            name: name.to_string(),
+            exchange: exchange.to_string(),
+            currency: "CHF".to_string(),
+            fiscal_year_end_month: 12,
+            isin: None,
+            valor: None,
+            wkn: None,
+        }
+    }
+
+    fn index() -> SearchIndex {
+        let mut nestle = ticker(1, "NESN.SW", "Nestlé S.A.", "SMI");
+        nestle.valor = Some("3886335".to_string());
+        SearchIndex::new(vec![
+            nestle,
+            ticker(2, "NESTE.HE", "Neste Oyj", "OMXH"),
+            ticker(
+                3,
+                "MUV2.DE",
+                "Münchener Rückversicherungs-Gesellschaft",
+                "DAX",
+            ),
+            ticker(4, "NES", "Nuverra Environmental", "NYSE"),
+            ticker(5, "ANES", "Canessa Holdings", "NYSE"),
+        ])
+    }
+
+    fn symbols(results: &[(MatchRank, &steady_invest_logic::TickerInfo)]) -> Vec<String> {
+        results.iter().map(|(_, t)| t.ticker.clone()).collect()
+    }
+
+    #[test]
+    fn test_fold_diacritics_and_punctuation() {
+        assert_eq!(fold("Nestlé S.A."), "nestle s a");
+        assert_eq!(fold("Münchener Rück"), "muenchener rueck");
+        assert_eq!(fold("  Straße & Co. "), "strasse co");
+        assert_eq!(fold("--"), "");
+    }
+
+    #[test]
+    fn test_exact_symbol_ranks_before_prefix_and_name() {
+        let index = index();
+        let results = index.search("nes");
+
+        assert_eq!(results[0].0, MatchRank::ExactSymbol);
+        assert_eq!(
+            symbols(&results),
+            vec!["NES", "NESN.SW", "NESTE.HE", "ANES"]
+        );
+        assert_eq!(results[1].0, MatchRank::SymbolPrefix);
+        assert_eq!(results[3].0, MatchRank::NameContains);
+    }
+
+    #[test]
+    fn test_symbol_without_listing_suffix_and_identifier_are_exact() {
+        let index = index();
+
+        let results = index.search("NESN");
+        assert_eq!(results[0].0, MatchRank::ExactSymbol);
+        assert_eq!(results[0].1.ticker, "NESN.SW");
+
+        let results = index.search("3886335");
+        assert_eq!(symbols(&results), vec!["NESN.SW"]);
+    }
+
+    #[test]
+    fn test_name_match_folds_diacritics_and_tolerates_typos() {
+        let index = index();
+
+        let results = index.search("Nestle");
+        assert_eq!(symbols(&results), vec!["NESN.SW", "NESTE.HE"]);
+        assert_eq!(results[0].0, MatchRank::NamePrefix);
+        assert_eq!(results[1].0, MatchRank::FuzzyName(1));
+        assert_eq!(symbols(&index.search("Muenchener")), vec!["MUV2.DE"]);
+
+        let results = index.search("Munchener");
+        assert_eq!(symbols(&results), vec!["MUV2.DE"]);
+        assert_eq!(results[0].0, MatchRank::FuzzyName(1));
+
+        let results = index.search("Nestel");
+        assert!(matches!(results[0].0, MatchRank::FuzzyName(_)));
+        // Short queries never match with typos
+        assert!(index.search("Nxs").is_empty());
+    }
+
+    #[test]
+    fn test_blank_query_matches_nothing() {
+        assert!(index().search("  ").is_empty());
+    }
+}
@@ -0,0 +1,62 @@
+//! `backfill_exchange_rates` task — fills `exchange_rates` with yearly
+//! average and year-end rates.
+//!
+//! ```sh
+//! cargo loco task backfill_exchange_rates
+//! cargo loco task backfill_exchange_rates years:15
+//! cargo loco task backfill_exchange_rates file:data/eurofxref-hist.csv
+//! ```
+//!
+//! Reads the Frankfurter time-series API unless `file` names an ECB
+//! `eurofxref-hist.csv`. `years` (default 12) counts calendar years back
+//! from the current one.
+
+use crate::services::exchange_rate_backfill::{self, DEFAULT_YEARS};
+use loco_rs::prelude::*;
+
+pub struct BackfillExchangeRates;
+
+#[async_trait]
+impl Task for BackfillExchangeRates {
+    fn task(&self) -> TaskInfo {
+        TaskInfo {
+            name: "backfill_exchange_rates".to_string(),
+            detail:
+                "Store yearly average and year-end exchange rates from Frankfurter or an ECB CSV"
+                    .to_string(),
+        }
+    }
+
+    async fn run(&self, ctx: &AppContext, vars: &task::Vars) -> Result<()> {
+        let years = match vars.cli_arg("years") {
+            Ok(value) => value
+                .parse::<i32>()
+                .ok()
+                .filter(|v| *v > 0)
+                .ok_or_else(|| Error::string(&format!("Invalid years '{value}'")))?,
+            Err(_) => DEFAULT_YEARS,
+        };
+        let years = exchange_rate_backfill::recent_years(years);
+
+        let report = match vars.cli_arg("file") {
+            Ok(path) => {
+                let bytes = std::fs::read(path)
+                    .map_err(|e| Error::string(&format!("Cannot read {path}: {e}")))?;
+                exchange_rate_backfill::backfill_from_ecb_csv(&ctx.db, &bytes, years).await?
+            }
+            Err(_) => exchange_rate_backfill::backfill_from_frankfurter(&ctx.db, years).await?,
+        };
+
+        println!(
+            "Exchange rates {}-{}: {} inserted, {} updated",
+            report.first_year, report.last_year, report.inserted, report.updated
+        );
+        if !report.missing_currencies.is_empty() {
+            println!(
+                "  no reference rates for {}",
+                report.missing_currencies.join(", ")
+            );
+        }
+        Ok(())
+    }
+}
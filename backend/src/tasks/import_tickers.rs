@@ -0,0 +1,57 @@
+//! `import_tickers` task — loads an index constituent file into `tickers`.
+//!
+//! ```sh
+//! cargo loco task import_tickers file:data/smi.csv index:SMI
+//! cargo loco task import_tickers file:data/universe.csv
+//! ```
+//!
+//! `index` (SMI, SPI, DAX, MDAX or SP500) fills in the exchange and currency
+//! of rows that leave them blank. Without it every row must carry both.
+
+use crate::services::ticker_import::{self, IndexUniverse};
+use loco_rs::prelude::*;
+
+pub struct ImportTickers;
+
+#[async_trait]
+impl Task for ImportTickers {
+    fn task(&self) -> TaskInfo {
+        TaskInfo {
+            name: "import_tickers".to_string(),
+            detail: "Import index constituents with ISIN, valor and WKN from a CSV file"
+                .to_string(),
+        }
+    }
+
+    async fn run(&self, ctx: &AppContext, vars: &task::Vars) -> Result<()> {
+        let path = vars
+            .cli_arg("file")
+            .map_err(|_| Error::string("file argument is required"))?;
+        let index = match vars.cli_arg("index") {
+            Ok(name) => Some(
+                IndexUniverse::parse(name)
+                    .ok_or_else(|| Error::string(&format!("Unknown index '{name}'")))?,
+            ),
+            Err(_) => None,
+        };
+        let bytes =
+            std::fs::read(path).map_err(|e| Error::string(&format!("Cannot read {path}: {e}")))?;
+
+        let report = ticker_import::import_tickers(&ctx.db, index, &bytes).await?;
+        if !report.errors.is_empty() {
+            for error in &report.errors {
+                let column = error.column.as_deref().unwrap_or("-");
+                println!("  row {} ({column}): {}", error.row, error.message);
+            }
+            return Err(Error::string(&format!(
+                "{} problem(s) found, nothing imported",
+                report.errors.len()
+            )));
+        }
+        println!(
+            "Imported {}: {} inserted, {} updated",
+            path, report.inserted, report.updated
+        );
+        Ok(())
+    }
+}
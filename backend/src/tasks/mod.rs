@@ -1 +1,9 @@
+//! Loco CLI tasks, run with `cargo loco task <name>`.
+//!
+//! - [`backfill_exchange_rates`] — Store yearly average and year-end exchange rates
+//! - [`import_tickers`]          — Import an index constituent file into `tickers`
+//! - [`refresh_all`]             — Re-harvest every tracked ticker
 
+pub mod backfill_exchange_rates;
+pub mod import_tickers;
+pub mod refresh_all;
@@ -0,0 +1,60 @@
+//! `refresh_all` task — re-harvests every tracked ticker.
+//!
+//! ```sh
+//! cargo loco task refresh_all
+//! cargo loco task refresh_all concurrency:2 budget:20
+//! ```
+//!
+//! `concurrency` and `budget` override `REFRESH_CONCURRENCY` and
+//! `REFRESH_PROVIDER_BUDGET` for this run.
+
+use crate::services::providers::ProviderRegistry;
+use crate::services::refresh::{self, RefreshOptions};
+use loco_rs::prelude::*;
+
+pub struct RefreshAll;
+
+/// Parses an optional positive integer task argument.
+fn positive_arg(vars: &task::Vars, name: &str) -> Result<Option<usize>> {
+    let Ok(value) = vars.cli_arg(name) else {
+        return Ok(None);
+    };
+    match value.parse::<usize>() {
+        Ok(v) if v > 0 => Ok(Some(v)),
+        _ => Err(Error::string(&format!(
+            "{name} must be a positive integer, got '{value}'"
+        ))),
+    }
+}
+
+#[async_trait]
+impl Task for RefreshAll {
+    fn task(&self) -> TaskInfo {
+        TaskInfo {
+            name: "refresh_all".to_string(),
+            detail: "Re-harvest every ticker with a snapshot or comparison-set membership"
+                .to_string(),
+        }
+    }
+
+    async fn run(&self, ctx: &AppContext, vars: &task::Vars) -> Result<()> {
+        let mut options = RefreshOptions::from_env();
+        if let Some(concurrency) = positive_arg(vars, "concurrency")? {
+            options.concurrency = concurrency;
+        }
+        if let Some(budget) = positive_arg(vars, "budget")? {
+            options.provider_budget = budget;
+        }
+
+        let report =
+            refresh::refresh_all(ctx, &ProviderRegistry::from_env(&ctx.db), options).await?;
+        println!("{}", report.summary());
+        for failure in &report.failed {
+            println!("  failed {}: {}", failure.ticker, failure.error);
+        }
+        if !report.skipped.is_empty() {
+            println!("  skipped (budget): {}", report.skipped.join(", "));
+        }
+        Ok(())
+    }
+}
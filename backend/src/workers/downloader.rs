@@ -1,8 +1,13 @@
 //! Background data download worker.
 //!
-//! Processes queued download jobs asynchronously. Currently a placeholder
-//! for future background data-fetch operations.
+//! Runs queued harvest jobs: each job harvests one ticker through
+//! [`harvest::harvest`] and records its progress in the `harvest_jobs` table,
+//! so clients can poll `GET /api/v1/harvest/jobs/{id}` instead of blocking on
+//! the synchronous harvest endpoint.
 
+use crate::models::harvest_jobs;
+use crate::services::harvest::{self, HarvestMode};
+use crate::services::providers::ProviderRegistry;
 use loco_rs::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -15,8 +20,8 @@ pub struct DownloadWorker {
 /// Arguments passed to a download job via the background queue.
 #[derive(Deserialize, Debug, Serialize)]
 pub struct DownloadWorkerArgs {
-    /// The unique identifier of the user who triggered the download.
-    pub user_guid: String,
+    /// Id of the `harvest_jobs` row to run.
+    pub job_id: i32,
 }
 
 #[async_trait]
@@ -24,9 +29,41 @@ impl BackgroundWorker<DownloadWorkerArgs> for DownloadWorker {
     fn build(ctx: &AppContext) -> Self {
         Self { ctx: ctx.clone() }
     }
-    async fn perform(&self, _args: DownloadWorkerArgs) -> Result<()> {
-        // TODO: Some actual work goes here...
 
+    /// Harvests the job's ticker. A failed harvest is recorded on the job
+    /// rather than returned, so the queue does not retry it.
+    async fn perform(&self, args: DownloadWorkerArgs) -> Result<()> {
+        let Some(job) = harvest_jobs::Entity::find_by_id(args.job_id)
+            .one(&self.ctx.db)
+            .await?
+        else {
+            tracing::warn!(job_id = args.job_id, "Harvest job not found");
+            return Ok(());
+        };
+        if job.status != harvest_jobs::QUEUED {
+            return Ok(());
+        }
+
+        let job = job.mark_running(&self.ctx.db).await?;
+        let mode = if job.refresh {
+            HarvestMode::Refresh
+        } else {
+            HarvestMode::InsertMissing
+        };
+        let registry = ProviderRegistry::from_env(&self.ctx.db);
+        let display_currency = job
+            .display_currency
+            .as_deref()
+            .unwrap_or(harvest::DEFAULT_DISPLAY_CURRENCY);
+        let outcome = harvest::harvest(&self.ctx, &job.ticker, &registry, mode, display_currency)
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|outcome| serde_json::to_value(outcome).map_err(|e| e.to_string()));
+        if let Err(error) = &outcome {
+            tracing::warn!(job_id = job.id, ticker = %job.ticker, %error, "Harvest job failed");
+        }
+
+        job.finish(&self.ctx.db, outcome).await?;
         Ok(())
     }
 }
@@ -3,6 +3,6 @@
 //! Workers run asynchronously via the Loco background queue and handle
 //! long-running tasks that should not block API responses.
 //!
-//! - [`downloader`] — Fetches historical data from financial API providers
+//! - [`downloader`] — Runs queued ticker harvests and tracks their status
 
 pub mod downloader;
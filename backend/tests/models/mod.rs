@@ -1,3 +1,5 @@
 mod users;
 
 mod tickers;
+
+mod provider_rate_limits;
@@ -0,0 +1,62 @@
+use backend::app::App;
+use backend::models::provider_rate_limits;
+use backend::services::rate_limiter::{RateLimit, RateLimiter};
+use loco_rs::testing::prelude::*;
+use sea_orm::{ActiveModelTrait, ActiveValue};
+use serial_test::serial;
+use std::time::Duration;
+
+#[tokio::test]
+#[serial]
+async fn rate_limiter_persists_consumption() {
+    let boot = boot_test::<App>().await.unwrap();
+    let db = &boot.app_context.db;
+    let limit = RateLimit {
+        per_minute: 1,
+        per_day: 50,
+    };
+    let limiter = RateLimiter::new(db, "rate-limit-test", limit).with_max_wait(Duration::ZERO);
+
+    limiter.acquire().await.unwrap();
+    let err = limiter.acquire().await.unwrap_err();
+    assert!(err.to_string().contains("rate-limit-test"), "{err}");
+
+    let stored = provider_rate_limits::Model::find_by_name(db, "rate-limit-test")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.quota_consumed, 2);
+    assert!(stored.minute_tokens.unwrap() < 1.0);
+    assert!(stored.day_tokens.unwrap() < 50.0);
+}
+
+#[tokio::test]
+#[serial]
+async fn rate_limiter_resumes_from_stored_buckets() {
+    let boot = boot_test::<App>().await.unwrap();
+    let db = &boot.app_context.db;
+
+    // Buckets emptied by an earlier process
+    provider_rate_limits::ActiveModel {
+        name: ActiveValue::set("exhausted-test".to_string()),
+        quota_consumed: ActiveValue::set(100),
+        last_updated: ActiveValue::set(chrono::Utc::now().naive_utc()),
+        minute_tokens: ActiveValue::set(Some(0.0)),
+        day_tokens: ActiveValue::set(Some(0.0)),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .unwrap();
+
+    let limit = RateLimit {
+        per_minute: 60,
+        per_day: 1_000,
+    };
+    let limiter = RateLimiter::new(db, "exhausted-test", limit).with_max_wait(Duration::ZERO);
+    assert!(limiter.acquire().await.is_err());
+
+    // Waiting is allowed for a token due soon, not for the daily allowance
+    let limiter = limiter.with_max_wait(Duration::from_secs(5));
+    assert!(limiter.acquire().await.is_err());
+}
@@ -3,7 +3,7 @@ use backend::models::_entities::{analysis_snapshots, tickers, users};
 use loco_rs::testing::prelude::request;
 use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 use serial_test::serial;
-use steady_invest_logic::{AnalysisSnapshot, HistoricalData};
+use steady_invest_logic::{AnalysisSnapshot, HistoricalData, LowPriceMethod};
 
 #[tokio::test]
 #[serial]
@@ -33,6 +33,9 @@ async fn can_lock_and_get_analyses() {
             projected_low_pe: 15.0,
             analyst_note: String::new(),
             captured_at: chrono::Utc::now(),
+            current_quote: None,
+            naic_test_overrides: Vec::new(),
+            low_price_method: LowPriceMethod::default(),
         };
 
         let req = serde_json::json!({
@@ -71,8 +71,8 @@ async fn test_harvest_anomaly_logging() {
         .unwrap();
 
         // 2. Run harvest (This triggers the anomaly logging)
-        let res = request.post(&format!("/api/harvest/{}", ticker)).await;
-        assert_eq!(res.status_code(), 200);
+        let harvested = request.post(&format!("/api/harvest/{}", ticker)).await;
+        assert_eq!(harvested.status_code(), 200);
 
         // 3. Verify audit log existence
         let res = request.get("/api/v1/system/audit-logs").await;
@@ -80,12 +80,33 @@ async fn test_harvest_anomaly_logging() {
         let body: serde_json::Value = serde_json::from_str(&res.text()).unwrap();
         let logs = body.as_array().expect("Audit logs should be an array");
 
-        let found = logs.iter().any(|l| {
-            l["ticker"] == "ANOMALY"
-                && l["event_type"] == "Anomaly"
-                && l["field_name"] == "Integrity"
-        });
-        assert!(found, "Expected anomaly log for ANOMALY ticker not found");
+        let anomaly_fields: Vec<&str> = logs
+            .iter()
+            .filter(|l| l["ticker"] == "ANOMALY" && l["event_type"] == "Anomaly")
+            .filter_map(|l| l["field_name"].as_str())
+            .collect();
+        // The fixture has negative equity and a loss year in 2019
+        assert!(anomaly_fields.contains(&"total_equity FY2019"));
+        assert!(anomaly_fields.contains(&"eps FY2019"));
+        assert!(anomaly_fields.contains(&"eps FY2020"));
+
+        // The findings are returned with the data
+        let data: steady_invest_logic::HistoricalData = harvested.json();
+        assert!(data
+            .anomalies
+            .iter()
+            .any(|a| a.kind == steady_invest_logic::AnomalyKind::NonPositiveEquity));
+
+        // A second harvest does not log the same findings again
+        let count = anomaly_fields.len();
+        request.post(&format!("/api/harvest/{}", ticker)).await;
+        let res = request.get("/api/v1/system/audit-logs").await;
+        let logs: Vec<serde_json::Value> = res.json();
+        let again = logs
+            .iter()
+            .filter(|l| l["ticker"] == "ANOMALY" && l["event_type"] == "Anomaly")
+            .count();
+        assert_eq!(again, count);
     })
     .await;
 }
@@ -5,7 +5,7 @@ use loco_rs::testing::prelude::request;
 use rust_decimal::Decimal;
 use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 use serial_test::serial;
-use steady_invest_logic::{AnalysisSnapshot, HistoricalData, HistoricalYearlyData};
+use steady_invest_logic::{AnalysisSnapshot, HistoricalData, HistoricalYearlyData, LowPriceMethod};
 
 /// Ensure a user and ticker exist for FK constraints.
 /// Returns the ticker_id for use in snapshot requests.
@@ -33,7 +33,6 @@ fn sample_snapshot_data() -> serde_json::Value {
         "projected_eps_cagr": 12.0,
         "projected_high_pe": 25.0,
         "projected_low_pe": 15.0,
-        "valuation_zone": "undervalued",
         "analyst_note": "",
         "captured_at": "2026-01-01T00:00:00Z"
     })
@@ -46,7 +45,6 @@ fn sample_snapshot_data_2() -> serde_json::Value {
         "projected_eps_cagr": 9.5,
         "projected_high_pe": 30.0,
         "projected_low_pe": 20.0,
-        "valuation_zone": "overvalued",
         "analyst_note": "",
         "captured_at": "2026-01-02T00:00:00Z"
     })
@@ -118,6 +116,9 @@ fn sample_snapshot_data_with_records() -> serde_json::Value {
         projected_low_pe: 5.0,
         analyst_note: "Test with records".to_string(),
         captured_at: chrono::Utc::now(),
+        current_quote: None,
+        naic_test_overrides: Vec::new(),
+        low_price_method: LowPriceMethod::default(),
     };
     serde_json::to_value(&snapshot).unwrap()
 }
@@ -154,8 +155,8 @@ async fn ad_hoc_compare_returns_latest_snapshots() {
         // Verify key metrics from sample_snapshot_data_2
         assert!((snapshots[0]["projected_sales_cagr"].as_f64().unwrap() - 8.0).abs() < 0.01);
         assert!((snapshots[0]["projected_eps_cagr"].as_f64().unwrap() - 9.5).abs() < 0.01);
-        assert_eq!(snapshots[0]["valuation_zone"], "overvalued");
-        // Monetary fields: empty records → prices null, but native_currency present
+        // Monetary fields: empty records → prices and zone null, but native_currency present
+        assert!(snapshots[0]["valuation_zone"].is_null());
         assert_eq!(snapshots[0]["native_currency"], "USD");
         assert!(snapshots[0]["current_price"].is_null());
         assert!(snapshots[0]["target_high_price"].is_null());
@@ -272,6 +273,76 @@ async fn ad_hoc_compare_returns_upside_downside_ratio() {
             "Expected target_low ~40.263, got {}",
             target_low
         );
+
+        // Section 4C thirds of 40.263–120.789: Buy below ~67.105, Sell above ~93.947
+        let buy_below = snapshots[0]["buy_below_price"].as_f64().unwrap();
+        let sell_above = snapshots[0]["sell_above_price"].as_f64().unwrap();
+        assert!((buy_below - 67.105).abs() < 0.01, "got {}", buy_below);
+        assert!((sell_above - 93.947).abs() < 0.01, "got {}", sell_above);
+        assert_eq!(snapshots[0]["valuation_zone"], "Buy");
+
+        // Section 5: current P/E 10 vs average P/E (10 + 6) / 2 = 8 → 125%
+        let relative_value = snapshots[0]["relative_value"].as_f64().unwrap();
+        assert!(
+            (relative_value - 125.0).abs() < 0.01,
+            "got {}",
+            relative_value
+        );
+        // Average price 10 × 8.0526 grows 50 at 10%/year; no dividends
+        let par = snapshots[0]["projected_average_return"].as_f64().unwrap();
+        assert!((par - 10.0).abs() < 0.01, "got {}", par);
+        assert!(snapshots[0]["present_yield"].is_null());
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn ad_hoc_compare_converts_foreign_quote_to_display_currency() {
+    request::<App, _, _>(|request, ctx| async move {
+        let ticker_id = seed_user_and_ticker(&ctx).await;
+
+        // The same figures, reported in CHF and normalized to USD, quoted at
+        // 40 CHF = 50 USD at the spot rate
+        let mut snapshot: AnalysisSnapshot =
+            serde_json::from_value(sample_snapshot_data_with_records()).unwrap();
+        snapshot.historical_data.currency = "CHF".to_string();
+        snapshot.historical_data.display_currency = Some("USD".to_string());
+        snapshot.historical_data.spot_exchange_rate = Some(Decimal::new(125, 2));
+        snapshot.current_quote = Some(steady_invest_logic::Quote {
+            price: Decimal::from(40),
+            currency: "CHF".to_string(),
+            as_of: chrono::Utc::now(),
+            source: "manual".to_string(),
+        });
+        create_snapshot(
+            &request,
+            ticker_id,
+            serde_json::to_value(&snapshot).unwrap(),
+        )
+        .await;
+
+        let res = request
+            .get(&format!("/api/v1/compare?ticker_ids={}", ticker_id))
+            .await;
+        res.assert_status_success();
+        let body: serde_json::Value = res.json();
+        let entry = &body["snapshots"][0];
+        assert_eq!(entry["native_currency"], "USD");
+        let current_price = entry["current_price"].as_f64().unwrap();
+        assert!((current_price - 50.0).abs() < 0.01, "got {}", current_price);
+
+        // Same results as the USD snapshot priced at 50
+        let relative_value = entry["relative_value"].as_f64().unwrap();
+        assert!(
+            (relative_value - 125.0).abs() < 0.01,
+            "got {}",
+            relative_value
+        );
+        let par = entry["projected_average_return"].as_f64().unwrap();
+        assert!((par - 10.0).abs() < 0.01, "got {}", par);
+        let ratio = entry["upside_downside_ratio"].as_f64().unwrap();
+        assert!(ratio > 7.0 && ratio < 8.0, "got {}", ratio);
     })
     .await;
 }
@@ -306,8 +377,8 @@ async fn can_create_comparison_set() {
         assert_eq!(items[0]["sort_order"], 1);
         assert_eq!(items[0]["snapshot"]["id"], snap_id);
         assert_eq!(items[0]["snapshot"]["ticker_symbol"], "AAPL");
-        assert_eq!(items[0]["snapshot"]["valuation_zone"], "undervalued");
-        // Monetary fields: empty records → prices null, native_currency present
+        // Monetary fields: empty records → prices and zone null, native_currency present
+        assert!(items[0]["snapshot"]["valuation_zone"].is_null());
         assert_eq!(items[0]["snapshot"]["native_currency"], "USD");
         assert!(items[0]["snapshot"]["current_price"].is_null());
     })
@@ -391,6 +462,40 @@ async fn create_comparison_preserves_sort_order() {
     .await;
 }
 
+#[tokio::test]
+#[serial]
+async fn create_comparison_accepts_only_supported_currencies() {
+    request::<App, _, _>(|request, ctx| async move {
+        let ticker_id = seed_user_and_ticker(&ctx).await;
+        let snap_id = create_snapshot(&request, ticker_id, sample_snapshot_data()).await;
+        let body = |currency: &str| {
+            serde_json::json!({
+                "name": format!("{currency} Set"),
+                "base_currency": currency,
+                "items": [
+                    { "analysis_snapshot_id": snap_id, "sort_order": 1 }
+                ]
+            })
+        };
+
+        let res = request.post("/api/v1/comparisons").json(&body("GBP")).await;
+        res.assert_status_success();
+        let detail: serde_json::Value = res.json();
+        assert_eq!(detail["base_currency"], "GBP");
+
+        let res = request.post("/api/v1/comparisons").json(&body("AUD")).await;
+        assert_eq!(res.status_code(), 422);
+        let err: serde_json::Value = res.json();
+        assert!(err["error"].as_str().unwrap().contains("JPY"), "{err}");
+
+        std::env::set_var("EXCHANGE_RATE_CURRENCIES", "AUD,CHF");
+        let res = request.post("/api/v1/comparisons").json(&body("AUD")).await;
+        std::env::remove_var("EXCHANGE_RATE_CURRENCIES");
+        res.assert_status_success();
+    })
+    .await;
+}
+
 // -----------------------------------------------------------------------
 // List comparison sets — AC #5
 // -----------------------------------------------------------------------
@@ -0,0 +1,203 @@
+use backend::app::App;
+use loco_rs::testing::prelude::*;
+use rust_decimal::Decimal;
+use serial_test::serial;
+
+fn split_payload(ratio: &str) -> serde_json::Value {
+    serde_json::json!({
+        "ticker": "msft",
+        "kind": "split",
+        "ratio": ratio,
+        "effective_date": "2022-06-01",
+        "note": "Test split"
+    })
+}
+
+async fn corporate_action_audit_count(request: &loco_rs::TestServer) -> usize {
+    let res = request.get("/api/v1/system/audit-logs").await;
+    let logs: Vec<serde_json::Value> = res.json();
+    logs.iter()
+        .filter(|l| l["ticker"] == "MSFT" && l["event_type"] == "CorporateAction")
+        .count()
+}
+
+#[tokio::test]
+#[serial]
+async fn ledger_drives_adjustment_factor() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request
+            .post("/api/v1/corporate-actions")
+            .json(&split_payload("2"))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let created: serde_json::Value = res.json();
+        let id = created["id"].as_i64().unwrap();
+        assert_eq!(created["ticker"], "MSFT");
+        assert_eq!(created["source"], "user");
+
+        let res = request.post("/api/harvest/MSFT").await;
+        assert_eq!(res.status_code(), 200);
+        let data: steady_invest_logic::HistoricalData = res.json();
+        assert!(data.is_split_adjusted);
+        for record in &data.records {
+            let expected = if record.fiscal_year < 2022 {
+                Decimal::from(2)
+            } else {
+                Decimal::ONE
+            };
+            assert_eq!(record.adjustment_factor, expected);
+        }
+
+        // The applied action is audited once, not on every harvest
+        assert_eq!(corporate_action_audit_count(&request).await, 1);
+        request.post("/api/harvest/MSFT").await;
+        assert_eq!(corporate_action_audit_count(&request).await, 1);
+
+        let res = request.get("/api/v1/corporate-actions?ticker=MSFT").await;
+        let entries: Vec<serde_json::Value> = res.json();
+        assert_eq!(entries.len(), 1);
+        assert!(!entries[0]["applied_at"].is_null());
+
+        let res = request
+            .delete(&format!("/api/v1/corporate-actions/{id}"))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let res = request.get("/api/v1/corporate-actions?ticker=MSFT").await;
+        let entries: Vec<serde_json::Value> = res.json();
+        assert!(entries.is_empty());
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn provider_actions_are_copied_into_ledger() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.post("/api/harvest/AAPL").await;
+        assert_eq!(res.status_code(), 200);
+
+        let res = request.get("/api/v1/corporate-actions?ticker=AAPL").await;
+        let entries: Vec<serde_json::Value> = res.json();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0]["kind"], "split");
+        assert_eq!(entries[0]["source"], "fixture");
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn rejects_invalid_corporate_actions() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request
+            .post("/api/v1/corporate-actions")
+            .json(&split_payload("2"))
+            .await;
+        assert_eq!(res.status_code(), 200);
+
+        // Duplicate entry
+        let res = request
+            .post("/api/v1/corporate-actions")
+            .json(&split_payload("3"))
+            .await;
+        assert_eq!(res.status_code(), 400);
+
+        // A split must increase the share count
+        let mut payload = split_payload("0.5");
+        payload["effective_date"] = "2023-01-02".into();
+        let res = request
+            .post("/api/v1/corporate-actions")
+            .json(&payload)
+            .await;
+        assert_eq!(res.status_code(), 400);
+
+        let mut payload = split_payload("2");
+        payload["kind"] = "merger".into();
+        let res = request
+            .post("/api/v1/corporate-actions")
+            .json(&payload)
+            .await;
+        assert_eq!(res.status_code(), 400);
+
+        let res = request
+            .put("/api/v1/corporate-actions/9999")
+            .json(&split_payload("2"))
+            .await;
+        assert_eq!(res.status_code(), 404);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn update_rejects_colliding_entries() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request
+            .post("/api/v1/corporate-actions")
+            .json(&split_payload("2"))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let mut payload = split_payload("3");
+        payload["effective_date"] = "2023-01-02".into();
+        let res = request
+            .post("/api/v1/corporate-actions")
+            .json(&payload)
+            .await;
+        let second: serde_json::Value = res.json();
+
+        // Moving the second split onto the first one's date
+        let res = request
+            .put(&format!("/api/v1/corporate-actions/{}", second["id"]))
+            .json(&split_payload("3"))
+            .await;
+        assert_eq!(res.status_code(), 400);
+
+        let res = request.get("/api/v1/corporate-actions?ticker=MSFT").await;
+        let entries: Vec<serde_json::Value> = res.json();
+        assert_eq!(entries.len(), 2);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn edited_provider_action_is_not_reimported() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.post("/api/harvest/AAPL").await;
+        assert_eq!(res.status_code(), 200);
+        let res = request.get("/api/v1/corporate-actions?ticker=AAPL").await;
+        let entries: Vec<serde_json::Value> = res.json();
+        let provided = &entries[0];
+
+        // The analyst moves the provider's split by a day
+        let date: chrono::NaiveDate = provided["effective_date"]
+            .as_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        let moved = date.succ_opt().unwrap();
+        let res = request
+            .put(&format!("/api/v1/corporate-actions/{}", provided["id"]))
+            .json(&serde_json::json!({
+                "ticker": "AAPL",
+                "kind": provided["kind"],
+                "ratio": provided["ratio"],
+                "effective_date": moved.to_string(),
+                "note": "Corrected date"
+            }))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let edited: serde_json::Value = res.json();
+        assert_eq!(edited["source"], "user");
+
+        // The provider still reports the original date; the split counts once
+        let res = request.post("/api/harvest/AAPL").await;
+        assert_eq!(res.status_code(), 200);
+        let res = request.get("/api/v1/corporate-actions?ticker=AAPL").await;
+        let entries: Vec<serde_json::Value> = res.json();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0]["id"], edited["id"]);
+        assert_eq!(entries[0]["effective_date"], moved.to_string());
+    })
+    .await;
+}
@@ -0,0 +1,108 @@
+use backend::app::App;
+use chrono::{Duration, NaiveDate};
+use loco_rs::testing::prelude::*;
+use rust_decimal::Decimal;
+use serial_test::serial;
+use steady_invest_logic::HistoricalData;
+
+const BOUNDARY: &str = "steadyinvest-daily";
+
+/// Builds a multipart body carrying `contents` as the `file` field.
+fn multipart_body(contents: &str) -> Vec<u8> {
+    format!(
+        "--{BOUNDARY}\r\n\
+         Content-Disposition: form-data; name=\"file\"; filename=\"prices.csv\"\r\n\
+         Content-Type: text/csv\r\n\r\n\
+         {contents}\r\n\
+         --{BOUNDARY}--\r\n"
+    )
+    .into_bytes()
+}
+
+/// Weekly bars covering Microsoft's FY2024 (July 2023 to June 2024), with a
+/// single spike high and dip low.
+fn fy2024_csv() -> String {
+    let mut csv = String::from("Date,Open,High,Low,Close,Adj Close,Volume\n");
+    let mut date = NaiveDate::from_ymd_opt(2023, 7, 3).unwrap();
+    let last = NaiveDate::from_ymd_opt(2024, 6, 28).unwrap();
+    let (spike, dip) = (
+        NaiveDate::from_ymd_opt(2024, 1, 8).unwrap(),
+        NaiveDate::from_ymd_opt(2023, 10, 2).unwrap(),
+    );
+    while date <= last {
+        let high = if date == spike { "468.35" } else { "400" };
+        let low = if date == dip { "309.45" } else { "350" };
+        csv.push_str(&format!("{date},380,{high},{low},390,389.5,1000000\n"));
+        date += Duration::days(7);
+    }
+    if date - Duration::days(7) != last {
+        csv.push_str(&format!("{last},380,400,350,390,389.5,1000000\n"));
+    }
+    csv
+}
+
+#[tokio::test]
+#[serial]
+async fn yearly_range_is_derived_from_daily_series() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request
+            .post("/api/v1/daily-prices/msft/upload")
+            .content_type(&format!("multipart/form-data; boundary={BOUNDARY}"))
+            .bytes(multipart_body(&fy2024_csv()).into())
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let report: serde_json::Value = res.json();
+        assert_eq!(report["first_date"], "2023-07-03");
+        assert_eq!(report["last_date"], "2024-06-28");
+
+        let res = request
+            .get("/api/v1/daily-prices/MSFT?from=2024-01-01&to=2024-01-31")
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let bars: Vec<serde_json::Value> = res.json();
+        assert_eq!(bars.len(), 5);
+        assert_eq!(bars[0]["source"], "uploaded");
+
+        let res = request.post("/api/harvest/MSFT").await;
+        assert_eq!(res.status_code(), 200);
+        let data: HistoricalData = res.json();
+
+        // FY2024 is covered by the series and takes its range from it
+        let fy2024 = data.records.iter().find(|r| r.fiscal_year == 2024).unwrap();
+        assert_eq!(fy2024.price_high, Decimal::new(46835, 2));
+        assert_eq!(fy2024.price_low, Decimal::new(30945, 2));
+        // FY2023 is not covered and keeps the provider's range
+        let fy2023 = data.records.iter().find(|r| r.fiscal_year == 2023).unwrap();
+        assert_eq!(fy2023.price_high, Decimal::new(3843, 1));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn invalid_daily_upload_stores_nothing() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let csv = "date,open,high,low,close\n2024-01-02,10,12,9,11\n2024-01-03,10,8,9,11\n";
+        let res = request
+            .post("/api/v1/daily-prices/MSFT/upload")
+            .content_type(&format!("multipart/form-data; boundary={BOUNDARY}"))
+            .bytes(multipart_body(csv).into())
+            .await;
+        assert_eq!(res.status_code(), 422);
+        let report: serde_json::Value = res.json();
+        assert_eq!(report["imported_days"], 0);
+        assert_eq!(report["errors"][0]["row"], 3);
+
+        let res = request.get("/api/v1/daily-prices/MSFT").await;
+        let bars: Vec<serde_json::Value> = res.json();
+        assert!(bars.is_empty());
+
+        let res = request
+            .post("/api/v1/daily-prices/NOPE/upload")
+            .content_type(&format!("multipart/form-data; boundary={BOUNDARY}"))
+            .bytes(multipart_body("date,open,high,low,close\n").into())
+            .await;
+        assert_eq!(res.status_code(), 404);
+    })
+    .await;
+}
@@ -185,6 +185,55 @@ async fn exchange_rates_falls_back_to_db_rates() {
     .await;
 }
 
+#[tokio::test]
+#[serial]
+async fn exchange_rates_db_fallback_covers_stored_currencies() {
+    request::<App, _, _>(|request, ctx| async move {
+        force_provider_offline();
+        exchange_rate_provider::clear_cache().await;
+
+        // A backfilled EUR→GBP rate for the latest seeded year joins the matrix
+        use backend::models::_entities::exchange_rates;
+        use sea_orm::{ActiveModelTrait, ActiveValue, EntityTrait, QueryOrder};
+        let latest = exchange_rates::Entity::find()
+            .order_by_desc(exchange_rates::Column::FiscalYear)
+            .one(&ctx.db)
+            .await
+            .unwrap()
+            .unwrap();
+        exchange_rates::ActiveModel {
+            from_currency: ActiveValue::set("EUR".to_string()),
+            to_currency: ActiveValue::set("GBP".to_string()),
+            fiscal_year: ActiveValue::set(latest.fiscal_year),
+            rate: ActiveValue::set(Decimal::new(8500, 4)),
+            ..Default::default()
+        }
+        .insert(&ctx.db)
+        .await
+        .unwrap();
+
+        let res = request.get("/api/v1/exchange-rates").await;
+        res.assert_status_success();
+
+        let body: ExchangeRateResponse = res.json();
+        assert_eq!(body.rates.len(), 4 * 3, "CHF, EUR, GBP and USD pairs");
+        let gbp_eur = body
+            .rates
+            .iter()
+            .find(|r| r.from_currency == "GBP" && r.to_currency == "EUR")
+            .expect("GBP→EUR pair");
+        assert_eq!(gbp_eur.rate.round_dp(4), Decimal::new(11765, 4));
+        assert!(body
+            .rates
+            .iter()
+            .any(|r| r.from_currency == "GBP" && r.to_currency == "CHF"));
+
+        exchange_rate_provider::clear_cache().await;
+        restore_provider_url();
+    })
+    .await;
+}
+
 #[tokio::test]
 #[serial]
 async fn exchange_rates_returns_503_when_no_data() {
@@ -77,6 +77,41 @@ async fn verify_currency_normalization_metadata() {
     .await;
 }
 
+#[tokio::test]
+#[serial]
+async fn fiscal_year_end_blends_exchange_rates() {
+    request::<App, _, _>(|request, ctx| async move {
+        use backend::models::tickers;
+        use sea_orm::{ActiveModelTrait, ActiveValue, ColumnTrait, EntityTrait, QueryFilter};
+
+        let nestle = tickers::Entity::find()
+            .filter(tickers::Column::Ticker.eq("NESN.SW"))
+            .one(&ctx.db)
+            .await
+            .unwrap()
+            .unwrap();
+        let mut active: tickers::ActiveModel = nestle.into();
+        active.fiscal_year_end_month = ActiveValue::set(6);
+        active.update(&ctx.db).await.unwrap();
+
+        let res = request.post("/api/harvest/NESN.SW").await;
+        assert_eq!(res.status_code(), 200);
+        let data: steady_invest_logic::HistoricalData = res.json();
+        let rate = |year: i32| {
+            data.records
+                .iter()
+                .find(|r| r.fiscal_year == year)
+                .and_then(|r| r.exchange_rate)
+        };
+
+        // FY2024 runs July 2023 to June 2024: half 2023 (1.10), half 2024 (1.12)
+        assert_eq!(rate(2024), Some(rust_decimal::Decimal::new(111, 2)));
+        // No 2015 rate is cached, so FY2016 uses the 2016 rate alone
+        assert_eq!(rate(2016), Some(rust_decimal::Decimal::new(101, 2)));
+    })
+    .await;
+}
+
 #[tokio::test]
 #[serial]
 async fn verify_normalization_math_consistency() {
@@ -116,7 +151,10 @@ async fn harvest_returns_records_in_chronological_order() {
         assert_eq!(res.status_code(), 200);
 
         let data: steady_invest_logic::HistoricalData = res.json();
-        assert!(data.records.len() >= 2, "Need at least 2 records to verify ordering");
+        assert!(
+            data.records.len() >= 2,
+            "Need at least 2 records to verify ordering"
+        );
 
         for window in data.records.windows(2) {
             assert!(
@@ -141,3 +179,201 @@ async fn cannot_harvest_invalid_ticker() {
     })
     .await;
 }
+
+#[tokio::test]
+#[serial]
+async fn refresh_updates_restated_values() {
+    request::<App, _, _>(|request, ctx| async move {
+        use backend::models::historicals;
+        use sea_orm::{ActiveModelTrait, ActiveValue, ColumnTrait, EntityTrait, QueryFilter};
+
+        let res = request.post("/api/harvest/MSFT").await;
+        assert_eq!(res.status_code(), 200);
+        let harvested: steady_invest_logic::HistoricalData = res.json();
+        let fy = harvested.records.last().unwrap().fiscal_year;
+
+        // Simulate a stored value the provider has since restated
+        let stored = historicals::Entity::find()
+            .filter(historicals::Column::Ticker.eq("MSFT"))
+            .filter(historicals::Column::FiscalYear.eq(fy))
+            .one(&ctx.db)
+            .await
+            .unwrap()
+            .unwrap();
+        let provider_sales = stored.sales;
+        let mut active: historicals::ActiveModel = stored.into();
+        active.sales = ActiveValue::set(rust_decimal::Decimal::from(1));
+        active.update(&ctx.db).await.unwrap();
+
+        // A regular harvest leaves stored years alone
+        request.post("/api/harvest/MSFT").await;
+        let stored = historicals::Entity::find()
+            .filter(historicals::Column::Ticker.eq("MSFT"))
+            .filter(historicals::Column::FiscalYear.eq(fy))
+            .one(&ctx.db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.sales, rust_decimal::Decimal::from(1));
+
+        let res = request.post("/api/harvest/MSFT?refresh=true").await;
+        assert_eq!(res.status_code(), 200);
+        let outcome: serde_json::Value = res.json();
+        let summary = &outcome["summary"];
+        assert_eq!(summary["updated_years"], serde_json::json!([fy]));
+        assert_eq!(summary["inserted_years"], serde_json::json!([]));
+        assert_eq!(summary["kept_years"].as_array().unwrap().len(), 9);
+        let changes = summary["changes"].as_array().unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0]["field"], "sales");
+        assert_eq!(changes[0]["fiscal_year"], fy);
+        assert_eq!(outcome["data"]["ticker"], "MSFT");
+
+        let stored = historicals::Entity::find()
+            .filter(historicals::Column::Ticker.eq("MSFT"))
+            .filter(historicals::Column::FiscalYear.eq(fy))
+            .one(&ctx.db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.sales, provider_sales);
+
+        let res = request.get("/api/v1/system/audit-logs").await;
+        let logs: Vec<serde_json::Value> = res.json();
+        let restatements: Vec<_> = logs
+            .iter()
+            .filter(|l| l["ticker"] == "MSFT" && l["event_type"] == "Restatement")
+            .collect();
+        assert_eq!(restatements.len(), 1);
+        assert_eq!(restatements[0]["field_name"], format!("sales FY{fy}"));
+        assert_eq!(restatements[0]["old_value"], "1");
+
+        // Nothing left to restate
+        let res = request.post("/api/harvest/MSFT?refresh=true").await;
+        let outcome: serde_json::Value = res.json();
+        assert_eq!(outcome["summary"]["changes"], serde_json::json!([]));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn harvest_stores_quarters_for_ttm_eps() {
+    request::<App, _, _>(|request, ctx| async move {
+        use backend::models::historicals_quarterly;
+
+        let res = request.post("/api/harvest/MSFT").await;
+        assert_eq!(res.status_code(), 200);
+        let data: steady_invest_logic::HistoricalData = res.json();
+        assert_eq!(data.quarters.len(), 4);
+
+        let stored = historicals_quarterly::Entity::find_by_ticker(&ctx.db, "MSFT")
+            .await
+            .unwrap();
+        assert_eq!(stored.len(), 4);
+        assert_eq!(stored[0].source.as_deref(), Some("fixture"));
+
+        // The quarters run past the latest annual report, so EPS is TTM
+        let current = steady_invest_logic::calculate_current_figures(&data);
+        let ttm = current.ttm.unwrap();
+        assert_eq!((ttm.fiscal_year, ttm.fiscal_quarter), (2026, 1));
+        assert_eq!(ttm.eps, rust_decimal::Decimal::new(1406, 2));
+        assert_eq!(current.eps, Some(14.06));
+
+        // Harvesting again does not duplicate quarters
+        request.post("/api/harvest/MSFT?refresh=true").await;
+        let stored = historicals_quarterly::Entity::find_by_ticker(&ctx.db, "MSFT")
+            .await
+            .unwrap();
+        assert_eq!(stored.len(), 4);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn can_queue_and_poll_harvest_jobs() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request
+            .post("/api/v1/harvest/jobs")
+            .json(&serde_json::json!({ "tickers": ["aapl", "NOPE"] }))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let jobs: Vec<serde_json::Value> = res.json();
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[0]["ticker"], "AAPL");
+
+        // Test workers run in the foreground, so the jobs are already finished
+        let res = request
+            .get(&format!("/api/v1/harvest/jobs/{}", jobs[0]["id"]))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let job: serde_json::Value = res.json();
+        assert_eq!(job["status"], "done");
+        let data: steady_invest_logic::HistoricalData =
+            serde_json::from_value(job["result"]["data"].clone()).unwrap();
+        assert_eq!(data.records.len(), 10);
+
+        let res = request
+            .get(&format!("/api/v1/harvest/jobs/{}", jobs[1]["id"]))
+            .await;
+        let job: serde_json::Value = res.json();
+        assert_eq!(job["status"], "failed");
+        assert!(job["error"].is_string());
+
+        let res = request.get("/api/v1/harvest/jobs/999999").await;
+        assert_eq!(res.status_code(), 404);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn harvest_job_converts_to_display_currency() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request
+            .post("/api/v1/harvest/jobs")
+            .json(&serde_json::json!({ "tickers": ["NESN.SW"], "display_currency": "EUR" }))
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let jobs: Vec<serde_json::Value> = res.json();
+        assert_eq!(jobs[0]["display_currency"], "EUR");
+        assert_eq!(jobs[0]["status"], "done");
+
+        let data: steady_invest_logic::HistoricalData =
+            serde_json::from_value(jobs[0]["result"]["data"].clone()).unwrap();
+        let rate = data
+            .records
+            .iter()
+            .find(|r| r.fiscal_year == 2024)
+            .and_then(|r| r.exchange_rate);
+        // Only USD legs are seeded: CHF/USD 1.12 crossed with EUR/USD 1.09
+        assert_eq!(rate, Some(rust_decimal::Decimal::new(10275, 4)));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn cannot_queue_invalid_harvest_jobs() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request
+            .post("/api/v1/harvest/jobs")
+            .json(&serde_json::json!({ "tickers": [] }))
+            .await;
+        assert_eq!(res.status_code(), 400);
+
+        let res = request
+            .post("/api/v1/harvest/jobs")
+            .json(&serde_json::json!({ "tickers": ["MSFT", "THISISSUPERLONG"] }))
+            .await;
+        assert_eq!(res.status_code(), 400);
+
+        let res = request
+            .post("/api/v1/harvest/jobs")
+            .json(&serde_json::json!({ "tickers": ["MSFT"], "display_currency": "XYZ" }))
+            .await;
+        assert_eq!(res.status_code(), 400);
+    })
+    .await;
+}
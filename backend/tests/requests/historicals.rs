@@ -0,0 +1,104 @@
+use backend::app::App;
+use backend::models::tickers;
+use loco_rs::testing::prelude::*;
+use sea_orm::{ActiveModelTrait, ActiveValue};
+use serial_test::serial;
+
+const BOUNDARY: &str = "steadyinvest-upload";
+
+/// Builds a multipart body carrying `contents` as the `file` field.
+fn multipart_body(file_name: &str, contents: &str) -> Vec<u8> {
+    format!(
+        "--{BOUNDARY}\r\n\
+         Content-Disposition: form-data; name=\"file\"; filename=\"{file_name}\"\r\n\
+         Content-Type: text/csv\r\n\r\n\
+         {contents}\r\n\
+         --{BOUNDARY}--\r\n"
+    )
+    .into_bytes()
+}
+
+fn ten_year_csv() -> String {
+    let mut csv = String::from("fiscal_year,sales,eps,price_high,price_low,total_equity\n");
+    for year in 2015..=2024 {
+        let i = year - 2015;
+        csv.push_str(&format!(
+            "{year},{},{}.5,{},{},{}\n",
+            500 + i * 20,
+            10 + i,
+            300 + i * 10,
+            200 + i * 10,
+            900 + i * 30
+        ));
+    }
+    csv
+}
+
+async fn seed_ticker(ctx: &loco_rs::app::AppContext, ticker: &str) {
+    tickers::ActiveModel {
+        ticker: ActiveValue::set(ticker.to_string()),
+        name: ActiveValue::set("Upload Test AG".to_string()),
+        exchange: ActiveValue::set("SPI".to_string()),
+        currency: ActiveValue::set("CHF".to_string()),
+        ..Default::default()
+    }
+    .insert(&ctx.db)
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+#[serial]
+async fn can_upload_historicals_and_harvest_them() {
+    request::<App, _, _>(|request, ctx| async move {
+        seed_ticker(&ctx, "UPLD.SW").await;
+
+        let res = request
+            .post("/api/historicals/upld.sw/upload")
+            .content_type(&format!("multipart/form-data; boundary={BOUNDARY}"))
+            .bytes(multipart_body("upld.csv", &ten_year_csv()).into())
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let report: serde_json::Value = res.json();
+        assert_eq!(report["imported_years"].as_array().unwrap().len(), 10);
+        assert!(report["errors"].as_array().unwrap().is_empty());
+
+        // No provider covers the ticker; the uploaded table is the base layer
+        let res = request.post("/api/harvest/UPLD.SW").await;
+        assert_eq!(res.status_code(), 200);
+        let data: steady_invest_logic::HistoricalData = res.json();
+        assert_eq!(data.records.len(), 10);
+        assert_eq!(data.records[0].fiscal_year, 2015);
+        assert_eq!(data.records[0].sales, rust_decimal::Decimal::from(500));
+        assert_eq!(data.records[9].price_high, rust_decimal::Decimal::from(390));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn upload_with_invalid_rows_returns_error_report() {
+    request::<App, _, _>(|request, ctx| async move {
+        seed_ticker(&ctx, "BAD.SW").await;
+
+        let csv = "fiscal_year,sales,eps,price_high,price_low\n2023,100,1,40,50\n";
+        let res = request
+            .post("/api/historicals/BAD.SW/upload")
+            .content_type(&format!("multipart/form-data; boundary={BOUNDARY}"))
+            .bytes(multipart_body("bad.csv", csv).into())
+            .await;
+        assert_eq!(res.status_code(), 422);
+        let report: serde_json::Value = res.json();
+        assert_eq!(report["errors"][0]["row"], 2);
+        assert_eq!(report["errors"][0]["column"], "price_low");
+        assert!(report["imported_years"].as_array().unwrap().is_empty());
+
+        let res = request
+            .post("/api/historicals/BAD.SW/upload")
+            .content_type(&format!("multipart/form-data; boundary={BOUNDARY}"))
+            .bytes(multipart_body("bad.txt", csv).into())
+            .await;
+        assert_eq!(res.status_code(), 400);
+    })
+    .await;
+}
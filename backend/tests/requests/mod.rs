@@ -2,9 +2,13 @@ mod analyses;
 mod audit;
 mod auth;
 mod comparisons;
+mod corporate_actions;
+mod daily_prices;
 mod exchange_rates;
 pub mod harvest;
+mod historicals;
 mod prepare_data;
+mod quotes;
 mod snapshots;
 pub mod system;
 pub mod tickers;
@@ -0,0 +1,152 @@
+use backend::app::App;
+use loco_rs::testing::prelude::*;
+use rust_decimal::Decimal;
+use serial_test::serial;
+use steady_invest_logic::Quote;
+
+#[tokio::test]
+#[serial]
+async fn manual_quote_becomes_current_price() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.get("/api/v1/quotes/AAPL").await;
+        assert_eq!(res.status_code(), 404);
+
+        let res = request
+            .post("/api/v1/quotes")
+            .json(&serde_json::json!({
+                "ticker": "aapl",
+                "price": "180.50",
+                "as_of": "2026-10-16T20:00:00Z"
+            }))
+            .await;
+        assert_eq!(res.status_code(), 200);
+
+        let res = request
+            .post("/api/v1/quotes")
+            .json(&serde_json::json!({ "ticker": "AAPL", "price": "182.25" }))
+            .await;
+        assert_eq!(res.status_code(), 200);
+
+        let res = request.get("/api/v1/quotes/AAPL").await;
+        assert_eq!(res.status_code(), 200);
+        let quote: Quote = res.json();
+        assert_eq!(quote.price, Decimal::new(18225, 2));
+        assert_eq!(quote.currency, "USD");
+        assert_eq!(quote.source, "manual");
+
+        // Each entry is audited as an override of the current price
+        let res = request.get("/api/v1/system/audit-logs").await;
+        let logs: Vec<serde_json::Value> = res.json();
+        let overrides: Vec<&serde_json::Value> = logs
+            .iter()
+            .filter(|l| l["ticker"] == "AAPL" && l["field_name"] == "current_price")
+            .collect();
+        assert_eq!(overrides.len(), 2);
+        assert!(overrides.iter().all(|l| l["event_type"] == "Override"));
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn manual_quote_rejects_invalid_input() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request
+            .post("/api/v1/quotes")
+            .json(&serde_json::json!({ "ticker": "AAPL", "price": "0" }))
+            .await;
+        assert_eq!(res.status_code(), 400);
+
+        let res = request
+            .post("/api/v1/quotes")
+            .json(&serde_json::json!({ "ticker": "NOPE", "price": "10" }))
+            .await;
+        assert_eq!(res.status_code(), 400);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn harvest_stores_provider_quote() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request.post("/api/harvest/MSFT").await;
+        assert_eq!(res.status_code(), 200);
+
+        let res = request.get("/api/v1/quotes/MSFT").await;
+        assert_eq!(res.status_code(), 200);
+        let quote: Quote = res.json();
+        assert_eq!(quote.price, Decimal::new(51735, 2));
+        assert_eq!(quote.source, "fixture");
+
+        // Harvesting again does not duplicate the same quote
+        request.post("/api/harvest/MSFT").await;
+        let res = request.get("/api/v1/quotes/MSFT").await;
+        let again: Quote = res.json();
+        assert_eq!(again, quote);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn snapshot_captures_latest_quote() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let res = request
+            .post("/api/v1/quotes")
+            .json(&serde_json::json!({
+                "ticker": "AAPL",
+                "price": "200",
+                "as_of": "2026-10-16T20:00:00Z"
+            }))
+            .await;
+        assert_eq!(res.status_code(), 200);
+
+        let body = serde_json::json!({
+            "ticker": "AAPL",
+            "snapshot_data": {
+                "historical_data": {
+                    "ticker": "AAPL",
+                    "currency": "USD",
+                    "records": [{
+                        "fiscal_year": 2025,
+                        "sales": 100000,
+                        "eps": 5.0,
+                        "price_high": 150.0,
+                        "price_low": 120.0,
+                        "adjustment_factor": 1.0
+                    }],
+                    "is_complete": true,
+                    "is_split_adjusted": true
+                },
+                "projected_sales_cagr": 10.5,
+                "projected_eps_cagr": 10.0,
+                "projected_high_pe": 25.0,
+                "projected_low_pe": 15.0,
+                "analyst_note": "",
+                "captured_at": "2026-10-17T00:00:00Z"
+            },
+            "thesis_locked": true,
+            "notes": "Quote test"
+        });
+        let res = request.post("/api/v1/snapshots").json(&body).await;
+        res.assert_status_success();
+        let created: serde_json::Value = res.json();
+        assert_eq!(
+            created["snapshot_data"]["current_quote"]["source"],
+            "manual"
+        );
+
+        let res = request
+            .get(&format!("/api/v1/snapshots/{}/history", created["id"]))
+            .await;
+        res.assert_status_success();
+        let history: serde_json::Value = res.json();
+
+        // The quote replaces the latest fiscal-year high as current price
+        let entry = &history["snapshots"][0];
+        assert!((entry["current_price"].as_f64().unwrap() - 200.0).abs() < 0.01);
+        assert_eq!(entry["price_as_of"], "2026-10-16T20:00:00Z");
+    })
+    .await;
+}
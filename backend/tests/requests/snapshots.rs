@@ -171,6 +171,9 @@ async fn can_list_snapshots_with_filters() {
         assert!((all[0]["projected_eps_cagr"].as_f64().unwrap() - 12.0).abs() < 0.01);
         assert!((all[0]["projected_high_pe"].as_f64().unwrap() - 25.0).abs() < 0.01);
         assert!((all[0]["projected_low_pe"].as_f64().unwrap() - 15.0).abs() < 0.01);
+        // Section 5 metrics need historical records
+        assert!(all[0]["relative_value"].is_null());
+        assert!(all[0]["projected_average_return"].is_null());
 
         // Filter by thesis_locked=true — should get 1
         let res = request
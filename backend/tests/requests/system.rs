@@ -1,22 +1,106 @@
 use backend::app::App;
+use backend::models::{provider_health_checks, provider_rate_limits};
+use chrono::{Duration, Utc};
 use loco_rs::testing::prelude::*;
+use sea_orm::{ActiveModelTrait, ActiveValue};
 use serial_test::serial;
 
 #[tokio::test]
 #[serial]
-async fn can_get_system_health() {
+async fn system_health_reports_probe_results() {
+    // Nothing listens here, so the Yahoo probe fails at once
+    std::env::set_var("YAHOO_FINANCE_EXCHANGES", "SMI,DAX");
+    std::env::set_var("YAHOO_FINANCE_BASE_URL", "http://127.0.0.1:1");
+
     request::<App, _, _>(|request, _ctx| async move {
         let res = request.get("/api/v1/system/health").await;
         assert_eq!(res.status_code(), 200);
 
-        let body: serde_json::Value = serde_json::from_str(&res.text()).unwrap();
-        assert!(body.is_array());
-        let providers = body.as_array().unwrap();
-        assert_eq!(providers.len(), 3);
-        assert_eq!(providers[0]["name"], "CH (SWX)");
+        let providers: Vec<serde_json::Value> = res.json();
+        assert_eq!(providers.len(), 2);
+
+        // The bundled fixtures are the default provider and always answer
+        assert_eq!(providers[0]["name"], "fixture");
         assert_eq!(providers[0]["status"], "Online");
+        assert_eq!(providers[0]["last_24h"]["checks"], 1);
+        assert_eq!(providers[0]["last_24h"]["uptime_percent"], 100.0);
+
+        assert_eq!(providers[1]["name"], "yahoo+fixture");
+        assert_eq!(providers[1]["exchanges"], serde_json::json!(["DAX", "SMI"]));
+        assert_eq!(providers[1]["status"], "Offline");
+        assert!(providers[1]["error"].as_str().is_some());
+        assert_eq!(providers[1]["last_7d"]["uptime_percent"], 0.0);
+
+        // A second request within the probe interval reuses the stored probes
+        let res = request.get("/api/v1/system/health").await;
+        let again: Vec<serde_json::Value> = res.json();
+        assert_eq!(again[0]["last_24h"]["checks"], 1);
+        assert_eq!(again[1]["last_24h"]["checks"], 1);
     })
     .await;
+
+    std::env::remove_var("YAHOO_FINANCE_BASE_URL");
+    std::env::remove_var("YAHOO_FINANCE_EXCHANGES");
+}
+
+#[tokio::test]
+#[serial]
+async fn system_health_uptime_comes_from_stored_checks() {
+    request::<App, _, _>(|request, ctx| async move {
+        // Two failed probes earlier today drag the fixture provider's uptime
+        // below the degraded threshold even though it answers now
+        for hours in [2, 3] {
+            provider_health_checks::ActiveModel {
+                provider: ActiveValue::set("fixture".to_string()),
+                checked_at: ActiveValue::set((Utc::now() - Duration::hours(hours)).into()),
+                success: ActiveValue::set(false),
+                latency_ms: ActiveValue::set(5_000),
+                error: ActiveValue::set(Some("timeout".to_string())),
+                ..Default::default()
+            }
+            .insert(&ctx.db)
+            .await
+            .unwrap();
+        }
+
+        let res = request.get("/api/v1/system/health").await;
+        let providers: Vec<serde_json::Value> = res.json();
+        let fixture = &providers[0];
+        assert_eq!(fixture["name"], "fixture");
+        assert_eq!(fixture["status"], "Degraded");
+        assert_eq!(fixture["last_24h"]["checks"], 3);
+        assert!(fixture["error"].is_null());
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn system_health_reports_rate_limit_consumption() {
+    std::env::set_var("YAHOO_FINANCE_EXCHANGES", "SMI,DAX");
+    std::env::set_var("YAHOO_FINANCE_BASE_URL", "http://127.0.0.1:1");
+    std::env::set_var("YAHOO_FINANCE_RATE_LIMIT_PER_DAY", "10");
+
+    request::<App, _, _>(|request, ctx| async move {
+        let res = request.get("/api/v1/system/health").await;
+        let providers: Vec<serde_json::Value> = res.json();
+
+        // The Yahoo probe took one of ten daily calls; the fixtures are local
+        assert_eq!(providers[0]["rate_limit_percent"], 0);
+        assert_eq!(providers[1]["name"], "yahoo+fixture");
+        assert_eq!(providers[1]["rate_limit_percent"], 10);
+
+        let stored = provider_rate_limits::Model::find_by_name(&ctx.db, "yahoo")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.quota_consumed, 10);
+    })
+    .await;
+
+    std::env::remove_var("YAHOO_FINANCE_RATE_LIMIT_PER_DAY");
+    std::env::remove_var("YAHOO_FINANCE_BASE_URL");
+    std::env::remove_var("YAHOO_FINANCE_EXCHANGES");
 }
 
 #[tokio::test]
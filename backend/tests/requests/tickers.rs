@@ -1,6 +1,7 @@
 use backend::app::App;
 use loco_rs::testing::prelude::*;
 use serial_test::serial;
+use steady_invest_logic::TickerSearchPage;
 
 #[tokio::test]
 #[serial]
@@ -24,3 +25,145 @@ async fn can_get_search() {
     })
     .await;
 }
+
+const BOUNDARY: &str = "steadyinvest-tickers";
+
+/// Builds a multipart body carrying `contents` as the `file` field.
+fn multipart_body(contents: &str) -> Vec<u8> {
+    format!(
+        "--{BOUNDARY}\r\n\
+         Content-Disposition: form-data; name=\"file\"; filename=\"constituents.csv\"\r\n\
+         Content-Type: text/csv\r\n\r\n\
+         {contents}\r\n\
+         --{BOUNDARY}--\r\n"
+    )
+    .into_bytes()
+}
+
+#[tokio::test]
+#[serial]
+async fn index_import_resolves_identifiers_in_search() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let csv = "\
+Symbol,Company,ISIN,Valor,Weight
+NESN,Nestle SA,CH0038863350,3886335,14.2
+UBSG,UBS Group AG,CH0244767585,24476758,6.1
+";
+        let res = request
+            .post("/api/tickers/import?index=SMI")
+            .content_type(&format!("multipart/form-data; boundary={BOUNDARY}"))
+            .bytes(multipart_body(csv).into())
+            .await;
+        assert_eq!(res.status_code(), 200);
+        let report: serde_json::Value = res.json();
+        assert_eq!(report["inserted"], 1);
+        assert_eq!(report["updated"], 1);
+
+        for query in ["CH0244767585", "24 476 758", "ch0244767585"] {
+            let res = request
+                .get(&format!(
+                    "/api/tickers/search?q={}",
+                    query.replace(' ', "%20")
+                ))
+                .await;
+            let found: TickerSearchPage = res.json();
+            assert_eq!(found.total, 1, "query {query}");
+            assert_eq!(found.results[0].ticker, "UBSG.SW");
+            assert_eq!(found.results[0].exchange, "SMI");
+            assert_eq!(found.results[0].currency, "CHF");
+        }
+
+        // Seeded German tickers carry their WKN
+        let res = request.get("/api/tickers/search?q=716460").await;
+        let found: TickerSearchPage = res.json();
+        assert_eq!(found.total, 1);
+        assert_eq!(found.results[0].ticker, "SAP.DE");
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn invalid_ticker_import_stores_nothing() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let csv = "\
+ticker,name,exchange,currency,isin,wkn
+NEW1,First New,XETRA,EUR,DE0007164600,716460
+NEW2,Second New,XETRA,EUR,DE0007164601,71646
+";
+        let res = request
+            .post("/api/tickers/import")
+            .content_type(&format!("multipart/form-data; boundary={BOUNDARY}"))
+            .bytes(multipart_body(csv).into())
+            .await;
+        assert_eq!(res.status_code(), 422);
+        let report: serde_json::Value = res.json();
+        assert_eq!(report["inserted"], 0);
+        let columns: Vec<&str> = report["errors"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|e| e["column"].as_str().unwrap())
+            .collect();
+        assert_eq!(columns, vec!["isin", "wkn"]);
+
+        let res = request.get("/api/tickers/search?q=NEW1").await;
+        let found: TickerSearchPage = res.json();
+        assert!(found.results.is_empty());
+
+        let res = request
+            .post("/api/tickers/import?index=FTSE")
+            .content_type(&format!("multipart/form-data; boundary={BOUNDARY}"))
+            .bytes(multipart_body(csv).into())
+            .await;
+        assert_eq!(res.status_code(), 400);
+    })
+    .await;
+}
+
+#[tokio::test]
+#[serial]
+async fn search_ranks_and_paginates_matches() {
+    request::<App, _, _>(|request, _ctx| async move {
+        let csv = "\
+ticker,name,exchange,currency
+MUV2.DE,Münchener Rückversicherungs-Gesellschaft AG,DAX,EUR
+AAA,Aapl Tracker Fund,NYSE,USD
+";
+        let res = request
+            .post("/api/tickers/import")
+            .content_type(&format!("multipart/form-data; boundary={BOUNDARY}"))
+            .bytes(multipart_body(csv).into())
+            .await;
+        assert_eq!(res.status_code(), 200);
+
+        // The exact symbol ranks before a name match that sorts first
+        let res = request.get("/api/tickers/search?q=aapl").await;
+        let found: TickerSearchPage = res.json();
+        let symbols: Vec<&str> = found.results.iter().map(|t| t.ticker.as_str()).collect();
+        assert_eq!(symbols, vec!["AAPL", "AAA"]);
+
+        // Diacritics are folded and a missing letter is tolerated
+        for query in ["Nestle", "Muenchener", "Munchener"] {
+            let res = request.get(&format!("/api/tickers/search?q={query}")).await;
+            let found: TickerSearchPage = res.json();
+            assert!(!found.results.is_empty(), "query {query}");
+        }
+
+        // Every NASDAQ ticker matches by exchange; pages do not overlap
+        let res = request.get("/api/tickers/search?q=NASDAQ&limit=3").await;
+        let first: TickerSearchPage = res.json();
+        assert_eq!(first.limit, 3);
+        assert_eq!(first.results.len(), 3);
+        assert_eq!(first.total, 4);
+
+        let res = request
+            .get("/api/tickers/search?q=NASDAQ&limit=3&offset=3")
+            .await;
+        let second: TickerSearchPage = res.json();
+        assert_eq!(second.offset, 3);
+        assert_eq!(second.results.len(), 1);
+        assert!(!first.results.contains(&second.results[0]));
+    })
+    .await;
+}
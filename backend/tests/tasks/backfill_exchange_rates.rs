@@ -0,0 +1,121 @@
+use backend::app::App;
+use backend::models::exchange_rates;
+use backend::services::exchange;
+use chrono::{Datelike, Utc};
+use loco_rs::{boot::run_task, task, testing::prelude::*};
+use rust_decimal::Decimal;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use serial_test::serial;
+use std::str::FromStr;
+use steady_invest_logic::FiscalCalendar;
+
+fn vars(args: &[(&str, &str)]) -> task::Vars {
+    task::Vars::from_cli_args(
+        args.iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect(),
+    )
+}
+
+#[tokio::test]
+#[serial]
+async fn test_backfill_exchange_rates_from_ecb_csv() {
+    let boot = boot_test::<App>().await.unwrap();
+    let ctx = &boot.app_context;
+
+    let year = Utc::now().year();
+    let last_year = year - 1;
+    let csv = format!(
+        "Date,USD,CHF,\n\
+         {year}-01-05,1.10,0.95,\n\
+         {last_year}-12-31,1.00,0.90,\n\
+         {last_year}-06-28,1.20,1.00,\n"
+    );
+    let path = std::env::temp_dir().join("steadyinvest-eurofxref-hist.csv");
+    std::fs::write(&path, csv).unwrap();
+    let path = path.display().to_string();
+
+    let result = run_task::<App>(
+        ctx,
+        Some(&"backfill_exchange_rates".to_string()),
+        &vars(&[("file", &path), ("years", "2")]),
+    )
+    .await;
+    assert!(result.is_ok(), "{result:?}");
+
+    let chf_usd = exchange_rates::Entity::find()
+        .filter(exchange_rates::Column::FromCurrency.eq("CHF"))
+        .filter(exchange_rates::Column::ToCurrency.eq("USD"))
+        .filter(exchange_rates::Column::FiscalYear.eq(last_year))
+        .one(&ctx.db)
+        .await
+        .unwrap()
+        .unwrap();
+    // (1.00/0.90 + 1.20/1.00) / 2, and the rate on the year's last day
+    assert_eq!(chf_usd.rate, Decimal::from_str("1.1556").unwrap());
+    assert_eq!(chf_usd.year_end_rate, Decimal::from_str("1.1111").ok());
+
+    let rate = exchange::get_rate(
+        &ctx.db,
+        "CHF",
+        "USD",
+        last_year,
+        FiscalCalendar::CALENDAR_YEAR,
+    )
+    .await
+    .unwrap();
+    assert_eq!(rate, Decimal::from_str("1.1556").ok());
+
+    // A June year end closes on June's last trading day, not December's
+    let june = FiscalCalendar::new(6).unwrap();
+    let closing = exchange::get_closing_rate(&ctx.db, "CHF", "USD", last_year, june)
+        .await
+        .unwrap();
+    assert_eq!(closing, Decimal::from_str("1.2").ok());
+    let closing = exchange::get_closing_rate(
+        &ctx.db,
+        "CHF",
+        "USD",
+        last_year,
+        FiscalCalendar::CALENDAR_YEAR,
+    )
+    .await
+    .unwrap();
+    assert_eq!(closing, Decimal::from_str("1.1111").ok());
+    // No rate was fixed in the last year's March
+    let march = FiscalCalendar::new(3).unwrap();
+    let closing = exchange::get_closing_rate(&ctx.db, "CHF", "USD", last_year, march)
+        .await
+        .unwrap();
+    assert_eq!(closing, None);
+
+    // Every ordered pair of CHF, EUR and USD for both years
+    let stored = exchange_rates::Entity::find()
+        .filter(exchange_rates::Column::FiscalYear.gte(last_year))
+        .all(&ctx.db)
+        .await
+        .unwrap();
+    assert_eq!(stored.len(), 12);
+    assert!(stored.iter().all(|r| r.year_end_rate.is_some()));
+}
+
+#[tokio::test]
+#[serial]
+async fn test_backfill_exchange_rates_rejects_invalid_arguments() {
+    let boot = boot_test::<App>().await.unwrap();
+    let ctx = &boot.app_context;
+    let task = Some("backfill_exchange_rates".to_string());
+
+    assert!(
+        run_task::<App>(ctx, task.as_ref(), &vars(&[("years", "none")]))
+            .await
+            .is_err()
+    );
+    assert!(run_task::<App>(
+        ctx,
+        task.as_ref(),
+        &vars(&[("file", "/nonexistent/ecb.csv")])
+    )
+    .await
+    .is_err());
+}
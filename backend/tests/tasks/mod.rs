@@ -1 +1,2 @@
-
+mod backfill_exchange_rates;
+mod refresh_all;
@@ -0,0 +1,92 @@
+use backend::app::App;
+use backend::models::_entities::{analysis_snapshots, audit_logs, tickers};
+use backend::services::providers::ProviderRegistry;
+use backend::services::refresh::{self, RefreshOptions};
+use loco_rs::{boot::run_task, task, testing::prelude::*};
+use sea_orm::{ActiveModelTrait, ActiveValue, ColumnTrait, EntityTrait, QueryFilter};
+use serial_test::serial;
+
+async fn add_snapshot(ctx: &loco_rs::app::AppContext, symbol: &str) {
+    let ticker = tickers::Entity::find()
+        .filter(tickers::Column::Ticker.eq(symbol))
+        .one(&ctx.db)
+        .await
+        .unwrap()
+        .unwrap();
+    analysis_snapshots::ActiveModel {
+        user_id: ActiveValue::set(1),
+        ticker_id: ActiveValue::set(ticker.id),
+        snapshot_data: ActiveValue::set(serde_json::json!({})),
+        thesis_locked: ActiveValue::set(false),
+        captured_at: ActiveValue::set(chrono::Utc::now().into()),
+        deleted_at: ActiveValue::set(None),
+        ..Default::default()
+    }
+    .insert(&ctx.db)
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+#[serial]
+async fn test_refresh_all_respects_provider_budget() {
+    let boot = boot_test::<App>().await.unwrap();
+    let ctx = &boot.app_context;
+    add_snapshot(ctx, "MSFT").await;
+    add_snapshot(ctx, "AAPL").await;
+    add_snapshot(ctx, "AAPL").await;
+
+    let tracked: Vec<String> = refresh::tracked_tickers(&ctx.db)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|t| t.ticker)
+        .collect();
+    assert_eq!(tracked, vec!["AAPL", "MSFT"]);
+
+    // Both tickers are served by the fixture provider
+    let options = RefreshOptions {
+        concurrency: 2,
+        provider_budget: 1,
+    };
+    let report = refresh::refresh_all(ctx, &ProviderRegistry::from_env(&ctx.db), options)
+        .await
+        .unwrap();
+    assert_eq!(report.refreshed, vec!["AAPL"]);
+    assert_eq!(report.skipped, vec!["MSFT"]);
+    assert!(report.failed.is_empty());
+
+    let summaries = audit_logs::Entity::find()
+        .filter(audit_logs::Column::EventType.eq("RefreshSummary"))
+        .all(&ctx.db)
+        .await
+        .unwrap();
+    assert_eq!(summaries.len(), 1);
+    assert_eq!(
+        summaries[0].new_value.as_deref(),
+        Some(report.summary().as_str())
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn test_run_refresh_all_task() {
+    let boot = boot_test::<App>().await.unwrap();
+    let ctx = &boot.app_context;
+    add_snapshot(ctx, "MSFT").await;
+
+    assert!(run_task::<App>(
+        ctx,
+        Some(&"refresh_all".to_string()),
+        &task::Vars::from_cli_args(vec![("concurrency".to_string(), "1".to_string())]),
+    )
+    .await
+    .is_ok());
+
+    let invalid = task::Vars::from_cli_args(vec![("budget".to_string(), "none".to_string())]);
+    assert!(
+        run_task::<App>(ctx, Some(&"refresh_all".to_string()), &invalid)
+            .await
+            .is_err()
+    );
+}
@@ -0,0 +1,67 @@
+use backend::app::App;
+use backend::models::harvest_jobs;
+use backend::workers::downloader::{DownloadWorker, DownloadWorkerArgs};
+use loco_rs::{bgworker::BackgroundWorker, testing::prelude::*};
+use sea_orm::EntityTrait;
+use serial_test::serial;
+
+#[tokio::test]
+#[serial]
+async fn test_run_harvest_job() {
+    let boot = boot_test::<App>().await.unwrap();
+    let ctx = &boot.app_context;
+
+    let job = harvest_jobs::Model::enqueue(&ctx.db, "MSFT", false, None)
+        .await
+        .unwrap();
+    assert_eq!(job.status, harvest_jobs::QUEUED);
+
+    DownloadWorker::build(ctx)
+        .perform(DownloadWorkerArgs { job_id: job.id })
+        .await
+        .unwrap();
+
+    let job = harvest_jobs::Entity::find_by_id(job.id)
+        .one(&ctx.db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(job.status, harvest_jobs::DONE);
+    assert!(job.started_at.is_some());
+    assert!(job.finished_at.is_some());
+    let result = job.result.unwrap();
+    assert_eq!(result["data"]["ticker"], "MSFT");
+    assert_eq!(
+        result["summary"]["inserted_years"]
+            .as_array()
+            .unwrap()
+            .len(),
+        10
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn test_failed_harvest_is_recorded() {
+    let boot = boot_test::<App>().await.unwrap();
+    let ctx = &boot.app_context;
+
+    let job = harvest_jobs::Model::enqueue(&ctx.db, "NOPE", false, None)
+        .await
+        .unwrap();
+
+    // The failure is stored on the job, not returned to the queue
+    DownloadWorker::build(ctx)
+        .perform(DownloadWorkerArgs { job_id: job.id })
+        .await
+        .unwrap();
+
+    let job = harvest_jobs::Entity::find_by_id(job.id)
+        .one(&ctx.db)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(job.status, harvest_jobs::FAILED);
+    assert!(job.error.is_some());
+    assert!(job.result.is_none());
+}
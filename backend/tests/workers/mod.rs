@@ -1 +1 @@
-
+mod downloader;
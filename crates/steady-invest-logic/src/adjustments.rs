@@ -1,4 +1,4 @@
-use crate::types::HistoricalData;
+use crate::types::{FxConvention, HistoricalData};
 
 impl HistoricalData {
     /// Applies split and dividend adjustments to per-share and share-count fields.
@@ -7,6 +7,7 @@ impl HistoricalData {
     /// by each record's `adjustment_factor`, and also multiplies
     /// `shares_outstanding` so pre-split share counts are comparable to
     /// post-split values. Records with a factor of `1` are left unchanged.
+    /// Quarterly `eps` and prices are adjusted the same way.
     /// Sets `is_split_adjusted = true` only when at least one record has a
     /// non-unity factor; for tickers without splits the flag stays `false`
     /// (no "Split-Adjusted" badge in UI) and subsequent calls re-scan harmlessly.
@@ -29,36 +30,96 @@ impl HistoricalData {
                 self.is_split_adjusted = true;
             }
         }
+
+        for quarter in &mut self.quarters {
+            let factor = quarter.adjustment_factor;
+            if factor != rust_decimal::Decimal::ONE && !factor.is_zero() {
+                quarter.eps *= factor;
+                for price in [&mut quarter.price_high, &mut quarter.price_low]
+                    .into_iter()
+                    .flatten()
+                {
+                    *price *= factor;
+                }
+                self.is_split_adjusted = true;
+            }
+        }
+    }
+
+    /// Normalizes all monetary fields to `target_currency` with the default
+    /// [`FxConvention`]: yearly average rates for income-statement items and
+    /// year-end rates for `total_equity`.
+    pub fn apply_normalization(&mut self, target_currency: &str) {
+        self.apply_normalization_with(target_currency, FxConvention::default());
     }
 
-    /// Normalizes all monetary fields to `target_currency` using per-record exchange rates.
+    /// Normalizes all monetary fields to `target_currency` at the rates
+    /// `convention` picks.
     ///
     /// Converts `sales`, `eps`, `price_high`, `price_low`, `net_income`,
-    /// `pretax_income`, `total_equity`, and `dividend_per_share`. Records
-    /// without an `exchange_rate` are left unchanged. `shares_outstanding`
-    /// is not converted (it is a count, not a monetary value). This method
-    /// is idempotent for the same target currency.
-    pub fn apply_normalization(&mut self, target_currency: &str) {
+    /// `pretax_income`, `total_equity`, and `dividend_per_share`. Only
+    /// `total_equity` is a balance-sheet item; under
+    /// [`FxConvention::AverageAndYearEnd`] it takes the record's
+    /// `closing_exchange_rate` while every other field takes the average
+    /// `exchange_rate`. A missing closing or spot rate falls back to the
+    /// average rate; records without an `exchange_rate` are left unchanged.
+    /// `shares_outstanding` is not converted (it is a count, not a monetary
+    /// value). Quarterly records are converted with their own
+    /// `exchange_rate`, or the spot rate. This method is idempotent for the
+    /// same target currency.
+    pub fn apply_normalization_with(&mut self, target_currency: &str, convention: FxConvention) {
         if self.display_currency.as_deref() == Some(target_currency) {
             return;
         }
 
+        let spot = self.spot_exchange_rate;
         for record in &mut self.records {
-            if let Some(rate) = record.exchange_rate {
+            let average = record.exchange_rate;
+            let closing = record.closing_exchange_rate.or(average);
+            let (flow_rate, balance_rate) = match convention {
+                FxConvention::AverageAndYearEnd => (average, closing),
+                FxConvention::Average => (average, average),
+                FxConvention::YearEnd => (closing, closing),
+                FxConvention::Spot => (spot.or(average), spot.or(average)),
+            };
+
+            if let Some(rate) = flow_rate {
                 record.sales *= rate;
                 record.eps *= rate;
                 record.price_high *= rate;
                 record.price_low *= rate;
-                if let Some(ref mut val) = record.net_income {
-                    *val *= rate;
-                }
-                if let Some(ref mut val) = record.pretax_income {
+                for val in [
+                    &mut record.net_income,
+                    &mut record.pretax_income,
+                    &mut record.dividend_per_share,
+                ]
+                .into_iter()
+                .flatten()
+                {
                     *val *= rate;
                 }
-                if let Some(ref mut val) = record.total_equity {
-                    *val *= rate;
-                }
-                if let Some(ref mut val) = record.dividend_per_share {
+            }
+            if let (Some(rate), Some(val)) = (balance_rate, record.total_equity.as_mut()) {
+                *val *= rate;
+            }
+        }
+        for quarter in &mut self.quarters {
+            let rate = match convention {
+                FxConvention::Spot => spot.or(quarter.exchange_rate),
+                _ => quarter.exchange_rate,
+            };
+            if let Some(rate) = rate {
+                quarter.sales *= rate;
+                quarter.eps *= rate;
+                for val in [
+                    &mut quarter.net_income,
+                    &mut quarter.pretax_income,
+                    &mut quarter.price_high,
+                    &mut quarter.price_low,
+                ]
+                .into_iter()
+                .flatten()
+                {
                     *val *= rate;
                 }
             }
@@ -78,6 +139,7 @@ mod tests {
             ticker: "NESN.SW".to_string(),
             currency: "CHF".to_string(),
             display_currency: None,
+            spot_exchange_rate: None,
             is_complete: true,
             is_split_adjusted: true,
             records: vec![HistoricalYearlyData {
@@ -88,6 +150,7 @@ mod tests {
                 price_low: Decimal::from(800),
                 adjustment_factor: Decimal::ONE,
                 exchange_rate: Some(Decimal::new(11, 1)), // 1.1
+                closing_exchange_rate: None,
                 net_income: None,
                 pretax_income: None,
                 total_equity: None,
@@ -96,6 +159,8 @@ mod tests {
                 overrides: vec![],
             }],
             pe_range_analysis: None,
+            quarters: Vec::new(),
+            anomalies: Vec::new(),
         };
 
         data.apply_normalization("USD");
@@ -111,6 +176,7 @@ mod tests {
             ticker: "AAPL".to_string(),
             currency: "USD".to_string(),
             display_currency: None,
+            spot_exchange_rate: None,
             is_complete: true,
             is_split_adjusted: false,
             records: vec![
@@ -134,6 +200,8 @@ mod tests {
                 },
             ],
             pe_range_analysis: None,
+            quarters: Vec::new(),
+            anomalies: Vec::new(),
         };
 
         data.apply_adjustments();
@@ -176,6 +244,76 @@ mod tests {
         );
     }
 
+    fn two_rate_data() -> HistoricalData {
+        HistoricalData {
+            ticker: "NESN.SW".to_string(),
+            currency: "CHF".to_string(),
+            spot_exchange_rate: Some(Decimal::new(12, 1)), // 1.2
+            records: vec![HistoricalYearlyData {
+                fiscal_year: 2023,
+                sales: Decimal::from(100),
+                eps: Decimal::from(10),
+                total_equity: Some(Decimal::from(50)),
+                exchange_rate: Some(Decimal::new(11, 1)), // 1.1
+                closing_exchange_rate: Some(Decimal::new(9, 1)), // 0.9
+                ..Default::default()
+            }],
+            quarters: vec![crate::types::HistoricalQuarterlyData {
+                fiscal_year: 2024,
+                fiscal_quarter: 1,
+                sales: Decimal::from(30),
+                exchange_rate: Some(Decimal::ONE),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_normalization_converts_equity_at_year_end_rate() {
+        let mut data = two_rate_data();
+        data.apply_normalization("USD");
+
+        let record = &data.records[0];
+        assert_eq!(record.sales, Decimal::from(110)); // 100 * 1.1
+        assert_eq!(record.eps, Decimal::from(11)); // 10 * 1.1
+        assert_eq!(record.total_equity, Some(Decimal::from(45))); // 50 * 0.9
+        assert_eq!(data.quarters[0].sales, Decimal::from(30));
+    }
+
+    #[test]
+    fn test_normalization_conventions() {
+        let mut average = two_rate_data();
+        average.apply_normalization_with("USD", FxConvention::Average);
+        assert_eq!(average.records[0].sales, Decimal::from(110));
+        assert_eq!(average.records[0].total_equity, Some(Decimal::from(55)));
+
+        let mut year_end = two_rate_data();
+        year_end.apply_normalization_with("USD", FxConvention::YearEnd);
+        assert_eq!(year_end.records[0].sales, Decimal::from(90));
+        assert_eq!(year_end.records[0].total_equity, Some(Decimal::from(45)));
+
+        let mut spot = two_rate_data();
+        spot.apply_normalization_with("USD", FxConvention::Spot);
+        assert_eq!(spot.records[0].sales, Decimal::from(120));
+        assert_eq!(spot.records[0].total_equity, Some(Decimal::from(60)));
+        assert_eq!(spot.quarters[0].sales, Decimal::from(36));
+    }
+
+    #[test]
+    fn test_normalization_falls_back_to_average_rate() {
+        let mut data = two_rate_data();
+        data.spot_exchange_rate = None;
+        data.records[0].closing_exchange_rate = None;
+        let mut spot = data.clone();
+
+        data.apply_normalization("USD");
+        assert_eq!(data.records[0].total_equity, Some(Decimal::from(55)));
+
+        spot.apply_normalization_with("USD", FxConvention::Spot);
+        assert_eq!(spot.records[0].sales, Decimal::from(110));
+    }
+
     #[test]
     fn test_split_adjustment_handles_dividend_and_shares() {
         let mut data = HistoricalData {
@@ -0,0 +1,387 @@
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::types::*;
+
+/// Scales a median absolute deviation to be comparable with a standard
+/// deviation (Iglewicz & Hoaglin modified z-score).
+const MAD_SCALE: f64 = 0.6745;
+
+/// Fewest year-over-year changes needed before outliers are looked for.
+const MIN_CHANGES_FOR_JUMPS: usize = 4;
+
+/// Finds data gaps and implausible values in historical data.
+///
+/// Checks, per record (oldest first):
+///
+/// - fiscal years missing between the first and last record
+/// - sales or prices that are zero, i.e. were not reported
+/// - zero or negative shareholders' equity
+/// - EPS changing sign from the previous year
+/// - year-over-year changes in sales and EPS whose modified z-score exceeds
+///   [`AnomalyConfig::jump_z_score`]
+/// - a low price above the high price
+/// - pre-tax income above sales, or a pre-tax margin further than
+///   [`AnomalyConfig::max_margin_deviation`] points from the series' median
+///
+/// # Examples
+///
+/// ```
+/// use steady_invest_logic::{
+///     AnomalyConfig, AnomalyKind, HistoricalData, HistoricalYearlyData, detect_anomalies,
+/// };
+/// use rust_decimal::Decimal;
+///
+/// let year = |fiscal_year, low| HistoricalYearlyData {
+///     fiscal_year,
+///     sales: Decimal::from(100),
+///     eps: Decimal::from(2),
+///     price_high: Decimal::from(50),
+///     price_low: Decimal::from(low),
+///     ..Default::default()
+/// };
+/// let data = HistoricalData {
+///     records: vec![year(2021, 40), year(2023, 60)],
+///     ..Default::default()
+/// };
+///
+/// let kinds: Vec<AnomalyKind> = detect_anomalies(&data, &AnomalyConfig::default())
+///     .iter()
+///     .map(|a| a.kind)
+///     .collect();
+/// assert_eq!(kinds, vec![AnomalyKind::MissingYear, AnomalyKind::PriceRangeInverted]);
+/// ```
+pub fn detect_anomalies(data: &HistoricalData, config: &AnomalyConfig) -> Vec<Anomaly> {
+    let mut records: Vec<&HistoricalYearlyData> = data.records.iter().collect();
+    records.sort_by_key(|r| r.fiscal_year);
+
+    let mut anomalies = Vec::new();
+    detect_missing_years(&records, &mut anomalies);
+    for record in &records {
+        detect_record_problems(record, &mut anomalies);
+    }
+    detect_eps_sign_flips(&records, &mut anomalies);
+    detect_jumps(&records, "sales", |r| r.sales, config, &mut anomalies);
+    detect_jumps(&records, "eps", |r| r.eps, config, &mut anomalies);
+    detect_margin_inconsistencies(&records, config, &mut anomalies);
+
+    // Stable sort keeps the check order within a year
+    anomalies.sort_by_key(|a| a.fiscal_year);
+    anomalies
+}
+
+fn push(anomalies: &mut Vec<Anomaly>, kind: AnomalyKind, year: i32, field: &str, message: String) {
+    anomalies.push(Anomaly {
+        kind,
+        fiscal_year: year,
+        field: field.to_string(),
+        message,
+    });
+}
+
+fn detect_missing_years(records: &[&HistoricalYearlyData], anomalies: &mut Vec<Anomaly>) {
+    for pair in records.windows(2) {
+        for year in pair[0].fiscal_year + 1..pair[1].fiscal_year {
+            push(
+                anomalies,
+                AnomalyKind::MissingYear,
+                year,
+                "fiscal_year",
+                format!("No data for fiscal year {year}"),
+            );
+        }
+    }
+}
+
+fn detect_record_problems(record: &HistoricalYearlyData, anomalies: &mut Vec<Anomaly>) {
+    let year = record.fiscal_year;
+    for (field, value) in [
+        ("sales", record.sales),
+        ("price_high", record.price_high),
+        ("price_low", record.price_low),
+    ] {
+        if value.is_zero() {
+            push(
+                anomalies,
+                AnomalyKind::MissingValue,
+                year,
+                field,
+                format!("{field} is missing for {year}"),
+            );
+        }
+    }
+
+    if let Some(equity) = record.total_equity
+        && equity <= Decimal::ZERO
+    {
+        push(
+            anomalies,
+            AnomalyKind::NonPositiveEquity,
+            year,
+            "total_equity",
+            format!("Shareholders' equity is {equity} in {year}"),
+        );
+    }
+
+    if record.price_low > record.price_high {
+        push(
+            anomalies,
+            AnomalyKind::PriceRangeInverted,
+            year,
+            "price_low",
+            format!(
+                "Low price {} is above high price {} in {year}",
+                record.price_low, record.price_high
+            ),
+        );
+    }
+
+    if let Some(pretax) = record.pretax_income
+        && record.sales > Decimal::ZERO
+        && pretax > record.sales
+    {
+        push(
+            anomalies,
+            AnomalyKind::InconsistentFigures,
+            year,
+            "pretax_income",
+            format!(
+                "Pre-tax income {pretax} exceeds sales {} in {year}",
+                record.sales
+            ),
+        );
+    }
+}
+
+fn detect_eps_sign_flips(records: &[&HistoricalYearlyData], anomalies: &mut Vec<Anomaly>) {
+    for pair in records.windows(2) {
+        let (prev, cur) = (pair[0], pair[1]);
+        if prev.eps.is_zero() || cur.eps.is_zero() {
+            continue;
+        }
+        if prev.eps.is_sign_positive() != cur.eps.is_sign_positive() {
+            push(
+                anomalies,
+                AnomalyKind::EpsSignFlip,
+                cur.fiscal_year,
+                "eps",
+                format!(
+                    "EPS changed sign from {} in {} to {} in {}",
+                    prev.eps, prev.fiscal_year, cur.eps, cur.fiscal_year
+                ),
+            );
+        }
+    }
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// Flags year-over-year relative changes of `field` that are outliers by
+/// modified z-score. Only consecutive years with a non-zero base are compared.
+fn detect_jumps(
+    records: &[&HistoricalYearlyData],
+    field: &str,
+    value: impl Fn(&HistoricalYearlyData) -> Decimal,
+    config: &AnomalyConfig,
+    anomalies: &mut Vec<Anomaly>,
+) {
+    let changes: Vec<(i32, f64)> = records
+        .windows(2)
+        .filter(|pair| pair[1].fiscal_year == pair[0].fiscal_year + 1)
+        .filter_map(|pair| {
+            let prev = value(pair[0]).to_f64()?;
+            let cur = value(pair[1]).to_f64()?;
+            (prev != 0.0).then(|| (pair[1].fiscal_year, (cur - prev) / prev.abs()))
+        })
+        .collect();
+    if changes.len() < MIN_CHANGES_FOR_JUMPS {
+        return;
+    }
+
+    let center = median(&mut changes.iter().map(|(_, c)| *c).collect::<Vec<_>>());
+    let mad = median(
+        &mut changes
+            .iter()
+            .map(|(_, c)| (c - center).abs())
+            .collect::<Vec<_>>(),
+    );
+    if mad == 0.0 {
+        return;
+    }
+
+    for (year, change) in changes {
+        let z = MAD_SCALE * (change - center) / mad;
+        if z.abs() > config.jump_z_score {
+            push(
+                anomalies,
+                AnomalyKind::ImplausibleJump,
+                year,
+                field,
+                format!(
+                    "{field} changed {:+.1}% in {year}, far outside its usual range (z = {z:.1})",
+                    change * 100.0
+                ),
+            );
+        }
+    }
+}
+
+/// Compares each year's pre-tax income with what its sales would yield at
+/// the series' median pre-tax margin.
+fn detect_margin_inconsistencies(
+    records: &[&HistoricalYearlyData],
+    config: &AnomalyConfig,
+    anomalies: &mut Vec<Anomaly>,
+) {
+    let margins: Vec<(&HistoricalYearlyData, f64)> = records
+        .iter()
+        .filter(|r| r.sales > Decimal::ZERO)
+        .filter_map(|r| {
+            let pretax = r.pretax_income?.to_f64()?;
+            let sales = r.sales.to_f64()?;
+            Some((*r, pretax / sales * 100.0))
+        })
+        .collect();
+    if margins.len() < 3 {
+        return;
+    }
+
+    let typical = median(&mut margins.iter().map(|(_, m)| *m).collect::<Vec<_>>());
+    for (record, margin) in margins {
+        if (margin - typical).abs() > config.max_margin_deviation {
+            let expected = record.sales.to_f64().unwrap_or(0.0) * typical / 100.0;
+            push(
+                anomalies,
+                AnomalyKind::InconsistentFigures,
+                record.fiscal_year,
+                "pretax_income",
+                format!(
+                    "Pre-tax income {} is inconsistent with sales {} at the usual {typical:.1}% margin (expected about {expected:.0})",
+                    record.pretax_income.unwrap_or_default(),
+                    record.sales
+                ),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(year: i32, sales: i64, eps: &str) -> HistoricalYearlyData {
+        HistoricalYearlyData {
+            fiscal_year: year,
+            sales: Decimal::from(sales),
+            eps: eps.parse().unwrap(),
+            price_high: Decimal::from(120),
+            price_low: Decimal::from(80),
+            pretax_income: Some(Decimal::from(sales / 5)),
+            total_equity: Some(Decimal::from(500)),
+            adjustment_factor: Decimal::ONE,
+            ..Default::default()
+        }
+    }
+
+    /// Ten years of steady ~5% growth with a 20% pre-tax margin.
+    fn steady() -> HistoricalData {
+        let records = (0..10)
+            .map(|i| {
+                let sales = 1000 + 50 * i as i64 + (i as i64 % 3) * 5;
+                record(2015 + i, sales, &format!("2.{i}"))
+            })
+            .collect();
+        HistoricalData {
+            ticker: "TEST".to_string(),
+            records,
+            ..Default::default()
+        }
+    }
+
+    fn kinds(data: &HistoricalData) -> Vec<(AnomalyKind, i32)> {
+        detect_anomalies(data, &AnomalyConfig::default())
+            .into_iter()
+            .map(|a| (a.kind, a.fiscal_year))
+            .collect()
+    }
+
+    #[test]
+    fn test_clean_history_has_no_anomalies() {
+        assert_eq!(kinds(&steady()), vec![]);
+    }
+
+    #[test]
+    fn test_missing_years_and_values() {
+        let mut data = steady();
+        data.records
+            .retain(|r| r.fiscal_year != 2018 && r.fiscal_year != 2019);
+        data.records[0].price_high = Decimal::ZERO;
+
+        let found = kinds(&data);
+        assert!(found.contains(&(AnomalyKind::MissingYear, 2018)));
+        assert!(found.contains(&(AnomalyKind::MissingYear, 2019)));
+        assert!(found.contains(&(AnomalyKind::MissingValue, 2015)));
+        // A zero high price also leaves the low price above it
+        assert!(found.contains(&(AnomalyKind::PriceRangeInverted, 2015)));
+    }
+
+    #[test]
+    fn test_equity_and_eps_sign_flip() {
+        let mut data = steady();
+        data.records[3].total_equity = Some(Decimal::from(-20));
+        data.records[5].eps = "-0.40".parse().unwrap();
+
+        let found = kinds(&data);
+        assert!(found.contains(&(AnomalyKind::NonPositiveEquity, 2018)));
+        // Flips into the loss year and back out of it
+        assert!(found.contains(&(AnomalyKind::EpsSignFlip, 2020)));
+        assert!(found.contains(&(AnomalyKind::EpsSignFlip, 2021)));
+    }
+
+    #[test]
+    fn test_implausible_jump_uses_configured_z_score() {
+        let mut data = steady();
+        // A tenfold sales year, with the margin kept consistent
+        data.records[6].sales = Decimal::from(13_000);
+        data.records[6].pretax_income = Some(Decimal::from(2_600));
+
+        let found = kinds(&data);
+        assert!(found.contains(&(AnomalyKind::ImplausibleJump, 2021)));
+
+        let lenient = AnomalyConfig {
+            jump_z_score: 1_000.0,
+            ..Default::default()
+        };
+        assert!(
+            !detect_anomalies(&data, &lenient)
+                .iter()
+                .any(|a| a.kind == AnomalyKind::ImplausibleJump)
+        );
+    }
+
+    #[test]
+    fn test_inconsistent_pretax_income() {
+        let mut data = steady();
+        // 60% margin against a 20% norm
+        data.records[2].pretax_income = Some(data.records[2].sales * Decimal::new(6, 1));
+        // Pre-tax income above sales
+        data.records[7].pretax_income = Some(data.records[7].sales * Decimal::from(2));
+
+        let found = detect_anomalies(&data, &AnomalyConfig::default());
+        let margin: Vec<_> = found
+            .iter()
+            .filter(|a| a.kind == AnomalyKind::InconsistentFigures)
+            .map(|a| a.fiscal_year)
+            .collect();
+        assert_eq!(margin, vec![2017, 2022, 2022]);
+        assert!(found.iter().all(|a| a.field == "pretax_income"));
+    }
+}
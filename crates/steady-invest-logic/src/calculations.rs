@@ -1,7 +1,9 @@
 use rust_decimal::prelude::ToPrimitive;
 
 use crate::projections::project_forward;
+use crate::quarterly::calculate_current_figures;
 use crate::types::*;
+use crate::valuation::{calculate_low_price_estimates, calculate_pe_breakdown};
 
 /// Computes historical High/Low P/E ratios and their averages.
 ///
@@ -242,11 +244,71 @@ pub fn calculate_upside_downside_ratio(
     Some(upside / downside)
 }
 
+/// Price of `quote` in the currency `data` is expressed in.
+///
+/// Quotes are in the ticker's trading currency while normalized data is in
+/// its display currency. A quote already in the display currency (or without
+/// a currency) is used as is; a quote in the native currency of normalized
+/// data is converted at the spot rate, or at the latest record's average
+/// rate when no spot rate was harvested.
+///
+/// Returns `None` when no rate connects the quote to the data, so callers
+/// never compare prices in two currencies.
+///
+/// # Examples
+///
+/// ```
+/// use steady_invest_logic::{HistoricalData, Quote, quote_price_in_display_currency};
+/// use rust_decimal::Decimal;
+///
+/// let data = HistoricalData {
+///     currency: "CHF".to_string(),
+///     display_currency: Some("USD".to_string()),
+///     spot_exchange_rate: Some(Decimal::new(110, 2)),
+///     ..Default::default()
+/// };
+/// let quote = Quote {
+///     price: Decimal::from(100),
+///     currency: "CHF".to_string(),
+///     ..Default::default()
+/// };
+/// assert!((quote_price_in_display_currency(&data, &quote).unwrap() - 110.0).abs() < 1e-9);
+/// ```
+pub fn quote_price_in_display_currency(data: &HistoricalData, quote: &Quote) -> Option<f64> {
+    let display = data.display_currency.as_deref().unwrap_or(&data.currency);
+    if quote.currency.is_empty() || display.is_empty() || quote.currency == display {
+        return quote.price.to_f64();
+    }
+    if quote.currency != data.currency {
+        return None;
+    }
+
+    let rate = data.spot_exchange_rate.or_else(|| {
+        data.records
+            .iter()
+            .max_by_key(|r| r.fiscal_year)
+            .and_then(|r| r.exchange_rate)
+    })?;
+    if rate <= rust_decimal::Decimal::ZERO {
+        return None;
+    }
+    (quote.price * rate).to_f64()
+}
+
 /// Extracts current and projected target prices from an [`AnalysisSnapshot`].
 ///
-/// Uses the latest historical record's high price as "current price" and
-/// projects 5-year EPS growth to compute target high/low prices.
-/// Returns all `None` if no records exist or EPS/price are non-positive.
+/// The current price is the snapshot's [`Quote`](crate::Quote) when it
+/// carries one, converted to the display currency of normalized data (see
+/// [`quote_price_in_display_currency`]); a quote no rate converts is ignored.
+/// Current EPS — and the price for snapshots without a quote —
+/// come from [`calculate_current_figures`]: TTM EPS and the latest quarter's
+/// high when quarterly records are newer than the latest annual one,
+/// otherwise the latest fiscal year's EPS and high price. 5-year EPS growth
+/// is projected from there to compute target high/low prices. The target low
+/// is the Section 4B candidate chosen by `low_price_method` (see
+/// [`crate::calculate_low_price_estimates`]), falling back to the projection
+/// when that candidate is unavailable.
+/// Target prices are `None` if no records exist or EPS/price are non-positive.
 ///
 /// # Examples
 ///
@@ -270,29 +332,37 @@ pub fn calculate_upside_downside_ratio(
 /// let prices = extract_snapshot_prices(&snapshot);
 /// assert!((prices.current_price.unwrap() - 50.0).abs() < 0.01);
 /// assert!(prices.target_high_price.unwrap() > 0.0);
+/// assert!(prices.price_as_of.is_none());
 /// ```
 pub fn extract_snapshot_prices(snapshot: &AnalysisSnapshot) -> SnapshotPrices {
-    use rust_decimal::prelude::ToPrimitive;
+    let quoted = snapshot.current_quote.as_ref().and_then(|quote| {
+        let price = quote_price_in_display_currency(&snapshot.historical_data, quote)?;
+        Some((price, quote.as_of))
+    });
+    if snapshot.historical_data.records.is_empty() {
+        return SnapshotPrices {
+            current_price: quoted.map(|(price, _)| price),
+            price_as_of: quoted.map(|(_, as_of)| as_of),
+            ..Default::default()
+        };
+    }
 
-    let latest = match snapshot
-        .historical_data
-        .records
-        .iter()
-        .max_by_key(|r| r.fiscal_year)
-    {
-        Some(r) => r,
-        None => return SnapshotPrices::default(),
+    let current = calculate_current_figures(&snapshot.historical_data);
+    let (current_price, price_as_of) = match quoted {
+        Some((price, as_of)) => (Some(price), Some(as_of)),
+        None => (current.price, None),
     };
-
-    let current_price = latest.price_high.to_f64();
-    let current_eps = latest.eps.to_f64();
+    let current_eps = current.eps;
 
     let (target_high, target_low) = match (current_eps, current_price) {
         (Some(eps), Some(price)) if eps > 0.0 && price > 0.0 => {
             let projected_eps_5yr = project_forward(eps, snapshot.projected_eps_cagr, 5);
             (
                 Some(snapshot.projected_high_pe * projected_eps_5yr),
-                Some(snapshot.projected_low_pe * projected_eps_5yr),
+                calculate_low_price_estimates(&snapshot.historical_data).select(
+                    snapshot.low_price_method,
+                    Some(snapshot.projected_low_pe * projected_eps_5yr),
+                ),
             )
         }
         _ => (None, None),
@@ -300,6 +370,7 @@ pub fn extract_snapshot_prices(snapshot: &AnalysisSnapshot) -> SnapshotPrices {
 
     SnapshotPrices {
         current_price,
+        price_as_of,
         target_high_price: target_high,
         target_low_price: target_low,
     }
@@ -320,6 +391,163 @@ pub fn compute_upside_downside_from_snapshot(snapshot: &AnalysisSnapshot) -> Opt
     calculate_upside_downside_ratio(current, high, low)
 }
 
+/// Calculates the NAIC Relative Value: the current P/E as a percentage of the
+/// 5-year average P/E.
+///
+/// NAIC considers a stock reasonably priced between 85% and 110%; below 85%
+/// usually warrants a look at why the market has lost interest.
+///
+/// # Formula
+///
+/// `(current_pe / average_pe) × 100`
+///
+/// # Returns
+///
+/// `None` if either P/E is zero or negative.
+///
+/// # Examples
+///
+/// ```
+/// use steady_invest_logic::calculate_relative_value;
+///
+/// let rv = calculate_relative_value(18.0, 20.0);
+/// assert!((rv.unwrap() - 90.0).abs() < 0.001);
+///
+/// // A loss-making company has no meaningful P/E
+/// assert!(calculate_relative_value(-5.0, 20.0).is_none());
+/// ```
+pub fn calculate_relative_value(current_pe: f64, average_pe: f64) -> Option<f64> {
+    if current_pe <= 0.0 || average_pe <= 0.0 {
+        return None;
+    }
+    Some(current_pe / average_pe * 100.0)
+}
+
+/// Calculates the NAIC Projected Average Return (PAR) as a percentage.
+///
+/// PAR is the compound annual appreciation from the current price to the
+/// average forecast price five years out (average P/E × projected EPS), plus
+/// the average yield. NAIC looks for a PAR of at least the return expected
+/// from the market, typically 5%–7% for large companies.
+///
+/// # Formula
+///
+/// ```text
+/// appreciation = ((projected_average_price / current_price)^(1/5) - 1) × 100
+/// PAR          = appreciation + average_yield
+/// ```
+///
+/// # Returns
+///
+/// `None` if either price is zero or negative.
+///
+/// # Examples
+///
+/// ```
+/// use steady_invest_logic::calculate_projected_average_return;
+///
+/// // Price doubles in 5 years (~14.87%/year) with a 2% average yield
+/// let par = calculate_projected_average_return(50.0, 100.0, 2.0);
+/// assert!((par.unwrap() - 16.87).abs() < 0.01);
+///
+/// assert!(calculate_projected_average_return(0.0, 100.0, 2.0).is_none());
+/// ```
+pub fn calculate_projected_average_return(
+    current_price: f64,
+    projected_average_price: f64,
+    average_yield: f64,
+) -> Option<f64> {
+    if current_price <= 0.0 || projected_average_price <= 0.0 {
+        return None;
+    }
+    let appreciation = ((projected_average_price / current_price).powf(1.0 / 5.0) - 1.0) * 100.0;
+    Some(calculate_total_return_simple(appreciation, average_yield))
+}
+
+/// Computes the NAIC SSG Section 5 metrics directly from an [`AnalysisSnapshot`].
+///
+/// The current price and EPS are those of [`extract_snapshot_prices`] and
+/// [`calculate_current_figures`]. Relative Value compares the current P/E to
+/// the average P/E of [`calculate_pe_breakdown`]; Present Yield uses the
+/// latest fiscal year's dividend; PAR projects EPS 5 years forward at the
+/// snapshot's EPS CAGR, prices it at the midpoint of the projected high and
+/// low P/E, and adds [`calculate_average_yield_5year`] (zero for companies
+/// without dividends).
+///
+/// Each metric is `None` when the figures it needs are missing or EPS/price
+/// are non-positive.
+///
+/// # Examples
+///
+/// ```
+/// use steady_invest_logic::{AnalysisSnapshot, HistoricalData, HistoricalYearlyData, compute_return_metrics_from_snapshot};
+/// use rust_decimal::Decimal;
+///
+/// let snapshot = AnalysisSnapshot {
+///     historical_data: HistoricalData {
+///         records: vec![HistoricalYearlyData {
+///             fiscal_year: 2023, eps: Decimal::from(5),
+///             price_high: Decimal::from(100), price_low: Decimal::from(60),
+///             dividend_per_share: Some(Decimal::from(2)),
+///             ..Default::default()
+///         }],
+///         ..Default::default()
+///     },
+///     projected_eps_cagr: 10.0,
+///     projected_high_pe: 20.0,
+///     projected_low_pe: 12.0,
+///     ..Default::default()
+/// };
+/// let metrics = compute_return_metrics_from_snapshot(&snapshot);
+/// // Current P/E 20 against an average P/E of 16
+/// assert!((metrics.relative_value.unwrap() - 125.0).abs() < 0.01);
+/// assert!((metrics.present_yield.unwrap() - 2.0).abs() < 0.01);
+/// assert!(metrics.projected_average_return.is_some());
+/// ```
+pub fn compute_return_metrics_from_snapshot(snapshot: &AnalysisSnapshot) -> ReturnMetrics {
+    let data = &snapshot.historical_data;
+    let price = extract_snapshot_prices(snapshot)
+        .current_price
+        .filter(|&p| p > 0.0);
+    let eps = calculate_current_figures(data).eps.filter(|&e| e > 0.0);
+
+    let average_pe = calculate_pe_breakdown(&calculate_pe_ranges(data)).map(|pe| pe.average);
+    let relative_value = match (price, eps, average_pe) {
+        (Some(price), Some(eps), Some(average_pe)) => {
+            calculate_relative_value(price / eps, average_pe)
+        }
+        _ => None,
+    };
+
+    let latest_dividend = data
+        .records
+        .iter()
+        .max_by_key(|r| r.fiscal_year)
+        .and_then(|r| r.dividend_per_share)
+        .and_then(|d| d.to_f64());
+    let present_yield = match (price, latest_dividend) {
+        (Some(price), Some(dividend)) => calculate_dividend_yield(dividend, price),
+        _ => None,
+    };
+
+    let projected_average_return = match (price, eps) {
+        (Some(price), Some(eps)) => {
+            let average_pe = (snapshot.projected_high_pe + snapshot.projected_low_pe) / 2.0;
+            let projected_eps_5yr = project_forward(eps, snapshot.projected_eps_cagr, 5);
+            let average_yield =
+                calculate_average_yield_5year(&calculate_dividend_metrics(data)).unwrap_or(0.0);
+            calculate_projected_average_return(price, average_pe * projected_eps_5yr, average_yield)
+        }
+        _ => None,
+    };
+
+    ReturnMetrics {
+        relative_value,
+        present_yield,
+        projected_average_return,
+    }
+}
+
 /// Computes CAGR and a best-fit linear regression trendline for a series of values.
 ///
 /// Regression is performed in log-space (`ln(y) = mx + b`) to produce a straight
@@ -818,6 +1046,128 @@ mod tests {
         assert!((prices.target_low_price.unwrap() - 161.05).abs() < 0.1);
     }
 
+    #[test]
+    fn test_extract_snapshot_prices_uses_selected_low_price() {
+        let mut snapshot = AnalysisSnapshot {
+            historical_data: HistoricalData {
+                records: vec![HistoricalYearlyData {
+                    fiscal_year: 2023,
+                    eps: Decimal::from(10),
+                    price_high: Decimal::from(50),
+                    price_low: Decimal::from(30),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            projected_eps_cagr: 10.0,
+            projected_high_pe: 20.0,
+            projected_low_pe: 10.0,
+            low_price_method: LowPriceMethod::SevereMarketLow,
+            ..Default::default()
+        };
+        let prices = extract_snapshot_prices(&snapshot);
+        assert_eq!(prices.target_low_price, Some(30.0));
+        // The high target still comes from the projection
+        assert!((prices.target_high_price.unwrap() - 322.10).abs() < 0.1);
+
+        // Without dividends the candidate is missing: fall back to the projection
+        snapshot.low_price_method = LowPriceMethod::DividendSupport;
+        let prices = extract_snapshot_prices(&snapshot);
+        assert!((prices.target_low_price.unwrap() - 161.05).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_relative_value_and_par_bounds() {
+        assert!((calculate_relative_value(22.0, 20.0).unwrap() - 110.0).abs() < 1e-9);
+        assert!(calculate_relative_value(22.0, 0.0).is_none());
+        // Flat price: PAR is the yield alone
+        assert!((calculate_projected_average_return(40.0, 40.0, 3.0).unwrap() - 3.0).abs() < 1e-9);
+        assert!(calculate_projected_average_return(40.0, -1.0, 3.0).is_none());
+    }
+
+    #[test]
+    fn test_compute_return_metrics_from_snapshot() {
+        let record = |year: i32, dividend: Option<i64>| HistoricalYearlyData {
+            fiscal_year: year,
+            eps: Decimal::from(5),
+            price_high: Decimal::from(100),
+            price_low: Decimal::from(50),
+            dividend_per_share: dividend.map(Decimal::from),
+            ..Default::default()
+        };
+        let mut snapshot = AnalysisSnapshot {
+            historical_data: HistoricalData {
+                records: vec![record(2022, Some(1)), record(2023, Some(3))],
+                ..Default::default()
+            },
+            current_quote: Some(Quote {
+                price: Decimal::from(75),
+                ..Default::default()
+            }),
+            projected_eps_cagr: 0.0,
+            projected_high_pe: 20.0,
+            projected_low_pe: 10.0,
+            ..Default::default()
+        };
+        let metrics = compute_return_metrics_from_snapshot(&snapshot);
+        // Current P/E 15 equals the average P/E (20 + 10) / 2
+        assert!((metrics.relative_value.unwrap() - 100.0).abs() < 1e-9);
+        // Latest dividend 3 on a 75 price
+        assert!((metrics.present_yield.unwrap() - 4.0).abs() < 1e-9);
+        // No growth: average price 15 × 5 = 75, so PAR is the average high yield (1% + 3%) / 2
+        assert!((metrics.projected_average_return.unwrap() - 2.0).abs() < 1e-9);
+
+        // Loss-making company: no P/E-based metrics, yield still defined
+        snapshot.historical_data.records[1].eps = Decimal::from(-1);
+        let metrics = compute_return_metrics_from_snapshot(&snapshot);
+        assert!(metrics.relative_value.is_none());
+        assert!(metrics.projected_average_return.is_none());
+        assert!(metrics.present_yield.is_some());
+
+        assert_eq!(
+            compute_return_metrics_from_snapshot(&AnalysisSnapshot::default()),
+            ReturnMetrics::default()
+        );
+    }
+
+    #[test]
+    fn test_compute_return_metrics_from_snapshot_with_foreign_quote() {
+        // CHF figures normalized to USD, quoted in CHF
+        let record = |year: i32, dividend: i64| HistoricalYearlyData {
+            fiscal_year: year,
+            eps: Decimal::from(5),
+            price_high: Decimal::from(100),
+            price_low: Decimal::from(50),
+            dividend_per_share: Some(Decimal::from(dividend)),
+            exchange_rate: Some(Decimal::new(110, 2)),
+            ..Default::default()
+        };
+        let snapshot = AnalysisSnapshot {
+            historical_data: HistoricalData {
+                currency: "CHF".to_string(),
+                display_currency: Some("USD".to_string()),
+                spot_exchange_rate: Some(Decimal::new(125, 2)),
+                records: vec![record(2022, 1), record(2023, 3)],
+                ..Default::default()
+            },
+            current_quote: Some(Quote {
+                price: Decimal::from(60),
+                currency: "CHF".to_string(),
+                ..Default::default()
+            }),
+            projected_eps_cagr: 0.0,
+            projected_high_pe: 20.0,
+            projected_low_pe: 10.0,
+            ..Default::default()
+        };
+
+        // 60 CHF is 75 USD: the same metrics as a 75 USD quote
+        let metrics = compute_return_metrics_from_snapshot(&snapshot);
+        assert!((metrics.relative_value.unwrap() - 100.0).abs() < 1e-9);
+        assert!((metrics.present_yield.unwrap() - 4.0).abs() < 1e-9);
+        assert!((metrics.projected_average_return.unwrap() - 2.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_extract_snapshot_prices_empty_records() {
         let snapshot = AnalysisSnapshot::default();
@@ -827,6 +1177,100 @@ mod tests {
         assert!(prices.target_low_price.is_none());
     }
 
+    #[test]
+    fn test_extract_snapshot_prices_prefers_quote() {
+        let as_of = chrono::DateTime::from_timestamp(1_760_000_000, 0).unwrap();
+        let mut snapshot = AnalysisSnapshot {
+            historical_data: HistoricalData {
+                records: vec![HistoricalYearlyData {
+                    fiscal_year: 2023,
+                    eps: Decimal::from(10),
+                    price_high: Decimal::from(50),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            projected_eps_cagr: 10.0,
+            projected_high_pe: 20.0,
+            projected_low_pe: 10.0,
+            current_quote: Some(Quote {
+                price: Decimal::new(4250, 2),
+                currency: "USD".to_string(),
+                as_of,
+                source: "manual".to_string(),
+            }),
+            ..Default::default()
+        };
+        let prices = extract_snapshot_prices(&snapshot);
+        assert_eq!(prices.current_price, Some(42.5));
+        assert_eq!(prices.price_as_of, Some(as_of));
+        // Targets still come from EPS, not from the quote
+        assert!((prices.target_high_price.unwrap() - 322.10).abs() < 0.1);
+
+        snapshot.historical_data.records.clear();
+        let prices = extract_snapshot_prices(&snapshot);
+        assert_eq!(prices.current_price, Some(42.5));
+        assert!(prices.target_high_price.is_none());
+    }
+
+    #[test]
+    fn test_extract_snapshot_prices_converts_quote_to_display_currency() {
+        let as_of = chrono::DateTime::from_timestamp(1_760_000_000, 0).unwrap();
+        // CHF figures already normalized to USD
+        let mut snapshot = AnalysisSnapshot {
+            historical_data: HistoricalData {
+                currency: "CHF".to_string(),
+                display_currency: Some("USD".to_string()),
+                spot_exchange_rate: Some(Decimal::new(125, 2)),
+                records: vec![HistoricalYearlyData {
+                    fiscal_year: 2023,
+                    eps: Decimal::from(10),
+                    price_high: Decimal::from(60),
+                    exchange_rate: Some(Decimal::new(110, 2)),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            projected_eps_cagr: 10.0,
+            projected_high_pe: 20.0,
+            projected_low_pe: 10.0,
+            current_quote: Some(Quote {
+                price: Decimal::from(40),
+                currency: "CHF".to_string(),
+                as_of,
+                source: "yahoo".to_string(),
+            }),
+            ..Default::default()
+        };
+
+        // 40 CHF at today's rate
+        let prices = extract_snapshot_prices(&snapshot);
+        assert!((prices.current_price.unwrap() - 50.0).abs() < 1e-9);
+        assert_eq!(prices.price_as_of, Some(as_of));
+
+        // Without a spot rate the latest year's average rate converts it
+        snapshot.historical_data.spot_exchange_rate = None;
+        let prices = extract_snapshot_prices(&snapshot);
+        assert!((prices.current_price.unwrap() - 44.0).abs() < 1e-9);
+
+        // Without any rate the quote is ignored: last close, not a CHF price
+        snapshot.historical_data.records[0].exchange_rate = None;
+        let prices = extract_snapshot_prices(&snapshot);
+        assert_eq!(prices.current_price, Some(60.0));
+        assert!(prices.price_as_of.is_none());
+
+        // A quote in the display currency is used as is
+        snapshot.current_quote.as_mut().unwrap().currency = "USD".to_string();
+        let prices = extract_snapshot_prices(&snapshot);
+        assert_eq!(prices.current_price, Some(40.0));
+
+        // A quote in a third currency cannot be converted
+        snapshot.current_quote.as_mut().unwrap().currency = "EUR".to_string();
+        snapshot.historical_data.spot_exchange_rate = Some(Decimal::new(125, 2));
+        let prices = extract_snapshot_prices(&snapshot);
+        assert_eq!(prices.current_price, Some(60.0));
+    }
+
     #[test]
     fn test_snapshot_serialization() {
         let snapshot = AnalysisSnapshot {
@@ -848,6 +1292,9 @@ mod tests {
             projected_low_pe: 15.0,
             analyst_note: "Test note".to_string(),
             captured_at: chrono::Utc::now(),
+            current_quote: None,
+            naic_test_overrides: Vec::new(),
+            low_price_method: LowPriceMethod::default(),
         };
 
         let json = serde_json::to_string(&snapshot).unwrap();
@@ -1,20 +1,32 @@
-/// Validates that a string is a valid ISO 4217 currency code (3 uppercase ASCII letters).
+/// Currencies supported out of the box: the Swiss, euro and US markets plus
+/// the other European markets and Japan held in portfolios.
 ///
-/// Used at API boundaries to enforce currency format per architecture spec.
+/// The backend can replace this list through configuration; the frontend
+/// offers it as display currencies.
+pub const SUPPORTED_CURRENCIES: &[&str] = &["CHF", "EUR", "USD", "GBP", "SEK", "DKK", "NOK", "JPY"];
+
+/// Validates that a string is a valid ISO 4217 currency code (3 uppercase ASCII
+/// letters) and one of the `supported` currencies.
+///
+/// Used at API boundaries to enforce currency format per architecture spec and
+/// to reject currencies no exchange rates are available for.
 ///
 /// # Examples
 ///
 /// ```
-/// use steady_invest_logic::is_valid_currency_code;
+/// use steady_invest_logic::{is_valid_currency_code, SUPPORTED_CURRENCIES};
 ///
-/// assert!(is_valid_currency_code("CHF"));
-/// assert!(is_valid_currency_code("USD"));
-/// assert!(!is_valid_currency_code("us"));     // too short + lowercase
-/// assert!(!is_valid_currency_code("USDX"));   // too long
-/// assert!(!is_valid_currency_code("123"));     // digits, not letters
+/// assert!(is_valid_currency_code("CHF", SUPPORTED_CURRENCIES));
+/// assert!(is_valid_currency_code("GBP", SUPPORTED_CURRENCIES));
+/// assert!(!is_valid_currency_code("AUD", SUPPORTED_CURRENCIES)); // not supported
+/// assert!(!is_valid_currency_code("us", SUPPORTED_CURRENCIES));  // too short + lowercase
+/// assert!(!is_valid_currency_code("USDX", SUPPORTED_CURRENCIES)); // too long
+/// assert!(!is_valid_currency_code("123", SUPPORTED_CURRENCIES));  // digits, not letters
 /// ```
-pub fn is_valid_currency_code(code: &str) -> bool {
-    code.len() == 3 && code.bytes().all(|b| b.is_ascii_uppercase())
+pub fn is_valid_currency_code<S: AsRef<str>>(code: &str, supported: &[S]) -> bool {
+    code.len() == 3
+        && code.bytes().all(|b| b.is_ascii_uppercase())
+        && supported.iter().any(|c| c.as_ref() == code)
 }
 
 /// Converts a monetary value from one currency to another using the given rate.
@@ -92,14 +104,28 @@ mod tests {
 
     #[test]
     fn test_is_valid_currency_code() {
-        assert!(is_valid_currency_code("CHF"));
-        assert!(is_valid_currency_code("USD"));
-        assert!(is_valid_currency_code("EUR"));
-        assert!(!is_valid_currency_code("us")); // too short + lowercase
-        assert!(!is_valid_currency_code("usd")); // lowercase
-        assert!(!is_valid_currency_code("USDX")); // too long
-        assert!(!is_valid_currency_code("123")); // digits
-        assert!(!is_valid_currency_code("")); // empty
-        assert!(!is_valid_currency_code("U D")); // contains space
+        assert!(is_valid_currency_code("CHF", SUPPORTED_CURRENCIES));
+        assert!(is_valid_currency_code("USD", SUPPORTED_CURRENCIES));
+        assert!(is_valid_currency_code("EUR", SUPPORTED_CURRENCIES));
+        assert!(!is_valid_currency_code("us", SUPPORTED_CURRENCIES)); // too short + lowercase
+        assert!(!is_valid_currency_code("usd", SUPPORTED_CURRENCIES)); // lowercase
+        assert!(!is_valid_currency_code("USDX", SUPPORTED_CURRENCIES)); // too long
+        assert!(!is_valid_currency_code("123", SUPPORTED_CURRENCIES)); // digits
+        assert!(!is_valid_currency_code("", SUPPORTED_CURRENCIES)); // empty
+        assert!(!is_valid_currency_code("U D", SUPPORTED_CURRENCIES)); // contains space
+    }
+
+    #[test]
+    fn test_is_valid_currency_code_follows_supported_list() {
+        for code in ["GBP", "SEK", "DKK", "NOK", "JPY"] {
+            assert!(is_valid_currency_code(code, SUPPORTED_CURRENCIES), "{code}");
+        }
+        assert!(!is_valid_currency_code("AUD", SUPPORTED_CURRENCIES));
+
+        let configured = vec!["AUD".to_string(), "EUR".to_string()];
+        assert!(is_valid_currency_code("AUD", &configured));
+        assert!(!is_valid_currency_code("CHF", &configured));
+        // A lowercase entry in the list never makes a lowercase code valid
+        assert!(!is_valid_currency_code("aud", &["aud"]));
     }
 }
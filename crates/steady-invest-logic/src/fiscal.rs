@@ -0,0 +1,103 @@
+use chrono::{Datelike, NaiveDate};
+
+use crate::types::FiscalCalendar;
+
+impl FiscalCalendar {
+    /// Fiscal years matching calendar years (December year end).
+    pub const CALENDAR_YEAR: Self = Self { year_end_month: 12 };
+
+    /// Creates a calendar ending in `year_end_month`, or `None` unless the
+    /// month is within 1–12.
+    pub fn new(year_end_month: u32) -> Option<Self> {
+        (1..=12)
+            .contains(&year_end_month)
+            .then_some(Self { year_end_month })
+    }
+
+    /// The fiscal year `date` falls in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use steady_invest_logic::FiscalCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let june = FiscalCalendar::new(6).unwrap();
+    /// let date = |m| NaiveDate::from_ymd_opt(2024, m, 15).unwrap();
+    /// assert_eq!(june.fiscal_year(date(6)), 2024);
+    /// assert_eq!(june.fiscal_year(date(7)), 2025);
+    /// ```
+    pub fn fiscal_year(&self, date: NaiveDate) -> i32 {
+        if date.month() > self.year_end_month {
+            date.year() + 1
+        } else {
+            date.year()
+        }
+    }
+
+    /// First day of `fiscal_year`.
+    pub fn start(&self, fiscal_year: i32) -> NaiveDate {
+        let (year, month) = if self.year_end_month >= 12 {
+            (fiscal_year, 1)
+        } else {
+            (fiscal_year - 1, self.year_end_month + 1)
+        };
+        NaiveDate::from_ymd_opt(year, month, 1).unwrap_or_default()
+    }
+
+    /// Last day of `fiscal_year`.
+    pub fn end(&self, fiscal_year: i32) -> NaiveDate {
+        self.start(fiscal_year + 1).pred_opt().unwrap_or_default()
+    }
+
+    /// Calendar years overlapped by `fiscal_year`, with the number of months
+    /// falling in each, oldest first. Months always add up to 12.
+    pub fn calendar_months(&self, fiscal_year: i32) -> Vec<(i32, u32)> {
+        let in_end_year = self.year_end_month.clamp(1, 12);
+        if in_end_year == 12 {
+            vec![(fiscal_year, 12)]
+        } else {
+            vec![
+                (fiscal_year - 1, 12 - in_end_year),
+                (fiscal_year, in_end_year),
+            ]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_calendar_year_periods() {
+        let cal = FiscalCalendar::default();
+        assert_eq!(cal.start(2024), date(2024, 1, 1));
+        assert_eq!(cal.end(2024), date(2024, 12, 31));
+        assert_eq!(cal.fiscal_year(date(2024, 12, 31)), 2024);
+        assert_eq!(cal.calendar_months(2024), vec![(2024, 12)]);
+    }
+
+    #[test]
+    fn test_non_calendar_periods() {
+        let sept = FiscalCalendar::new(9).unwrap();
+        assert_eq!(sept.start(2025), date(2024, 10, 1));
+        assert_eq!(sept.end(2025), date(2025, 9, 30));
+        assert_eq!(sept.fiscal_year(date(2024, 10, 1)), 2025);
+        assert_eq!(sept.fiscal_year(date(2025, 9, 30)), 2025);
+        assert_eq!(sept.calendar_months(2025), vec![(2024, 3), (2025, 9)]);
+
+        let feb = FiscalCalendar::new(2).unwrap();
+        assert_eq!(feb.end(2024), date(2024, 2, 29));
+    }
+
+    #[test]
+    fn test_rejects_invalid_month() {
+        assert!(FiscalCalendar::new(0).is_none());
+        assert!(FiscalCalendar::new(13).is_none());
+    }
+}
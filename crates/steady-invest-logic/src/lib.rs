@@ -12,6 +12,20 @@
 //!   indicators ([`calculate_quality_analysis`])
 //! - **Projections** — CAGR-based future trendlines for valuation zone
 //!   calculations ([`calculate_projected_trendline`])
+//! - **Trailing twelve months** — TTM figures from quarterly records and the
+//!   current EPS/price a valuation starts from ([`calculate_ttm`],
+//!   [`calculate_current_figures`])
+//! - **Anomaly detection** — data gaps and implausible values in harvested
+//!   history ([`detect_anomalies`])
+//! - **NAIC tests** — Management and Safety-of-Price pass/fail checks with
+//!   analyst overrides kept in the snapshot ([`evaluate_naic_tests`])
+//! - **Valuation zones** — the five P/E tiers and the Buy/Maybe/Sell thirds of
+//!   the forecast price range ([`calculate_pe_breakdown`],
+//!   [`calculate_price_zones`])
+//! - **Section 5 metrics** — Relative Value, Present Yield and Projected
+//!   Average Return ([`compute_return_metrics_from_snapshot`])
+//! - **Analysis summary** — guided Stock Checklist conclusions on growth and
+//!   price, benchmarked by company size ([`calculate_analysis_summary`])
 //!
 //! ## Key Types
 //!
@@ -20,6 +34,7 @@
 //! - [`AnalysisSnapshot`] — point-in-time capture of an analyst's full thesis
 //! - [`TrendAnalysis`] — CAGR value plus best-fit trendline points
 //! - [`PeRangeAnalysis`] — per-year High/Low P/E with computed averages
+//! - [`FiscalCalendar`] — fiscal-year-end month and the periods it defines
 //!
 //! ## Design Principles
 //!
@@ -28,12 +43,23 @@
 //! math (trendlines, CAGR) uses `f64` where acceptable.
 
 mod adjustments;
+mod anomalies;
 mod calculations;
 mod currency;
+mod fiscal;
+mod naic_tests;
 mod projections;
+mod quarterly;
+mod summary;
 mod types;
+mod valuation;
 
+pub use anomalies::*;
 pub use calculations::*;
 pub use currency::*;
+pub use naic_tests::*;
 pub use projections::*;
+pub use quarterly::*;
+pub use summary::*;
 pub use types::*;
+pub use valuation::*;
@@ -0,0 +1,632 @@
+use crate::calculations::{calculate_upside_downside_ratio, extract_snapshot_prices};
+use crate::types::*;
+
+/// Years of quality metrics the Management tests look at (NAIC Section 2).
+const MANAGEMENT_YEARS: usize = 5;
+
+/// Most EPS growth may exceed sales growth, in percentage points, for the
+/// growth to still count as sales-driven.
+const MAX_EPS_GROWTH_EXCESS: f64 = 5.0;
+
+/// Yearly change in Profit-on-Sales, in percentage points, below which the
+/// trend counts as falling (the quality dashboard's ±0.1 pp dead-band).
+const PROFIT_TREND_DEAD_BAND: f64 = 0.1;
+
+/// Minimum five-year average ROE (%).
+const MIN_AVERAGE_ROE: f64 = 10.0;
+
+/// Minimum upside/downside ratio (3:1).
+const MIN_UPSIDE_DOWNSIDE: f64 = 3.0;
+
+/// Minimum five-year appreciation of the forecast high price (%).
+const MIN_APPRECIATION: f64 = 100.0;
+
+impl NaicTest {
+    /// All tests in SSG order: the three Management tests, then the three
+    /// Safety-of-Price tests.
+    pub const ALL: [NaicTest; 6] = [
+        NaicTest::SalesDrivenGrowth,
+        NaicTest::ProfitOnSalesTrend,
+        NaicTest::ReturnOnEquity,
+        NaicTest::GettingOutEven,
+        NaicTest::UpsideDownside,
+        NaicTest::Appreciation,
+    ];
+
+    /// SSG section the test belongs to.
+    pub fn group(self) -> NaicTestGroup {
+        match self {
+            NaicTest::SalesDrivenGrowth
+            | NaicTest::ProfitOnSalesTrend
+            | NaicTest::ReturnOnEquity => NaicTestGroup::Management,
+            NaicTest::GettingOutEven | NaicTest::UpsideDownside | NaicTest::Appreciation => {
+                NaicTestGroup::SafetyOfPrice
+            }
+        }
+    }
+
+    /// Short label for tables and reports.
+    pub fn label(self) -> &'static str {
+        match self {
+            NaicTest::SalesDrivenGrowth => "Sales-driven growth",
+            NaicTest::ProfitOnSalesTrend => "Profit-on-sales trend",
+            NaicTest::ReturnOnEquity => "ROE of 10% or more",
+            NaicTest::GettingOutEven => "Getting out even",
+            NaicTest::UpsideDownside => "Upside/downside of 3:1",
+            NaicTest::Appreciation => "100% appreciation in 5 years",
+        }
+    }
+}
+
+impl NaicTestResult {
+    /// The analyst's conclusion when overridden, otherwise the computed one.
+    pub fn conclusion(&self) -> NaicTestOutcome {
+        self.analyst_override
+            .as_ref()
+            .map_or(self.outcome, |o| o.outcome)
+    }
+}
+
+impl AnalysisSnapshot {
+    /// Stores an analyst override, replacing any earlier one for the same test.
+    pub fn set_naic_test_override(&mut self, analyst_override: NaicTestOverride) {
+        self.clear_naic_test_override(analyst_override.test);
+        self.naic_test_overrides.push(analyst_override);
+    }
+
+    /// Removes the override for `test`, restoring the computed conclusion.
+    pub fn clear_naic_test_override(&mut self, test: NaicTest) {
+        self.naic_test_overrides.retain(|o| o.test != test);
+    }
+}
+
+/// Evaluates the NAIC Management and Safety-of-Price tests (PRD FR2.8).
+///
+/// The Management tests read the last five years of `quality` and the
+/// historical `sales_growth` and `eps_growth` trends:
+///
+/// 1. **Sales-driven growth** — sales grow and EPS growth exceeds sales
+///    growth by at most 5 percentage points.
+/// 2. **Profit-on-sales trend** — the least-squares slope of pre-tax Profit
+///    on Sales is not falling by more than 0.1 pp a year.
+/// 3. **ROE vs 10%** — average ROE is at least 10%.
+///
+/// The Safety-of-Price tests use the current and forecast prices from
+/// [`extract_snapshot_prices`]:
+///
+/// 4. **Getting out even** — the forecast low price is at least 100% of the
+///    current price.
+/// 5. **Upside/downside** — the ratio is at least 3:1; a current price at or
+///    below the forecast low has no downside and passes.
+/// 6. **100% appreciation** — the forecast high price is at least double the
+///    current price.
+///
+/// Tests without the data they need are [`NaicTestOutcome::Inconclusive`].
+/// Overrides stored in `snapshot` are attached to their results; see
+/// [`NaicTestResult::conclusion`].
+///
+/// # Examples
+///
+/// ```
+/// use steady_invest_logic::{
+///     AnalysisSnapshot, NaicTest, NaicTestOutcome, QualityAnalysis, QualityPoint,
+///     TrendAnalysis, evaluate_naic_tests,
+/// };
+///
+/// let quality = QualityAnalysis {
+///     points: vec![QualityPoint { year: 2023, roe: 18.0, profit_on_sales: 12.0, ..Default::default() }],
+/// };
+/// let results = evaluate_naic_tests(
+///     &quality,
+///     &TrendAnalysis::default(),
+///     &TrendAnalysis::default(),
+///     &AnalysisSnapshot::default(),
+/// );
+/// assert_eq!(results.len(), 6);
+/// let roe = results.iter().find(|r| r.test == NaicTest::ReturnOnEquity).unwrap();
+/// assert_eq!(roe.outcome, NaicTestOutcome::Pass);
+/// ```
+pub fn evaluate_naic_tests(
+    quality: &QualityAnalysis,
+    sales_growth: &TrendAnalysis,
+    eps_growth: &TrendAnalysis,
+    snapshot: &AnalysisSnapshot,
+) -> Vec<NaicTestResult> {
+    let mut points: Vec<&QualityPoint> = quality.points.iter().collect();
+    points.sort_by_key(|p| p.year);
+    let recent = &points[points.len().saturating_sub(MANAGEMENT_YEARS)..];
+
+    let prices = extract_snapshot_prices(snapshot);
+    let current = prices.current_price.filter(|&p| p > 0.0);
+
+    let mut results = vec![
+        sales_driven_growth(sales_growth, eps_growth),
+        profit_on_sales_trend(recent),
+        return_on_equity(recent),
+        getting_out_even(current, prices.target_low_price),
+        upside_downside(current, prices.target_high_price, prices.target_low_price),
+        appreciation(current, prices.target_high_price),
+    ];
+    for result in &mut results {
+        result.analyst_override = snapshot
+            .naic_test_overrides
+            .iter()
+            .find(|o| o.test == result.test)
+            .cloned();
+    }
+    results
+}
+
+fn result(
+    test: NaicTest,
+    outcome: NaicTestOutcome,
+    value: Option<f64>,
+    benchmark: f64,
+    explanation: String,
+) -> NaicTestResult {
+    NaicTestResult {
+        test,
+        outcome,
+        value,
+        benchmark,
+        explanation,
+        analyst_override: None,
+    }
+}
+
+fn pass_if(passed: bool) -> NaicTestOutcome {
+    if passed {
+        NaicTestOutcome::Pass
+    } else {
+        NaicTestOutcome::Fail
+    }
+}
+
+fn sales_driven_growth(sales_growth: &TrendAnalysis, eps_growth: &TrendAnalysis) -> NaicTestResult {
+    let test = NaicTest::SalesDrivenGrowth;
+    if sales_growth.trendline.is_empty() || eps_growth.trendline.is_empty() {
+        return result(
+            test,
+            NaicTestOutcome::Inconclusive,
+            None,
+            MAX_EPS_GROWTH_EXCESS,
+            "At least two years of positive sales and EPS are needed to compare their growth."
+                .to_string(),
+        );
+    }
+
+    let excess = eps_growth.cagr - sales_growth.cagr;
+    let (outcome, explanation) = if sales_growth.cagr <= 0.0 {
+        (
+            NaicTestOutcome::Fail,
+            format!(
+                "Sales are not growing ({:.1}% a year), so earnings growth cannot come from sales.",
+                sales_growth.cagr
+            ),
+        )
+    } else if excess > MAX_EPS_GROWTH_EXCESS {
+        (
+            NaicTestOutcome::Fail,
+            format!(
+                "EPS grew {:.1}% a year against sales at {:.1}%; growth this far ahead of sales \
+                 comes from cost cuts or buybacks and rarely lasts.",
+                eps_growth.cagr, sales_growth.cagr
+            ),
+        )
+    } else {
+        (
+            NaicTestOutcome::Pass,
+            format!(
+                "EPS grew {:.1}% a year with sales at {:.1}%, so earnings growth is carried by sales.",
+                eps_growth.cagr, sales_growth.cagr
+            ),
+        )
+    };
+    result(
+        test,
+        outcome,
+        Some(excess),
+        MAX_EPS_GROWTH_EXCESS,
+        explanation,
+    )
+}
+
+fn profit_on_sales_trend(recent: &[&QualityPoint]) -> NaicTestResult {
+    let test = NaicTest::ProfitOnSalesTrend;
+    let series: Vec<(f64, f64)> = recent
+        .iter()
+        .filter(|p| p.profit_on_sales != 0.0)
+        .map(|p| (f64::from(p.year), p.profit_on_sales))
+        .collect();
+    let Some(slope) = slope(&series).filter(|_| series.len() >= 3) else {
+        return result(
+            test,
+            NaicTestOutcome::Inconclusive,
+            None,
+            -PROFIT_TREND_DEAD_BAND,
+            "At least three years of pre-tax profit and sales are needed to judge the trend."
+                .to_string(),
+        );
+    };
+
+    let passed = slope >= -PROFIT_TREND_DEAD_BAND;
+    let explanation = if passed {
+        format!(
+            "Pre-tax profit on sales moved {slope:+.2} pp a year over the last {} years: \
+             management is holding or improving margins.",
+            series.len()
+        )
+    } else {
+        format!(
+            "Pre-tax profit on sales fell {:.2} pp a year over the last {} years: margins are eroding.",
+            -slope,
+            series.len()
+        )
+    };
+    result(
+        test,
+        pass_if(passed),
+        Some(slope),
+        -PROFIT_TREND_DEAD_BAND,
+        explanation,
+    )
+}
+
+fn return_on_equity(recent: &[&QualityPoint]) -> NaicTestResult {
+    let test = NaicTest::ReturnOnEquity;
+    let roes: Vec<f64> = recent.iter().map(|p| p.roe).filter(|r| *r != 0.0).collect();
+    if roes.is_empty() {
+        return result(
+            test,
+            NaicTestOutcome::Inconclusive,
+            None,
+            MIN_AVERAGE_ROE,
+            "No year reports both net income and equity.".to_string(),
+        );
+    }
+
+    let average = roes.iter().sum::<f64>() / roes.len() as f64;
+    let passed = average >= MIN_AVERAGE_ROE;
+    let explanation = format!(
+        "Return on equity averaged {average:.1}% over {} years, {} the 10% NAIC benchmark.",
+        roes.len(),
+        if passed { "meeting" } else { "below" }
+    );
+    result(
+        test,
+        pass_if(passed),
+        Some(average),
+        MIN_AVERAGE_ROE,
+        explanation,
+    )
+}
+
+fn missing_prices(test: NaicTest, benchmark: f64) -> NaicTestResult {
+    result(
+        test,
+        NaicTestOutcome::Inconclusive,
+        None,
+        benchmark,
+        "A current price and positive current EPS are needed to forecast prices.".to_string(),
+    )
+}
+
+fn getting_out_even(current: Option<f64>, low: Option<f64>) -> NaicTestResult {
+    let test = NaicTest::GettingOutEven;
+    let (Some(current), Some(low)) = (current, low) else {
+        return missing_prices(test, 100.0);
+    };
+
+    let percent = low / current * 100.0;
+    let passed = percent >= 100.0;
+    let explanation = if passed {
+        format!(
+            "The forecast low price of {low:.2} is {percent:.0}% of today's {current:.2}: \
+             even at the low you would get out even in five years."
+        )
+    } else {
+        format!(
+            "The forecast low price of {low:.2} is only {percent:.0}% of today's {current:.2}: \
+             a sale at the low would be at a loss."
+        )
+    };
+    result(test, pass_if(passed), Some(percent), 100.0, explanation)
+}
+
+fn upside_downside(current: Option<f64>, high: Option<f64>, low: Option<f64>) -> NaicTestResult {
+    let test = NaicTest::UpsideDownside;
+    let (Some(current), Some(high), Some(low)) = (current, high, low) else {
+        return missing_prices(test, MIN_UPSIDE_DOWNSIDE);
+    };
+
+    let Some(ratio) = calculate_upside_downside_ratio(current, high, low) else {
+        return result(
+            test,
+            NaicTestOutcome::Pass,
+            None,
+            MIN_UPSIDE_DOWNSIDE,
+            format!(
+                "Today's price of {current:.2} is at or below the forecast low of {low:.2}, \
+                 leaving no measurable downside."
+            ),
+        );
+    };
+    let passed = ratio >= MIN_UPSIDE_DOWNSIDE;
+    let explanation = format!(
+        "Upside to the forecast high is {ratio:.1} times the downside to the forecast low; \
+         NAIC asks for at least 3 to 1."
+    );
+    result(
+        test,
+        pass_if(passed),
+        Some(ratio),
+        MIN_UPSIDE_DOWNSIDE,
+        explanation,
+    )
+}
+
+fn appreciation(current: Option<f64>, high: Option<f64>) -> NaicTestResult {
+    let test = NaicTest::Appreciation;
+    let (Some(current), Some(high)) = (current, high) else {
+        return missing_prices(test, MIN_APPRECIATION);
+    };
+
+    let percent = (high / current - 1.0) * 100.0;
+    let passed = percent >= MIN_APPRECIATION;
+    let explanation = format!(
+        "The forecast high of {high:.2} is {percent:.0}% above today's {current:.2}; \
+         doubling in five years takes about 15% a year."
+    );
+    result(
+        test,
+        pass_if(passed),
+        Some(percent),
+        MIN_APPRECIATION,
+        explanation,
+    )
+}
+
+/// Least-squares slope of `(x, y)` points; `None` for fewer than two
+/// distinct `x` values.
+fn slope(points: &[(f64, f64)]) -> Option<f64> {
+    let n = points.len() as f64;
+    let sum_x: f64 = points.iter().map(|(x, _)| x).sum();
+    let sum_y: f64 = points.iter().map(|(_, y)| y).sum();
+    let sum_xx: f64 = points.iter().map(|(x, _)| x * x).sum();
+    let sum_xy: f64 = points.iter().map(|(x, y)| x * y).sum();
+    let denominator = n * sum_xx - sum_x * sum_x;
+    if points.len() < 2 || denominator == 0.0 {
+        return None;
+    }
+    Some((n * sum_xy - sum_x * sum_y) / denominator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    fn quality(points: &[(i32, f64, f64)]) -> QualityAnalysis {
+        QualityAnalysis {
+            points: points
+                .iter()
+                .map(|&(year, roe, profit_on_sales)| QualityPoint {
+                    year,
+                    roe,
+                    profit_on_sales,
+                    ..Default::default()
+                })
+                .collect(),
+        }
+    }
+
+    fn trend(cagr: f64) -> TrendAnalysis {
+        TrendAnalysis {
+            cagr,
+            trendline: vec![
+                TrendPoint {
+                    year: 2019,
+                    value: 1.0,
+                },
+                TrendPoint {
+                    year: 2023,
+                    value: 2.0,
+                },
+            ],
+        }
+    }
+
+    /// Snapshot priced at 50 with EPS 5, no growth and P/E 20/8: forecast
+    /// high 100, low 40.
+    fn priced_snapshot() -> AnalysisSnapshot {
+        AnalysisSnapshot {
+            historical_data: HistoricalData {
+                records: vec![HistoricalYearlyData {
+                    fiscal_year: 2023,
+                    eps: Decimal::from(5),
+                    price_high: Decimal::from(50),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            projected_high_pe: 20.0,
+            projected_low_pe: 8.0,
+            ..Default::default()
+        }
+    }
+
+    fn find(results: &[NaicTestResult], test: NaicTest) -> &NaicTestResult {
+        results.iter().find(|r| r.test == test).unwrap()
+    }
+
+    #[test]
+    fn test_management_tests_pass_for_steady_grower() {
+        let quality = quality(&[
+            (2019, 14.0, 10.0),
+            (2020, 15.0, 10.5),
+            (2021, 16.0, 10.4),
+            (2022, 15.5, 11.0),
+            (2023, 16.5, 11.2),
+        ]);
+        let results = evaluate_naic_tests(&quality, &trend(8.0), &trend(10.0), &priced_snapshot());
+
+        let growth = find(&results, NaicTest::SalesDrivenGrowth);
+        assert_eq!(growth.outcome, NaicTestOutcome::Pass);
+        assert!((growth.value.unwrap() - 2.0).abs() < 1e-9);
+
+        let margin = find(&results, NaicTest::ProfitOnSalesTrend);
+        assert_eq!(margin.outcome, NaicTestOutcome::Pass);
+        assert!(margin.value.unwrap() > 0.0);
+
+        let roe = find(&results, NaicTest::ReturnOnEquity);
+        assert_eq!(roe.outcome, NaicTestOutcome::Pass);
+        assert!((roe.value.unwrap() - 15.4).abs() < 1e-9);
+        assert_eq!(roe.benchmark, 10.0);
+    }
+
+    #[test]
+    fn test_management_tests_fail_on_buyback_growth_and_eroding_margins() {
+        let quality = quality(&[
+            (2017, 30.0, 20.0), // outside the five-year window
+            (2019, 9.0, 12.0),
+            (2020, 8.0, 11.0),
+            (2021, 9.5, 10.0),
+            (2022, 8.5, 9.0),
+            (2023, 9.0, 8.0),
+        ]);
+        let results = evaluate_naic_tests(&quality, &trend(2.0), &trend(12.0), &priced_snapshot());
+
+        assert_eq!(
+            find(&results, NaicTest::SalesDrivenGrowth).outcome,
+            NaicTestOutcome::Fail
+        );
+        let margin = find(&results, NaicTest::ProfitOnSalesTrend);
+        assert_eq!(margin.outcome, NaicTestOutcome::Fail);
+        assert!((margin.value.unwrap() + 1.0).abs() < 1e-9);
+        assert_eq!(
+            find(&results, NaicTest::ReturnOnEquity).outcome,
+            NaicTestOutcome::Fail
+        );
+
+        // Shrinking sales fail regardless of EPS
+        let results = evaluate_naic_tests(&quality, &trend(-1.0), &trend(-2.0), &priced_snapshot());
+        let growth = find(&results, NaicTest::SalesDrivenGrowth);
+        assert_eq!(growth.outcome, NaicTestOutcome::Fail);
+        assert!(growth.explanation.contains("not growing"));
+    }
+
+    #[test]
+    fn test_safety_tests_from_forecast_prices() {
+        let results = evaluate_naic_tests(
+            &QualityAnalysis::default(),
+            &trend(5.0),
+            &trend(5.0),
+            &priced_snapshot(),
+        );
+
+        // Low 40 against a price of 50
+        let even = find(&results, NaicTest::GettingOutEven);
+        assert_eq!(even.outcome, NaicTestOutcome::Fail);
+        assert!((even.value.unwrap() - 80.0).abs() < 1e-9);
+
+        // Upside 50, downside 10
+        let ratio = find(&results, NaicTest::UpsideDownside);
+        assert_eq!(ratio.outcome, NaicTestOutcome::Pass);
+        assert!((ratio.value.unwrap() - 5.0).abs() < 1e-9);
+
+        // High 100 doubles 50
+        let gain = find(&results, NaicTest::Appreciation);
+        assert_eq!(gain.outcome, NaicTestOutcome::Pass);
+        assert!((gain.value.unwrap() - 100.0).abs() < 1e-9);
+
+        // Priced below the forecast low: out even and no downside
+        let mut cheap = priced_snapshot();
+        cheap.projected_low_pe = 12.0;
+        let results = evaluate_naic_tests(
+            &QualityAnalysis::default(),
+            &trend(5.0),
+            &trend(5.0),
+            &cheap,
+        );
+        assert_eq!(
+            find(&results, NaicTest::GettingOutEven).outcome,
+            NaicTestOutcome::Pass
+        );
+        let ratio = find(&results, NaicTest::UpsideDownside);
+        assert_eq!(ratio.outcome, NaicTestOutcome::Pass);
+        assert!(ratio.value.is_none());
+    }
+
+    #[test]
+    fn test_missing_data_is_inconclusive() {
+        let results = evaluate_naic_tests(
+            &quality(&[(2023, 0.0, 9.0)]),
+            &TrendAnalysis::default(),
+            &trend(5.0),
+            &AnalysisSnapshot::default(),
+        );
+
+        assert_eq!(results.len(), 6);
+        assert!(
+            results
+                .iter()
+                .all(|r| r.outcome == NaicTestOutcome::Inconclusive && r.value.is_none()),
+            "{results:?}"
+        );
+        assert_eq!(
+            results.iter().map(|r| r.test).collect::<Vec<_>>(),
+            NaicTest::ALL.to_vec()
+        );
+    }
+
+    #[test]
+    fn test_overrides_persist_in_snapshot_and_replace_conclusion() {
+        let mut snapshot = priced_snapshot();
+        snapshot.set_naic_test_override(NaicTestOverride {
+            test: NaicTest::GettingOutEven,
+            outcome: NaicTestOutcome::Inconclusive,
+            note: None,
+        });
+        snapshot.set_naic_test_override(NaicTestOverride {
+            test: NaicTest::GettingOutEven,
+            outcome: NaicTestOutcome::Pass,
+            note: Some("Net cash covers the gap".to_string()),
+        });
+        assert_eq!(snapshot.naic_test_overrides.len(), 1);
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let restored: AnalysisSnapshot = serde_json::from_str(&json).unwrap();
+        let results = evaluate_naic_tests(
+            &QualityAnalysis::default(),
+            &trend(5.0),
+            &trend(5.0),
+            &restored,
+        );
+
+        let even = find(&results, NaicTest::GettingOutEven);
+        assert_eq!(even.outcome, NaicTestOutcome::Fail);
+        assert_eq!(even.conclusion(), NaicTestOutcome::Pass);
+        assert_eq!(
+            even.analyst_override.as_ref().unwrap().note.as_deref(),
+            Some("Net cash covers the gap")
+        );
+        assert_eq!(
+            find(&results, NaicTest::Appreciation).conclusion(),
+            NaicTestOutcome::Pass
+        );
+
+        let mut cleared = restored;
+        cleared.clear_naic_test_override(NaicTest::GettingOutEven);
+        assert!(cleared.naic_test_overrides.is_empty());
+    }
+
+    #[test]
+    fn test_test_groups() {
+        let management: Vec<NaicTest> = NaicTest::ALL
+            .into_iter()
+            .filter(|t| t.group() == NaicTestGroup::Management)
+            .collect();
+        assert_eq!(management.len(), 3);
+        assert_eq!(NaicTest::Appreciation.group(), NaicTestGroup::SafetyOfPrice);
+    }
+}
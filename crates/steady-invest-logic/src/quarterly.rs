@@ -0,0 +1,213 @@
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::types::*;
+
+/// Sums the four latest quarters into trailing-twelve-month figures.
+///
+/// Returns `None` unless the four latest quarters are consecutive, so a
+/// missing quarter never produces a nine- or six-month "TTM". Net and
+/// pre-tax income are only summed when every quarter reports them.
+///
+/// # Examples
+///
+/// ```
+/// use steady_invest_logic::{HistoricalData, HistoricalQuarterlyData, calculate_ttm};
+/// use rust_decimal::Decimal;
+/// use chrono::NaiveDate;
+///
+/// let quarter = |year, q: u8, month| HistoricalQuarterlyData {
+///     fiscal_year: year,
+///     fiscal_quarter: q,
+///     period_end: NaiveDate::from_ymd_opt(year, month, 30).unwrap(),
+///     sales: Decimal::from(100),
+///     eps: Decimal::new(125, 2),
+///     ..Default::default()
+/// };
+/// let data = HistoricalData {
+///     quarters: vec![quarter(2024, 3, 9), quarter(2024, 4, 11), quarter(2025, 1, 3), quarter(2025, 2, 6)],
+///     ..Default::default()
+/// };
+///
+/// let ttm = calculate_ttm(&data).unwrap();
+/// assert_eq!(ttm.eps, Decimal::from(5));
+/// assert_eq!((ttm.fiscal_year, ttm.fiscal_quarter), (2025, 2));
+/// ```
+pub fn calculate_ttm(data: &HistoricalData) -> Option<TtmFigures> {
+    let mut quarters: Vec<&HistoricalQuarterlyData> = data.quarters.iter().collect();
+    quarters.sort_by_key(|q| q.sequence());
+    quarters.dedup_by_key(|q| q.sequence());
+
+    let latest = quarters
+        .len()
+        .checked_sub(4)
+        .map(|start| &quarters[start..])?;
+    let consecutive = latest
+        .windows(2)
+        .all(|pair| pair[1].sequence() == pair[0].sequence() + 1);
+    if !consecutive {
+        return None;
+    }
+
+    let last = latest[3];
+    let sum_optional = |value: fn(&HistoricalQuarterlyData) -> Option<rust_decimal::Decimal>| {
+        latest
+            .iter()
+            .map(|q| value(q))
+            .sum::<Option<rust_decimal::Decimal>>()
+    };
+    Some(TtmFigures {
+        fiscal_year: last.fiscal_year,
+        fiscal_quarter: last.fiscal_quarter,
+        period_end: last.period_end,
+        sales: latest.iter().map(|q| q.sales).sum(),
+        eps: latest.iter().map(|q| q.eps).sum(),
+        net_income: sum_optional(|q| q.net_income),
+        pretax_income: sum_optional(|q| q.pretax_income),
+    })
+}
+
+/// Returns the EPS and price a valuation should start from.
+///
+/// Uses TTM EPS and the latest quarter's high price when at least four
+/// consecutive quarters are available and the latest one is newer than the
+/// latest annual record. Otherwise falls back to the latest fiscal year's
+/// EPS and high price.
+pub fn calculate_current_figures(data: &HistoricalData) -> CurrentFigures {
+    let latest_year = data.records.iter().max_by_key(|r| r.fiscal_year);
+    let mut figures = CurrentFigures {
+        eps: latest_year.and_then(|r| r.eps.to_f64()),
+        price: latest_year.and_then(|r| r.price_high.to_f64()),
+        ttm: None,
+    };
+
+    let Some(ttm) = calculate_ttm(data) else {
+        return figures;
+    };
+    // A TTM ending with (or before) the latest annual report adds nothing newer
+    let superseded = latest_year.is_some_and(|r| {
+        ttm.fiscal_year < r.fiscal_year
+            || (ttm.fiscal_year == r.fiscal_year && ttm.fiscal_quarter == 4)
+    });
+    if superseded {
+        return figures;
+    }
+
+    let latest_quarter_price = data
+        .quarters
+        .iter()
+        .find(|q| q.fiscal_year == ttm.fiscal_year && q.fiscal_quarter == ttm.fiscal_quarter)
+        .and_then(|q| q.price_high)
+        .and_then(|p| p.to_f64());
+    figures.eps = ttm.eps.to_f64();
+    if latest_quarter_price.is_some() {
+        figures.price = latest_quarter_price;
+    }
+    figures.ttm = Some(ttm);
+    figures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+
+    fn quarter(year: i32, q: u8, eps: &str) -> HistoricalQuarterlyData {
+        HistoricalQuarterlyData {
+            fiscal_year: year,
+            fiscal_quarter: q,
+            period_end: NaiveDate::from_ymd_opt(year, u32::from(q) * 3, 28).unwrap(),
+            sales: Decimal::from(250),
+            eps: eps.parse().unwrap(),
+            net_income: Some(Decimal::from(25)),
+            pretax_income: Some(Decimal::from(30)),
+            price_high: Some(Decimal::from(60 + i64::from(q))),
+            price_low: Some(Decimal::from(40)),
+            adjustment_factor: Decimal::ONE,
+            exchange_rate: None,
+        }
+    }
+
+    fn annual(year: i32, eps: i64) -> HistoricalYearlyData {
+        HistoricalYearlyData {
+            fiscal_year: year,
+            eps: Decimal::from(eps),
+            price_high: Decimal::from(50),
+            adjustment_factor: Decimal::ONE,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_ttm_sums_latest_four_quarters() {
+        let data = HistoricalData {
+            quarters: vec![
+                quarter(2024, 1, "9.99"),
+                quarter(2024, 2, "1.00"),
+                quarter(2024, 3, "1.10"),
+                quarter(2024, 4, "1.20"),
+                quarter(2025, 1, "1.30"),
+            ],
+            ..Default::default()
+        };
+        let ttm = calculate_ttm(&data).unwrap();
+        assert_eq!(ttm.eps, Decimal::new(460, 2));
+        assert_eq!(ttm.sales, Decimal::from(1000));
+        assert_eq!(ttm.net_income, Some(Decimal::from(100)));
+        assert_eq!((ttm.fiscal_year, ttm.fiscal_quarter), (2025, 1));
+    }
+
+    #[test]
+    fn test_ttm_requires_consecutive_quarters() {
+        let mut data = HistoricalData {
+            quarters: vec![
+                quarter(2024, 1, "1"),
+                quarter(2024, 3, "1"),
+                quarter(2024, 4, "1"),
+                quarter(2025, 1, "1"),
+            ],
+            ..Default::default()
+        };
+        assert!(calculate_ttm(&data).is_none());
+
+        data.quarters.truncate(3);
+        assert!(calculate_ttm(&data).is_none());
+    }
+
+    #[test]
+    fn test_ttm_income_needs_every_quarter() {
+        let mut quarters: Vec<_> = (1..=4).map(|q| quarter(2024, q, "1")).collect();
+        quarters[2].pretax_income = None;
+        let ttm = calculate_ttm(&HistoricalData {
+            quarters,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(ttm.pretax_income, None);
+        assert_eq!(ttm.net_income, Some(Decimal::from(100)));
+    }
+
+    #[test]
+    fn test_current_figures_prefer_newer_quarters() {
+        let mut data = HistoricalData {
+            records: vec![annual(2023, 4), annual(2024, 5)],
+            quarters: (2..=4)
+                .map(|q| quarter(2024, q, "1.25"))
+                .chain([quarter(2025, 1, "1.50")])
+                .collect(),
+            ..Default::default()
+        };
+        let current = calculate_current_figures(&data);
+        assert_eq!(current.eps, Some(5.25));
+        assert_eq!(current.price, Some(61.0));
+        assert!(current.ttm.is_some());
+
+        // Without the 2025 quarter the TTM equals the annual report
+        data.quarters.pop();
+        data.quarters.insert(0, quarter(2024, 1, "1.25"));
+        let current = calculate_current_figures(&data);
+        assert_eq!(current.eps, Some(5.0));
+        assert_eq!(current.price, Some(50.0));
+        assert!(current.ttm.is_none());
+    }
+}
@@ -0,0 +1,449 @@
+use crate::calculations::calculate_growth_analysis;
+use crate::types::*;
+use crate::valuation::calculate_valuation_zones;
+use rust_decimal::prelude::ToPrimitive;
+
+/// Annual sales, in millions, below which a company counts as small.
+const SMALL_COMPANY_MAX_SALES: f64 = 1_000.0;
+
+/// Annual sales, in millions, above which a company counts as large.
+const LARGE_COMPANY_MIN_SALES: f64 = 10_000.0;
+
+impl CompanySize {
+    /// Size class for latest annual sales, in millions.
+    pub fn from_sales(sales_millions: f64) -> Self {
+        if sales_millions < SMALL_COMPANY_MAX_SALES {
+            CompanySize::Small
+        } else if sales_millions <= LARGE_COMPANY_MIN_SALES {
+            CompanySize::Medium
+        } else {
+            CompanySize::Large
+        }
+    }
+
+    /// Display label ("Small", "Medium", "Large").
+    pub fn label(self) -> &'static str {
+        match self {
+            CompanySize::Small => "Small",
+            CompanySize::Medium => "Medium",
+            CompanySize::Large => "Large",
+        }
+    }
+
+    /// Expected yearly growth in percent: the minimum and, except for small
+    /// companies, the usual maximum.
+    pub fn growth_range(self) -> (f64, Option<f64>) {
+        match self {
+            CompanySize::Small => (12.0, None),
+            CompanySize::Medium => (7.0, Some(12.0)),
+            CompanySize::Large => (5.0, Some(7.0)),
+        }
+    }
+
+    /// Expected growth as text, e.g. "7–12%" or "more than 12%".
+    pub fn growth_range_text(self) -> String {
+        match self.growth_range() {
+            (min, Some(max)) => format!("{min:.0}–{max:.0}%"),
+            (min, None) => format!("more than {min:.0}%"),
+        }
+    }
+}
+
+impl ChecklistQuestion {
+    /// All questions in Stock Checklist order.
+    pub const ALL: [ChecklistQuestion; 4] = [
+        ChecklistQuestion::SalesGrowth,
+        ChecklistQuestion::EarningsGrowth,
+        ChecklistQuestion::FutureGrowth,
+        ChecklistQuestion::PriceAcceptability,
+    ];
+
+    /// The question as put to the reader.
+    pub fn label(self) -> &'static str {
+        match self {
+            ChecklistQuestion::SalesGrowth => "Is sales growth adequate?",
+            ChecklistQuestion::EarningsGrowth => "Is earnings growth adequate?",
+            ChecklistQuestion::FutureGrowth => "Is future growth likely to be adequate?",
+            ChecklistQuestion::PriceAcceptability => "Is the price acceptable?",
+        }
+    }
+}
+
+/// Derives the four guided conclusions of the NAIC Stock Checklist (PRD FR2.10).
+///
+/// The company is sized by its latest annual sales (in millions) and each
+/// growth rate is compared with the growth expected at that size:
+///
+/// 1. **Sales growth** — historical sales CAGR.
+/// 2. **Earnings growth** — historical EPS CAGR; the explanation warns when
+///    the Sales-driven growth test concludes EPS outpaced sales.
+/// 3. **Future growth** — the analyst's projected EPS CAGR.
+/// 4. **Price acceptability** — the Safety-of-Price conclusions in
+///    `naic_tests`, honouring analyst overrides, with the current price zone.
+///
+/// Growth conclusions are [`NaicTestOutcome::Inconclusive`] without sales to
+/// size the company or without enough positive values to measure growth.
+///
+/// # Examples
+///
+/// ```
+/// use steady_invest_logic::{
+///     AnalysisSnapshot, ChecklistQuestion, CompanySize, HistoricalData, HistoricalYearlyData,
+///     NaicTestOutcome, calculate_analysis_summary,
+/// };
+/// use rust_decimal::Decimal;
+///
+/// // Sales of 500–680 million growing about 8% a year
+/// let records = (2019..=2023)
+///     .zip([500, 540, 583, 630, 680])
+///     .map(|(year, sales)| HistoricalYearlyData {
+///         fiscal_year: year,
+///         sales: Decimal::from(sales),
+///         ..Default::default()
+///     })
+///     .collect();
+/// let snapshot = AnalysisSnapshot {
+///     historical_data: HistoricalData { records, ..Default::default() },
+///     ..Default::default()
+/// };
+/// let summary = calculate_analysis_summary(&snapshot, &[]);
+/// assert_eq!(summary.company_size, Some(CompanySize::Small));
+/// let sales = &summary.conclusions[0];
+/// assert_eq!(sales.question, ChecklistQuestion::SalesGrowth);
+/// // Small companies are expected to grow more than 12% a year
+/// assert_eq!(sales.outcome, NaicTestOutcome::Fail);
+/// ```
+pub fn calculate_analysis_summary(
+    snapshot: &AnalysisSnapshot,
+    naic_tests: &[NaicTestResult],
+) -> AnalysisSummary {
+    let mut records: Vec<&HistoricalYearlyData> = snapshot.historical_data.records.iter().collect();
+    records.sort_by_key(|r| r.fiscal_year);
+
+    let latest_sales = records
+        .last()
+        .and_then(|r| r.sales.to_f64())
+        .filter(|s| *s > 0.0);
+    let company_size = latest_sales.map(CompanySize::from_sales);
+
+    let years: Vec<i32> = records.iter().map(|r| r.fiscal_year).collect();
+    let historical_cagr = |values: Vec<f64>| {
+        let trend = calculate_growth_analysis(&years, &values);
+        (!trend.trendline.is_empty()).then_some(trend.cagr)
+    };
+    let sales_cagr = historical_cagr(
+        records
+            .iter()
+            .map(|r| r.sales.to_f64().unwrap_or(0.0))
+            .collect(),
+    );
+    let eps_cagr = historical_cagr(
+        records
+            .iter()
+            .map(|r| r.eps.to_f64().unwrap_or(0.0))
+            .collect(),
+    );
+
+    let sales = growth_conclusion(
+        ChecklistQuestion::SalesGrowth,
+        company_size,
+        sales_cagr.map(|v| (v, format!("Sales grew {v:.1}% a year"))),
+        "sales",
+    );
+
+    let mut earnings = growth_conclusion(
+        ChecklistQuestion::EarningsGrowth,
+        company_size,
+        eps_cagr.map(|v| (v, format!("EPS grew {v:.1}% a year"))),
+        "EPS",
+    );
+    let eps_outpaced_sales = naic_tests
+        .iter()
+        .any(|r| r.test == NaicTest::SalesDrivenGrowth && r.conclusion() == NaicTestOutcome::Fail);
+    if eps_outpaced_sales && earnings.outcome != NaicTestOutcome::Inconclusive {
+        earnings
+            .explanation
+            .push_str(" Earnings did not grow in line with sales, which rarely lasts.");
+    }
+
+    let future = growth_conclusion(
+        ChecklistQuestion::FutureGrowth,
+        company_size,
+        Some((
+            snapshot.projected_eps_cagr,
+            format!(
+                "EPS is estimated to grow {:.1}% a year on sales growth of {:.1}%",
+                snapshot.projected_eps_cagr, snapshot.projected_sales_cagr
+            ),
+        )),
+        "EPS",
+    );
+
+    AnalysisSummary {
+        company_size,
+        latest_sales,
+        conclusions: vec![
+            sales,
+            earnings,
+            future,
+            price_conclusion(snapshot, naic_tests),
+        ],
+    }
+}
+
+/// Compares a growth rate with the growth expected of the company's size.
+/// `observed` pairs the rate with the sentence describing it.
+fn growth_conclusion(
+    question: ChecklistQuestion,
+    size: Option<CompanySize>,
+    observed: Option<(f64, String)>,
+    subject: &str,
+) -> ChecklistConclusion {
+    let conclusion = |outcome, value, benchmark, explanation| ChecklistConclusion {
+        question,
+        outcome,
+        value,
+        benchmark,
+        explanation,
+    };
+    let Some(size) = size else {
+        return conclusion(
+            NaicTestOutcome::Inconclusive,
+            observed.map(|(v, _)| v),
+            0.0,
+            "Latest annual sales are needed to size the company and pick a growth benchmark."
+                .to_string(),
+        );
+    };
+    let (min, max) = size.growth_range();
+    let Some((value, observed)) = observed else {
+        return conclusion(
+            NaicTestOutcome::Inconclusive,
+            None,
+            min,
+            format!("At least two years of positive {subject} are needed to measure growth."),
+        );
+    };
+
+    let expected = format!(
+        "the {} expected of a {} company",
+        size.growth_range_text(),
+        size.label().to_lowercase()
+    );
+    let (outcome, explanation) = if value < min {
+        (
+            NaicTestOutcome::Fail,
+            format!("{observed}, below {expected}."),
+        )
+    } else if max.is_some_and(|max| value > max) {
+        (
+            NaicTestOutcome::Pass,
+            format!("{observed}, above {expected}; check that this pace can last."),
+        )
+    } else {
+        (
+            NaicTestOutcome::Pass,
+            format!("{observed}, meeting {expected}."),
+        )
+    };
+    conclusion(outcome, Some(value), min, explanation)
+}
+
+/// Combines the Safety-of-Price test conclusions into one verdict.
+fn price_conclusion(
+    snapshot: &AnalysisSnapshot,
+    naic_tests: &[NaicTestResult],
+) -> ChecklistConclusion {
+    let safety: Vec<&NaicTestResult> = naic_tests
+        .iter()
+        .filter(|r| r.test.group() == NaicTestGroup::SafetyOfPrice)
+        .collect();
+    let failed: Vec<&str> = safety
+        .iter()
+        .filter(|r| r.conclusion() == NaicTestOutcome::Fail)
+        .map(|r| r.test.label())
+        .collect();
+    let upside_downside = safety.iter().find(|r| r.test == NaicTest::UpsideDownside);
+
+    let (outcome, mut explanation) = if !failed.is_empty() {
+        (
+            NaicTestOutcome::Fail,
+            format!("The price fails: {}.", failed.join(", ")),
+        )
+    } else if !safety.is_empty()
+        && safety
+            .iter()
+            .all(|r| r.conclusion() == NaicTestOutcome::Pass)
+    {
+        (
+            NaicTestOutcome::Pass,
+            "The price passes every Safety-of-Price test.".to_string(),
+        )
+    } else {
+        (
+            NaicTestOutcome::Inconclusive,
+            "A current price and forecast high and low prices are needed to judge the price."
+                .to_string(),
+        )
+    };
+    if let Some(zone) = calculate_valuation_zones(snapshot).current_zone {
+        explanation.push_str(&format!(" Today's price is in the {} zone.", zone.label()));
+    }
+
+    ChecklistConclusion {
+        question: ChecklistQuestion::PriceAcceptability,
+        outcome,
+        value: upside_downside.and_then(|r| r.value),
+        benchmark: upside_downside.map_or(3.0, |r| r.benchmark),
+        explanation,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculations::calculate_quality_analysis;
+    use crate::naic_tests::evaluate_naic_tests;
+    use rust_decimal::Decimal;
+
+    /// Five years of sales and EPS growing at `growth` percent a year from
+    /// `sales` and an EPS of 2, with a price of 40 in the last year.
+    fn snapshot(sales: f64, growth: f64) -> AnalysisSnapshot {
+        let records = (0..5)
+            .map(|i| {
+                let factor = (1.0 + growth / 100.0).powi(i);
+                HistoricalYearlyData {
+                    fiscal_year: 2019 + i,
+                    sales: Decimal::from_f64_retain(sales * factor).unwrap(),
+                    eps: Decimal::from_f64_retain(2.0 * factor).unwrap(),
+                    price_high: Decimal::from(40),
+                    price_low: Decimal::from(20),
+                    ..Default::default()
+                }
+            })
+            .collect();
+        AnalysisSnapshot {
+            historical_data: HistoricalData {
+                records,
+                ..Default::default()
+            },
+            projected_eps_cagr: growth,
+            projected_sales_cagr: growth,
+            projected_high_pe: 25.0,
+            projected_low_pe: 12.0,
+            ..Default::default()
+        }
+    }
+
+    fn naic_tests(snapshot: &AnalysisSnapshot) -> Vec<NaicTestResult> {
+        let data = &snapshot.historical_data;
+        let years: Vec<i32> = data.records.iter().map(|r| r.fiscal_year).collect();
+        let series = |f: fn(&HistoricalYearlyData) -> Decimal| {
+            let values: Vec<f64> = data
+                .records
+                .iter()
+                .map(|r| f(r).to_f64().unwrap())
+                .collect();
+            calculate_growth_analysis(&years, &values)
+        };
+        evaluate_naic_tests(
+            &calculate_quality_analysis(data),
+            &series(|r| r.sales),
+            &series(|r| r.eps),
+            snapshot,
+        )
+    }
+
+    fn conclusion(summary: &AnalysisSummary, question: ChecklistQuestion) -> &ChecklistConclusion {
+        summary
+            .conclusions
+            .iter()
+            .find(|c| c.question == question)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_company_size_boundaries() {
+        assert_eq!(CompanySize::from_sales(999.0), CompanySize::Small);
+        assert_eq!(CompanySize::from_sales(1_000.0), CompanySize::Medium);
+        assert_eq!(CompanySize::from_sales(10_000.0), CompanySize::Medium);
+        assert_eq!(CompanySize::from_sales(10_001.0), CompanySize::Large);
+        assert_eq!(CompanySize::Medium.growth_range_text(), "7–12%");
+        assert_eq!(CompanySize::Small.growth_range_text(), "more than 12%");
+    }
+
+    #[test]
+    fn test_growth_benchmarks_depend_on_size() {
+        // 8% a year is adequate for a medium company but not for a small one
+        let medium = snapshot(5_000.0, 8.0);
+        let summary = calculate_analysis_summary(&medium, &naic_tests(&medium));
+        assert_eq!(summary.company_size, Some(CompanySize::Medium));
+        assert_eq!(summary.conclusions.len(), 4);
+        let sales = conclusion(&summary, ChecklistQuestion::SalesGrowth);
+        assert_eq!(sales.outcome, NaicTestOutcome::Pass);
+        assert!((sales.value.unwrap() - 8.0).abs() < 0.01);
+        assert_eq!(sales.benchmark, 7.0);
+        assert!(sales.explanation.contains("7–12%"), "{}", sales.explanation);
+
+        let small = snapshot(300.0, 8.0);
+        let summary = calculate_analysis_summary(&small, &naic_tests(&small));
+        assert_eq!(summary.company_size, Some(CompanySize::Small));
+        for question in [
+            ChecklistQuestion::SalesGrowth,
+            ChecklistQuestion::EarningsGrowth,
+            ChecklistQuestion::FutureGrowth,
+        ] {
+            let c = conclusion(&summary, question);
+            assert_eq!(c.outcome, NaicTestOutcome::Fail, "{question:?}");
+            assert_eq!(c.benchmark, 12.0);
+        }
+    }
+
+    #[test]
+    fn test_fast_growth_for_size_passes_with_caution() {
+        let large = snapshot(50_000.0, 10.0);
+        let summary = calculate_analysis_summary(&large, &naic_tests(&large));
+        let earnings = conclusion(&summary, ChecklistQuestion::EarningsGrowth);
+        assert_eq!(earnings.outcome, NaicTestOutcome::Pass);
+        assert!(earnings.explanation.contains("can last"));
+    }
+
+    #[test]
+    fn test_price_acceptability_follows_safety_tests_and_overrides() {
+        // EPS 2.93 → 4.72 in five years: forecast 56.6–117.9 against a price
+        // of 40, so there is no downside to measure
+        let mut snap = snapshot(5_000.0, 10.0);
+        let summary = calculate_analysis_summary(&snap, &naic_tests(&snap));
+        let price = conclusion(&summary, ChecklistQuestion::PriceAcceptability);
+        assert_eq!(price.outcome, NaicTestOutcome::Pass);
+        assert_eq!(price.value, None);
+        assert_eq!(price.benchmark, 3.0);
+        assert!(
+            price.explanation.contains("Buy zone"),
+            "{}",
+            price.explanation
+        );
+
+        snap.set_naic_test_override(NaicTestOverride {
+            test: NaicTest::Appreciation,
+            outcome: NaicTestOutcome::Fail,
+            note: None,
+        });
+        let summary = calculate_analysis_summary(&snap, &naic_tests(&snap));
+        let price = conclusion(&summary, ChecklistQuestion::PriceAcceptability);
+        assert_eq!(price.outcome, NaicTestOutcome::Fail);
+        assert!(price.explanation.contains("100% appreciation"));
+    }
+
+    #[test]
+    fn test_summary_without_data_is_inconclusive() {
+        let summary = calculate_analysis_summary(&AnalysisSnapshot::default(), &[]);
+        assert_eq!(summary.company_size, None);
+        assert!(
+            summary
+                .conclusions
+                .iter()
+                .all(|c| c.outcome == NaicTestOutcome::Inconclusive)
+        );
+    }
+}
@@ -13,6 +13,35 @@ pub struct TickerInfo {
     pub currency: String,
 }
 
+/// One page of ranked ticker search results.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct TickerSearchPage {
+    /// Matches on this page, best first.
+    pub results: Vec<TickerInfo>,
+    /// Number of matches across all pages.
+    pub total: usize,
+    /// Position of the first result within all matches.
+    pub offset: usize,
+    /// Maximum number of results per page.
+    pub limit: usize,
+}
+
+/// When a company's fiscal year ends.
+///
+/// Fiscal years are labelled by the calendar year they end in: with a June
+/// year end, FY2025 runs from July 2024 to June 2025.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct FiscalCalendar {
+    /// Month (1–12) in which the fiscal year ends.
+    pub year_end_month: u32,
+}
+
+impl Default for FiscalCalendar {
+    fn default() -> Self {
+        Self::CALENDAR_YEAR
+    }
+}
+
 /// A manual data override for a specific field in a [`HistoricalYearlyData`] record.
 ///
 /// Overrides allow an analyst to replace API-sourced values with corrected figures
@@ -33,7 +62,8 @@ pub struct ManualOverride {
 /// marked `Option` may be unavailable from certain data providers.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct HistoricalYearlyData {
-    /// The calendar year of the fiscal period (e.g., 2023).
+    /// The fiscal year, labelled by the calendar year it ends in (e.g., 2023
+    /// for a fiscal year ending June 2023). See [`FiscalCalendar`].
     pub fiscal_year: i32,
     /// Total revenue / net sales.
     pub sales: rust_decimal::Decimal,
@@ -51,8 +81,12 @@ pub struct HistoricalYearlyData {
     pub total_equity: Option<rust_decimal::Decimal>,
     /// Multiplier to adjust historical values for splits/dividends.
     pub adjustment_factor: rust_decimal::Decimal,
-    /// Rate to convert native currency to user's display currency.
+    /// Average rate over the fiscal year to convert native currency to
+    /// user's display currency.
     pub exchange_rate: Option<rust_decimal::Decimal>,
+    /// Closing rate at fiscal year end, used for balance-sheet items.
+    #[serde(default)]
+    pub closing_exchange_rate: Option<rust_decimal::Decimal>,
     /// Annual dividend per share (used for NAIC yield and payout ratio calculations).
     pub dividend_per_share: Option<rust_decimal::Decimal>,
     /// Total shares outstanding (used for NAIC total return and per-share metrics).
@@ -62,6 +96,99 @@ pub struct HistoricalYearlyData {
     pub overrides: Vec<ManualOverride>,
 }
 
+/// Financial results and pricing for a single fiscal quarter.
+///
+/// Quarterly records follow a company between annual reports; four
+/// consecutive quarters add up to the trailing-twelve-month figures returned
+/// by [`crate::calculate_ttm`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct HistoricalQuarterlyData {
+    /// Fiscal year the quarter belongs to.
+    pub fiscal_year: i32,
+    /// Quarter within the fiscal year (1–4).
+    pub fiscal_quarter: u8,
+    /// Last day of the quarter.
+    pub period_end: chrono::NaiveDate,
+    /// Revenue for the quarter.
+    pub sales: rust_decimal::Decimal,
+    /// Earnings per share (diluted) for the quarter.
+    pub eps: rust_decimal::Decimal,
+    /// Net income after tax for the quarter.
+    pub net_income: Option<rust_decimal::Decimal>,
+    /// Pre-tax income for the quarter.
+    pub pretax_income: Option<rust_decimal::Decimal>,
+    /// Highest stock price during the quarter.
+    pub price_high: Option<rust_decimal::Decimal>,
+    /// Lowest stock price during the quarter.
+    pub price_low: Option<rust_decimal::Decimal>,
+    /// Multiplier to adjust per-share values for later splits.
+    #[serde(default = "unit_factor")]
+    pub adjustment_factor: rust_decimal::Decimal,
+    /// Rate to convert native currency to user's display currency.
+    pub exchange_rate: Option<rust_decimal::Decimal>,
+}
+
+fn unit_factor() -> rust_decimal::Decimal {
+    rust_decimal::Decimal::ONE
+}
+
+impl HistoricalQuarterlyData {
+    /// Sequential quarter number, so that consecutive quarters differ by one.
+    pub fn sequence(&self) -> i32 {
+        self.fiscal_year * 4 + i32::from(self.fiscal_quarter)
+    }
+}
+
+/// Trailing-twelve-month figures summed over the four latest quarters.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct TtmFigures {
+    /// Fiscal year of the latest quarter.
+    pub fiscal_year: i32,
+    /// Latest quarter included (1–4).
+    pub fiscal_quarter: u8,
+    /// Last day of the latest quarter.
+    pub period_end: chrono::NaiveDate,
+    /// Sum of quarterly sales.
+    pub sales: rust_decimal::Decimal,
+    /// Sum of quarterly EPS.
+    pub eps: rust_decimal::Decimal,
+    /// Sum of quarterly net income, if every quarter reports it.
+    pub net_income: Option<rust_decimal::Decimal>,
+    /// Sum of quarterly pre-tax income, if every quarter reports it.
+    pub pretax_income: Option<rust_decimal::Decimal>,
+}
+
+/// EPS and price a valuation starts from.
+///
+/// Built by [`crate::calculate_current_figures`] from the TTM figures and
+/// latest quarter when available, otherwise from the latest annual record.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CurrentFigures {
+    /// Current EPS (TTM when available, else the latest fiscal year's).
+    pub eps: Option<f64>,
+    /// Current price (the latest quarter's high when available, else the
+    /// latest fiscal year's high).
+    pub price: Option<f64>,
+    /// TTM figures the EPS was taken from, if any.
+    pub ttm: Option<TtmFigures>,
+}
+
+/// Which exchange rate [`HistoricalData::apply_normalization`] converts each
+/// field with.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum FxConvention {
+    /// Income-statement items, prices and dividends at the yearly average
+    /// rate, `total_equity` at the fiscal year-end rate.
+    #[default]
+    AverageAndYearEnd,
+    /// Every field at the yearly average rate.
+    Average,
+    /// Every field at the fiscal year-end rate.
+    YearEnd,
+    /// Every field at today's rate, so all years share one rate.
+    Spot,
+}
+
 /// A collection of historical financial records for a ticker.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct HistoricalData {
@@ -71,6 +198,10 @@ pub struct HistoricalData {
     pub currency: String,
     /// Current display currency (if normalized).
     pub display_currency: Option<String>,
+    /// Today's rate from the native to the display currency, used by
+    /// [`FxConvention::Spot`].
+    #[serde(default)]
+    pub spot_exchange_rate: Option<rust_decimal::Decimal>,
     /// Chronological list of yearly records.
     pub records: Vec<HistoricalYearlyData>,
     /// Flag indicating if data retrieval was successful/complete.
@@ -79,6 +210,63 @@ pub struct HistoricalData {
     pub is_split_adjusted: bool,
     /// Calculated P/E ranges and averages (last 5 years per NAIC Section 3).
     pub pe_range_analysis: Option<PeRangeAnalysis>,
+    /// Quarterly records, oldest first (may be empty).
+    #[serde(default)]
+    pub quarters: Vec<HistoricalQuarterlyData>,
+    /// Data gaps and implausible values found by [`crate::detect_anomalies`].
+    #[serde(default)]
+    pub anomalies: Vec<Anomaly>,
+}
+
+/// Category of a problem found in historical data.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum AnomalyKind {
+    /// A fiscal year between the first and last record has no record.
+    MissingYear,
+    /// A required value (sales, prices) is zero, i.e. was not reported.
+    MissingValue,
+    /// Shareholders' equity is zero or negative.
+    NonPositiveEquity,
+    /// EPS changed sign from the previous year.
+    EpsSignFlip,
+    /// A year-over-year change is an outlier within the series.
+    ImplausibleJump,
+    /// The year's low price is above its high price.
+    PriceRangeInverted,
+    /// Pre-tax income does not fit the year's sales at the usual margin.
+    InconsistentFigures,
+}
+
+/// A single problem found in historical data.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Anomaly {
+    /// Category of the problem.
+    pub kind: AnomalyKind,
+    /// Fiscal year the problem was found in.
+    pub fiscal_year: i32,
+    /// Field the problem concerns (e.g., `"eps"`).
+    pub field: String,
+    /// Human-readable description for the UI and audit log.
+    pub message: String,
+}
+
+/// Thresholds used by [`crate::detect_anomalies`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct AnomalyConfig {
+    /// Modified z-score above which a year-over-year change is implausible.
+    pub jump_z_score: f64,
+    /// Largest accepted distance, in percentage points, between a year's
+    /// pre-tax margin and the series' median margin.
+    pub max_margin_deviation: f64,
+}
+
+impl Default for AnomalyConfig {
+    fn default() -> Self {
+        Self {
+            jump_z_score: 3.5,
+            max_margin_deviation: 25.0,
+        }
+    }
 }
 
 /// A single data point on a calculated trendline.
@@ -159,6 +347,96 @@ pub struct PeRangeAnalysis {
     pub avg_low_pe: f64,
 }
 
+/// The five P/E tiers of NAIC SSG Section 3, built by
+/// [`crate::calculate_pe_breakdown`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub struct PeBreakdown {
+    /// Highest yearly high P/E.
+    pub highest: f64,
+    /// Average of the yearly high P/Es.
+    pub average_high: f64,
+    /// Midpoint of the average high and average low P/E.
+    pub average: f64,
+    /// Average of the yearly low P/Es.
+    pub average_low: f64,
+    /// Lowest yearly low P/E.
+    pub lowest: f64,
+}
+
+/// Third of the forecast price range a price falls in (NAIC SSG Section 4C).
+///
+/// Ordered from the cheapest zone to the dearest.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PriceZone {
+    /// Lower third of the range, or below the forecast low.
+    Buy,
+    /// Middle third of the range.
+    Maybe,
+    /// Upper third of the range, or above the forecast high.
+    Sell,
+}
+
+/// Forecast price range split into Buy, Maybe and Sell thirds, built by
+/// [`crate::calculate_price_zones`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct PriceZones {
+    /// Forecast high price (Section 4A).
+    pub forecast_high: f64,
+    /// Forecast low price (Section 4B).
+    pub forecast_low: f64,
+    /// Upper bound of the Buy zone: forecast low plus a third of the range.
+    pub buy_below: f64,
+    /// Lower bound of the Sell zone: forecast high less a third of the range.
+    pub sell_above: f64,
+    /// Currency of the prices.
+    pub currency: String,
+}
+
+/// P/E tiers and price zones of a snapshot, built by
+/// [`crate::calculate_valuation_zones`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ValuationZones {
+    /// P/E tiers; `None` without positive-EPS years.
+    pub pe_breakdown: Option<PeBreakdown>,
+    /// Price zones; `None` without forecast prices.
+    pub price_zones: Option<PriceZones>,
+    /// Zone of the current price, when both are known.
+    pub current_zone: Option<PriceZone>,
+}
+
+/// Source of the forecast low price used for the downside (NAIC SSG
+/// Section 4B).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum LowPriceMethod {
+    /// Estimated average low P/E times the projected five-year EPS.
+    #[default]
+    ProjectedLowPe,
+    /// Historical average low P/E times current EPS (4B-a).
+    AverageLowPe,
+    /// Average of the yearly low prices over the last five years (4B-b).
+    AverageLowPrice,
+    /// Lowest yearly low price over the last five years (4B-c).
+    SevereMarketLow,
+    /// Current dividend divided by the highest yield of the last five
+    /// years (4B-d).
+    DividendSupport,
+}
+
+/// The four alternative low-price candidates of NAIC SSG Section 4B, built
+/// by [`crate::calculate_low_price_estimates`]. Each is `None` when the data
+/// it needs is missing.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub struct LowPriceEstimates {
+    /// Historical average low P/E times current EPS.
+    pub average_low_pe: Option<f64>,
+    /// Average yearly low price over the last five years.
+    pub average_low_price: Option<f64>,
+    /// Lowest yearly low price over the last five years.
+    pub severe_market_low: Option<f64>,
+    /// Price at which the current dividend yields the five-year high yield.
+    pub dividend_support: Option<f64>,
+}
+
 /// Per-year dividend metrics for NAIC SSG Section 3 P/E History table.
 ///
 /// Corresponds to columns F (Dividend Per Share), G (% Payout),
@@ -195,6 +473,148 @@ pub struct AnalysisSnapshot {
     pub analyst_note: String,
     /// The timestamp when the snapshot was captured (UTC).
     pub captured_at: chrono::DateTime<chrono::Utc>,
+    /// Market price at capture time; `None` for snapshots taken without a
+    /// quote, which fall back to the latest fiscal year's high.
+    #[serde(default)]
+    pub current_quote: Option<Quote>,
+    /// Analyst overrides of NAIC test conclusions, at most one per test.
+    #[serde(default)]
+    pub naic_test_overrides: Vec<NaicTestOverride>,
+    /// Source of the forecast low price chosen by the analyst.
+    #[serde(default)]
+    pub low_price_method: LowPriceMethod,
+}
+
+/// One of the NAIC SSG Management and Safety-of-Price tests (PRD FR2.8).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum NaicTest {
+    /// Earnings growth is carried by sales growth, not cost cutting alone.
+    SalesDrivenGrowth,
+    /// Pre-tax profit on sales is holding or rising.
+    ProfitOnSalesTrend,
+    /// Return on equity averages at least 10%.
+    ReturnOnEquity,
+    /// The forecast low price is at or above today's price.
+    GettingOutEven,
+    /// Upside is at least three times the downside.
+    UpsideDownside,
+    /// The forecast high price doubles today's price within five years.
+    Appreciation,
+}
+
+/// Section of the SSG a [`NaicTest`] belongs to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum NaicTestGroup {
+    /// Evaluate Management (SSG Section 2).
+    Management,
+    /// Safety of price (SSG Sections 4 and 5).
+    SafetyOfPrice,
+}
+
+/// Conclusion of a [`NaicTest`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum NaicTestOutcome {
+    /// The benchmark is met.
+    Pass,
+    /// The benchmark is missed.
+    Fail,
+    /// Not enough data to judge.
+    Inconclusive,
+}
+
+/// An analyst's conclusion replacing the computed one for a [`NaicTest`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NaicTestOverride {
+    /// Test the conclusion applies to.
+    pub test: NaicTest,
+    /// Conclusion chosen by the analyst.
+    pub outcome: NaicTestOutcome,
+    /// Optional analyst note explaining the override.
+    pub note: Option<String>,
+}
+
+/// Structured result of one NAIC test, built by [`crate::evaluate_naic_tests`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NaicTestResult {
+    /// The test evaluated.
+    pub test: NaicTest,
+    /// Conclusion computed from the data.
+    pub outcome: NaicTestOutcome,
+    /// Computed value compared against the benchmark, if available.
+    pub value: Option<f64>,
+    /// Benchmark the value is compared against, in the value's unit.
+    pub benchmark: f64,
+    /// Plain-language explanation of the computed conclusion.
+    pub explanation: String,
+    /// Analyst override stored in the snapshot, if any.
+    pub analyst_override: Option<NaicTestOverride>,
+}
+
+/// Company size class by latest annual sales, setting the growth expected of
+/// the company (NAIC Stock Checklist).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CompanySize {
+    /// Sales below 1 billion: growth above 12% expected.
+    Small,
+    /// Sales of 1 to 10 billion: growth of 7–12% expected.
+    Medium,
+    /// Sales above 10 billion: growth of 5–7% expected.
+    Large,
+}
+
+/// Question answered by a guided conclusion of the analysis summary.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ChecklistQuestion {
+    /// Has sales growth been adequate for the company's size?
+    SalesGrowth,
+    /// Has earnings growth been adequate for the company's size?
+    EarningsGrowth,
+    /// Does the estimated future growth meet the size benchmark?
+    FutureGrowth,
+    /// Is the current price acceptable?
+    PriceAcceptability,
+}
+
+/// One guided conclusion, built by [`crate::calculate_analysis_summary`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChecklistConclusion {
+    /// Question answered.
+    pub question: ChecklistQuestion,
+    /// Whether the benchmark is met.
+    pub outcome: NaicTestOutcome,
+    /// Value compared against the benchmark: a growth rate in percent, or
+    /// the upside/downside ratio for price acceptability.
+    pub value: Option<f64>,
+    /// Minimum the value must reach, in the value's unit.
+    pub benchmark: f64,
+    /// Plain-language explanation for readers new to the SSG.
+    pub explanation: String,
+}
+
+/// Guided summary of an analysis (PRD FR2.10), built by
+/// [`crate::calculate_analysis_summary`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct AnalysisSummary {
+    /// Size class the growth benchmarks are taken from; `None` without sales.
+    pub company_size: Option<CompanySize>,
+    /// Latest annual sales the size is derived from, in millions of the
+    /// reporting currency.
+    pub latest_sales: Option<f64>,
+    /// The four conclusions, in [`ChecklistQuestion`] order.
+    pub conclusions: Vec<ChecklistConclusion>,
+}
+
+/// A market price for a ticker at a point in time.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct Quote {
+    /// Price in the ticker's trading currency.
+    pub price: rust_decimal::Decimal,
+    /// Currency of `price`.
+    pub currency: String,
+    /// When the price was observed (UTC).
+    pub as_of: chrono::DateTime<chrono::Utc>,
+    /// Where the price came from (a provider name or `"manual"`).
+    pub source: String,
 }
 
 /// Extracted monetary prices from an analysis snapshot.
@@ -203,14 +623,32 @@ pub struct AnalysisSnapshot {
 /// duplicating the extraction logic (Cardinal Rule).
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct SnapshotPrices {
-    /// Current price (latest fiscal year's high price).
+    /// Current price: the snapshot's quote when present, else the price from
+    /// [`crate::calculate_current_figures`].
     pub current_price: Option<f64>,
+    /// When the current price was quoted; `None` when it is a historical high.
+    pub price_as_of: Option<chrono::DateTime<chrono::Utc>>,
     /// Target high price (projected_high_pe × projected 5-year EPS).
     pub target_high_price: Option<f64>,
-    /// Target low price (projected_low_pe × projected 5-year EPS).
+    /// Target low price: the Section 4B candidate chosen by the snapshot's
+    /// [`LowPriceMethod`], falling back to projected_low_pe × projected
+    /// 5-year EPS when that candidate is unavailable.
     pub target_low_price: Option<f64>,
 }
 
+/// NAIC SSG Section 5 metrics of an [`AnalysisSnapshot`], computed by
+/// [`crate::compute_return_metrics_from_snapshot`]. All values are percentages.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub struct ReturnMetrics {
+    /// Current P/E as a percentage of the 5-year average P/E.
+    pub relative_value: Option<f64>,
+    /// Latest annual dividend as a percentage of the current price.
+    pub present_yield: Option<f64>,
+    /// Projected Average Return (PAR): annual appreciation to the average
+    /// forecast price plus the 5-year average yield.
+    pub projected_average_return: Option<f64>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
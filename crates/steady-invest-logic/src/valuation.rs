@@ -0,0 +1,467 @@
+use crate::calculations::{calculate_pe_ranges, extract_snapshot_prices};
+use crate::quarterly::calculate_current_figures;
+use crate::types::*;
+use rust_decimal::prelude::ToPrimitive;
+
+/// Years of history the Section 4B low-price candidates look back over.
+const LOW_PRICE_YEARS: usize = 5;
+
+impl PriceZone {
+    /// Display label used on the SSG form ("Buy", "Maybe", "Sell").
+    pub fn label(self) -> &'static str {
+        match self {
+            PriceZone::Buy => "Buy",
+            PriceZone::Maybe => "Maybe",
+            PriceZone::Sell => "Sell",
+        }
+    }
+}
+
+impl PriceZones {
+    /// Width of the forecast price range.
+    pub fn range(&self) -> f64 {
+        self.forecast_high - self.forecast_low
+    }
+
+    /// Zone a price falls in. Prices below the forecast low count as Buy and
+    /// prices above the forecast high as Sell; a price on a boundary belongs
+    /// to the middle zone.
+    pub fn zone_for(&self, price: f64) -> PriceZone {
+        if price < self.buy_below {
+            PriceZone::Buy
+        } else if price > self.sell_above {
+            PriceZone::Sell
+        } else {
+            PriceZone::Maybe
+        }
+    }
+}
+
+impl LowPriceMethod {
+    /// All methods in selection order: the projection, then the four
+    /// Section 4B candidates.
+    pub const ALL: [LowPriceMethod; 5] = [
+        LowPriceMethod::ProjectedLowPe,
+        LowPriceMethod::AverageLowPe,
+        LowPriceMethod::AverageLowPrice,
+        LowPriceMethod::SevereMarketLow,
+        LowPriceMethod::DividendSupport,
+    ];
+
+    /// Short label for selects and reports.
+    pub fn label(self) -> &'static str {
+        match self {
+            LowPriceMethod::ProjectedLowPe => "Estimated low P/E × 5-year EPS",
+            LowPriceMethod::AverageLowPe => "Average low P/E × current EPS",
+            LowPriceMethod::AverageLowPrice => "Average low price (5 years)",
+            LowPriceMethod::SevereMarketLow => "Severe market low (5 years)",
+            LowPriceMethod::DividendSupport => "Price supported by dividend",
+        }
+    }
+}
+
+impl LowPriceEstimates {
+    /// Candidate for `method`; always `None` for
+    /// [`LowPriceMethod::ProjectedLowPe`], which comes from the projections.
+    pub fn get(&self, method: LowPriceMethod) -> Option<f64> {
+        match method {
+            LowPriceMethod::ProjectedLowPe => None,
+            LowPriceMethod::AverageLowPe => self.average_low_pe,
+            LowPriceMethod::AverageLowPrice => self.average_low_price,
+            LowPriceMethod::SevereMarketLow => self.severe_market_low,
+            LowPriceMethod::DividendSupport => self.dividend_support,
+        }
+    }
+
+    /// Low price for `method`, falling back to `projected_low` when the
+    /// method is the projection or its candidate is unavailable.
+    pub fn select(&self, method: LowPriceMethod, projected_low: Option<f64>) -> Option<f64> {
+        self.get(method).or(projected_low)
+    }
+}
+
+/// Computes the four alternative low-price candidates of NAIC SSG Section 4B
+/// from the last five fiscal years.
+///
+/// * **Average low P/E × current EPS** — the stored P/E range analysis (or one
+///   computed from the records) and [`calculate_current_figures`] EPS.
+/// * **Average low price** — mean of the positive yearly low prices.
+/// * **Severe market low** — lowest positive yearly low price.
+/// * **Dividend support** — latest dividend divided by the highest yearly
+///   yield at the low price.
+///
+/// # Examples
+///
+/// ```
+/// use steady_invest_logic::{HistoricalData, HistoricalYearlyData, calculate_low_price_estimates};
+/// use rust_decimal::Decimal;
+///
+/// let records = [(2022, 30, 1), (2023, 20, 2)]
+///     .into_iter()
+///     .map(|(year, low, dps)| HistoricalYearlyData {
+///         fiscal_year: year,
+///         eps: Decimal::from(4),
+///         price_low: Decimal::from(low),
+///         dividend_per_share: Some(Decimal::from(dps)),
+///         ..Default::default()
+///     })
+///     .collect();
+/// let estimates = calculate_low_price_estimates(&HistoricalData { records, ..Default::default() });
+/// assert_eq!(estimates.average_low_price, Some(25.0));
+/// assert_eq!(estimates.severe_market_low, Some(20.0));
+/// // A dividend of 2 at the 10% high yield of 2023
+/// assert_eq!(estimates.dividend_support, Some(20.0));
+/// ```
+pub fn calculate_low_price_estimates(data: &HistoricalData) -> LowPriceEstimates {
+    let mut records: Vec<&HistoricalYearlyData> = data.records.iter().collect();
+    records.sort_by_key(|r| r.fiscal_year);
+    let recent = &records[records.len().saturating_sub(LOW_PRICE_YEARS)..];
+
+    let lows: Vec<f64> = recent
+        .iter()
+        .filter_map(|r| r.price_low.to_f64())
+        .filter(|p| *p > 0.0)
+        .collect();
+    let average_low_price =
+        (!lows.is_empty()).then(|| lows.iter().sum::<f64>() / lows.len() as f64);
+    let severe_market_low = lows.iter().copied().reduce(f64::min);
+
+    let pe = match &data.pe_range_analysis {
+        Some(pe) => pe.clone(),
+        None => calculate_pe_ranges(data),
+    };
+    let average_low_pe = match calculate_current_figures(data).eps {
+        Some(eps) if eps > 0.0 && pe.avg_low_pe > 0.0 => Some(pe.avg_low_pe * eps),
+        _ => None,
+    };
+
+    let dividend = |r: &HistoricalYearlyData| {
+        r.dividend_per_share
+            .and_then(|d| d.to_f64())
+            .filter(|d| *d > 0.0)
+    };
+    let high_yield = recent
+        .iter()
+        .filter_map(|r| {
+            let low = r.price_low.to_f64().filter(|p| *p > 0.0)?;
+            Some(dividend(r)? / low)
+        })
+        .reduce(f64::max);
+    let dividend_support = match (recent.last().and_then(|r| dividend(r)), high_yield) {
+        (Some(dps), Some(high_yield)) => Some(dps / high_yield),
+        _ => None,
+    };
+
+    LowPriceEstimates {
+        average_low_pe,
+        average_low_price,
+        severe_market_low,
+        dividend_support,
+    }
+}
+
+/// Builds the five P/E tiers of NAIC SSG Section 3 from a P/E range analysis.
+///
+/// Highest and lowest are the extremes of the yearly high and low P/Es; the
+/// average P/E is the midpoint of the average high and average low.
+///
+/// Returns `None` when the analysis has no data points.
+///
+/// # Examples
+///
+/// ```
+/// use steady_invest_logic::{PeRangeAnalysis, PeRangePoint, calculate_pe_breakdown};
+///
+/// let pe = PeRangeAnalysis {
+///     points: vec![
+///         PeRangePoint { year: 2022, high_pe: 24.0, low_pe: 12.0 },
+///         PeRangePoint { year: 2023, high_pe: 20.0, low_pe: 14.0 },
+///     ],
+///     avg_high_pe: 22.0,
+///     avg_low_pe: 13.0,
+/// };
+/// let tiers = calculate_pe_breakdown(&pe).unwrap();
+/// assert_eq!(tiers.highest, 24.0);
+/// assert_eq!(tiers.average, 17.5);
+/// assert_eq!(tiers.lowest, 12.0);
+/// ```
+pub fn calculate_pe_breakdown(pe: &PeRangeAnalysis) -> Option<PeBreakdown> {
+    if pe.points.is_empty() {
+        return None;
+    }
+    let highest = pe.points.iter().map(|p| p.high_pe).fold(f64::MIN, f64::max);
+    let lowest = pe.points.iter().map(|p| p.low_pe).fold(f64::MAX, f64::min);
+    Some(PeBreakdown {
+        highest,
+        average_high: pe.avg_high_pe,
+        average: (pe.avg_high_pe + pe.avg_low_pe) / 2.0,
+        average_low: pe.avg_low_pe,
+        lowest,
+    })
+}
+
+/// Splits the forecast price range into thirds (NAIC SSG Section 4C): the
+/// lower third is the Buy zone, the middle third Maybe, the upper third Sell.
+///
+/// Returns `None` unless `0 < forecast_low < forecast_high`.
+///
+/// # Examples
+///
+/// ```
+/// use steady_invest_logic::{PriceZone, calculate_price_zones};
+///
+/// let zones = calculate_price_zones(150.0, 60.0, "USD").unwrap();
+/// assert_eq!(zones.buy_below, 90.0);
+/// assert_eq!(zones.sell_above, 120.0);
+/// assert_eq!(zones.zone_for(100.0), PriceZone::Maybe);
+/// ```
+pub fn calculate_price_zones(
+    forecast_high: f64,
+    forecast_low: f64,
+    currency: &str,
+) -> Option<PriceZones> {
+    if !(forecast_low > 0.0 && forecast_high > forecast_low) {
+        return None;
+    }
+    let third = (forecast_high - forecast_low) / 3.0;
+    Some(PriceZones {
+        forecast_high,
+        forecast_low,
+        buy_below: forecast_low + third,
+        sell_above: forecast_high - third,
+        currency: currency.to_string(),
+    })
+}
+
+/// Computes the P/E tiers and price zones of a snapshot, and the zone of its
+/// current price.
+///
+/// Uses the stored P/E range analysis when present, else computes it from the
+/// records. Forecast and current prices come from [`extract_snapshot_prices`]
+/// and are in the display currency when the data was normalized, so a quote
+/// in the trading currency is zoned after conversion.
+pub fn calculate_valuation_zones(snapshot: &AnalysisSnapshot) -> ValuationZones {
+    let data = &snapshot.historical_data;
+    let pe_breakdown = match &data.pe_range_analysis {
+        Some(pe) => calculate_pe_breakdown(pe),
+        None => calculate_pe_breakdown(&calculate_pe_ranges(data)),
+    };
+
+    let prices = extract_snapshot_prices(snapshot);
+    let currency = data.display_currency.as_deref().unwrap_or(&data.currency);
+    let price_zones = match (prices.target_high_price, prices.target_low_price) {
+        (Some(high), Some(low)) => calculate_price_zones(high, low, currency),
+        _ => None,
+    };
+    let current_zone = match (&price_zones, prices.current_price) {
+        (Some(zones), Some(price)) if price > 0.0 => Some(zones.zone_for(price)),
+        _ => None,
+    };
+
+    ValuationZones {
+        pe_breakdown,
+        price_zones,
+        current_zone,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    fn pe_point(year: i32, high_pe: f64, low_pe: f64) -> PeRangePoint {
+        PeRangePoint {
+            year,
+            high_pe,
+            low_pe,
+        }
+    }
+
+    #[test]
+    fn test_pe_breakdown_tiers_are_ordered() {
+        let pe = PeRangeAnalysis {
+            points: vec![
+                pe_point(2019, 30.0, 15.0),
+                pe_point(2020, 25.0, 10.0),
+                pe_point(2021, 20.0, 14.0),
+            ],
+            avg_high_pe: 25.0,
+            avg_low_pe: 13.0,
+        };
+        let tiers = calculate_pe_breakdown(&pe).unwrap();
+        assert_eq!(tiers.highest, 30.0);
+        assert_eq!(tiers.average_high, 25.0);
+        assert_eq!(tiers.average, 19.0);
+        assert_eq!(tiers.average_low, 13.0);
+        assert_eq!(tiers.lowest, 10.0);
+    }
+
+    #[test]
+    fn test_pe_breakdown_empty_analysis() {
+        assert_eq!(calculate_pe_breakdown(&PeRangeAnalysis::default()), None);
+    }
+
+    #[test]
+    fn test_price_zones_boundaries() {
+        let zones = calculate_price_zones(90.0, 30.0, "CHF").unwrap();
+        assert_eq!(zones.range(), 60.0);
+        assert_eq!(zones.buy_below, 50.0);
+        assert_eq!(zones.sell_above, 70.0);
+        assert_eq!(zones.currency, "CHF");
+
+        assert_eq!(zones.zone_for(20.0), PriceZone::Buy);
+        assert_eq!(zones.zone_for(49.9), PriceZone::Buy);
+        assert_eq!(zones.zone_for(50.0), PriceZone::Maybe);
+        assert_eq!(zones.zone_for(70.0), PriceZone::Maybe);
+        assert_eq!(zones.zone_for(70.1), PriceZone::Sell);
+        assert_eq!(zones.zone_for(120.0), PriceZone::Sell);
+    }
+
+    #[test]
+    fn test_price_zones_reject_invalid_range() {
+        assert!(calculate_price_zones(50.0, 50.0, "USD").is_none());
+        assert!(calculate_price_zones(40.0, 50.0, "USD").is_none());
+        assert!(calculate_price_zones(50.0, 0.0, "USD").is_none());
+    }
+
+    #[test]
+    fn test_valuation_zones_from_snapshot() {
+        let records = (2019..=2023)
+            .map(|year| HistoricalYearlyData {
+                fiscal_year: year,
+                eps: Decimal::from(5),
+                price_high: Decimal::from(100),
+                price_low: Decimal::from(50),
+                ..Default::default()
+            })
+            .collect();
+        let snapshot = AnalysisSnapshot {
+            historical_data: HistoricalData {
+                currency: "CHF".to_string(),
+                display_currency: Some("USD".to_string()),
+                records,
+                ..Default::default()
+            },
+            projected_eps_cagr: 0.0,
+            projected_high_pe: 20.0,
+            projected_low_pe: 8.0,
+            ..Default::default()
+        };
+        let zones = calculate_valuation_zones(&snapshot);
+
+        let tiers = zones.pe_breakdown.unwrap();
+        assert_eq!(tiers.highest, 20.0);
+        assert_eq!(tiers.average, 15.0);
+        assert_eq!(tiers.lowest, 10.0);
+
+        // Flat EPS of 5: forecast range 40–100, thirds at 60 and 80
+        let price_zones = zones.price_zones.unwrap();
+        assert!((price_zones.buy_below - 60.0).abs() < 1e-9);
+        assert!((price_zones.sell_above - 80.0).abs() < 1e-9);
+        assert_eq!(price_zones.currency, "USD");
+        // Current price is the latest high of 100
+        assert_eq!(zones.current_zone, Some(PriceZone::Sell));
+    }
+
+    #[test]
+    fn test_valuation_zone_of_foreign_quote() {
+        let records = (2019..=2023)
+            .map(|year| HistoricalYearlyData {
+                fiscal_year: year,
+                eps: Decimal::from(5),
+                price_high: Decimal::from(100),
+                price_low: Decimal::from(50),
+                ..Default::default()
+            })
+            .collect();
+        // USD-normalized figures with a quote of 50 CHF at 1.50 USD per CHF
+        let snapshot = AnalysisSnapshot {
+            historical_data: HistoricalData {
+                currency: "CHF".to_string(),
+                display_currency: Some("USD".to_string()),
+                spot_exchange_rate: Some(Decimal::new(150, 2)),
+                records,
+                ..Default::default()
+            },
+            current_quote: Some(Quote {
+                price: Decimal::from(50),
+                currency: "CHF".to_string(),
+                ..Default::default()
+            }),
+            projected_eps_cagr: 0.0,
+            projected_high_pe: 20.0,
+            projected_low_pe: 8.0,
+            ..Default::default()
+        };
+
+        // 75 USD sits between 60 and 80; the raw 50 would read as Buy
+        let zones = calculate_valuation_zones(&snapshot);
+        assert_eq!(zones.current_zone, Some(PriceZone::Maybe));
+    }
+
+    #[test]
+    fn test_low_price_estimates_use_last_five_years() {
+        let records = [
+            (2017, 2, 5, None), // outside the five-year window
+            (2019, 4, 40, Some(1)),
+            (2020, 4, 30, Some(1)),
+            (2021, 5, 50, Some(2)),
+            (2022, 5, 45, None),
+            (2023, 6, 35, Some(2)),
+        ]
+        .into_iter()
+        .map(|(year, eps, low, dps)| HistoricalYearlyData {
+            fiscal_year: year,
+            eps: Decimal::from(eps),
+            price_high: Decimal::from(low * 2),
+            price_low: Decimal::from(low),
+            dividend_per_share: dps.map(Decimal::from),
+            ..Default::default()
+        })
+        .collect();
+        let data = HistoricalData {
+            records,
+            pe_range_analysis: Some(PeRangeAnalysis {
+                points: vec![pe_point(2023, 12.0, 6.0)],
+                avg_high_pe: 12.0,
+                avg_low_pe: 6.0,
+            }),
+            ..Default::default()
+        };
+        let estimates = calculate_low_price_estimates(&data);
+
+        // Stored average low P/E of 6 times the latest EPS of 6
+        assert_eq!(estimates.average_low_pe, Some(36.0));
+        assert_eq!(estimates.average_low_price, Some(40.0));
+        assert_eq!(estimates.severe_market_low, Some(30.0));
+        // Highest yield 2/35 in 2023; the latest dividend of 2 is supported at 35
+        assert!((estimates.dividend_support.unwrap() - 35.0).abs() < 1e-9);
+
+        assert_eq!(estimates.get(LowPriceMethod::ProjectedLowPe), None);
+        assert_eq!(
+            estimates.select(LowPriceMethod::ProjectedLowPe, Some(20.0)),
+            Some(20.0)
+        );
+        assert_eq!(
+            estimates.select(LowPriceMethod::SevereMarketLow, Some(20.0)),
+            Some(30.0)
+        );
+    }
+
+    #[test]
+    fn test_low_price_estimates_without_data() {
+        let estimates = calculate_low_price_estimates(&HistoricalData::default());
+        assert_eq!(estimates, LowPriceEstimates::default());
+        assert_eq!(
+            estimates.select(LowPriceMethod::AverageLowPrice, None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_valuation_zones_without_records() {
+        let zones = calculate_valuation_zones(&AnalysisSnapshot::default());
+        assert_eq!(zones, ValuationZones::default());
+    }
+}
@@ -0,0 +1,96 @@
+//! Guided analysis summary component (PRD FR2.10).
+//!
+//! Answers the four NAIC Stock Checklist questions — sales growth, earnings
+//! growth, future growth and price — in plain language, benchmarked against
+//! the growth expected for the company's size, for members new to the SSG.
+
+use leptos::prelude::*;
+use steady_invest_logic::{
+    AnalysisSnapshot, HistoricalData, LowPriceMethod, NaicTestOutcome, NaicTestOverride,
+    TrendAnalysis, calculate_analysis_summary, calculate_quality_analysis, evaluate_naic_tests,
+};
+
+fn verdict_badge(outcome: NaicTestOutcome) -> (&'static str, &'static str) {
+    match outcome {
+        NaicTestOutcome::Pass => ("naic-pass", "YES"),
+        NaicTestOutcome::Fail => ("naic-fail", "NO"),
+        NaicTestOutcome::Inconclusive => ("naic-inconclusive", "N/A"),
+    }
+}
+
+/// Guided summary of the live analysis.
+///
+/// Future growth follows the EPS projection slider and price acceptability
+/// follows the P/E sliders and the analyst's NAIC test overrides.
+#[component]
+pub fn AnalysisSummaryPanel(
+    data: HistoricalData,
+    sales_growth: TrendAnalysis,
+    eps_growth: TrendAnalysis,
+    projected_sales_cagr: RwSignal<f64>,
+    projected_eps_cagr: RwSignal<f64>,
+    future_high_pe: RwSignal<f64>,
+    future_low_pe: RwSignal<f64>,
+    low_price_method: RwSignal<LowPriceMethod>,
+    overrides: RwSignal<Vec<NaicTestOverride>>,
+) -> impl IntoView {
+    let quality = calculate_quality_analysis(&data);
+    let summary = Memo::new(move |_| {
+        let snapshot = AnalysisSnapshot {
+            historical_data: data.clone(),
+            projected_sales_cagr: projected_sales_cagr.get(),
+            projected_eps_cagr: projected_eps_cagr.get(),
+            projected_high_pe: future_high_pe.get(),
+            projected_low_pe: future_low_pe.get(),
+            naic_test_overrides: overrides.get(),
+            low_price_method: low_price_method.get(),
+            ..Default::default()
+        };
+        let naic_tests = evaluate_naic_tests(&quality, &sales_growth, &eps_growth, &snapshot);
+        calculate_analysis_summary(&snapshot, &naic_tests)
+    });
+
+    let size_text = move || {
+        let summary = summary.get();
+        match (summary.company_size, summary.latest_sales) {
+            (Some(size), Some(sales)) => format!(
+                "{} company (latest sales {sales:.0}M) — expected growth {}",
+                size.label(),
+                size.growth_range_text()
+            ),
+            _ => "Company size unknown".to_string(),
+        }
+    };
+
+    view! {
+        <div class="quality-dashboard analysis-summary">
+            <div class="header-flex">
+                <h3>"Analysis Summary"</h3>
+                <span class="hud-subtitle">{size_text}</span>
+            </div>
+            <ul class="analysis-summary-list">
+                {move || {
+                    summary
+                        .get()
+                        .conclusions
+                        .into_iter()
+                        .map(|conclusion| {
+                            let (class, label) = verdict_badge(conclusion.outcome);
+                            view! {
+                                <li class="analysis-summary-item">
+                                    <div class="analysis-summary-question">
+                                        <span>{conclusion.question.label()}</span>
+                                        <span class=class>{label}</span>
+                                    </div>
+                                    <p class="analysis-summary-explanation">
+                                        {conclusion.explanation}
+                                    </p>
+                                </li>
+                            }
+                        })
+                        .collect_view()
+                }}
+            </ul>
+        </div>
+    }
+}
@@ -1,21 +1,28 @@
 //! Main analysis workspace component.
 //!
 //! The Analyst HUD is the primary interactive view, combining the SSG chart,
-//! Fundamental Company Data table, Evaluate Management dashboard, and valuation
-//! panel into a single cohesive workspace per NAIC Figure 2.1 layout.
+//! Fundamental Company Data table, Evaluate Management dashboard, valuation
+//! panel, NAIC tests and guided summary into a single cohesive workspace per
+//! NAIC Figure 2.1 layout.
 
+use crate::components::analysis_summary_panel::AnalysisSummaryPanel;
 use crate::components::lock_thesis_modal::LockThesisModal;
+use crate::components::naic_tests_panel::NaicTestsPanel;
 use crate::components::override_modal::OverrideModal;
 use crate::components::quality_dashboard::QualityDashboard;
 use crate::components::ssg_chart::SSGChart;
 use crate::components::valuation_panel::ValuationPanel;
 use leptos::prelude::*;
 use rust_decimal::prelude::ToPrimitive;
-use steady_invest_logic::{HistoricalData, TickerInfo, calculate_growth_analysis, project_forward};
+use steady_invest_logic::{
+    HistoricalData, LowPriceMethod, NaicTestOverride, TickerInfo, calculate_growth_analysis,
+    project_forward,
+};
 
 /// Multi-panel analysis workspace for live data (NAIC Figure 2.1 layout).
 ///
-/// Layout order: SSGChart → Fundamental Company Data → Evaluate Management → ValuationPanel.
+/// Layout order: SSGChart → Fundamental Company Data → Evaluate Management → ValuationPanel
+/// → NAIC Tests → Analysis Summary.
 /// Manages shared reactive signals for Sales/EPS/PTP CAGR and P/E projections.
 #[component]
 pub fn AnalystHUD(
@@ -30,6 +37,10 @@ pub fn AnalystHUD(
     let ptp_projection_cagr = RwSignal::new(0.0);
     let future_high_pe = RwSignal::new(0.0);
     let future_low_pe = RwSignal::new(0.0);
+    // Analyst overrides of NAIC test conclusions, saved with the snapshot
+    let naic_test_overrides = RwSignal::new(Vec::<NaicTestOverride>::new());
+    // Section 4B source of the forecast low price, saved with the snapshot
+    let low_price_method = RwSignal::new(LowPriceMethod::default());
 
     // Manual Override UI state
     #[derive(Clone, Debug)]
@@ -117,6 +128,9 @@ pub fn AnalystHUD(
                                     projected_low_pe: future_low_pe.get(),
                                     analyst_note: String::new(),
                                     captured_at: chrono::Utc::now(),
+                                    current_quote: None,
+                                    naic_test_overrides: naic_test_overrides.get(),
+                                    low_price_method: low_price_method.get(),
                                 };
                                 let _ = crate::persistence::save_snapshot(&snapshot);
                             }
@@ -145,6 +159,22 @@ pub fn AnalystHUD(
                 </div>
             </div>
 
+            {if data.anomalies.is_empty() {
+                ().into_any()
+            } else {
+                let anomalies = data.anomalies.clone();
+                view! {
+                    <div class="integrity-alert anomaly-list">
+                        <strong>{format!("Data Anomalies ({})", anomalies.len())}</strong>
+                        <ul>
+                            {anomalies.into_iter().map(|a| {
+                                view! { <li>{format!("FY{}: {}", a.fiscal_year, a.message)}</li> }
+                            }).collect_view()}
+                        </ul>
+                    </div>
+                }.into_any()
+            }}
+
             // Section 1: Visual Analysis (SSG Chart)
             <SSGChart
                 data=data.clone()
@@ -270,6 +300,32 @@ pub fn AnalystHUD(
                 projected_eps_cagr=eps_projection_cagr
                 future_high_pe=future_high_pe
                 future_low_pe=future_low_pe
+                low_price_method=low_price_method
+            />
+
+            // NAIC Management and Safety-of-Price tests (FR2.8)
+            <NaicTestsPanel
+                data=data.clone()
+                sales_growth=sales_growth.clone()
+                eps_growth=eps_growth.clone()
+                projected_eps_cagr=eps_projection_cagr
+                future_high_pe=future_high_pe
+                future_low_pe=future_low_pe
+                low_price_method=low_price_method
+                overrides=naic_test_overrides
+            />
+
+            // Guided Stock Checklist conclusions (FR2.10)
+            <AnalysisSummaryPanel
+                data=data.clone()
+                sales_growth=sales_growth.clone()
+                eps_growth=eps_growth.clone()
+                projected_sales_cagr=sales_projection_cagr
+                projected_eps_cagr=eps_projection_cagr
+                future_high_pe=future_high_pe
+                future_low_pe=future_low_pe
+                low_price_method=low_price_method
+                overrides=naic_test_overrides
             />
 
             {let data = data.clone(); move || active_override.get().map(|ovr| {
@@ -301,6 +357,8 @@ pub fn AnalystHUD(
                         ptp_projection_cagr=ptp_projection_cagr.get()
                         future_high_pe=future_high_pe.get()
                         future_low_pe=future_low_pe.get()
+                        naic_test_overrides=naic_test_overrides.get()
+                        low_price_method=low_price_method.get()
                         on_close=Callback::new(move |_| set_show_lock_modal.set(false))
                         on_locked=Callback::new(move |id: i32| {
                             set_show_lock_modal.set(false);
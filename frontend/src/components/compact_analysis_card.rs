@@ -1,9 +1,11 @@
 //! Compact Analysis Card — condensed snapshot summary for Library and Comparison views.
 //!
-//! Displays ticker symbol, date, lock status, and key projected metrics
-//! in a dense card layout following the Institutional HUD design system.
+//! Displays ticker symbol, date, lock status, key projected metrics and the
+//! NAIC Section 5 Relative Value, PAR and Present Yield in a dense card layout
+//! following the Institutional HUD design system.
 
 use leptos::prelude::*;
+use steady_invest_logic::PriceZone;
 
 /// Data needed to render a Compact Analysis Card.
 #[derive(Debug, Clone)]
@@ -16,8 +18,12 @@ pub struct CompactCardData {
     pub projected_eps_cagr: Option<f64>,
     pub projected_high_pe: Option<f64>,
     pub projected_low_pe: Option<f64>,
-    pub valuation_zone: Option<String>,
+    pub valuation_zone: Option<PriceZone>,
     pub upside_downside_ratio: Option<f64>,
+    /// NAIC Section 5 metrics, in percent.
+    pub relative_value: Option<f64>,
+    pub present_yield: Option<f64>,
+    pub projected_average_return: Option<f64>,
     pub current_price: Option<f64>,
     pub target_high_price: Option<f64>,
     pub target_low_price: Option<f64>,
@@ -55,20 +61,14 @@ pub fn CompactAnalysisCard(data: CompactCardData, on_click: Callback<i32>) -> im
         _ => "\u{2014}".to_string(),
     };
 
-    // Valuation zone: colored dot + text
-    let (zone_class, zone_text) = match data.valuation_zone.as_deref() {
-        Some(z) => {
-            let lower = z.to_lowercase();
-            if lower.contains("under") || lower == "buy" {
-                ("zone-dot zone-buy", "Buy")
-            } else if lower.contains("over") || lower == "sell" {
-                ("zone-dot zone-sell", "Sell")
-            } else {
-                ("zone-dot zone-hold", "Hold")
-            }
-        }
-        None => ("zone-dot zone-none", "\u{2014}"),
+    // Valuation zone (NAIC Section 4C): colored dot + text
+    let zone_class = match data.valuation_zone {
+        Some(PriceZone::Buy) => "zone-dot zone-buy",
+        Some(PriceZone::Maybe) => "zone-dot zone-hold",
+        Some(PriceZone::Sell) => "zone-dot zone-sell",
+        None => "zone-dot zone-none",
     };
+    let zone_text = data.valuation_zone.map_or("\u{2014}", PriceZone::label);
 
     // Upside/downside ratio: color-coded per NAIC 3:1 rule
     let (ud_class, ud_text) = match data.upside_downside_ratio {
@@ -78,6 +78,15 @@ pub fn CompactAnalysisCard(data: CompactCardData, on_click: Callback<i32>) -> im
         None => ("ud-ratio ud-none", "\u{2014}".to_string()),
     };
 
+    // NAIC Section 5: Relative Value, Projected Average Return, Present Yield
+    let fmt_pct = |v: Option<f64>, decimals: usize| {
+        v.map(|v| format!("{:.*}%", decimals, v))
+            .unwrap_or_else(|| "\u{2014}".to_string())
+    };
+    let relative_value = fmt_pct(data.relative_value, 0);
+    let par = fmt_pct(data.projected_average_return, 1);
+    let present_yield = fmt_pct(data.present_yield, 1);
+
     // Monetary values: current price + target range
     let currency_prefix = data.display_currency.as_deref().unwrap_or("");
     let price_text = data
@@ -127,6 +136,18 @@ pub fn CompactAnalysisCard(data: CompactCardData, on_click: Callback<i32>) -> im
                     <span class="metric-label">"U/D"</span>
                     <span class=ud_class>{ud_text}</span>
                 </div>
+                <div class="metric-row">
+                    <span class="metric-label">"Rel. Value"</span>
+                    <span class="metric-value">{relative_value}</span>
+                </div>
+                <div class="metric-row">
+                    <span class="metric-label">"PAR"</span>
+                    <span class="metric-value">{par}</span>
+                </div>
+                <div class="metric-row">
+                    <span class="metric-label">"Yield"</span>
+                    <span class="metric-value">{present_yield}</span>
+                </div>
                 {if has_prices {
                     view! {
                         <div class="metric-row metric-price-row">
@@ -7,7 +7,7 @@
 use crate::components::ssg_chart;
 use leptos::prelude::*;
 use serde::{Deserialize, Serialize};
-use steady_invest_logic::{AnalysisSnapshot, HistoricalData};
+use steady_invest_logic::{AnalysisSnapshot, HistoricalData, LowPriceMethod, NaicTestOverride};
 
 /// JSON request body for the Phase 1 snapshot API.
 #[derive(Debug, Clone, Serialize)]
@@ -33,6 +33,8 @@ pub fn LockThesisModal(
     ptp_projection_cagr: f64,
     future_high_pe: f64,
     future_low_pe: f64,
+    naic_test_overrides: Vec<NaicTestOverride>,
+    low_price_method: LowPriceMethod,
     on_close: Callback<()>,
     on_locked: Callback<i32>,
 ) -> impl IntoView {
@@ -81,6 +83,9 @@ pub fn LockThesisModal(
                 projected_low_pe: future_low_pe,
                 analyst_note: note_val.clone(),
                 captured_at: chrono::Utc::now(),
+                current_quote: None,
+                naic_test_overrides: naic_test_overrides.clone(),
+                low_price_method,
             };
 
             let snapshot_data = serde_json::to_value(&snapshot)
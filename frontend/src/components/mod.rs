@@ -10,6 +10,8 @@
 //! - [`ssg_chart`]             — Logarithmic SSG chart with draggable trendlines
 //! - [`valuation_panel`]       — P/E slider controls and buy/sell zone display
 //! - [`quality_dashboard`]     — ROE and Profit-on-Sales table with trend indicators
+//! - [`naic_tests_panel`]      — NAIC Management and Safety-of-Price tests with overrides
+//! - [`analysis_summary_panel`] — Guided Stock Checklist conclusions by company size
 //! - [`snapshot_hud`]          — Read-only view of a locked analysis snapshot
 //! - [`history_timeline`]      — Vertical timeline sidebar for thesis evolution
 //! - [`snapshot_comparison`]   — Side-by-side comparison cards with metric deltas
@@ -21,6 +23,7 @@
 //! - [`override_modal`]     — Manual data override entry form
 //! - [`lock_thesis_modal`]  — Thesis lock confirmation dialog
 
+pub mod analysis_summary_panel;
 pub mod analyst_hud;
 pub mod command_strip;
 pub mod compact_analysis_card;
@@ -28,6 +31,7 @@ pub mod counter_btn;
 pub mod footer;
 pub mod history_timeline;
 pub mod lock_thesis_modal;
+pub mod naic_tests_panel;
 pub mod override_modal;
 pub mod quality_dashboard;
 pub mod search_bar;
@@ -0,0 +1,177 @@
+//! NAIC Management and Safety-of-Price tests component (PRD FR2.8).
+//!
+//! Lists the six NAIC tests with their computed value, benchmark and
+//! explanation. Each conclusion can be overridden; overrides are kept in a
+//! signal the HUD writes into the snapshot when the thesis is saved or locked.
+
+use leptos::prelude::*;
+use steady_invest_logic::{
+    AnalysisSnapshot, HistoricalData, LowPriceMethod, NaicTest, NaicTestGroup, NaicTestOutcome,
+    NaicTestOverride, NaicTestResult, TrendAnalysis, calculate_quality_analysis,
+    evaluate_naic_tests,
+};
+
+/// Override choices offered per test; `None` keeps the computed conclusion.
+const OVERRIDE_CHOICES: [(Option<NaicTestOutcome>, &str); 4] = [
+    (None, "Computed"),
+    (Some(NaicTestOutcome::Pass), "Pass"),
+    (Some(NaicTestOutcome::Fail), "Fail"),
+    (Some(NaicTestOutcome::Inconclusive), "Inconclusive"),
+];
+
+fn outcome_badge(outcome: NaicTestOutcome) -> (&'static str, &'static str) {
+    match outcome {
+        NaicTestOutcome::Pass => ("naic-pass", "PASS"),
+        NaicTestOutcome::Fail => ("naic-fail", "FAIL"),
+        NaicTestOutcome::Inconclusive => ("naic-inconclusive", "N/A"),
+    }
+}
+
+/// Formats a test's value or benchmark in the test's unit.
+fn format_value(test: NaicTest, value: f64) -> String {
+    match test {
+        NaicTest::SalesDrivenGrowth => format!("{value:+.1} pp"),
+        NaicTest::ProfitOnSalesTrend => format!("{value:+.2} pp/yr"),
+        NaicTest::UpsideDownside => format!("{value:.1}:1"),
+        NaicTest::ReturnOnEquity | NaicTest::GettingOutEven | NaicTest::Appreciation => {
+            format!("{value:.0}%")
+        }
+    }
+}
+
+/// NAIC test results for the live analysis, with per-test override selects.
+///
+/// Safety-of-Price results follow the EPS projection and P/E sliders.
+#[component]
+pub fn NaicTestsPanel(
+    data: HistoricalData,
+    sales_growth: TrendAnalysis,
+    eps_growth: TrendAnalysis,
+    projected_eps_cagr: RwSignal<f64>,
+    future_high_pe: RwSignal<f64>,
+    future_low_pe: RwSignal<f64>,
+    low_price_method: RwSignal<LowPriceMethod>,
+    overrides: RwSignal<Vec<NaicTestOverride>>,
+) -> impl IntoView {
+    let quality = calculate_quality_analysis(&data);
+    let results = Memo::new(move |_| {
+        let snapshot = AnalysisSnapshot {
+            historical_data: data.clone(),
+            projected_eps_cagr: projected_eps_cagr.get(),
+            projected_high_pe: future_high_pe.get(),
+            projected_low_pe: future_low_pe.get(),
+            naic_test_overrides: overrides.get(),
+            low_price_method: low_price_method.get(),
+            ..Default::default()
+        };
+        evaluate_naic_tests(&quality, &sales_growth, &eps_growth, &snapshot)
+    });
+    let result_for = move |test: NaicTest| -> Option<NaicTestResult> {
+        results.get().into_iter().find(|r| r.test == test)
+    };
+
+    let set_override = move |test: NaicTest, outcome: Option<NaicTestOutcome>| {
+        overrides.update(|list| {
+            list.retain(|o| o.test != test);
+            if let Some(outcome) = outcome {
+                list.push(NaicTestOverride {
+                    test,
+                    outcome,
+                    note: None,
+                });
+            }
+        });
+    };
+
+    let group_rows = move |group: NaicTestGroup| {
+        NaicTest::ALL
+            .into_iter()
+            .filter(|t| t.group() == group)
+            .map(|test| {
+                view! {
+                    <tr>
+                        <td class="metric-label">{test.label()}</td>
+                        <td class="value-cell">{move || {
+                            result_for(test)
+                                .and_then(|r| r.value)
+                                .map(|v| format_value(test, v))
+                                .unwrap_or_else(|| "—".to_string())
+                        }}</td>
+                        <td class="value-cell">{move || {
+                            result_for(test)
+                                .map(|r| format_value(test, r.benchmark))
+                                .unwrap_or_default()
+                        }}</td>
+                        <td class=move || {
+                            let overridden = result_for(test).is_some_and(|r| r.analyst_override.is_some());
+                            if overridden { "overridden-cell trend-cell" } else { "trend-cell" }
+                        }>{move || {
+                            result_for(test).map(|r| {
+                                let (class, label) = outcome_badge(r.conclusion());
+                                view! { <span class=class>{label}</span> }
+                            })
+                        }}</td>
+                        <td class="naic-explanation">{move || {
+                            result_for(test).map(|r| r.explanation).unwrap_or_default()
+                        }}</td>
+                        <td>
+                            <select
+                                class="naic-override-select"
+                                aria-label=format!("Override {}", test.label())
+                                on:change=move |ev| {
+                                    if let Some((outcome, _)) = event_target_value(&ev)
+                                        .parse::<usize>()
+                                        .ok()
+                                        .and_then(|i| OVERRIDE_CHOICES.get(i))
+                                    {
+                                        set_override(test, *outcome);
+                                    }
+                                }
+                            >
+                                {OVERRIDE_CHOICES.into_iter().enumerate().map(|(i, (outcome, label))| view! {
+                                    <option
+                                        value=i.to_string()
+                                        selected=move || {
+                                            overrides.get().iter().find(|o| o.test == test).map(|o| o.outcome) == outcome
+                                        }
+                                    >
+                                        {label}
+                                    </option>
+                                }).collect_view()}
+                            </select>
+                        </td>
+                    </tr>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <div class="quality-dashboard naic-tests">
+            <div class="header-flex">
+                <h3>"NAIC Tests"</h3>
+                <span class="hud-subtitle">"Management & Safety of Price"</span>
+            </div>
+            <div class="table-scroll-wrapper">
+                <table class="quality-grid">
+                    <thead>
+                        <tr>
+                            <th class="metric-col">"Test"</th>
+                            <th>"Value"</th>
+                            <th>"Benchmark"</th>
+                            <th class="summary-col">"Result"</th>
+                            <th>"Why"</th>
+                            <th>"Override"</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        <tr class="naic-group-row"><td colspan="6">"Management"</td></tr>
+                        {group_rows(NaicTestGroup::Management)}
+                        <tr class="naic-group-row"><td colspan="6">"Safety of Price"</td></tr>
+                        {group_rows(NaicTestGroup::SafetyOfPrice)}
+                    </tbody>
+                </table>
+            </div>
+        </div>
+    }
+}
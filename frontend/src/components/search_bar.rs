@@ -1,12 +1,15 @@
 //! Ticker search bar with autocomplete dropdown.
 //!
 //! Provides a text input that queries `/api/tickers/search` as the user types
-//! (minimum 2 characters). Also includes an "Open from file" button for
+//! (minimum 2 characters), showing the first page of ranked matches. Also includes an "Open from file" button for
 //! importing previously saved analysis snapshots.
 
 use gloo_net::http::Request;
 use leptos::prelude::*;
-use steady_invest_logic::TickerInfo;
+use steady_invest_logic::{TickerInfo, TickerSearchPage};
+
+/// Matches shown in the autocomplete dropdown.
+const RESULT_LIMIT: usize = 8;
 
 /// Ticker search input with autocomplete results.
 ///
@@ -29,14 +32,18 @@ where
             if q.len() < 2 {
                 return Vec::new();
             }
-            let url = format!("/api/tickers/search?q={}", q);
+            let url = format!(
+                "/api/tickers/search?q={}&limit={RESULT_LIMIT}",
+                String::from(js_sys::encode_uri_component(&q))
+            );
             Request::get(&url)
                 .send()
                 .await
                 .unwrap()
-                .json::<Vec<TickerInfo>>()
+                .json::<TickerSearchPage>()
                 .await
                 .unwrap_or_default()
+                .results
         }
     });
 
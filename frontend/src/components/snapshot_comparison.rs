@@ -6,18 +6,30 @@
 
 use crate::components::history_timeline::{MetricDelta, TimelineEntry};
 use leptos::prelude::*;
+use steady_invest_logic::{PriceZone, calculate_price_zones};
 
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
 
-/// Derive valuation zone from upside/downside ratio.
-/// Same logic as `compact_analysis_card.rs`.
-fn derive_valuation_zone(ratio: Option<f64>) -> (&'static str, &'static str) {
-    match ratio {
-        Some(r) if r >= 3.0 => ("zone-dot zone-buy", "Buy"),
-        Some(r) if r >= 1.0 => ("zone-dot zone-hold", "Hold"),
-        Some(_) => ("zone-dot zone-sell", "Sell"),
+/// Derive the NAIC Section 4C zone of the entry's current price within its
+/// forecast price range.
+fn derive_valuation_zone(entry: &TimelineEntry) -> (&'static str, &'static str) {
+    let zone = match (
+        entry.target_high_price,
+        entry.target_low_price,
+        entry.current_price,
+    ) {
+        (Some(high), Some(low), Some(price)) if price > 0.0 => {
+            calculate_price_zones(high, low, entry.native_currency.as_deref().unwrap_or(""))
+                .map(|zones| zones.zone_for(price))
+        }
+        _ => None,
+    };
+    match zone {
+        Some(PriceZone::Buy) => ("zone-dot zone-buy", "Buy"),
+        Some(PriceZone::Maybe) => ("zone-dot zone-hold", "Maybe"),
+        Some(PriceZone::Sell) => ("zone-dot zone-sell", "Sell"),
         None => ("zone-dot zone-none", "\u{2014}"),
     }
 }
@@ -85,8 +97,8 @@ pub fn SnapshotComparison(
     let current_date = current.captured_at.chars().take(10).collect::<String>();
     let past_date = past.captured_at.chars().take(10).collect::<String>();
 
-    let (cur_zone_class, cur_zone_text) = derive_valuation_zone(current.upside_downside_ratio);
-    let (past_zone_class, past_zone_text) = derive_valuation_zone(past.upside_downside_ratio);
+    let (cur_zone_class, cur_zone_text) = derive_valuation_zone(&current);
+    let (past_zone_class, past_zone_text) = derive_valuation_zone(&past);
 
     // Build metric rows with deltas
     let sales_delta = delta.as_ref().and_then(|d| d.sales_cagr_delta);
@@ -27,6 +27,7 @@ pub fn SnapshotHUD(ticker: TickerInfo, model: LockedAnalysisModel) -> impl IntoV
     let ptp_projection_cagr = RwSignal::new(snapshot.projected_ptp_cagr);
     let future_high_pe = RwSignal::new(snapshot.projected_high_pe);
     let future_low_pe = RwSignal::new(snapshot.projected_low_pe);
+    let low_price_method = RwSignal::new(snapshot.low_price_method);
 
     // Precompute historical growth CAGRs for Fundamental Company Data table
     let raw_years: Vec<i32> = data.records.iter().map(|r| r.fiscal_year).collect();
@@ -209,6 +210,7 @@ pub fn SnapshotHUD(ticker: TickerInfo, model: LockedAnalysisModel) -> impl IntoV
                 projected_eps_cagr=eps_projection_cagr
                 future_high_pe=future_high_pe
                 future_low_pe=future_low_pe
+                low_price_method=low_price_method
             />
         </div>
     }
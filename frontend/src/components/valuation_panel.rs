@@ -2,15 +2,39 @@
 //!
 //! Displays historical P/E context and lets the analyst adjust future High/Low
 //! P/E estimates via range sliders. Computes projected buy-zone (floor) and
-//! sell-zone (ceiling) target prices from EPS CAGR projections.
+//! sell-zone (ceiling) target prices from EPS CAGR projections, lets the
+//! analyst pick the Section 4B source of the forecast low price, and splits the
+//! forecast range into the NAIC Section 4C Buy/Maybe/Sell thirds.
 
 use leptos::prelude::*;
-use rust_decimal::prelude::ToPrimitive;
-use steady_invest_logic::{HistoricalData, project_forward};
+use steady_invest_logic::{
+    HistoricalData, LowPriceMethod, PriceZone, calculate_current_figures,
+    calculate_low_price_estimates, calculate_pe_breakdown, calculate_price_zones, project_forward,
+};
 
 /// Maximum value for the High P/E range slider.
 const PE_SLIDER_MAX: f64 = 100.0;
 
+/// One row of the historical P/E tiers.
+fn pe_tier_row(label: &'static str, value: Option<f64>) -> impl IntoView {
+    view! {
+        <div style="
+            display: flex;
+            justify-content: space-between;
+            color: var(--text-secondary);
+            font-family: 'Inter', sans-serif;
+            font-size: var(--text-sm);
+        ">
+            <span>{label}</span>
+            <span style="
+                color: var(--text-primary);
+                font-weight: 500;
+                font-family: 'JetBrains Mono', monospace;
+            ">{value.map(|v| format!("{v:.1}")).unwrap_or_else(|| "\u{2014}".to_string())}</span>
+        </div>
+    }
+}
+
 /// Interactive valuation analysis panel.
 ///
 /// Shows 10-year historical P/E averages and current EPS, then lets the analyst
@@ -22,20 +46,37 @@ const PE_SLIDER_MAX: f64 = 100.0;
 /// * `data` — Historical financial data (for P/E context and current EPS).
 /// * `projected_eps_cagr` — Reactive EPS CAGR from the SSG chart sliders.
 /// * `future_high_pe` / `future_low_pe` — Two-way bound P/E projection signals.
+/// * `low_price_method` — Two-way bound Section 4B source of the forecast low price.
 #[component]
 pub fn ValuationPanel(
     data: HistoricalData,
     #[prop(into)] projected_eps_cagr: Signal<f64>,
     future_high_pe: RwSignal<f64>,
     future_low_pe: RwSignal<f64>,
+    low_price_method: RwSignal<LowPriceMethod>,
 ) -> impl IntoView {
     let pe_analysis = data.pe_range_analysis.clone().unwrap_or_default();
+    let pe_tiers = calculate_pe_breakdown(&pe_analysis);
+    let low_price_estimates = calculate_low_price_estimates(&data);
+    let currency = data
+        .display_currency
+        .clone()
+        .unwrap_or_else(|| data.currency.clone());
 
-    // Calculate current TTM EPS (latest record)
-    let latest_record = data.records.iter().max_by_key(|r| r.fiscal_year);
-    let current_eps = latest_record
-        .map(|r| r.eps.to_f64().unwrap_or(0.0))
-        .unwrap_or(0.0);
+    // Current EPS: TTM when quarters newer than the latest annual report exist
+    let current = calculate_current_figures(&data);
+    let current_eps = current.eps.unwrap_or(0.0);
+    let current_price = current.price.filter(|p| *p > 0.0);
+    let current_eps_label = match &current.ttm {
+        Some(ttm) => format!(
+            "Current EPS (TTM to Q{} FY{})",
+            ttm.fiscal_quarter, ttm.fiscal_year
+        ),
+        None => match data.records.iter().map(|r| r.fiscal_year).max() {
+            Some(year) => format!("Current EPS (FY{year})"),
+            None => "Current EPS".to_string(),
+        },
+    };
 
     // Calculate Projected EPS
     let projected_eps = move || {
@@ -45,7 +86,15 @@ pub fn ValuationPanel(
 
     // Calculate Target Zones
     let target_high_price = move || future_high_pe.get() * projected_eps();
-    let target_low_price = move || future_low_pe.get() * projected_eps();
+    let target_low_price = move || {
+        let projected_low = future_low_pe.get() * projected_eps();
+        low_price_estimates
+            .select(low_price_method.get(), Some(projected_low))
+            .unwrap_or(projected_low)
+    };
+    let price_zones = Memo::new(move |_| {
+        calculate_price_zones(target_high_price(), target_low_price(), &currency)
+    });
 
     view! {
         <div class="valuation-panel" style="
@@ -91,34 +140,11 @@ pub fn ValuationPanel(
                         flex-direction: column;
                         gap: var(--spacing-3);
                     ">
-                        <div style="
-                            display: flex;
-                            justify-content: space-between;
-                            color: var(--text-secondary);
-                            font-family: 'Inter', sans-serif;
-                            font-size: var(--text-sm);
-                        ">
-                            <span>"Avg. High P/E"</span>
-                            <span style="
-                                color: var(--text-primary);
-                                font-weight: 500;
-                                font-family: 'JetBrains Mono', monospace;
-                            ">{format!("{:.1}", pe_analysis.avg_high_pe)}</span>
-                        </div>
-                        <div style="
-                            display: flex;
-                            justify-content: space-between;
-                            color: var(--text-secondary);
-                            font-family: 'Inter', sans-serif;
-                            font-size: var(--text-sm);
-                        ">
-                            <span>"Avg. Low P/E"</span>
-                            <span style="
-                                color: var(--text-primary);
-                                font-weight: 500;
-                                font-family: 'JetBrains Mono', monospace;
-                            ">{format!("{:.1}", pe_analysis.avg_low_pe)}</span>
-                        </div>
+                        {pe_tier_row("Highest P/E", pe_tiers.map(|t| t.highest))}
+                        {pe_tier_row("Avg. High P/E", pe_tiers.map(|t| t.average_high))}
+                        {pe_tier_row("Average P/E", pe_tiers.map(|t| t.average))}
+                        {pe_tier_row("Avg. Low P/E", pe_tiers.map(|t| t.average_low))}
+                        {pe_tier_row("Lowest P/E", pe_tiers.map(|t| t.lowest))}
                         <div style="
                             display: flex;
                             justify-content: space-between;
@@ -128,7 +154,7 @@ pub fn ValuationPanel(
                             font-family: 'Inter', sans-serif;
                             font-size: var(--text-sm);
                         ">
-                            <span>"Current EPS (TTM)"</span>
+                            <span>{current_eps_label}</span>
                             <span style="
                                 color: var(--text-primary);
                                 font-weight: 500;
@@ -231,6 +257,42 @@ pub fn ValuationPanel(
                             "
                         />
                     </div>
+
+                    // Section 4B: alternative sources of the forecast low price
+                    <div class="control-group low-price-source">
+                        <label for="low-price-method">"Forecast Low Price Source"</label>
+                        <select
+                            id="low-price-method"
+                            on:change=move |ev| {
+                                if let Some(method) = event_target_value(&ev)
+                                    .parse::<usize>()
+                                    .ok()
+                                    .and_then(|i| LowPriceMethod::ALL.get(i))
+                                {
+                                    low_price_method.set(*method);
+                                }
+                            }
+                        >
+                            {LowPriceMethod::ALL.into_iter().enumerate().map(|(i, method)| {
+                                let candidate = low_price_estimates.get(method);
+                                let available = method == LowPriceMethod::ProjectedLowPe || candidate.is_some();
+                                let text = match candidate {
+                                    Some(price) => format!("{} ({price:.2})", method.label()),
+                                    None if available => method.label().to_string(),
+                                    None => format!("{} (n/a)", method.label()),
+                                };
+                                view! {
+                                    <option
+                                        value=i.to_string()
+                                        disabled=!available
+                                        selected=move || low_price_method.get() == method
+                                    >
+                                        {text}
+                                    </option>
+                                }
+                            }).collect_view()}
+                        </select>
+                    </div>
                 </div>
             </div>
 
@@ -288,6 +350,44 @@ pub fn ValuationPanel(
                 </div>
             </div>
 
+            // Section 4C: Buy/Maybe/Sell thirds of the forecast range
+            {move || {
+                price_zones.get().map(|zones| {
+                    let current_zone = current_price.map(|p| zones.zone_for(p));
+                    let zone_class = move |zone: PriceZone| {
+                        let base = match zone {
+                            PriceZone::Buy => "price-zone price-zone-buy",
+                            PriceZone::Maybe => "price-zone price-zone-maybe",
+                            PriceZone::Sell => "price-zone price-zone-sell",
+                        };
+                        if current_zone == Some(zone) { format!("{base} current") } else { base.to_string() }
+                    };
+                    let bounds = [
+                        (PriceZone::Buy, zones.forecast_low, zones.buy_below),
+                        (PriceZone::Maybe, zones.buy_below, zones.sell_above),
+                        (PriceZone::Sell, zones.sell_above, zones.forecast_high),
+                    ];
+                    let currency = zones.currency.clone();
+                    view! {
+                        <div class="price-zones">
+                            {bounds.into_iter().map(|(zone, from, to)| view! {
+                                <div class=zone_class(zone)>
+                                    <span class="price-zone-label">{zone.label()}</span>
+                                    <span class="price-zone-range">
+                                        {format!("{currency} {from:.2} \u{2013} {to:.2}")}
+                                    </span>
+                                </div>
+                            }).collect_view()}
+                        </div>
+                        {current_price.zip(current_zone).map(|(price, zone)| view! {
+                            <p class="price-zone-current">
+                                {format!("Current price {} {price:.2} is in the {} zone", zones.currency, zone.label())}
+                            </p>
+                        })}
+                    }
+                })
+            }}
+
             <p style="
                 color: var(--text-muted);
                 font-size: var(--text-xs);
@@ -20,8 +20,9 @@ use leptos_router::hooks::use_location;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use steady_invest_logic::{
-    AnalysisSnapshot, compute_upside_downside_from_snapshot, convert_monetary_value,
-    extract_snapshot_prices,
+    AnalysisSnapshot, PriceZone, SUPPORTED_CURRENCIES, calculate_valuation_zones,
+    compute_return_metrics_from_snapshot, compute_upside_downside_from_snapshot,
+    convert_monetary_value, extract_snapshot_prices,
 };
 
 // ---------------------------------------------------------------------------
@@ -57,12 +58,15 @@ struct ComparisonSnapshotSummary {
     projected_eps_cagr: Option<f64>,
     projected_high_pe: Option<f64>,
     projected_low_pe: Option<f64>,
-    valuation_zone: Option<String>,
+    valuation_zone: Option<PriceZone>,
     upside_downside_ratio: Option<f64>,
     native_currency: Option<String>,
     current_price: Option<f64>,
     target_high_price: Option<f64>,
     target_low_price: Option<f64>,
+    relative_value: Option<f64>,
+    present_yield: Option<f64>,
+    projected_average_return: Option<f64>,
 }
 
 /// Ad-hoc compare response from `GET /api/v1/compare`.
@@ -157,12 +161,15 @@ struct ComparisonEntry {
     projected_eps_cagr: Option<f64>,
     projected_high_pe: Option<f64>,
     projected_low_pe: Option<f64>,
-    valuation_zone: Option<String>,
+    valuation_zone: Option<PriceZone>,
     upside_downside_ratio: Option<f64>,
     native_currency: Option<String>,
     current_price: Option<f64>,
     target_high_price: Option<f64>,
     target_low_price: Option<f64>,
+    relative_value: Option<f64>,
+    present_yield: Option<f64>,
+    projected_average_return: Option<f64>,
 }
 
 impl From<ComparisonSnapshotSummary> for ComparisonEntry {
@@ -182,25 +189,25 @@ impl From<ComparisonSnapshotSummary> for ComparisonEntry {
             current_price: s.current_price,
             target_high_price: s.target_high_price,
             target_low_price: s.target_low_price,
+            relative_value: s.relative_value,
+            present_yield: s.present_yield,
+            projected_average_return: s.projected_average_return,
         }
     }
 }
 
 /// Build a ComparisonEntry from a full snapshot response by extracting fields
-/// from snapshot_data and computing upside/downside ratio client-side.
+/// from snapshot_data and computing upside/downside ratio, zone and Section 5
+/// metrics client-side.
 fn entry_from_full_snapshot(resp: SnapshotFullResponse) -> ComparisonEntry {
     let snapshot: Option<AnalysisSnapshot> =
         serde_json::from_value(resp.snapshot_data.clone()).ok();
 
     if let Some(ref snap) = snapshot {
-        let zone = resp
-            .snapshot_data
-            .get("valuation_zone")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_owned());
-
+        let zone = calculate_valuation_zones(snap).current_zone;
         let ud = compute_upside_downside_from_snapshot(snap);
         let prices = extract_snapshot_prices(snap);
+        let returns = compute_return_metrics_from_snapshot(snap);
 
         ComparisonEntry {
             id: resp.id,
@@ -213,10 +220,19 @@ fn entry_from_full_snapshot(resp: SnapshotFullResponse) -> ComparisonEntry {
             projected_low_pe: Some(snap.projected_low_pe),
             valuation_zone: zone,
             upside_downside_ratio: ud,
-            native_currency: Some(snap.historical_data.currency.clone()),
+            // Prices are in the display currency of normalized data
+            native_currency: Some(
+                snap.historical_data
+                    .display_currency
+                    .clone()
+                    .unwrap_or_else(|| snap.historical_data.currency.clone()),
+            ),
             current_price: prices.current_price,
             target_high_price: prices.target_high_price,
             target_low_price: prices.target_low_price,
+            relative_value: returns.relative_value,
+            present_yield: returns.present_yield,
+            projected_average_return: returns.projected_average_return,
         }
     } else {
         ComparisonEntry {
@@ -246,6 +262,9 @@ fn entry_from_full_snapshot(resp: SnapshotFullResponse) -> ComparisonEntry {
             current_price: None,
             target_high_price: None,
             target_low_price: None,
+            relative_value: None,
+            present_yield: None,
+            projected_average_return: None,
         }
     }
 }
@@ -298,6 +317,9 @@ enum SortColumn {
     LowPe,
     ValuationZone,
     UpsideDownside,
+    RelativeValue,
+    PresentYield,
+    ProjectedAverageReturn,
 }
 
 fn sort_entries(entries: &mut [ComparisonEntry], col: &SortColumn, ascending: bool) {
@@ -309,14 +331,15 @@ fn sort_entries(entries: &mut [ComparisonEntry], col: &SortColumn, ascending: bo
             SortColumn::EpsCagr => cmp_opt_f64(a.projected_eps_cagr, b.projected_eps_cagr),
             SortColumn::HighPe => cmp_opt_f64(a.projected_high_pe, b.projected_high_pe),
             SortColumn::LowPe => cmp_opt_f64(a.projected_low_pe, b.projected_low_pe),
-            SortColumn::ValuationZone => a
-                .valuation_zone
-                .as_deref()
-                .unwrap_or("")
-                .cmp(b.valuation_zone.as_deref().unwrap_or("")),
+            SortColumn::ValuationZone => a.valuation_zone.cmp(&b.valuation_zone),
             SortColumn::UpsideDownside => {
                 cmp_opt_f64(a.upside_downside_ratio, b.upside_downside_ratio)
             }
+            SortColumn::RelativeValue => cmp_opt_f64(a.relative_value, b.relative_value),
+            SortColumn::PresentYield => cmp_opt_f64(a.present_yield, b.present_yield),
+            SortColumn::ProjectedAverageReturn => {
+                cmp_opt_f64(a.projected_average_return, b.projected_average_return)
+            }
         };
         if ascending { cmp } else { cmp.reverse() }
     });
@@ -603,9 +626,9 @@ pub fn Comparison() -> impl IntoView {
                         active_currency.set(event_target_value(&ev));
                     }
                 >
-                    <option value="CHF" selected=move || active_currency.get() == "CHF">"CHF"</option>
-                    <option value="EUR" selected=move || active_currency.get() == "EUR">"EUR"</option>
-                    <option value="USD" selected=move || active_currency.get() == "USD">"USD"</option>
+                    {SUPPORTED_CURRENCIES.iter().map(|code| view! {
+                        <option value=*code selected=move || active_currency.get() == *code>{*code}</option>
+                    }).collect_view()}
                 </select>
             </div>
 
@@ -752,6 +775,15 @@ pub fn Comparison() -> impl IntoView {
                                             <button class="sort-header" on:click=move |_| ts(SortColumn::UpsideDownside)>
                                                 "U/D Ratio " {sort_indicator(&SortColumn::UpsideDownside)}
                                             </button>
+                                            <button class="sort-header" on:click=move |_| ts(SortColumn::RelativeValue)>
+                                                "Rel. Value " {sort_indicator(&SortColumn::RelativeValue)}
+                                            </button>
+                                            <button class="sort-header" on:click=move |_| ts(SortColumn::ProjectedAverageReturn)>
+                                                "PAR " {sort_indicator(&SortColumn::ProjectedAverageReturn)}
+                                            </button>
+                                            <button class="sort-header" on:click=move |_| ts(SortColumn::PresentYield)>
+                                                "Yield " {sort_indicator(&SortColumn::PresentYield)}
+                                            </button>
                                         </div>
                                     }
                                 };
@@ -768,6 +800,9 @@ pub fn Comparison() -> impl IntoView {
                                             "high_pe" => SortColumn::HighPe,
                                             "low_pe" => SortColumn::LowPe,
                                             "zone" => SortColumn::ValuationZone,
+                                            "relative_value" => SortColumn::RelativeValue,
+                                            "present_yield" => SortColumn::PresentYield,
+                                            "par" => SortColumn::ProjectedAverageReturn,
                                             _ => SortColumn::UpsideDownside,
                                         };
                                         set_sort_col.set(col);
@@ -786,6 +821,9 @@ pub fn Comparison() -> impl IntoView {
                                                 <option value="high_pe">"High P/E"</option>
                                                 <option value="low_pe">"Low P/E"</option>
                                                 <option value="zone">"Zone"</option>
+                                                <option value="relative_value">"Rel. Value"</option>
+                                                <option value="par">"PAR"</option>
+                                                <option value="present_yield">"Yield"</option>
                                             </select>
                                             <button class="sort-dir-btn" on:click=on_dir_toggle>
                                                 {move || if sort_asc.get() { "\u{25B2}" } else { "\u{25BC}" }}
@@ -841,8 +879,11 @@ pub fn Comparison() -> impl IntoView {
                                         projected_eps_cagr: entry.projected_eps_cagr,
                                         projected_high_pe: entry.projected_high_pe,
                                         projected_low_pe: entry.projected_low_pe,
-                                        valuation_zone: entry.valuation_zone.clone(),
+                                        valuation_zone: entry.valuation_zone,
                                         upside_downside_ratio: entry.upside_downside_ratio,
+                                        relative_value: entry.relative_value,
+                                        present_yield: entry.present_yield,
+                                        projected_average_return: entry.projected_average_return,
                                         current_price: convert_price(entry.current_price, native, &currency, rates_slice),
                                         target_high_price: convert_price(entry.target_high_price, native, &currency, rates_slice),
                                         target_low_price: convert_price(entry.target_low_price, native, &currency, rates_slice),
@@ -19,7 +19,9 @@ use crate::types::LockedAnalysisModel;
 use leptos::prelude::*;
 use leptos_router::hooks::use_location;
 use serde::Deserialize;
-use steady_invest_logic::{AnalysisSnapshot, HistoricalData, TickerInfo};
+use steady_invest_logic::{
+    AnalysisSnapshot, FxConvention, HistoricalData, SUPPORTED_CURRENCIES, TickerInfo,
+};
 
 /// DTO for the raw `GET /api/v1/snapshots/:id` response used by deep linking.
 #[derive(Debug, Clone, Deserialize)]
@@ -33,6 +35,103 @@ struct FullSnapshotResponse {
     captured_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// A background harvest job from `/api/v1/harvest/jobs`.
+#[derive(Debug, Clone, Deserialize)]
+struct HarvestJob {
+    id: i32,
+    /// `queued`, `running`, `done` or `failed`.
+    status: String,
+    error: Option<String>,
+    result: Option<HarvestJobResult>,
+}
+
+/// The part of a finished job's outcome the page uses.
+#[derive(Debug, Clone, Deserialize)]
+struct HarvestJobResult {
+    data: HistoricalData,
+}
+
+/// Delay between two polls of a running harvest job.
+const HARVEST_POLL_MS: i32 = 500;
+/// Polls before giving up on a harvest job (one minute).
+const HARVEST_MAX_POLLS: usize = 120;
+
+/// Resolves after `ms` milliseconds.
+async fn sleep_ms(ms: i32) {
+    let promise = js_sys::Promise::new(&mut |resolve, _| {
+        if let Some(window) = web_sys::window() {
+            let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms);
+        }
+    });
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+/// Queues a background harvest for `ticker` and polls it until it finishes.
+///
+/// The job's exchange rates convert to `display_currency`.
+async fn harvest_in_background(
+    ticker: &str,
+    display_currency: &str,
+) -> Result<HistoricalData, String> {
+    let response = gloo_net::http::Request::post("/api/v1/harvest/jobs")
+        .json(&serde_json::json!({ "tickers": [ticker], "display_currency": display_currency }))
+        .map_err(|e| e.to_string())?
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.ok() {
+        return Err(format!("Harvest failed: {}", response.status()));
+    }
+    let mut job = response
+        .json::<Vec<HarvestJob>>()
+        .await
+        .map_err(|e| e.to_string())?
+        .pop()
+        .ok_or_else(|| "Harvest failed: no job was queued".to_string())?;
+
+    for _ in 0..HARVEST_MAX_POLLS {
+        match job.status.as_str() {
+            "done" => {
+                return job
+                    .result
+                    .map(|r| r.data)
+                    .ok_or_else(|| "Harvest failed: job has no result".to_string());
+            }
+            "failed" => {
+                return Err(format!(
+                    "Harvest failed: {}",
+                    job.error.unwrap_or_else(|| "unknown error".to_string())
+                ));
+            }
+            _ => {}
+        }
+
+        sleep_ms(HARVEST_POLL_MS).await;
+        let url = format!("/api/v1/harvest/jobs/{}", job.id);
+        let response = gloo_net::http::Request::get(&url)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?;
+        if !response.ok() {
+            return Err(format!("Harvest failed: {}", response.status()));
+        }
+        job = response
+            .json::<HarvestJob>()
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    Err("Harvest timed out".to_string())
+}
+
+/// Conversion conventions offered next to the display currency, with labels.
+const FX_CONVENTIONS: [(FxConvention, &str); 4] = [
+    (FxConvention::AverageAndYearEnd, "Avg + Year-End"),
+    (FxConvention::Average, "Average"),
+    (FxConvention::YearEnd, "Year-End"),
+    (FxConvention::Spot, "Spot"),
+];
+
 /// Main analysis page rendered at `/`.
 ///
 /// Manages ticker selection state, fetches historical data and locked snapshots,
@@ -42,6 +141,7 @@ struct FullSnapshotResponse {
 pub fn Home() -> impl IntoView {
     let (selected_ticker, set_selected_ticker) = signal(Option::<TickerInfo>::None);
     let (target_currency, set_target_currency) = signal("USD".to_string());
+    let (fx_convention, set_fx_convention) = signal(FxConvention::default());
     let (selected_snapshot_id, set_selected_snapshot_id) = signal(Option::<i32>::None);
     let (imported_snapshot, set_imported_snapshot) = signal(Option::<AnalysisSnapshot>::None);
     let navigate_home = leptos_router::hooks::use_navigate();
@@ -119,44 +219,40 @@ pub fn Home() -> impl IntoView {
         }
     });
 
+    // Harvested data with the currency it was harvested for; normalization
+    // happens in `normalized_historicals` so switching the FX convention does
+    // not harvest again
     let historicals = LocalResource::new(move || {
         let ticker_info = selected_ticker.get();
         let target_cur = target_currency.get();
         async move {
             if let Some(info) = ticker_info {
-                let url = format!("/api/harvest/{}", info.ticker);
-                let response = gloo_net::http::Request::post(&url)
-                    .send()
-                    .await
-                    .map_err(|e| e.to_string())?;
-
-                if response.ok() {
-                    let mut data = response
-                        .json::<HistoricalData>()
-                        .await
-                        .map_err(|e| e.to_string())?;
-
-                    if !data.is_complete {
-                        return Err(
-                            "Integrity Alert: Data population incomplete for this ticker."
-                                .to_string(),
-                        );
-                    }
-
-                    if data.currency != target_cur {
-                        data.apply_normalization(&target_cur);
-                    }
+                let data = harvest_in_background(&info.ticker, &target_cur).await?;
 
-                    Ok(data)
-                } else {
-                    Err(format!("Harvest failed: {}", response.status()))
+                if !data.is_complete {
+                    return Err(
+                        "Integrity Alert: Data population incomplete for this ticker.".to_string(),
+                    );
                 }
+
+                Ok((data, target_cur))
             } else {
-                Ok::<HistoricalData, String>(HistoricalData::default())
+                Ok::<_, String>((HistoricalData::default(), target_cur))
             }
         }
     });
 
+    let normalized_historicals = move || {
+        historicals.get().map(|res| {
+            res.map(|(mut data, target_cur)| {
+                if data.currency != target_cur {
+                    data.apply_normalization_with(&target_cur, fx_convention.get());
+                }
+                data
+            })
+        })
+    };
+
     let snapshots = LocalResource::new(move || {
         let ticker_info = selected_ticker.get();
         async move {
@@ -418,9 +514,25 @@ pub fn Home() -> impl IntoView {
                                         <select on:change=move |ev| {
                                             set_target_currency.set(event_target_value(&ev));
                                         }>
-                                            <option value="USD" selected={move || target_currency.get() == "USD"}>"USD"</option>
-                                            <option value="CHF" selected={move || target_currency.get() == "CHF"}>"CHF"</option>
-                                            <option value="EUR" selected={move || target_currency.get() == "EUR"}>"EUR"</option>
+                                            {SUPPORTED_CURRENCIES.iter().map(|code| view! {
+                                                <option value=*code selected={move || target_currency.get() == *code}>{*code}</option>
+                                            }).collect_view()}
+                                        </select>
+                                        <select
+                                            title="Exchange rates used for conversion"
+                                            on:change=move |ev| {
+                                                if let Some((convention, _)) = event_target_value(&ev)
+                                                    .parse::<usize>()
+                                                    .ok()
+                                                    .and_then(|i| FX_CONVENTIONS.get(i))
+                                                {
+                                                    set_fx_convention.set(*convention);
+                                                }
+                                            }
+                                        >
+                                            {FX_CONVENTIONS.into_iter().enumerate().map(|(i, (convention, label))| view! {
+                                                <option value=i.to_string() selected={move || fx_convention.get() == convention}>{label}</option>
+                                            }).collect_view()}
                                         </select>
                                     </div>
                                     // History toggle button (replaces view-selector dropdown)
@@ -500,7 +612,7 @@ pub fn Home() -> impl IntoView {
                                         view! { <div class="error-msg">"Snapshot not found"</div> }.into_any()
                                     } else {
                                         // Render Live HUD
-                                        match historicals.get() {
+                                        match normalized_historicals() {
                                             Some(Ok(res)) if !res.records.is_empty() => {
                                                 view! {
                                                     <AnalystHUD
@@ -27,6 +27,9 @@ struct SnapshotSummary {
     projected_eps_cagr: Option<f64>,
     projected_high_pe: Option<f64>,
     projected_low_pe: Option<f64>,
+    relative_value: Option<f64>,
+    present_yield: Option<f64>,
+    projected_average_return: Option<f64>,
 }
 
 /// Library page at `/library`.
@@ -138,6 +141,9 @@ pub fn Library() -> impl IntoView {
                                             projected_low_pe: s.projected_low_pe,
                                             valuation_zone: None,
                                             upside_downside_ratio: None,
+                                            relative_value: s.relative_value,
+                                            present_yield: s.present_yield,
+                                            projected_average_return: s.projected_average_return,
                                             current_price: None,
                                             target_high_price: None,
                                             target_low_price: None,
@@ -1,7 +1,8 @@
 //! System Monitor page (`/system-monitor`).
 //!
-//! Displays API provider health status cards with latency and rate-limit
-//! consumption. Includes a force-refresh button and links to the audit log.
+//! Displays API provider health status cards with latency, uptime and
+//! rate-limit consumption. Includes a force-refresh button and links to the
+//! audit log.
 
 use leptos::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -9,14 +10,46 @@ use serde::{Deserialize, Serialize};
 /// Frontend mirror of the backend's `ProviderHealth` response.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ProviderHealth {
-    /// Provider display name (e.g., "US (NYSE/NASDAQ)").
+    /// Provider name (e.g., "yahoo+fixture").
     pub name: String,
+    /// Exchanges routed to the provider; empty for the default provider.
+    #[serde(default)]
+    pub exchanges: Vec<String>,
     /// Connectivity status string ("Online", "Degraded", "Offline").
     pub status: String,
-    /// Response latency in milliseconds.
+    /// Latency of the latest probe in milliseconds.
     pub latency_ms: u64,
     /// Percentage of API quota consumed (0-100).
     pub rate_limit_percent: u32,
+    /// Failure reported by the latest probe.
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default)]
+    pub last_24h: WindowStats,
+    #[serde(default)]
+    pub last_7d: WindowStats,
+}
+
+/// Frontend mirror of the backend's `WindowStats`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct WindowStats {
+    /// Probes in the window.
+    pub checks: usize,
+    /// Share of successful probes, in percent.
+    pub uptime_percent: Option<f64>,
+    /// 95th percentile latency of successful probes.
+    pub p95_latency_ms: Option<u64>,
+}
+
+/// Formats a window's uptime and p95 latency, e.g. "99.5% · p95 180ms".
+fn format_window(stats: &WindowStats) -> String {
+    let uptime = stats
+        .uptime_percent
+        .map_or_else(|| "—".to_string(), |u| format!("{u:.1}%"));
+    match stats.p95_latency_ms {
+        Some(p95) => format!("{uptime} · p95 {p95}ms"),
+        None => uptime,
+    }
 }
 
 /// System health monitoring dashboard page.
@@ -70,8 +103,7 @@ pub fn SystemMonitor() -> impl IntoView {
 
             <div class="admin-console-panel">
                 <h2>"ADMIN CONSOLE"</h2>
-                <p class="console-line success">"> Monitoring primary feeds (CH, DE, US)..."</p>
-                <p class="console-line success">"> All systems nominal."</p>
+                <p class="console-line success">"> Probing configured data providers..."</p>
                 <div class="console-nav">
                     <a href="/audit-log" class="console-link">"> System Audit Log"</a>
                     <a href="/" class="console-link">"<- Return to Terminal"</a>
@@ -92,11 +124,18 @@ fn HealthIndicator(provider: ProviderHealth) -> impl IntoView {
         "latency-good"
     };
 
-    let status_class = if provider.status == "Online" {
-        "status-online"
+    let status_class = match provider.status.as_str() {
+        "Online" => "status-online",
+        "Degraded" => "status-degraded",
+        _ => "status-offline",
+    };
+    let exchanges = if provider.exchanges.is_empty() {
+        "All other exchanges".to_string()
     } else {
-        "status-offline"
+        provider.exchanges.join(", ")
     };
+    let last_24h = format_window(&provider.last_24h);
+    let last_7d = format_window(&provider.last_7d);
 
     view! {
         <div class="health-indicator-card">
@@ -106,6 +145,8 @@ fn HealthIndicator(provider: ProviderHealth) -> impl IntoView {
                     {provider.status}
                 </span>
             </div>
+            <div class="provider-exchanges">{exchanges}</div>
+            {provider.error.map(|e| view! { <div class="provider-error">{e}</div> })}
 
             <div class="indicator-metrics">
                 <div class="metric-block">
@@ -115,6 +156,16 @@ fn HealthIndicator(provider: ProviderHealth) -> impl IntoView {
                     </span>
                 </div>
 
+                <div class="metric-block">
+                    <label class="metric-label">"Uptime 24h"</label>
+                    <span class="metric-value">{last_24h}</span>
+                </div>
+
+                <div class="metric-block">
+                    <label class="metric-label">"Uptime 7d"</label>
+                    <span class="metric-value">{last_7d}</span>
+                </div>
+
                 <div class="metric-block">
                     <label class="metric-label">"Rate Limit Consumption"</label>
                     <div class="rate-limit-bar">
@@ -22,27 +22,28 @@ mod tests {
         let header: WebElement = _page.find(By::Tag("h1")).await?;
         assert!(header.text().await?.contains("SYSTEM"));
 
-        // 2. Verify health indicator panels
-        let ch_provider: WebElement = ctx
+        // 2. Verify the health indicator panel of the default provider, which
+        // every setup has; per-exchange providers depend on the environment
+        let fixture_provider: WebElement = ctx
             .driver
-            .query(By::XPath("//div[contains(., 'CH (SWX)')]"))
+            .query(By::XPath(
+                "//div[@class='health-indicator-card'][contains(., 'All other exchanges')]",
+            ))
             .first()
             .await?;
-        assert!(ch_provider.is_displayed().await?);
+        assert!(fixture_provider.is_displayed().await?);
 
-        let de_provider: WebElement = ctx
-            .driver
-            .query(By::XPath("//div[contains(., 'DE (DAX)')]"))
-            .first()
+        let provider_name: WebElement = fixture_provider
+            .find(By::ClassName("provider-name"))
             .await?;
-        assert!(de_provider.is_displayed().await?);
+        assert_eq!(provider_name.text().await?, "fixture");
 
-        let us_provider: WebElement = ctx
+        let uptime: WebElement = ctx
             .driver
-            .query(By::XPath("//div[contains(., 'US (NYSE/NASDAQ)')]"))
+            .query(By::XPath("//label[contains(., 'Uptime 24h')]"))
             .first()
             .await?;
-        assert!(us_provider.is_displayed().await?);
+        assert!(uptime.is_displayed().await?);
 
         // 3. Verify status indicator colors/text
         let status: WebElement = ctx